@@ -0,0 +1,227 @@
+use crate::block::epoch;
+use crate::{Cursor, IdRange};
+use std::fmt;
+use std::time::SystemTime;
+
+/// 将要接管 id 空间的生成方案。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Scheme {
+    /// `Token`：之后生成的 id 的游标不小于 `start`，低 32 位可能取任意值。
+    Token { start: Cursor },
+
+    /// 自增计数器（如 `IncrSerialer`）：之后生成的值从 `next` 开始递增。
+    Counter { next: u64 },
+}
+
+/// ## 导入检查
+///
+/// 将已有的 id（如旧系统中的主键）迁移到 fastsend 管理的 id 空间之前，`ImportCheck` 检查其中哪些值可能与之后生成的
+/// id 冲突，并给出需要预留的区间以及安全的起始游标或计数器：
+///
+/// ```
+/// use fastsend::analysis::ImportCheck;
+///
+/// let report = ImportCheck::counter(1000).check([3, 1000, 1001, 1002, 2048]);
+/// assert_eq!(report.overlaps(), &[1000, 1001, 1002, 2048]);
+/// assert_eq!(report.reserved_ranges().len(), 2);
+/// assert_eq!(report.safe_start(), Some(2049));
+/// ```
+///
+/// 对 `Token` 而言，游标代表生成时间（按当前的基准起始时间及纪元版本计算），因此只有游标不早于起始时间的旧 id 才
+/// 可能冲突；`safe_cursor` 是晚于所有旧 id 的第一个游标，等待至该游标对应的时间之后（或通过 `set_epoch` 切换到
+/// 更大的纪元版本）生成的 id 一定不会与旧 id 冲突。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ImportCheck {
+    scheme: Scheme,
+}
+
+impl ImportCheck {
+    /// 检查与从当前时间起生成的 `Token` 是否冲突。
+    pub fn token() -> ImportCheck {
+        Self::token_since(crate::clock::now())
+    }
+
+    /// 检查与从 `time` 起生成的 `Token` 是否冲突，`time` 早于基准起始时间时视为基准起始时间。
+    pub fn token_since(time: SystemTime) -> ImportCheck {
+        // 不通过 `Cursor::new` 计算，避免检查过程锁定基准起始时间
+        let offset = time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            .saturating_sub(Cursor::timebase());
+
+        ImportCheck {
+            scheme: Scheme::Token {
+                start: epoch::encode_saturating(offset),
+            },
+        }
+    }
+
+    /// 检查与从 `next` 开始递增的计数器是否冲突。
+    pub fn counter(next: u64) -> ImportCheck {
+        ImportCheck {
+            scheme: Scheme::Counter { next },
+        }
+    }
+
+    pub fn check<I: IntoIterator<Item = u64>>(&self, ids: I) -> ImportReport {
+        self.check_values(ids.into_iter().map(Some))
+    }
+
+    /// 检查字符串形式的 id，按十进制解析，无法解析的值不会与生成的数值冲突，只计入 `ImportReport::unparsed`。
+    pub fn check_serials<I, S>(&self, serials: I) -> ImportReport
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.check_values(
+            serials
+                .into_iter()
+                .map(|serial| serial.as_ref().trim().parse::<u64>().ok()),
+        )
+    }
+
+    fn check_values<I: Iterator<Item = Option<u64>>>(&self, values: I) -> ImportReport {
+        let mut count = 0;
+        let mut unparsed = 0;
+        let mut max = None;
+        let mut overlaps = Vec::new();
+
+        for value in values {
+            count += 1;
+            let Some(value) = value else {
+                unparsed += 1;
+                continue;
+            };
+
+            max = max.max(Some(value));
+            if self.overlaps(value) {
+                overlaps.push(value);
+            }
+        }
+
+        overlaps.sort_unstable();
+        overlaps.dedup();
+
+        ImportReport {
+            scheme: self.scheme,
+            count,
+            unparsed,
+            reserved: coalesce(&overlaps),
+            overlaps,
+            max,
+        }
+    }
+
+    fn overlaps(&self, value: u64) -> bool {
+        match self.scheme {
+            Scheme::Token { start } => (value >> 32) as u32 >= start.into_inner(),
+            Scheme::Counter { next } => value >= next,
+        }
+    }
+}
+
+/// 将有序且不重复的值合并为连续的区间。
+fn coalesce(values: &[u64]) -> Vec<IdRange> {
+    let mut ranges: Vec<IdRange> = Vec::new();
+    for &value in values {
+        match ranges.last_mut() {
+            Some(range) if range.end() == value => {
+                *range = IdRange::from_bounds(range.start(), value.saturating_add(1));
+            }
+            _ => ranges.push(IdRange::from_bounds(value, value.saturating_add(1))),
+        }
+    }
+    ranges
+}
+
+/// `ImportCheck` 的结果。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportReport {
+    scheme: Scheme,
+
+    count: usize,
+
+    unparsed: usize,
+
+    /// 可能冲突的值，升序且不重复。
+    overlaps: Vec<u64>,
+
+    /// 覆盖 `overlaps` 的连续区间。
+    reserved: Vec<IdRange>,
+
+    /// 所有能够解析的值中的最大值。
+    max: Option<u64>,
+}
+
+impl ImportReport {
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// 无法解析为数值的字符串数量。
+    pub fn unparsed(&self) -> usize {
+        self.unparsed
+    }
+
+    /// 可能与之后生成的 id 冲突的值（升序、不重复）。
+    pub fn overlaps(&self) -> &[u64] {
+        &self.overlaps
+    }
+
+    /// 没有任何可能冲突的值。
+    pub fn is_safe(&self) -> bool {
+        self.overlaps.is_empty()
+    }
+
+    /// 建议预留的区间：将可能冲突的值合并为连续的区间，生成时跳过这些区间即可避免冲突。
+    pub fn reserved_ranges(&self) -> &[IdRange] {
+        &self.reserved
+    }
+
+    /// 晚于所有旧 id 的第一个游标（不早于起始游标），仅适用于 `ImportCheck::token`；游标超出范围时返回 `None`。
+    pub fn safe_cursor(&self) -> Option<Cursor> {
+        let Scheme::Token { start } = self.scheme else {
+            return None;
+        };
+
+        match self.max {
+            Some(max) => {
+                let next = ((max >> 32) as u32).checked_add(1)?;
+                Some(Cursor::from_inner(next.max(start.into_inner())))
+            }
+            None => Some(start),
+        }
+    }
+
+    /// 安全的起始值：`Token` 为 `safe_cursor` 的第一个 id，计数器为大于所有旧值的第一个值（不小于 `next`），超出
+    /// u64 的范围时返回 `None`。
+    pub fn safe_start(&self) -> Option<u64> {
+        match self.scheme {
+            Scheme::Token { .. } => self
+                .safe_cursor()
+                .map(|cursor| (cursor.into_inner() as u64) << 32),
+            Scheme::Counter { next } => match self.max {
+                Some(max) => max.checked_add(1).map(|start| start.max(next)),
+                None => Some(next),
+            },
+        }
+    }
+}
+
+impl fmt::Display for ImportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "count: {}", self.count)?;
+        writeln!(f, "unparsed: {}", self.unparsed)?;
+        writeln!(f, "overlaps: {}", self.overlaps.len())?;
+        match self.safe_start() {
+            Some(start) => writeln!(f, "safe start: {}", start)?,
+            None => writeln!(f, "safe start: none")?,
+        }
+        writeln!(f, "reserved ranges:")?;
+        for range in &self.reserved {
+            writeln!(f, "  [{}, {})", range.start(), range.end())?;
+        }
+        Ok(())
+    }
+}
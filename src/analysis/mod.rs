@@ -0,0 +1,238 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+mod import;
+pub use import::{ImportCheck, ImportReport};
+
+/// ## 统计报告
+///
+/// `report` 对一组 id（通常是 `Token::id` 或 `ShardedId` 等的 u64 表示）进行统计：每一位为 1 的比例、相邻 id
+/// 递增的比例以及重复的数量，用于评估 id 中哪些位是可预测的（如时间戳所在的高位），以及 id 是否会暴露生成顺序：
+///
+/// ```
+/// use fastsend::analysis;
+///
+/// let report = analysis::report(&[1, 2, 3, 3]);
+/// assert_eq!(report.count(), 4);
+/// assert_eq!(report.collisions(), 1);
+/// assert_eq!(report.bit_ratio(0), 0.75);
+/// assert!((report.monotonicity() - 2.0 / 3.0).abs() < 1e-9);
+/// ```
+///
+/// 统计结果只描述给定的样本，样本应当来自实际的生成环境（多线程、多设备），样本越大结果越可信。
+pub fn report(ids: &[u64]) -> IdReport {
+    let mut ones = [0usize; 64];
+    for id in ids {
+        for (bit, count) in ones.iter_mut().enumerate() {
+            *count += (id >> bit & 1) as usize;
+        }
+    }
+
+    let increasing = ids.windows(2).filter(|pair| pair[1] > pair[0]).count();
+    let unique = ids.iter().collect::<HashSet<_>>().len();
+
+    IdReport {
+        count: ids.len(),
+        ones,
+        increasing,
+        collisions: ids.len() - unique,
+    }
+}
+
+/// `report_serials` 对一组序列号进行统计：各字符出现的次数、长度分布、相邻序列号按字典序递增的比例以及重复的
+/// 数量。
+///
+/// ```
+/// use fastsend::analysis;
+///
+/// let report = analysis::report_serials(&["AB", "AC", "AC", "B"]);
+/// assert_eq!(report.collisions(), 1);
+/// assert_eq!(report.char_frequency()[&'A'], 3);
+/// assert_eq!(report.length_distribution()[&2], 3);
+/// ```
+pub fn report_serials<S: AsRef<str>>(serials: &[S]) -> SerialReport {
+    let mut chars = BTreeMap::new();
+    let mut lengths = BTreeMap::new();
+    for serial in serials {
+        let serial = serial.as_ref();
+        for c in serial.chars() {
+            *chars.entry(c).or_insert(0) += 1;
+        }
+        *lengths.entry(serial.chars().count()).or_insert(0) += 1;
+    }
+
+    let increasing = serials
+        .windows(2)
+        .filter(|pair| pair[1].as_ref() > pair[0].as_ref())
+        .count();
+    let unique = serials
+        .iter()
+        .map(AsRef::as_ref)
+        .collect::<HashSet<_>>()
+        .len();
+
+    SerialReport {
+        count: serials.len(),
+        chars,
+        lengths,
+        increasing,
+        collisions: serials.len() - unique,
+    }
+}
+
+/// 香农熵（比特），`counts` 为各取值出现的次数。
+fn entropy(counts: impl IntoIterator<Item = usize>, total: usize) -> f64 {
+    counts
+        .into_iter()
+        .filter(|&count| count > 0)
+        .map(|count| {
+            let p = count as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// 相邻元素递增的比例，少于 2 个元素时为 0。
+fn ratio(increasing: usize, count: usize) -> f64 {
+    if count < 2 {
+        return 0.0;
+    }
+    increasing as f64 / (count - 1) as f64
+}
+
+/// `report` 的结果。
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdReport {
+    count: usize,
+
+    /// 第 n 位（0 为最低位）为 1 的 id 数量。
+    ones: [usize; 64],
+
+    /// 相邻 id 中后者大于前者的次数。
+    increasing: usize,
+
+    collisions: usize,
+}
+
+impl IdReport {
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// 第 `bit` 位（0 为最低位）为 1 的比例，均匀随机的位接近 0.5，恒为 0 或 1 的位是完全可预测的。
+    pub fn bit_ratio(&self, bit: usize) -> f64 {
+        assert!(bit < 64);
+        if self.count == 0 {
+            return 0.0;
+        }
+        self.ones[bit] as f64 / self.count as f64
+    }
+
+    /// 所有 id 中取值都相同的位（从低位到高位）。
+    pub fn constant_bits(&self) -> Vec<usize> {
+        (0..64)
+            .filter(|&bit| self.ones[bit] == 0 || self.ones[bit] == self.count)
+            .collect()
+    }
+
+    /// 各个位的熵之和（比特），是 id 熵的上限：位之间存在相关性（如时间戳）时，实际的熵会更低。
+    pub fn bit_entropy(&self) -> f64 {
+        self.ones
+            .iter()
+            .map(|&ones| entropy([ones, self.count - ones], self.count))
+            .sum()
+    }
+
+    /// 相邻 id 中后者大于前者的比例，接近 1 说明 id 基本按生成顺序递增，接近 0.5 说明顺序接近随机。
+    pub fn monotonicity(&self) -> f64 {
+        ratio(self.increasing, self.count)
+    }
+
+    /// 重复的 id 数量（与之前的某个 id 相同即计为一次）。
+    pub fn collisions(&self) -> usize {
+        self.collisions
+    }
+}
+
+impl fmt::Display for IdReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "count: {}", self.count)?;
+        writeln!(f, "collisions: {}", self.collisions)?;
+        writeln!(f, "monotonicity: {:.4}", self.monotonicity())?;
+        writeln!(f, "bit entropy: {:.2} bits", self.bit_entropy())?;
+        write!(f, "bit ratios (msb first):")?;
+        for bit in (0..64).rev() {
+            if bit % 8 == 7 {
+                writeln!(f)?;
+                write!(f, "  {:>2}:", bit)?;
+            }
+            write!(f, " {:.2}", self.bit_ratio(bit))?;
+        }
+        writeln!(f)
+    }
+}
+
+/// `report_serials` 的结果。
+#[derive(Debug, Clone, PartialEq)]
+pub struct SerialReport {
+    count: usize,
+
+    /// 各字符出现的次数。
+    chars: BTreeMap<char, usize>,
+
+    /// 各长度（字符数）的序列号数量。
+    lengths: BTreeMap<usize, usize>,
+
+    /// 相邻序列号中后者按字典序大于前者的次数。
+    increasing: usize,
+
+    collisions: usize,
+}
+
+impl SerialReport {
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn char_frequency(&self) -> &BTreeMap<char, usize> {
+        &self.chars
+    }
+
+    pub fn length_distribution(&self) -> &BTreeMap<usize, usize> {
+        &self.lengths
+    }
+
+    /// 单个字符的熵（比特），即按字符频率估计的每个字符携带的信息量，字符之间存在相关性时实际的熵会更低。
+    pub fn char_entropy(&self) -> f64 {
+        let total = self.chars.values().sum();
+        entropy(self.chars.values().copied(), total)
+    }
+
+    /// 相邻序列号中后者按字典序大于前者的比例。
+    pub fn monotonicity(&self) -> f64 {
+        ratio(self.increasing, self.count)
+    }
+
+    /// 重复的序列号数量（与之前的某个序列号相同即计为一次）。
+    pub fn collisions(&self) -> usize {
+        self.collisions
+    }
+}
+
+impl fmt::Display for SerialReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "count: {}", self.count)?;
+        writeln!(f, "collisions: {}", self.collisions)?;
+        writeln!(f, "monotonicity: {:.4}", self.monotonicity())?;
+        writeln!(f, "char entropy: {:.2} bits", self.char_entropy())?;
+        writeln!(f, "lengths:")?;
+        for (length, count) in &self.lengths {
+            writeln!(f, "  {}: {}", length, count)?;
+        }
+        writeln!(f, "chars:")?;
+        for (c, count) in &self.chars {
+            writeln!(f, "  {:?}: {}", c, count)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use crate::IdRange;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+/// 通过 `exclude_ids` 登记的区间，按 `start` 升序排列且互不相交（相邻或重叠的区间会被合并）。
+static RANGES: RwLock<Vec<IdRange>> = RwLock::new(Vec::new());
+
+/// 是否登记过区间，未登记时生成 id 的过程不需要获取读锁。
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// 登记一段生成器不应发放的 id 区间（如迁移前的旧 id、预留给 VIP 用户的号码），之后构造的 `Block<Token>`（包括
+/// 全局生成器及自行构造的 `BlockFrame<Token>`）、'strict_order' 模式下的全局计数器以及 `IncrState` 在发放时会跳过
+/// 落入区间的值，调用方不需要在生成之后再自行过滤：
+///
+/// ```
+/// use fastsend::{Block, ConstructBlock, Cursor, Token, ID};
+///
+/// let cursor = Cursor::new();
+/// let legacy = Token::construct_block(0, cursor).nth(3).unwrap().id();
+/// fastsend::exclude_ids(legacy..legacy + 1);
+///
+/// assert!(fastsend::is_excluded(legacy));
+/// assert!(Token::construct_block(0, cursor).all(|token| token.id() != legacy));
+/// ```
+///
+/// 区间对 `Token` 的 id 与 `IncrState` 的计数器取值同时生效，`ImportCheck` 给出的 `reserved_ranges` 可以直接登记。
+/// 跳过的值不会被补发，因此被跳过较多的 `Block` 中可发放的 id 会相应减少；登记只影响之后构造的 `Block`，线程已经
+/// 领取的 `Block` 在构造时已经完成了检查，应在首次生成 id 之前登记。标签（见 `set_tag_bits`）在发放时写入线程号的
+/// 高位，`next_token_tagged` 会对写入标签之后的 id 再检查一次，落入区间时改用下一个 `Token`，因此带标签的 id
+/// 同样不会落入登记的区间。
+pub fn exclude_ids<R: Into<IdRange>>(range: R) {
+    let range = range.into();
+    if range.is_empty() {
+        return;
+    }
+
+    let mut ranges = RANGES.write().unwrap_or_else(|error| error.into_inner());
+    let (mut start, mut end) = (range.start(), range.end());
+
+    // 与新区间相邻或重叠的区间合并为一个区间
+    ranges.retain(|range| {
+        let disjoint = range.end() < start || range.start() > end;
+        if !disjoint {
+            start = start.min(range.start());
+            end = end.max(range.end());
+        }
+        disjoint
+    });
+
+    let index = ranges.partition_point(|range| range.start() < start);
+    ranges.insert(index, IdRange::from_bounds(start, end));
+    ACTIVE.store(true, Ordering::Release);
+}
+
+/// `id` 是否落入通过 `exclude_ids` 登记的区间。
+pub fn is_excluded(id: u64) -> bool {
+    if !ACTIVE.load(Ordering::Acquire) {
+        return false;
+    }
+
+    let ranges = RANGES.read().unwrap_or_else(|error| error.into_inner());
+    let index = ranges.partition_point(|range| range.start() <= id);
+    index > 0 && ranges[index - 1].contains(id)
+}
+
+/// 是否登记过区间，用于在构造 `Block` 时跳过逐个检查。
+pub(crate) fn is_active() -> bool {
+    ACTIVE.load(Ordering::Acquire)
+}
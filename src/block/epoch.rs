@@ -0,0 +1,156 @@
+use crate::{Cursor, FastsendError};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::RwLock;
+use std::time::SystemTime;
+
+/// 当前生效的纪元布局：低 8 位为版本位数，之后 8 位为当前版本，见 `set_epoch`。
+static LAYOUT: AtomicU32 = AtomicU32::new(0);
+
+/// 各版本使用的基准起始时间（秒级 Unix 时间戳），用于解析不同纪元下生成的 id。
+static TIMEBASES: RwLock<[Option<u64>; 4]> = RwLock::new([None; 4]);
+
+/// 版本位数的上限。
+const MAX_BITS: u32 = 2;
+
+/// 在首次获取 `Cursor` 之前切换到新的纪元：之后生成的 `Token` 以 `timebase` 为基准起始时间（见 `set_timebase`），
+/// 并在游标中预留 `bits`（1..=2）位写入版本号 `version`，`Cursor` 已经被使用时设置不会生效并返回 `false`。
+///
+/// 版本位紧随 id 的最高位（符号位）之后，最高位始终为 0，因此 id 作为 i64 存储时仍为正数。新旧纪元的 id 可以
+/// 保存在同一列中：只要旧纪元的游标没有用到版本位（如 1 位版本时旧纪元的游标小于 2^30，缺省基准起始时间下可以
+/// 使用到 2055 年），旧 id 的版本即为 0，新纪元使用更大的版本号即可保证新 id 全部大于旧 id，两者不会冲突，也不会
+/// 打乱先后顺序：
+///
+/// ```
+/// use fastsend::{Cursor, ID};
+/// use std::time::{Duration, SystemTime};
+///
+/// let legacy = SystemTime::UNIX_EPOCH + Duration::from_secs(Cursor::timebase());
+/// fastsend::register_epoch(0, legacy);
+///
+/// let old_id = 0x0000_0001_1234_5678;
+/// assert!(fastsend::set_epoch(1, 1, SystemTime::now()));
+///
+/// # futures::executor::block_on(async {
+/// let token = fastsend::next_token().await;
+/// assert_eq!(fastsend::epoch_of(token.id()), 1);
+/// assert_eq!(fastsend::epoch_of(old_id), 0);
+/// assert!(token.id() > old_id && (token.id() as i64) > 0);
+/// # });
+/// ```
+///
+/// 版本位会占用游标的范围：1 位版本时每个纪元约可使用 34 年，2 位版本时约 17 年，超出后返回
+/// `FastsendError::CursorOverflow`。解析 id 的进程需要设置相同的版本位数，并通过 `register_epoch` 登记各个版本的
+/// 基准起始时间。
+///
+/// # Panics
+///
+/// `bits` 不在 1..=2 之间、`version` 无法以 `bits` 位表示或 `timebase` 不满足 `set_timebase` 的要求时 panic。
+pub fn set_epoch(version: u8, bits: u32, timebase: SystemTime) -> bool {
+    assert!(
+        (1..=MAX_BITS).contains(&bits),
+        "epoch bits must be within 1..={}",
+        MAX_BITS
+    );
+    assert!(
+        (version as u32) < 1 << bits,
+        "epoch version {} does not fit in {} bits",
+        version,
+        bits
+    );
+
+    if !crate::set_timebase(timebase) {
+        return false;
+    }
+
+    LAYOUT.store((version as u32) << 8 | bits, Ordering::SeqCst);
+    register_epoch(version, timebase);
+    true
+}
+
+/// 登记版本 `version` 使用的基准起始时间，`inspect_id`、`Token::partition_key` 等按 id 中的版本选择基准起始时间
+/// 还原生成时间，未登记的版本使用当前的基准起始时间（`Cursor::timebase()`）。`set_epoch` 会自动登记当前版本。
+///
+/// # Panics
+///
+/// `version` 超过 3 或 `timebase` 早于 '1970-01-01 00:00:00' 时 panic。
+pub fn register_epoch(version: u8, timebase: SystemTime) {
+    assert!(
+        (version as u32) < 1 << MAX_BITS,
+        "epoch version must be within 0..{}",
+        1 << MAX_BITS
+    );
+    let timebase = timebase
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("timebase must be later than UNIX_EPOCH")
+        .as_secs();
+
+    TIMEBASES.write().unwrap_or_else(|error| error.into_inner())[version as usize] = Some(timebase);
+}
+
+/// id 的纪元版本，按当前进程的版本位数（见 `set_epoch`）计算，未设置时始终为 0。
+pub fn epoch_of(id: u64) -> u8 {
+    split((id >> 32) as u32).0
+}
+
+/// 当前的版本位数与版本。
+fn layout() -> (u32, u8) {
+    let layout = LAYOUT.load(Ordering::SeqCst);
+    (layout & 0xff, (layout >> 8) as u8)
+}
+
+/// 版本位数为 `bits` 时游标中用于计时的位数。
+fn offset_bits(bits: u32) -> u32 {
+    if bits == 0 {
+        u32::BITS
+    } else {
+        u32::BITS - 1 - bits
+    }
+}
+
+/// 将版本 `version` 与距基准起始时间的秒数 `offset` 组合为游标，`offset` 超出可用的位数时返回错误。
+fn compose(version: u8, offset: u64) -> Result<Cursor, FastsendError> {
+    let (bits, _) = layout();
+    let limit = 1u64 << offset_bits(bits);
+    if offset >= limit {
+        return Err(FastsendError::CursorOverflow);
+    }
+
+    let version = (version as u64) << offset_bits(bits);
+    Ok(Cursor::from_inner((version | offset) as u32))
+}
+
+/// 以当前版本将距基准起始时间的秒数编码为游标。
+pub(crate) fn encode(offset: u64) -> Result<Cursor, FastsendError> {
+    compose(layout().1, offset)
+}
+
+/// 以当前版本将距基准起始时间的秒数编码为游标，超出可用的位数时取当前版本的最后一个游标。
+pub(crate) fn encode_saturating(offset: u64) -> Cursor {
+    let limit = 1u64 << offset_bits(layout().0);
+    encode(offset.min(limit - 1)).unwrap_or_else(|_| unreachable!())
+}
+
+/// 将游标拆分为版本与距基准起始时间的秒数。
+pub(crate) fn split(cursor: u32) -> (u8, u32) {
+    let (bits, _) = layout();
+    if bits == 0 {
+        return (0, cursor);
+    }
+
+    let offset = offset_bits(bits);
+    let version = (cursor >> offset) & ((1 << bits) - 1);
+    (version as u8, cursor & ((1 << offset) - 1))
+}
+
+/// 在同一版本内将游标向后推进 `n` 秒，超出该版本的范围时返回错误。
+pub(crate) fn checked_add(cursor: Cursor, n: u32) -> Result<Cursor, FastsendError> {
+    let (version, offset) = split(cursor.into_inner());
+    compose(version, offset as u64 + n as u64)
+}
+
+/// 游标代表的秒级 Unix 时间戳，按游标中的版本选择基准起始时间。
+pub(crate) fn unix_secs(cursor: Cursor) -> u64 {
+    let (version, offset) = split(cursor.into_inner());
+    let timebase = TIMEBASES.read().unwrap_or_else(|error| error.into_inner())[version as usize];
+    timebase.unwrap_or_else(Cursor::timebase) + offset as u64
+}
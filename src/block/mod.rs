@@ -1,14 +1,25 @@
-use crossbeam::atomic::AtomicCell;
-use crossbeam::queue::ArrayQueue;
+use crate::FastsendError;
 use crossbeam::utils::Backoff;
 use lazy_static::lazy_static;
 use rand::seq::SliceRandom;
+use rand::Rng;
 use std::future::Future;
+use std::marker::PhantomData;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll, Waker};
-use std::thread;
-use std::time::{Instant, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
+
+pub(crate) mod epoch;
+pub use epoch::{epoch_of, register_epoch, set_epoch};
+
+pub(crate) mod blocklist;
+pub use blocklist::{exclude_ids, is_excluded};
+
+mod sync;
+use sync::{
+    AtomicBool, AtomicU32, Backend, Mutex, MutexGuard, Ordering, Queue, Selected, SelectedQueue,
+};
 
 /// # BlockFrame 的设计理念
 ///
@@ -24,15 +35,34 @@ pub struct BlockFrame<T> {
     /// `cursor` 标记了当前 `BlockFrame` 所处的时间节点，其作用在于当 `queue` 队列内容不足需要进行补充时，使用
     /// `Cursor` 来正确进行时间线延后操作，即队列补充的时间线不应与当前时间线重合，避免冲突（通过调用
     /// `Cursor::next` 方法）。
-    cursor: Arc<AtomicCell<Cursor>>,
+    /// （`Cursor` 以其内部的 u32 计数存储）
+    cursor: Arc<AtomicU32>,
 
     /// `fresh` 是包含所有新产生 `Block` 的队列，队列大小为 cap=QUEUE_SIZE， 在初始化及补充完成的场合，
     /// `fresh` 队列应包含全部 `Block`。
-    queue: Arc<ArrayQueue<Block<T>>>,
+    /// （队列的具体实现由编译时选定的同步原语决定，缺省为 crossbeam 的 `ArrayQueue`，详见 `sync` 模块）
+    ///
+    /// 队列中存放的并非 `Block` 本身，而是构造 `Block` 所需的 `BlockDescriptor`，`Block` 在发放给线程时才会
+    /// 通过 `ConstructBlock` 构造，从而避免每次补充都在队列中预先构造全部 `Block`（对 `Token` 而言约 65k 个元素）。
+    queue: Arc<SelectedQueue<BlockDescriptor>>,
 
     /// `state` 代表当前 `supply` 的执行进度，false 代表无正在执行的 `supply` 线程，true 代表当前有正在
     /// 执行的 `supply` 线程。
-    state: Arc<AtomicCell<bool>>,
+    state: Arc<AtomicBool>,
+
+    /// `waiters` 保存等待本轮补充完成的 `Waker`，`supply` 线程在推送首个 `Block` 以及补充结束时唤醒全部
+    /// `waiters`。
+    waiters: Arc<Mutex<Vec<Waker>>>,
+
+    /// `failure` 保存 `supply` 线程补充失败（获取 `Cursor` 失败）时的错误，由下一个被唤醒的 `BlockFuture`
+    /// 取出并返回给调用方。
+    failure: Arc<Mutex<Option<FastsendError>>>,
+
+    /// 构造 `Block` 时使用的发号序号步长，见 `sequence_stride`。
+    stride: SequenceStride,
+
+    /// 队列中只保存 `BlockDescriptor`，`T` 仅在构造 `Block` 时使用。
+    marker: PhantomData<fn() -> T>,
 }
 
 impl<T> Default for BlockFrame<T> {
@@ -44,113 +74,273 @@ impl<T> Default for BlockFrame<T> {
 impl<T> BlockFrame<T> {
     /// `ELEMENT_CAP` 表示一个 `BlockFrame` 在一个 `Cursor` 下所能产生的所有元素的数量，该数量指 T 的
     /// 数量而非 `Block` 的数量。
+    #[cfg_attr(fastsend_loom, allow(dead_code))]
     pub(crate) const ELEMENT_CAP: usize = u16::MAX as usize + 1;
 
     /// `QUEUE_SIZE` 指 `queue` 队列中总计的 `Block` 的数量。
+    /// （loom 会穷举每一次队列操作的线程交错，因此在 `cfg(fastsend_loom)` 下缩小队列以控制状态空间）
+    #[cfg(not(fastsend_loom))]
     pub(crate) const QUEUE_SIZE: usize = BlockFrame::<T>::ELEMENT_CAP / Block::<T>::SIZE;
+    #[cfg(fastsend_loom)]
+    pub(crate) const QUEUE_SIZE: usize = 2;
 
     pub fn new() -> Self {
+        Self::try_new().unwrap_or_else(|error| panic!("{} on BlockFrame::new()", error))
+    }
+
+    /// `new` 方法的非 panic 版本，获取初始 `Cursor` 失败时返回错误。
+    pub fn try_new() -> Result<Self, FastsendError> {
         #[allow(unused)]
-        let cursor = Cursor::new();
+        let cursor = Cursor::try_new()?;
 
         // 使用 `next` 方法完成对 `Cursor` 的初始化，目的在于保证所获得的 `Cursor` 在但应用单进程场景下
         // 是唯一的，即每台机器只有一个 `fastsend` 进程，每个 `fastsend` 进程只包含一个 `BlockFrame`
         // 实例（一般以全局变量的形式存在），此举是应对短时间内（1 秒内）程序退出又重启时，可能出现的 `Cursor`
         // 冲突的情况。
         #[cfg(feature = "pause_on_start")]
-        let cursor = cursor.next();
+        let cursor = cursor.try_next()?;
+
+        Ok(BlockFrame {
+            cursor: Arc::new(AtomicU32::new(cursor.into_inner())),
+            queue: Arc::new(Queue::with_capacity(Self::QUEUE_SIZE)),
+            state: Arc::new(AtomicBool::new(false)),
+            waiters: Arc::new(Mutex::new(Vec::new())),
+            failure: Arc::new(Mutex::new(None)),
+            stride: SequenceStride::NONE,
+            marker: PhantomData,
+        })
+    }
 
-        BlockFrame {
-            cursor: Arc::new(AtomicCell::new(cursor)),
-            queue: Arc::new(ArrayQueue::new(Self::QUEUE_SIZE)),
-            state: Arc::new(AtomicCell::new(false)),
-        }
+    /// 只使用满足 `sequence % step == offset` 的发号序号构造 `Block`，每个 `Cursor` 下可发放的元素数量相应地减少为
+    /// 原来的 1/`step`。多个进程（如同时写入同一张表的两个服务）使用相同的 `step`、不同的 `offset` 时，各自生成的
+    /// 元素在发号序号上互不相交，无需额外的协调服务。
+    ///
+    /// 需要 `T` 实现 `ConstructBlock::construct_strided_block`（`Token` 已实现），应在获取 `Block` 之前设置。
+    ///
+    /// # Panics
+    ///
+    /// 参数不满足 `SequenceStride::new` 的要求时 panic。
+    pub fn sequence_stride(mut self, offset: u16, step: u16) -> Self {
+        self.stride = SequenceStride::new(offset, step).with_entropy(self.stride.entropy_bits());
+        self
+    }
+
+    /// 将发号序号的低 `bits` 位改为随机填充，见 `SequenceStride::with_entropy`，每个 `Cursor` 下可发放的元素数量
+    /// 相应地减少为原来的 1/2^`bits`。与 `sequence_stride` 相同，需要 `T` 实现 `ConstructBlock::construct_strided_block`，
+    /// 应在获取 `Block` 之前设置。
+    ///
+    /// # Panics
+    ///
+    /// 参数不满足 `SequenceStride::with_entropy` 的要求时 panic。
+    pub fn entropy_bits(mut self, bits: u32) -> Self {
+        self.stride = self.stride.with_entropy(bits);
+        self
     }
 }
 
 impl<T: ConstructBlock> BlockFrame<T> {
-    pub fn next_block(&self) -> Pin<Box<dyn Future<Output = Block<T>> + Send + 'static>>
-    where
-        T: Send + 'static,
-    {
-        Box::pin(BlockFuture {
+    pub fn next_block(&self) -> NextBlock<T> {
+        NextBlock(self.try_next_block())
+    }
+
+    /// `next_block` 方法的非 panic 版本，补充 `Block` 时获取 `Cursor` 失败则返回错误。补充失败不会影响之后的
+    /// 调用，下一次调用会重新尝试补充。
+    ///
+    /// 返回的 `BlockFuture` 仅持有 `BlockFrame` 内部状态的引用计数，获取 `Block` 的过程不需要分配堆内存。
+    pub fn try_next_block(&self) -> BlockFuture<T> {
+        BlockFuture {
+            frame: self.share(),
+        }
+    }
+
+    /// 不等待补充、直接从队列中取出一个 `Block`，队列为空时返回 `None`（也不会触发补充），用于在已经通过
+    /// `next_block` 获得 `Block` 之后额外领取更多的 `Block`。
+    pub fn pop_block(&self) -> Option<Block<T>> {
+        // 全部元素都落入 `exclude_ids` 登记的区间的 `Block` 不会被发放
+        loop {
+            let block = Queue::pop(&*self.queue)?.construct(self.stride);
+            if block.remaining() > 0 {
+                return Some(block);
+            }
+        }
+    }
+
+    /// 当前 `Cursor` 下队列中剩余的 `Block` 数量，为 0 时下一次获取 `Block` 需要等待补充（开启 'pause_on_start'
+    /// 时即等待至下一秒）。并发获取时返回的只是某一时刻的快照。
+    pub fn remaining_blocks(&self) -> usize {
+        Queue::len(&*self.queue)
+    }
+
+    /// 为调用方预留 `cursors` 个连续的新 `Cursor` 并返回其中的第一个，当前 `BlockFrame` 之后构造的 `Block` 不会使用
+    /// 预留的 `Cursor`，用于批量分配 id 区间等场景（见 `fastsend::reserve_range`）。
+    ///
+    /// 开启 'pause_on_start' 时预留的第一个 `Cursor` 需要等待至下一秒，预留多个 `Cursor` 时之后的补充也需要等待时间
+    /// 超过预留的最后一个 `Cursor`；未开启时直接递增游标，不会等待。
+    pub fn reserve_cursors(&self, cursors: u32) -> Result<Cursor, FastsendError> {
+        assert!(cursors > 0, "must reserve at least one cursor");
+        self.advance(cursors)
+    }
+
+    /// 为当前 `BlockFrame` 占用 `cursors` 个连续的新 `Cursor` 并返回其中的第一个：通过 CAS 操作将旧 `Cursor`
+    /// 置换为占用的最后一个 `Cursor`，确保新的游标一定滞后于之前使用过的游标，之后的补充也一定滞后于本次占用的游标。
+    fn advance(&self, cursors: u32) -> Result<Cursor, FastsendError> {
+        debug_assert!(cursors > 0);
+
+        loop {
+            let prev = Cursor::from_inner(self.cursor.load(Ordering::Acquire));
+
+            // HACK:
+            // 此处是一个针对 'cfg(not(feature = "pause_on_start"))' 的一个 HACK，目的
+            // 是提高程序启动加载的速度（常用于命令行应用），'pause_on_start' 特性在被禁用的
+            // 情况下，会在程序启动的过程中针对性地停用 `Cursor::next` 方法的调用，但在这里却
+            // 不能*简单粗暴*地禁用 `next` 方法的执行（不然会造成元素生成冲突）。
+            //
+            // 因此使用了一个折衷但却并不安全的方法来达到预期提高启动速度的目的：调用 unsafe 的
+            // `Cursor::incr` 方法对计数进行累加，可以避免在等待时间流逝过程中的阻塞时间，同时
+            // 也能确保生成的元素具有唯一性，但其不安全点在于，如果一个程序过快地重复执行（或重启）
+            // 生成地元素有较小概率会重复，这就需要使用者（调用方）自己做判重处理。
+            let first = if cfg!(feature = "pause_on_start") {
+                prev.try_next()?
+            } else {
+                unsafe { prev.incr() }
+            };
+            let last = epoch::checked_add(first, cursors - 1)?.into_inner();
+
+            if self
+                .cursor
+                .compare_exchange(prev.into_inner(), last, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                return Ok(first);
+            }
+        }
+    }
+
+    /// `supply` 补充程序，首先通过 `Cursor::next` 方法确保补充的 `Block` 滞后于当前的 `Cursor`，
+    /// 这一步的目的是保证补充的 `Block` 在进行后续操作时，不与之前的 `Block` 产生时间线和数值上的冲突，
+    /// 即即使 `Block` 的内容与先前的 `Block` 相同，但由于已经经过 `Cursor::next` 拉长时间间隔，新
+    /// `Block` 是处在新的时间线上（时间线间隔为秒），所以并不会造成冲突。
+    /// （时间线与数值冲突指在同一时间线（秒）上，使用了相同的数值，产生冲突）
+    ///
+    /// 调用 `supply` 前，调用方已经通过 `state` 的 CAS 取得了补充的权利，`supply` 结束时（包括 panic）释放该
+    /// 权利并唤醒全部 `waiters`。
+    fn supply(&self) {
+        let _guard = SupplyGuard {
+            state: &self.state,
+            waiters: &self.waiters,
+        };
+
+        let next = match self.advance(1) {
+            Ok(next) => next,
+            // 获取 `Cursor` 失败时放弃本轮补充，将错误交给等待中的 `BlockFuture`
+            Err(error) => {
+                *lock(&self.failure) = Some(error);
+                return;
+            }
+        };
+
+        // `ConstructBlock` 在构造时需要传入当前构造的 `Block` 批次数 `n`，这里将预先构造出
+        // `n` 的序列并打乱顺序，以期在生成 `Block` 时能更具有迷惑性和随机性，但又不在数量和稳
+        // 定性上影响整体构造逻辑。
+        // 使用步长或随机位时每个 `Cursor` 下只有 1/`step << entropy` 的发号序号可用，`Block` 的数量相应减少
+        let blocks = Self::QUEUE_SIZE / self.stride.ratio();
+        let mut seq = (0..blocks).collect::<Vec<usize>>();
+        #[cfg(feature = "test-util")]
+        let seeded = crate::testing::with_rng(|rng| seq.shuffle(rng)).is_some();
+        #[cfg(not(feature = "test-util"))]
+        let seeded = false;
+
+        if !seeded {
+            seq.shuffle(&mut rand::thread_rng());
+        }
+
+        // 将新 `Block` 的 `BlockDescriptor` 全部推送至 `queue` 队列中，新生成的 next `Cursor`
+        // 将在发放时被用于创建 `Block` 中的元素 T。
+        for (i, n) in seq.into_iter().enumerate() {
+            let descriptor = BlockDescriptor {
+                n: n as u32,
+                cursor: next,
+            };
+
+            // `Err` 表示队列已满，剩余内容不再推送（实际场景中应为所有 `Block` 均应被推送至
+            // 队列中，不会存在队列已满的情况）
+            if Queue::push(&*self.queue, descriptor).is_err() {
+                break;
+            }
+
+            // 在成功推送第一条 `Block` 后，立刻唤醒等待的 `Future` 以实现快速响应
+            if i == 0 {
+                wake_all(&self.waiters);
+            }
+        }
+    }
+}
+
+/// 在 `supply` 结束时释放补充的权利并唤醒全部 `waiters`。补充过程 panic 时同样会释放，否则 `state` 将一直为
+/// true，之后的 `BlockFuture` 既不会发起新的补充也不会被唤醒。
+struct SupplyGuard<'a> {
+    state: &'a AtomicBool,
+    waiters: &'a Mutex<Vec<Waker>>,
+}
+
+impl Drop for SupplyGuard<'_> {
+    fn drop(&mut self) {
+        self.state.store(false, Ordering::SeqCst);
+        wake_all(self.waiters);
+    }
+}
+
+impl<T> BlockFrame<T> {
+    /// 当前 `BlockFrame` 已经占用的最后一个 `Cursor`，即正在发放的元素所属的 `Cursor`。
+    #[cfg_attr(feature = "strict_order", allow(dead_code))]
+    pub(crate) fn cursor(&self) -> Cursor {
+        Cursor::from_inner(self.cursor.load(Ordering::Acquire))
+    }
+
+    /// 共享同一份内部状态的 `BlockFrame`，用于在 `BlockFuture` 及补充线程中访问 `BlockFrame`。
+    fn share(&self) -> BlockFrame<T> {
+        BlockFrame {
+            cursor: Arc::clone(&self.cursor),
             queue: Arc::clone(&self.queue),
-            supply: Arc::new({
-                let cursor = Arc::clone(&self.cursor);
-                let queue = Arc::clone(&self.queue);
-                let state = Arc::clone(&self.state);
-
-                // `supply` 补充程序，首先通过 `Cursor::next` 方法确保补充的 `Block` 滞后于当前的 `Cursor`，
-                // 这一步的目的是保证补充的 `Block` 在进行后续操作时，不与之前的 `Block` 产生时间线和数值上的冲突，
-                // 即即使 `Block` 的内容与先前的 `Block` 相同，但由于已经经过 `Cursor::next` 拉长时间间隔，新
-                // `Block` 是处在新的时间线上（时间线间隔为秒），所以并不会造成冲突。
-                // （时间线与数值冲突指在同一时间线（秒）上，使用了相同的数值，产生冲突）
-                move |mut waker| {
-                    // 同一时间仅需要一个队列补充任务，通过 CAS 来确保唯一性
-                    if state.compare_exchange(false, true).is_ok() {
-                        let mut prev;
-                        let mut next;
-
-                        // 通过 CAS 操作将旧 `Cursor` 置换为 `next`，确保 `next` 游标一定滞后于 `prev`，
-                        loop {
-                            prev = cursor.load();
-
-                            // HACK:
-                            // 此处是一个针对 'cfg(not(feature = "pause_on_start"))' 的一个 HACK，目的
-                            // 是提高程序启动加载的速度（常用于命令行应用），'pause_on_start' 特性在被禁用的
-                            // 情况下，会在程序启动的过程中针对性地停用 `Cursor::next` 方法的调用，但在这里却
-                            // 不能*简单粗暴*地禁用 `next` 方法的执行（不然会造成元素生成冲突）。
-                            //
-                            // 因此使用了一个折衷但却并不安全的方法来达到预期提高启动速度的目的：调用 unsafe 的
-                            // `Cursor::incr` 方法对计数进行累加，可以避免在等待时间流逝过程中的阻塞时间，同时
-                            // 也能确保生成的元素具有唯一性，但其不安全点在于，如果一个程序过快地重复执行（或重启）
-                            // 生成地元素有较小概率会重复，这就需要使用者（调用方）自己做判重处理。
-                            next = if cfg!(feature = "pause_on_start") {
-                                prev.next()
-                            } else {
-                                unsafe { prev.incr() }
-                            };
-
-                            if cursor.compare_exchange(prev, next).is_ok() {
-                                break;
-                            }
-                        }
-
-                        // `ConstructBlock` 在构造时需要传入当前构造的 `Block` 批次数 `n`，这里将预先构造出
-                        // `n` 的序列并打乱顺序，以期在生成 `Block` 时能更具有迷惑性和随机性，但又不在数量和稳
-                        // 定性上影响整体构造逻辑。
-                        let mut seq = (0..Self::QUEUE_SIZE).collect::<Vec<usize>>();
-                        seq.shuffle(&mut rand::thread_rng());
-
-                        // 通过 `ConstructBlock` trait 构建新的 `Block`，并全部推送至 `queue` 队列中，新
-                        // 生成的 next `Cursor` 将被用于创建 `Block` 中的元素 T。
-                        for n in seq {
-                            let block = T::construct_block(n, next);
-
-                            // `Err` 表示队列已满，剩余内容不再推送（实际场景中应为所有 `Block` 均应被推送至
-                            // 队列中，不会存在队列已满的情况）
-                            if queue.push(block).is_err() {
-                                break;
-                            }
-
-                            // 在成功推送至少一条 `Block` 后，立刻唤醒等待的 `Future` 以实现快速响应，通过
-                            // `Option::take` 实现，在完成 take 后，`Option` 中便无 `Waker` 可唤醒。
-                            if let Some(waker) = waker.take() {
-                                waker.wake_by_ref();
-                            }
-
-                            debug_assert!(waker.is_none());
-                        }
-
-                        state.store(false);
-                    }
-
-                    if let Some(waker) = waker {
-                        waker.wake_by_ref();
-                    }
-                }
-            }),
-        })
+            state: Arc::clone(&self.state),
+            waiters: Arc::clone(&self.waiters),
+            failure: Arc::clone(&self.failure),
+            stride: self.stride,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// `BlockDescriptor` 是尚未构造的 `Block`：记录构造时传给 `ConstructBlock::construct_block` 的批次数 `n`
+/// 以及时间锚点 `cursor`，仅占用 8 个字节。
+#[derive(Debug, Copy, Clone)]
+struct BlockDescriptor {
+    n: u32,
+    cursor: Cursor,
+}
+
+impl BlockDescriptor {
+    fn construct<T: ConstructBlock>(self, stride: SequenceStride) -> Block<T> {
+        if stride == SequenceStride::NONE {
+            T::construct_block(self.n as usize, self.cursor)
+        } else {
+            T::construct_strided_block(self.n as usize, self.cursor, stride)
+        }
+    }
+}
+
+/// 获取锁并忽略 poison，`supply` 线程 panic 不应导致之后的 `next_block` 全部失败。
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// 取出并唤醒全部等待中的 `Waker`，唤醒在释放锁之后进行。
+fn wake_all(waiters: &Mutex<Vec<Waker>>) {
+    let waiters = std::mem::take(&mut *lock(waiters));
+    for waker in waiters {
+        waker.wake();
     }
 }
 
@@ -164,13 +354,38 @@ pub struct Block<T> {
     /// 使用 `array` 而非 `Vec` 来存储 T，因为在大多数场景下，T 满足 T: Copy，
     /// 在栈上分配空间以提高效率。
     array: [T; Block::<()>::SIZE],
+
+    /// 第 i 位为 1 表示 `array[i]` 不会被发放，见 `Block::skip_where`。
+    skipped: u8,
 }
 
 impl<T> Block<T> {
     pub(crate) const SIZE: usize = 8;
 
     pub(crate) fn new(array: [T; 8]) -> Self {
-        Block { index: 0, array }
+        Block {
+            index: 0,
+            array,
+            skipped: 0,
+        }
+    }
+
+    /// 构造时标记满足 `f` 的元素，迭代时跳过这些元素，用于跳过 `exclude_ids` 登记的区间。
+    pub(crate) fn skip_where<F: Fn(&T) -> bool>(mut self, f: F) -> Self {
+        for (i, element) in self.array.iter().enumerate() {
+            if f(element) {
+                self.skipped |= 1 << i;
+            }
+        }
+        self
+    }
+
+    /// 剩余可生成的元素数量。
+    pub(crate) fn remaining(&self) -> usize {
+        if self.index >= Self::SIZE {
+            return 0;
+        }
+        (!self.skipped >> self.index).count_ones() as usize
     }
 }
 
@@ -184,6 +399,10 @@ impl<T: Clone> Iterator for Block<T> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
+        while self.index < Self::SIZE && self.skipped & (1 << self.index) != 0 {
+            self.index += 1;
+        }
+
         if self.index >= Self::SIZE {
             return None;
         }
@@ -197,40 +416,68 @@ impl<T: Clone> Iterator for Block<T> {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let remain = Self::SIZE - self.index;
+        let remain = self.remaining();
         (remain, Some(remain))
     }
 }
 
-/// `BlockFuture` 代表发放 `Block` 的异步任务，当队列内的 `Block` 不足时，会通过额外的线程补充
-/// 队列内容，并返回 `Pending`，其余情况则返回 `Ready`。
-struct BlockFuture<T> {
-    /// 继承自 `BlockFrame` 的 `queue` 队列。
-    queue: Arc<ArrayQueue<Block<T>>>,
-
-    /// `supply` 表示当 Future 返回 `Pending` 时，应该执行的补充队列的操作, `supply` 获取一个 `Waker`
-    /// 引用，应确保调用完毕时，执行 `Waker::wake_by_ref` 操作。
-    /// （使用 `Waker` 引用的目的是为之后可能产生的其他有关 waker 的操作预留扩展空间，如果接受的是带有所有权
-    /// 的 `Waker`，有可能出现所有权纠纷）
-    /// （使用 `Option<&Waker>` 的原因是为了实现只唤醒一次的特性，详见 `BlockFrame::next_block` 中的注释）
-    supply: Arc<dyn Fn(Option<&Waker>) + Send + Sync + 'static>,
+/// `BlockFuture` 代表发放 `Block` 的异步任务，由 `BlockFrame::try_next_block` 返回。当队列内的 `Block`
+/// 不足时，会通过额外的线程补充队列内容，并返回 `Pending`，其余情况则返回 `Ready`。
+///
+/// 补充线程只处理 `BlockDescriptor`，`Block` 在轮询 `BlockFuture` 的线程中构造，因此 `T` 不需要实现 `Send`：
+/// 只在单线程运行时（如 tokio 的 `LocalSet`）中使用的、包含 `Rc` 等类型的 `T` 同样可以通过 `BlockFrame` 发放。
+#[derive(Debug)]
+pub struct BlockFuture<T> {
+    /// 与发放 `BlockFuture` 的 `BlockFrame` 共享内部状态。
+    frame: BlockFrame<T>,
 }
 
-impl<T> Future for BlockFuture<T> {
+/// `NextBlock` 是 `BlockFrame::next_block` 返回的异步任务，与 `BlockFuture` 相同，但在获取 `Block` 失败时
+/// panic。
+#[derive(Debug)]
+pub struct NextBlock<T>(BlockFuture<T>);
+
+impl<T: ConstructBlock + 'static> Future for NextBlock<T> {
     type Output = Block<T>;
 
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx).map(|result| {
+            result.unwrap_or_else(|error| panic!("{} on BlockFrame::next_block()", error))
+        })
+    }
+}
+
+impl<T: ConstructBlock + 'static> Future for BlockFuture<T> {
+    type Output = Result<Block<T>, FastsendError>;
+
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        // 从队列获取 `Block`
-        if let Some(block) = self.queue.pop() {
-            return Poll::Ready(block);
+        // 从队列获取 `BlockDescriptor`，并在当前线程构造 `Block`
+        if let Some(block) = self.frame.pop_block() {
+            return Poll::Ready(Ok(block));
+        }
+
+        // 上一轮补充失败，由当前 `BlockFuture` 取出错误并返回，其余等待者会重新发起补充
+        if let Some(error) = lock(&self.frame.failure).take() {
+            return Poll::Ready(Err(error));
         }
 
         // 当 `queue` 队列中无 `Block` 时，代表当前时间段内所有 `Block` 都已经发放， 并且尚未回收，
-        // 等待该段时间间隔后重新尝试获取队列内容。
+        // 需要等待补充完成后重新尝试获取队列内容。
+        //
+        // 先登记 `Waker` 再尝试 CAS：若 CAS 失败，正在执行的 `supply` 在将 `state` 置为 false 之后才会唤醒
+        // `waiters`，因此一定能看到这里登记的 `Waker`，不会出现丢失唤醒的情况。
+        lock(&self.frame.waiters).push(cx.waker().clone());
+
+        // 同一时间仅需要一个队列补充任务，通过 CAS 来确保唯一性，CAS 失败时由正在执行的任务负责唤醒（补充任务
+        // 的执行方式由 `sync` 模块决定）。
+        if self
+            .frame
+            .state
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
         {
-            let waker = cx.waker().clone();
-            let supply = Arc::clone(&self.supply);
-            thread::spawn(move || supply(Some(&waker)));
+            let frame = self.frame.share();
+            Selected::spawn(move || frame.supply());
         }
 
         Poll::Pending
@@ -239,70 +486,141 @@ impl<T> Future for BlockFuture<T> {
 
 /// `Cursor` 用于表示一个时间锚点
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "bincode", derive(bincode::Encode, bincode::Decode))]
 pub struct Cursor(u32);
 
 impl Cursor {
     pub(crate) fn into_inner(self) -> u32 {
         self.0
     }
+
+    pub(crate) fn from_inner(inner: u32) -> Self {
+        Cursor(inner)
+    }
+}
+
+/// `Cursor` 使用的基准起始时间（秒级 Unix 时间戳），可以在首次获取 `Cursor` 之前通过 `set_timebase` 修改。
+///
+/// 最高位（`TIMEBASE_IN_USE`）表示是否已经使用基准起始时间计算过 `Cursor`，使用后基准起始时间不能再修改。二者放在
+/// 同一个原子变量中，修改与首次使用之间不会出现先检查后写入的竞争。
+static TIMEBASE: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(Cursor::TIMEBASE);
+const TIMEBASE_IN_USE: u64 = 1 << 63;
+
+/// 在首次获取 `Cursor`（包括首次调用 `next_token`、`TimeSerialer::build` 等）之前修改 `Cursor` 的基准起始时间，
+/// `Cursor` 已经被使用时修改不会生效并返回 `false`。
+///
+/// u32 的秒级游标从基准起始时间起约可使用 136 年，缺省的基准起始时间 '2021-12-10 12:27:33' 将在 2157 年耗尽。新部署
+/// 的系统可以将基准起始时间设置为上线时间以延长可用年限。需要注意的是，不同基准起始时间下生成的 `Token` 使用相同的
+/// 游标取值区间，彼此之间可能冲突且无法比较先后，因此同一个 id 空间只能使用同一个基准起始时间，解析已归档的 id 时
+/// 也需要使用生成时的基准起始时间。需要在同一个 id 空间内迁移时使用 `set_epoch`。
+///
+/// # Panics
+///
+/// `timebase` 早于 '1970-01-01 00:00:00' 或晚于当前时间时 panic。
+pub fn set_timebase(timebase: SystemTime) -> bool {
+    assert!(
+        timebase <= crate::clock::now(),
+        "timebase must not be later than now"
+    );
+    let timebase = timebase
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("timebase must be later than UNIX_EPOCH")
+        .as_secs();
+
+    TIMEBASE
+        .fetch_update(
+            std::sync::atomic::Ordering::SeqCst,
+            std::sync::atomic::Ordering::SeqCst,
+            |state| (state & TIMEBASE_IN_USE == 0).then_some(timebase),
+        )
+        .is_ok()
 }
 
 impl Cursor {
-    // 用于计算时间戳的基准起始时间 '2021-12-10 12:27:33'
-    const TIMEBASE: u64 = 1639110453;
+    // 缺省的基准起始时间 '2021-12-10 12:27:33'
+    pub(crate) const TIMEBASE: u64 = 1639110453;
+
+    /// 当前生效的基准起始时间（秒级 Unix 时间戳），游标 `n` 代表的时间为 `timebase() + n`。
+    pub fn timebase() -> u64 {
+        TIMEBASE.load(std::sync::atomic::Ordering::SeqCst) & !TIMEBASE_IN_USE
+    }
 
     pub fn new() -> Self {
+        Self::try_new().unwrap_or_else(|error| panic!("{} on Cursor::new()", error))
+    }
+
+    /// `new` 方法的非 panic 版本，当前时间早于基准起始时间或超出 u32 范围时返回错误。
+    ///
+    /// 游标以进程首次调用时的系统时间为锚点，之后通过单调时钟计算时间间隔，因此首次调用时的时间错误（如系统时间被
+    /// 设置到了过去）会在整个进程周期内持续返回错误。
+    pub fn try_new() -> Result<Self, FastsendError> {
+        // 使用虚拟时间时（见 `fastsend::testing::freeze_time`）直接由虚拟时间计算游标
+        if let Some(now) = crate::clock::mocked() {
+            return Cursor::from_system_time(now);
+        }
+
         lazy_static! {
             // `START` 用于表示当前进程的起始时间，用于计算时间间隔
             static ref START: Instant = Instant::now();
 
-            // `TIMESTAMP` 代表秒级别的 UNIX 时间戳，通过 `SystemTime` 计算获得，如果在计算时间戳时发生了时间
-            // 倒转，则会在初始化时 panic，所获得的时间戳会由 u64 类型转化为 u32 类型，并做对应的溢出检测，溢出
-            // 的时间将会引发 panic。
+            // `TIMESTAMP` 代表秒级别的时间戳，通过 `SystemTime` 计算获得，计算失败（时间早于基准起始时间）时保存
+            // 对应的错误。
             //
-            // （该时间戳的基准时间点并非 '1970-01-01 00:00:00'，而是 `Cursor::TIMEBASE`）
-            static ref TIMESTAMP: u32 = {
-                let mut timestamp = match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
-                    Ok(duration) => duration.as_secs(),
-                    Err(error) => {
-                        panic!("error occur when initializing `Cursor::TIMESTAMP`: {}", error)
-                    }
-                };
-
-                // 为了能支撑更长久的程序运行周期，因此以 `TIMEBASE` 为截断点，仅计算此时间之后的时间戳，`u32`
-                // 类型的秒级时间戳理论上能支撑程序运行 100+ 年。
-                timestamp -= Cursor::TIMEBASE;
-
-                if timestamp > u32::MAX as u64 {
-                    panic!("`Cursor::TIMESTAMP` overflows over u32")
-                }
-
-                timestamp as u32
-            };
+            // （该时间戳的基准时间点并非 '1970-01-01 00:00:00'，而是 `Cursor::timebase()`）
+            static ref TIMESTAMP: Result<u64, FastsendError> =
+                Cursor::offset_of(SystemTime::now());
         }
 
-        // `elapsed` 代表从进程开始到当前的时间间隔，该时间间隔会由 u64 类型转化为 u32 类型，并做溢出检测。
+        // 提供秒级别的游标控制，每个游标之间的间隔为 1 秒，`elapsed` 代表从进程开始到当前的时间间隔，超出游标的
+        // 范围（设置了纪元版本时为版本位之外的位数，见 `set_epoch`）时返回错误
         let elapsed = START.elapsed().as_secs();
-        if elapsed > u32::MAX as u64 {
-            panic!("`elapsed` overflows over u32 on Cursor::new()");
+        epoch::encode((*TIMESTAMP)? + elapsed)
+    }
+
+    /// 由 `time` 计算游标，为了能支撑更长久的程序运行周期，以基准起始时间为截断点，仅计算此时间之后的时间戳，
+    /// u32 类型的秒级时间戳理论上能支撑程序运行 100+ 年。
+    fn from_system_time(time: SystemTime) -> Result<Self, FastsendError> {
+        epoch::encode(Cursor::offset_of(time)?)
+    }
+
+    /// `time` 距基准起始时间的秒数。
+    fn offset_of(time: SystemTime) -> Result<u64, FastsendError> {
+        // 标记为已使用，并取得标记时的基准起始时间
+        let mut timebase = TIMEBASE.load(std::sync::atomic::Ordering::SeqCst);
+        if timebase & TIMEBASE_IN_USE == 0 {
+            timebase = TIMEBASE.fetch_or(TIMEBASE_IN_USE, std::sync::atomic::Ordering::SeqCst);
         }
 
-        // 提供秒级别的游标控制，每个游标之间的间隔为 1 秒
-        Cursor(
-            TIMESTAMP
-                .checked_add(elapsed as u32)
-                .expect("`TIMESTAMP + elapsed` overflows over u32 on Cursor::new()"),
-        )
+        time.duration_since(SystemTime::UNIX_EPOCH)
+            .map_err(|_| FastsendError::ClockBeforeTimebase)?
+            .as_secs()
+            .checked_sub(timebase & !TIMEBASE_IN_USE)
+            .ok_or(FastsendError::ClockBeforeTimebase)
     }
 
     /// `next` 方法将在新的时间线（秒）创建 `Cursor`，其内部实现为通过 loop 自旋不断地尝试获取 `Cursor`，当
     /// 新生成的 `Cursor` 大于当前 `Cursor` 时结束自旋，并返回新的 `Cursor`。
     pub fn next(self) -> Self {
+        self.try_next()
+            .unwrap_or_else(|error| panic!("{} on Cursor::next()", error))
+    }
+
+    /// `next` 方法的非 panic 版本，等待期间获取游标失败时返回错误。
+    pub fn try_next(self) -> Result<Self, FastsendError> {
         let backoff = Backoff::new();
         loop {
-            let next = Self::new();
+            let next = Self::try_new()?;
             if next > self {
-                return next;
+                return Ok(next);
+            }
+
+            // 使用虚拟时间时不需要等待，直接推进虚拟时间
+            if crate::clock::advance(Duration::from_secs(1)) {
+                continue;
             }
 
             // 使用 `snooze` 而非 `spin`，在一秒的间隔内挂起当前线程也许已经足够让 CPU 处理更多其他内容，可能是
@@ -333,4 +651,132 @@ impl Default for Cursor {
 pub trait ConstructBlock: Sized {
     /// `n` 代表是对 `Block` 的第 N 次创建, 0 <= n < BlockFrame::QUEUE_SIZE。`cursor` 代表当前的时间锚点。
     fn construct_block(n: usize, cursor: Cursor) -> Block<Self>;
+
+    /// 按步长 `stride` 构造 `Block`，`Block` 中第 i 个元素应使用 `stride.sequence(n * Block::SIZE + i)` 作为发号
+    /// 序号，0 <= n < BlockFrame::QUEUE_SIZE / (stride.step() << stride.entropy_bits())，见
+    /// `BlockFrame::sequence_stride` 及 `BlockFrame::entropy_bits`。
+    ///
+    /// 缺省实现不支持步长，`stride` 不为 `SequenceStride::NONE` 时 panic。
+    fn construct_strided_block(n: usize, cursor: Cursor, stride: SequenceStride) -> Block<Self> {
+        assert_eq!(
+            stride,
+            SequenceStride::NONE,
+            "sequence stride is not supported by this type"
+        );
+        Self::construct_block(n, cursor)
+    }
+}
+
+/// `SequenceStride` 表示只使用满足 `sequence % step == offset` 的发号序号，见 `BlockFrame::sequence_stride`。
+///
+/// 设置了随机位数（见 `SequenceStride::with_entropy`）时，发号序号的低 `entropy` 位在每次调用 `sequence` 时由随机数
+/// 填充，步长作用于其余的高位，即 `(sequence >> entropy) % step == offset`。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SequenceStride {
+    offset: u16,
+    step: u16,
+    entropy: u32,
+}
+
+impl SequenceStride {
+    /// 不使用步长，即使用全部发号序号。
+    pub const NONE: SequenceStride = SequenceStride {
+        offset: 0,
+        step: 1,
+        entropy: 0,
+    };
+
+    /// `step` 的上限，保证每个 `Cursor` 下仍有足够数量的 `Block`。
+    pub const MAX_STEP: u16 = 256;
+
+    /// 随机位数的上限。
+    pub const MAX_ENTROPY_BITS: u32 = 8;
+
+    /// # Panics
+    ///
+    /// `step` 不在 1..=MAX_STEP 的范围内，或 `offset` 不小于 `step` 时 panic。
+    pub fn new(offset: u16, step: u16) -> Self {
+        assert!(
+            (1..=Self::MAX_STEP).contains(&step),
+            "sequence stride step must be within 1..={}",
+            Self::MAX_STEP
+        );
+        assert!(
+            offset < step,
+            "sequence stride offset must be less than step"
+        );
+        SequenceStride {
+            offset,
+            step,
+            entropy: 0,
+        }
+    }
+
+    /// 将发号序号的低 `bits` 位改为随机填充，使同一秒内的 id 难以被枚举（即便知道某个 id，也无法推算出相邻的 id）。
+    /// 每个 `Cursor` 下可用的发号序号数量相应地减少为原来的 1/2^`bits`，建议使用 4~8 位。
+    ///
+    /// # Panics
+    ///
+    /// `bits` 超过 `MAX_ENTROPY_BITS`，或步长与随机位数合计的缩减比例（`step << bits`）超过 `MAX_STEP` 时 panic。
+    pub fn with_entropy(mut self, bits: u32) -> Self {
+        assert!(
+            bits <= Self::MAX_ENTROPY_BITS,
+            "entropy bits must be within 0..={}",
+            Self::MAX_ENTROPY_BITS
+        );
+        assert!(
+            (self.step as u32) << bits <= Self::MAX_STEP as u32,
+            "sequence stride step combined with entropy bits must not exceed {}",
+            Self::MAX_STEP
+        );
+        self.entropy = bits;
+        self
+    }
+
+    pub fn offset(&self) -> u16 {
+        self.offset
+    }
+
+    pub fn step(&self) -> u16 {
+        self.step
+    }
+
+    pub fn entropy_bits(&self) -> u32 {
+        self.entropy
+    }
+
+    /// 第 `k` 个可用的发号序号，0 <= k < `capacity()`。设置了随机位数时低位为随机数，每次调用的结果都不同，但不同
+    /// 的 `k` 得到的发号序号一定不同。
+    pub fn sequence(&self, k: usize) -> u16 {
+        let sequence = ((k * self.step as usize + self.offset as usize) << self.entropy) as u16;
+        if self.entropy == 0 {
+            return sequence;
+        }
+
+        let mask = (1u16 << self.entropy) - 1;
+
+        // 处于确定性模式（见 `fastsend::testing::seed`）时使用全局的随机数生成器，否则使用线程本地的生成器
+        #[cfg(feature = "test-util")]
+        if let Some(random) = crate::testing::with_rng(|rng| rng.gen::<u16>()) {
+            return sequence | (random & mask);
+        }
+
+        sequence | (rand::thread_rng().gen::<u16>() & mask)
+    }
+
+    /// 每个 `Cursor` 下可用的发号序号数量。
+    pub fn capacity(&self) -> usize {
+        (u16::MAX as usize + 1) / self.ratio()
+    }
+
+    /// 全部发号序号与可用的发号序号数量之比，即 `step << entropy_bits`。
+    pub(crate) fn ratio(&self) -> usize {
+        (self.step as usize) << self.entropy
+    }
+}
+
+impl Default for SequenceStride {
+    fn default() -> Self {
+        Self::NONE
+    }
 }
@@ -0,0 +1,178 @@
+// `BlockFrame` 中的游标与补充状态使用原子变量表示，等待补充的 `Waker` 使用 `Mutex` 保护，在
+// `cfg(fastsend_loom)` 下替换为 loom 的实现，以便 loom 可以穷举补充流程中所有可能的线程交错。
+#[cfg(fastsend_loom)]
+pub(crate) use loom::sync::{
+    atomic::{AtomicBool, AtomicU32, Ordering},
+    Mutex, MutexGuard,
+};
+#[cfg(not(fastsend_loom))]
+pub(crate) use std::sync::{
+    atomic::{AtomicBool, AtomicU32, Ordering},
+    Mutex, MutexGuard,
+};
+
+/// `Backend` 描述了 `BlockFrame` 补充 `Block` 时所依赖的同步原语：存放 `Block` 的有界队列，以及执行补充任务
+/// 的方式。不同的实现通过 feature 或 cfg 选择，见 `Selected`。
+pub(crate) trait Backend: 'static {
+    type Queue<T>: Queue<T>;
+
+    /// 在后台执行补充任务，`f` 执行完毕前 `spawn` 即可返回。
+    fn spawn<F: FnOnce() + Send + 'static>(f: F);
+}
+
+/// `Queue` 是容量固定的多生产者多消费者队列。
+pub(crate) trait Queue<T> {
+    fn with_capacity(capacity: usize) -> Self;
+
+    /// 队列已满时返回 `Err`，并归还 `value`。
+    fn push(&self, value: T) -> Result<(), T>;
+
+    fn pop(&self) -> Option<T>;
+
+    fn len(&self) -> usize;
+}
+
+/// 缺省实现：crossbeam 的无锁队列 `ArrayQueue`，补充任务在新的系统线程中执行。
+#[cfg(all(not(fastsend_loom), not(feature = "parking_lot")))]
+#[derive(Debug)]
+pub(crate) struct Crossbeam;
+
+#[cfg(all(not(fastsend_loom), not(feature = "parking_lot")))]
+impl Backend for Crossbeam {
+    type Queue<T> = crossbeam::queue::ArrayQueue<T>;
+
+    fn spawn<F: FnOnce() + Send + 'static>(f: F) {
+        std::thread::spawn(f);
+    }
+}
+
+#[cfg(all(not(fastsend_loom), not(feature = "parking_lot")))]
+impl<T> Queue<T> for crossbeam::queue::ArrayQueue<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        crossbeam::queue::ArrayQueue::new(capacity)
+    }
+
+    fn push(&self, value: T) -> Result<(), T> {
+        crossbeam::queue::ArrayQueue::push(self, value)
+    }
+
+    fn pop(&self) -> Option<T> {
+        crossbeam::queue::ArrayQueue::pop(self)
+    }
+
+    fn len(&self) -> usize {
+        crossbeam::queue::ArrayQueue::len(self)
+    }
+}
+
+/// 'parking_lot' 实现：以 `parking_lot::Mutex` 保护的 `VecDeque` 作为队列，适用于更在意内存占用而非极端
+/// 并发的场合（`ArrayQueue` 会为每个槽位额外保存一个 stamp）。
+#[cfg(feature = "parking_lot")]
+#[derive(Debug)]
+pub(crate) struct ParkingLot;
+
+#[cfg(feature = "parking_lot")]
+impl Backend for ParkingLot {
+    type Queue<T> = MutexQueue<T>;
+
+    fn spawn<F: FnOnce() + Send + 'static>(f: F) {
+        std::thread::spawn(f);
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+#[derive(Debug)]
+pub(crate) struct MutexQueue<T> {
+    capacity: usize,
+    inner: parking_lot::Mutex<std::collections::VecDeque<T>>,
+}
+
+#[cfg(feature = "parking_lot")]
+impl<T> Queue<T> for MutexQueue<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        MutexQueue {
+            capacity,
+            inner: parking_lot::Mutex::new(std::collections::VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    fn push(&self, value: T) -> Result<(), T> {
+        let mut inner = self.inner.lock();
+        if inner.len() >= self.capacity {
+            return Err(value);
+        }
+
+        inner.push_back(value);
+        Ok(())
+    }
+
+    fn pop(&self) -> Option<T> {
+        self.inner.lock().pop_front()
+    }
+
+    fn len(&self) -> usize {
+        self.inner.lock().len()
+    }
+}
+
+/// loom 实现：队列与补充线程均由 loom 模拟，只在 `RUSTFLAGS="--cfg fastsend_loom"` 下编译。
+#[cfg(fastsend_loom)]
+#[derive(Debug)]
+pub(crate) struct Loom;
+
+#[cfg(fastsend_loom)]
+impl Backend for Loom {
+    type Queue<T> = LoomQueue<T>;
+
+    fn spawn<F: FnOnce() + Send + 'static>(f: F) {
+        loom::thread::spawn(f);
+    }
+}
+
+#[cfg(fastsend_loom)]
+#[derive(Debug)]
+pub(crate) struct LoomQueue<T> {
+    capacity: usize,
+    inner: loom::sync::Mutex<std::collections::VecDeque<T>>,
+}
+
+#[cfg(fastsend_loom)]
+impl<T> Queue<T> for LoomQueue<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        LoomQueue {
+            capacity,
+            inner: loom::sync::Mutex::new(std::collections::VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    fn push(&self, value: T) -> Result<(), T> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.len() >= self.capacity {
+            return Err(value);
+        }
+
+        inner.push_back(value);
+        Ok(())
+    }
+
+    fn pop(&self) -> Option<T> {
+        self.inner.lock().unwrap().pop_front()
+    }
+
+    fn len(&self) -> usize {
+        self.inner.lock().unwrap().len()
+    }
+}
+
+/// 编译时选定的实现，优先级为 `cfg(fastsend_loom)` > 'parking_lot' > crossbeam。
+#[cfg(fastsend_loom)]
+pub(crate) type Selected = Loom;
+
+#[cfg(all(not(fastsend_loom), feature = "parking_lot"))]
+pub(crate) type Selected = ParkingLot;
+
+#[cfg(all(not(fastsend_loom), not(feature = "parking_lot")))]
+pub(crate) type Selected = Crossbeam;
+
+/// 选定实现下存放 `T` 的队列。
+pub(crate) type SelectedQueue<T> = <Selected as Backend>::Queue<T>;
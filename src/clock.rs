@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// fastsend 中所有基于时间的组件（`Cursor`、`TimeSerialer` 以及各个按时间排序的 `Serialer`）获取当前时间的
+/// 统一入口。开启 'test-util' feature 后，可以通过 `fastsend::testing::freeze_time` 等方法替换为虚拟时间，
+/// 未开启时等价于 `SystemTime::now`。
+pub(crate) fn now() -> SystemTime {
+    mocked().unwrap_or_else(SystemTime::now)
+}
+
+/// 本地时区的当前时间。
+#[cfg(any(feature = "invoice", feature = "order"))]
+pub(crate) fn now_local() -> chrono::DateTime<chrono::Local> {
+    now().into()
+}
+
+/// 使用虚拟时间时返回虚拟的当前时间，否则返回 `None`。
+pub(crate) fn mocked() -> Option<SystemTime> {
+    #[cfg(feature = "test-util")]
+    return crate::testing::clock::now();
+
+    #[cfg(not(feature = "test-util"))]
+    None
+}
+
+/// 在需要等待时间流逝的场合（如等待下一秒），如果使用的是虚拟时间，则直接推进虚拟时间并返回 `true`，调用方不需要
+/// 再自旋等待；使用系统时间时返回 `false`。
+pub(crate) fn advance(duration: Duration) -> bool {
+    #[cfg(feature = "test-util")]
+    return crate::testing::clock::advance_mocked(duration);
+
+    #[cfg(not(feature = "test-util"))]
+    {
+        let _ = duration;
+        false
+    }
+}
+
+/// 距离下一个整秒的时间。
+#[cfg(not(feature = "strict_order"))]
+pub(crate) fn until_next_second() -> Duration {
+    let subsec = now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.subsec_nanos())
+        .unwrap_or_default();
+    Duration::from_secs(1) - Duration::from_nanos(subsec as u64)
+}
+
+/// 等待 `duration`（不受虚拟时间影响），不会阻塞异步运行时。所有等待共用一个计时线程，见 `Sleep`。
+pub(crate) fn sleep(duration: Duration) -> Sleep {
+    Sleep {
+        deadline: Instant::now() + duration,
+        key: None,
+    }
+}
+
+/// `sleep` 返回的 `Future`。
+///
+/// 等待中的 `Sleep` 按到期时间登记在 `TIMERS` 中，由首次等待时启动的计时线程在到期时唤醒；`Sleep` 被丢弃时（如
+/// `next_token_timeout` 在超时之前获取到了 `Token`）立即撤销登记，因此大量提前结束的等待不会留下任何线程或登记。
+#[derive(Debug)]
+pub(crate) struct Sleep {
+    deadline: Instant,
+
+    /// 在 `TIMERS` 中登记的键，尚未登记时为 `None`。
+    key: Option<(Instant, u64)>,
+}
+
+#[derive(Debug)]
+struct Timers {
+    /// 以到期时间及登记序号为键的 `Waker`。
+    entries: BTreeMap<(Instant, u64), Waker>,
+
+    next: u64,
+
+    /// 计时线程是否已经启动。
+    running: bool,
+}
+
+static TIMERS: Mutex<Timers> = Mutex::new(Timers {
+    entries: BTreeMap::new(),
+    next: 0,
+    running: false,
+});
+
+/// 登记了更早的到期时间时通知计时线程重新计算等待时间。
+static CHANGED: Condvar = Condvar::new();
+
+fn timers() -> MutexGuard<'static, Timers> {
+    TIMERS.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut timers = timers();
+        if Instant::now() >= self.deadline {
+            if let Some(key) = self.key.take() {
+                timers.entries.remove(&key);
+            }
+            return Poll::Ready(());
+        }
+
+        match self.key {
+            Some(key) => {
+                if let Some(waker) = timers.entries.get_mut(&key) {
+                    waker.clone_from(cx.waker());
+                }
+            }
+            None => {
+                let key = (self.deadline, timers.next);
+                timers.next += 1;
+                timers.entries.insert(key, cx.waker().clone());
+                self.key = Some(key);
+
+                if !timers.running {
+                    timers.running = true;
+                    thread::spawn(run_timers);
+                }
+
+                if timers.entries.keys().next() == Some(&key) {
+                    CHANGED.notify_one();
+                }
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            timers().entries.remove(&key);
+        }
+    }
+}
+
+/// 计时线程：唤醒全部到期的 `Sleep`，并等待至下一个到期时间或有更早的登记。唤醒在释放锁之后进行。
+fn run_timers() {
+    loop {
+        let mut timers = timers();
+        let now = Instant::now();
+
+        let mut expired = Vec::new();
+        while let Some(entry) = timers.entries.first_entry() {
+            if entry.key().0 > now {
+                break;
+            }
+            expired.push(entry.remove());
+        }
+
+        if expired.is_empty() {
+            // 等待结束后重新获取锁并检查，poison 不影响登记的内容
+            match timers.entries.keys().next() {
+                Some(&(deadline, _)) => drop(CHANGED.wait_timeout(timers, deadline - now)),
+                None => drop(CHANGED.wait(timers)),
+            }
+            continue;
+        }
+
+        drop(timers);
+        expired.into_iter().for_each(Waker::wake);
+    }
+}
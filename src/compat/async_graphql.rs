@@ -0,0 +1,57 @@
+//! async-graphql 的标量实现：
+//!
+//!   - `Token` 输出为十进制字符串，因为 64 位整数超出了 JavaScript 能精确表示的范围（2^53），输入同时接受
+//!     字符串和整数；
+//!   - `UUID`（需同时启用 'uuid' feature）输出为标准的带连字符形式。
+
+use crate::{Token, ID};
+use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+
+/// 64 位的 fastsend id，以十进制字符串表示。
+#[Scalar(name = "Token")]
+impl ScalarType for Token {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        let id = match &value {
+            Value::String(s) => s.parse::<u64>().map_err(InputValueError::custom)?,
+            Value::Number(n) => n
+                .as_u64()
+                .ok_or_else(|| InputValueError::custom("expected a non-negative integer"))?,
+            _ => return Err(InputValueError::expected_type(value)),
+        };
+
+        Ok(Token::from_id(id))
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        matches!(value, Value::String(_) | Value::Number(_))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.id().to_string())
+    }
+}
+
+#[cfg(feature = "uuid")]
+mod uuid {
+    use crate::serial::uuid::UUID;
+    use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+
+    /// V3、V4、V5 或 V7 版本的 UUID，以带连字符的形式表示。
+    #[Scalar(name = "UUID")]
+    impl ScalarType for UUID {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            match &value {
+                Value::String(s) => s.parse().map_err(InputValueError::custom),
+                _ => Err(InputValueError::expected_type(value)),
+            }
+        }
+
+        fn is_valid(value: &Value) -> bool {
+            matches!(value, Value::String(_))
+        }
+
+        fn to_value(&self) -> Value {
+            Value::String(self.to_string())
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! `UUID` 的 bincode 实现：只编码 `to_bytes` 的 16 字节，解码时与 `UUID::from_bytes` 一样校验版本号，不支持的版本
+//! 返回错误，而不是构造出版本号与字节内容不一致的 `UUID`。
+
+use crate::serial::uuid::UUID;
+use bincode::de::Decoder;
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{Decode, Encode};
+
+impl Encode for UUID {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.to_bytes().encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for UUID {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        UUID::from_bytes(<[u8; 16]>::decode(decoder)?)
+            .ok_or(DecodeError::Other("unsupported uuid version"))
+    }
+}
+
+bincode::impl_borrow_decode!(UUID);
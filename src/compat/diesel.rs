@@ -0,0 +1,61 @@
+//! Diesel 的 `ToSql`/`FromSql` 实现，`Token` 与 `UUID` 同时派生了 `AsExpression` 和 `FromSqlRow`，因此可以
+//! 直接用作 `Insertable`、`Queryable` 等 schema 结构体的字段：
+//!
+//!   - `Token` 映射为 `BigInt`，id 的最高位会被映射为符号位，读取时按位还原，写入支持 Postgres 与 MySQL，
+//!     读取支持所有后端。SQLite 的参数以值而非字节绑定，`ToSql` 无法借用临时计算出的 id，而为 SQLite 单独实现
+//!     又会与通用的实现冲突，因此 `Token` 不能直接写入 SQLite，需要改为绑定 `token.id() as i64`；
+//!   - `UUID`（需启用 'uuid' 与 'diesel-postgres' feature）映射为 Postgres 的 `Uuid`。
+//!
+//! 'diesel' feature 本身不启用 diesel 的任何后端，Postgres 专属的映射由 'diesel-postgres' feature 启用。
+
+use crate::{Token, ID};
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::query_builder::bind_collector::RawBytesBindCollector;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::BigInt;
+
+impl<DB> ToSql<BigInt, DB> for Token
+where
+    for<'c> DB: Backend<BindCollector<'c> = RawBytesBindCollector<DB>>,
+    i64: ToSql<BigInt, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        let id = self.id() as i64;
+        <i64 as ToSql<BigInt, DB>>::to_sql(&id, &mut out.reborrow())
+    }
+}
+
+impl<DB: Backend> FromSql<BigInt, DB> for Token
+where
+    i64: FromSql<BigInt, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        <i64 as FromSql<BigInt, DB>>::from_sql(bytes).map(|id| Token::from_id(id as u64))
+    }
+}
+
+#[cfg(all(feature = "uuid", feature = "diesel-postgres"))]
+mod uuid {
+    use crate::serial::uuid::UUID;
+    use diesel::deserialize::{self, FromSql};
+    use diesel::pg::{Pg, PgValue};
+    use diesel::serialize::{self, IsNull, Output, ToSql};
+    use diesel::sql_types::Uuid;
+    use std::io::Write;
+
+    impl ToSql<Uuid, Pg> for UUID {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+            out.write_all(&self.to_bytes())?;
+            Ok(IsNull::No)
+        }
+    }
+
+    impl FromSql<Uuid, Pg> for UUID {
+        fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+            let bytes = <[u8; 16]>::try_from(value.as_bytes())
+                .map_err(|_| "invalid uuid length, expected 16 bytes")?;
+            Ok(UUID::from_bytes(bytes).ok_or("unsupported uuid version")?)
+        }
+    }
+}
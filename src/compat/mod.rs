@@ -0,0 +1,25 @@
+//! 为 fastsend 生成的 id 类型实现第三方库的 trait，各实现通过对应的 feature 启用，不包含任何公开的 API。
+
+#[cfg(feature = "serde")]
+mod serde;
+
+#[cfg(feature = "sqlx")]
+mod sqlx;
+
+#[cfg(feature = "diesel")]
+mod diesel;
+
+#[cfg(feature = "sea-orm")]
+mod sea_orm;
+
+#[cfg(feature = "async-graphql")]
+mod async_graphql;
+
+#[cfg(feature = "uuid_interop")]
+mod uuid;
+
+#[cfg(all(feature = "rkyv", feature = "uuid"))]
+mod rkyv;
+
+#[cfg(all(feature = "bincode", feature = "uuid"))]
+mod bincode;
@@ -0,0 +1,36 @@
+//! `UUID` 的 rkyv 实现：只归档 `to_bytes` 的 16 字节，反序列化时与 `UUID::from_bytes` 一样校验版本号，不支持的版本
+//! 返回错误，而不是构造出版本号与字节内容不一致的 `UUID`。
+
+use crate::serial::uuid::{ParseUUIDError, UUID};
+use rkyv::rancor::{Fallible, Source};
+use rkyv::{Archive, Deserialize, Place, Serialize};
+
+/// `UUID` 的归档形式，即 `to_bytes` 的 16 字节。
+#[doc(hidden)]
+#[derive(Archive, Serialize)]
+#[rkyv(archived = ArchivedUUID)]
+pub struct UUIDBytes([u8; 16]);
+
+impl Archive for UUID {
+    type Archived = ArchivedUUID;
+    type Resolver = <UUIDBytes as Archive>::Resolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        UUIDBytes(self.to_bytes()).resolve(resolver, out)
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for UUID {
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        UUIDBytes(self.to_bytes()).serialize(serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<UUID, D> for ArchivedUUID
+where
+    D::Error: Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<UUID, D::Error> {
+        UUID::from_bytes(self.0).ok_or_else(|| D::Error::new(ParseUUIDError))
+    }
+}
@@ -0,0 +1,103 @@
+//! SeaORM 的值转换实现，使 `Token`（映射为 BIGINT）和 `UUID`（需同时启用 'uuid' feature，映射为 UUID）可以
+//! 直接用作实体（`Model`）的字段，包括主键，例如 `ActiveModel { id: Set(fastsend::next_token().await), .. }`。
+//! id 的最高位会被映射为符号位，读取时按位还原。
+
+use crate::{Token, ID};
+use sea_orm::sea_query::{ArrayType, ColumnType, Nullable, Value, ValueType, ValueTypeErr};
+use sea_orm::{ColIdx, DbErr, QueryResult, TryFromU64, TryGetError, TryGetable};
+
+impl From<Token> for Value {
+    fn from(token: Token) -> Self {
+        Value::BigInt(Some(token.id() as i64))
+    }
+}
+
+impl Nullable for Token {
+    fn null() -> Value {
+        Value::BigInt(None)
+    }
+}
+
+impl ValueType for Token {
+    fn try_from(v: Value) -> Result<Self, ValueTypeErr> {
+        <i64 as ValueType>::try_from(v).map(|id| Token::from_id(id as u64))
+    }
+
+    fn type_name() -> String {
+        "Token".to_owned()
+    }
+
+    fn array_type() -> ArrayType {
+        ArrayType::BigInt
+    }
+
+    fn column_type() -> ColumnType {
+        ColumnType::BigInteger
+    }
+}
+
+impl TryGetable for Token {
+    fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+        <i64 as TryGetable>::try_get_by(res, index).map(|id| Token::from_id(id as u64))
+    }
+}
+
+impl TryFromU64 for Token {
+    fn try_from_u64(n: u64) -> Result<Self, DbErr> {
+        Ok(Token::from_id(n))
+    }
+}
+
+#[cfg(feature = "uuid")]
+mod uuid {
+    use crate::serial::uuid::UUID;
+    use sea_orm::prelude::Uuid;
+    use sea_orm::sea_query::{ArrayType, ColumnType, Nullable, Value, ValueType, ValueTypeErr};
+    use sea_orm::{ColIdx, DbErr, QueryResult, TryFromU64, TryGetError, TryGetable};
+
+    impl From<UUID> for Value {
+        fn from(uuid: UUID) -> Self {
+            Uuid::from_bytes(uuid.to_bytes()).into()
+        }
+    }
+
+    impl Nullable for UUID {
+        fn null() -> Value {
+            <Uuid as Nullable>::null()
+        }
+    }
+
+    impl ValueType for UUID {
+        fn try_from(v: Value) -> Result<Self, ValueTypeErr> {
+            let uuid = <Uuid as ValueType>::try_from(v)?;
+            UUID::from_bytes(uuid.into_bytes()).ok_or(ValueTypeErr)
+        }
+
+        fn type_name() -> String {
+            "UUID".to_owned()
+        }
+
+        fn array_type() -> ArrayType {
+            ArrayType::Uuid
+        }
+
+        fn column_type() -> ColumnType {
+            ColumnType::Uuid
+        }
+    }
+
+    impl TryGetable for UUID {
+        fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+            let uuid = <Uuid as TryGetable>::try_get_by(res, index)?;
+            UUID::from_bytes(uuid.into_bytes()).ok_or_else(|| {
+                TryGetError::DbErr(DbErr::Type(format!("unsupported uuid version: {}", uuid)))
+            })
+        }
+    }
+
+    impl TryFromU64 for UUID {
+        fn try_from_u64(_: u64) -> Result<Self, DbErr> {
+            Err(DbErr::ConvertFromU64("UUID"))
+        }
+    }
+}
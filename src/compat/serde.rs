@@ -0,0 +1,15 @@
+use crate::{Token, ID};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// `Token` 始终以数字形式序列化，需要以字符串表示时使用 `fastsend::js_safe`（'js_safe' feature）。
+impl Serialize for Token {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.id())
+    }
+}
+
+impl<'de> Deserialize<'de> for Token {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u64::deserialize(deserializer).map(Token::from_id)
+    }
+}
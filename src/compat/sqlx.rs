@@ -0,0 +1,92 @@
+//! sqlx 的 `Type`/`Encode`/`Decode` 实现，对所有数据库（Postgres、MySQL、SQLite 等）通用：
+//!
+//!   - `Token` 以 64 位有符号整数（BIGINT / INTEGER）存储，id 的最高位会被映射为符号位，读取时按位还原；
+//!   - `UUID`（需同时启用 'uuid' feature）以数据库的 uuid 类型存储（Postgres 为 UUID，MySQL 为 BINARY(16)，
+//!     SQLite 为 BLOB）。
+
+use crate::{Token, ID};
+use sqlx::database::Database;
+use sqlx::decode::Decode;
+use sqlx::encode::{Encode, IsNull};
+use sqlx::error::BoxDynError;
+use sqlx::types::Type;
+
+impl<DB: Database> Type<DB> for Token
+where
+    i64: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <i64 as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <i64 as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for Token
+where
+    i64: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        (self.id() as i64).encode_by_ref(buf)
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for Token
+where
+    i64: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(Token::from_id(<i64 as Decode<DB>>::decode(value)? as u64))
+    }
+}
+
+#[cfg(feature = "uuid")]
+mod uuid {
+    use crate::serial::uuid::UUID;
+    use sqlx::database::Database;
+    use sqlx::decode::Decode;
+    use sqlx::encode::{Encode, IsNull};
+    use sqlx::error::BoxDynError;
+    use sqlx::types::{Type, Uuid};
+
+    impl<DB: Database> Type<DB> for UUID
+    where
+        Uuid: Type<DB>,
+    {
+        fn type_info() -> DB::TypeInfo {
+            <Uuid as Type<DB>>::type_info()
+        }
+
+        fn compatible(ty: &DB::TypeInfo) -> bool {
+            <Uuid as Type<DB>>::compatible(ty)
+        }
+    }
+
+    impl<'q, DB: Database> Encode<'q, DB> for UUID
+    where
+        Uuid: Encode<'q, DB>,
+    {
+        fn encode_by_ref(
+            &self,
+            buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+        ) -> Result<IsNull, BoxDynError> {
+            Uuid::from_bytes(self.to_bytes()).encode_by_ref(buf)
+        }
+    }
+
+    impl<'r, DB: Database> Decode<'r, DB> for UUID
+    where
+        Uuid: Decode<'r, DB>,
+    {
+        fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+            let uuid = <Uuid as Decode<DB>>::decode(value)?;
+            UUID::from_bytes(uuid.into_bytes())
+                .ok_or_else(|| format!("unsupported uuid version: {}", uuid).into())
+        }
+    }
+}
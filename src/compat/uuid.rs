@@ -0,0 +1,34 @@
+//! 与 `uuid` crate 的互相转换：`UUID` 可以与 `uuid::Uuid` 无损互转，`TypeId` 的后缀（任意版本的 UUID）可以转换为
+//! `uuid::Uuid`，`uuid::Uuid` 也实现了 `Serial`，可以直接作为 `Serialer` 的输入。
+
+use crate::serial::uuid::{ParseUUIDError, UUID};
+use crate::{Serial, Serialer};
+use uuid_crate::Uuid;
+
+impl From<UUID> for Uuid {
+    fn from(uuid: UUID) -> Self {
+        Uuid::from_bytes(uuid.to_bytes())
+    }
+}
+
+/// `UUID` 仅支持 V3、V4、V5、V7，其余版本（包括 nil UUID）转换失败。
+impl TryFrom<Uuid> for UUID {
+    type Error = ParseUUIDError;
+
+    fn try_from(uuid: Uuid) -> Result<Self, Self::Error> {
+        UUID::from_bytes(uuid.into_bytes()).ok_or(ParseUUIDError)
+    }
+}
+
+#[cfg(feature = "typeid")]
+impl From<&crate::serial::typeid::TypeId> for Uuid {
+    fn from(typeid: &crate::serial::typeid::TypeId) -> Self {
+        Uuid::from_bytes(typeid.to_bytes())
+    }
+}
+
+impl Serial for Uuid {
+    fn serial<S: Serialer>(self, serialer: &mut S) {
+        serialer.feed(self.as_bytes());
+    }
+}
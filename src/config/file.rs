@@ -0,0 +1,281 @@
+use super::{Config, ConfigError, DeviceIdSetting};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// `fastsend.toml` 的文件结构，例如：
+///
+/// ```toml
+/// device_id = 3          # 或 "mac" / "k8s" / "ip"
+/// timebase = 1639110453  # `Cursor` 的基准起始时间，秒级 Unix 时间戳
+/// blocks_per_claim = 4   # 每个线程每次领取的 `Block` 数量
+/// sequence_stride = { offset = 1, step = 2 }  # 只使用奇数发号序号
+/// id_permutation = 20211210  # `Token::public_id` 使用的全局置换的种子
+///
+/// [shard]
+/// epoch = 1577836800000  # 毫秒级 Unix 时间戳
+///
+/// [presets.coupon.spring]
+/// prefix = "SPRING"
+/// length = 8
+/// group = 4
+///
+/// [presets.slug.links]
+/// length = 7
+/// base58 = true
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    device_id: Option<DeviceIdValue>,
+
+    timebase: Option<u64>,
+
+    blocks_per_claim: Option<usize>,
+
+    sequence_stride: Option<StrideSection>,
+
+    id_permutation: Option<u64>,
+
+    #[cfg(feature = "sharded")]
+    shard: Option<ShardSection>,
+
+    // 未启用任何 `Serialer` feature 时预设为空
+    #[cfg_attr(
+        not(any(feature = "coupon", feature = "slug", feature = "cuid2")),
+        allow(dead_code)
+    )]
+    #[serde(default)]
+    presets: PresetsSection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DeviceIdValue {
+    Value(u8),
+    Source(String),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StrideSection {
+    offset: u16,
+    step: u16,
+}
+
+#[cfg(feature = "sharded")]
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ShardSection {
+    epoch: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PresetsSection {
+    #[cfg(feature = "coupon")]
+    #[serde(default)]
+    coupon: std::collections::HashMap<String, CouponPreset>,
+
+    #[cfg(feature = "slug")]
+    #[serde(default)]
+    slug: std::collections::HashMap<String, SlugPreset>,
+
+    #[cfg(feature = "cuid2")]
+    #[serde(default)]
+    cuid2: std::collections::HashMap<String, Cuid2Preset>,
+}
+
+#[cfg(feature = "coupon")]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CouponPreset {
+    prefix: Option<String>,
+    alphabet: Option<String>,
+    length: Option<usize>,
+    group: Option<usize>,
+}
+
+#[cfg(feature = "slug")]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SlugPreset {
+    length: Option<usize>,
+    #[serde(default)]
+    base58: bool,
+    retry_times: Option<usize>,
+}
+
+#[cfg(feature = "cuid2")]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Cuid2Preset {
+    length: Option<usize>,
+}
+
+impl Config {
+    /// 从 TOML 文件 `path` 中读取配置，读取后仍需调用 `apply` 才会生效。
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Config, ConfigError> {
+        Self::from_toml(&fs::read_to_string(path).map_err(ConfigError::Io)?)
+    }
+
+    /// 从 TOML 字符串中读取配置，读取后仍需调用 `apply` 才会生效。配置项的合法性在读取时校验，确保 `apply` 及
+    /// 之后获取预设时不会因为配置错误而 panic。
+    pub fn from_toml(s: &str) -> Result<Config, ConfigError> {
+        let file: ConfigFile =
+            toml::from_str(s).map_err(|error| ConfigError::Parse(error.to_string()))?;
+
+        let mut config = Config::new();
+
+        if let Some(value) = file.device_id {
+            config = config.device_id(match value {
+                DeviceIdValue::Value(device_id) => DeviceIdSetting::Value(device_id),
+                DeviceIdValue::Source(source) => match &*source {
+                    "mac" => DeviceIdSetting::Mac,
+                    "k8s" => DeviceIdSetting::K8s,
+                    "ip" => DeviceIdSetting::Ip,
+                    _ => return Err(invalid(format!("unknown device_id source {:?}", source))),
+                },
+            });
+        }
+
+        if let Some(timebase) = file.timebase {
+            let timebase = std::time::UNIX_EPOCH + std::time::Duration::from_secs(timebase);
+            if timebase > std::time::SystemTime::now() {
+                return Err(invalid("timebase is later than now".to_owned()));
+            }
+            config = config.timebase(timebase);
+        }
+
+        if let Some(blocks) = file.blocks_per_claim {
+            if blocks == 0 {
+                return Err(invalid("blocks_per_claim must be positive".to_owned()));
+            }
+            config = config.blocks_per_claim(blocks);
+        }
+
+        if let Some(stride) = file.sequence_stride {
+            if !(1..=crate::SequenceStride::MAX_STEP).contains(&stride.step)
+                || stride.offset >= stride.step
+            {
+                return Err(invalid(format!(
+                    "sequence_stride must satisfy offset < step <= {}",
+                    crate::SequenceStride::MAX_STEP
+                )));
+            }
+            config = config.sequence_stride(stride.offset, stride.step);
+        }
+
+        if let Some(seed) = file.id_permutation {
+            config = config.id_permutation(seed);
+        }
+
+        #[cfg(feature = "sharded")]
+        if let Some(epoch) = file.shard.and_then(|shard| shard.epoch) {
+            let epoch = std::time::UNIX_EPOCH + std::time::Duration::from_millis(epoch);
+            if epoch > std::time::SystemTime::now() {
+                return Err(invalid("shard epoch is later than now".to_owned()));
+            }
+            config = config.shard_epoch(epoch);
+        }
+
+        #[cfg(feature = "coupon")]
+        for (name, preset) in file.presets.coupon {
+            if let Some(prefix) = &preset.prefix {
+                ensure(
+                    prefix.bytes().all(|c| c.is_ascii_alphanumeric()),
+                    &name,
+                    "prefix must be alphanumeric",
+                )?;
+            }
+            if let Some(alphabet) = &preset.alphabet {
+                let upper = alphabet.to_ascii_uppercase();
+                let unique = upper.bytes().collect::<std::collections::HashSet<_>>();
+                ensure(
+                    upper.len() >= 2
+                        && upper.bytes().all(|c| c.is_ascii_alphanumeric())
+                        && unique.len() == upper.len(),
+                    &name,
+                    "alphabet must contain at least 2 distinct alphanumeric characters",
+                )?;
+            }
+            ensure(preset.length != Some(0), &name, "length must be positive")?;
+
+            config = config.preset(&name, move || {
+                let mut serialer = crate::CouponSerialer::new();
+                if let Some(prefix) = &preset.prefix {
+                    serialer = serialer.prefix(prefix);
+                }
+                if let Some(alphabet) = &preset.alphabet {
+                    serialer = serialer.alphabet(alphabet);
+                }
+                if let Some(length) = preset.length {
+                    serialer = serialer.length(length);
+                }
+                if let Some(group) = preset.group {
+                    serialer = serialer.group(group);
+                }
+                serialer
+            });
+        }
+
+        #[cfg(feature = "slug")]
+        for (name, preset) in file.presets.slug {
+            ensure(
+                preset
+                    .length
+                    .is_none_or(|length| (6..=10).contains(&length)),
+                &name,
+                "length must be within 6..=10",
+            )?;
+
+            config = config.preset(&name, move || {
+                let mut serialer = crate::SlugSerialer::new();
+                if let Some(length) = preset.length {
+                    serialer = serialer.length(length);
+                }
+                if preset.base58 {
+                    serialer = serialer.base58();
+                }
+                if let Some(retry_times) = preset.retry_times {
+                    serialer = serialer.retry_times(retry_times);
+                }
+                serialer
+            });
+        }
+
+        #[cfg(feature = "cuid2")]
+        for (name, preset) in file.presets.cuid2 {
+            ensure(
+                preset
+                    .length
+                    .is_none_or(|length| (2..=32).contains(&length)),
+                &name,
+                "length must be within 2..=32",
+            )?;
+
+            config = config.preset(&name, move || {
+                let serialer = crate::Cuid2Serialer::new();
+                match preset.length {
+                    Some(length) => serialer.length(length),
+                    None => serialer,
+                }
+            });
+        }
+
+        Ok(config)
+    }
+}
+
+fn invalid(message: String) -> ConfigError {
+    ConfigError::Invalid(message)
+}
+
+#[cfg(any(feature = "coupon", feature = "slug", feature = "cuid2"))]
+fn ensure(condition: bool, preset: &str, message: &str) -> Result<(), ConfigError> {
+    if condition {
+        Ok(())
+    } else {
+        Err(invalid(format!("preset {:?}: {}", preset, message)))
+    }
+}
@@ -0,0 +1,259 @@
+use lazy_static::lazy_static;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+#[cfg(feature = "config")]
+mod file;
+
+/// 预设的构造函数，以 `Box<dyn Any>` 的形式擦除 `Serialer` 的具体类型。
+type Factory = Box<dyn Any + Send + Sync>;
+
+lazy_static! {
+    /// 通过 `Config::apply` 注册的全局预设，以「`Serialer` 类型 + 预设名称」作为键。
+    static ref PRESETS: RwLock<HashMap<(TypeId, String), Factory>> = RwLock::new(HashMap::new());
+}
+
+/// 设备号的配置方式。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DeviceIdSetting {
+    /// 直接指定设备号。
+    Value(u8),
+
+    /// 从主网卡的 MAC 地址中派生，见 `device_id::from_mac`。
+    Mac,
+
+    /// 从 StatefulSet 的 pod 序号中获取，见 `device_id::from_k8s`。
+    K8s,
+
+    /// 从私有 IP 地址的最后一个字节中获取，见 `device_id::from_ip`。
+    Ip,
+}
+
+/// ## 运行时配置
+///
+/// `Config` 用于在运行时集中配置 fastsend，而不必依赖编译期常量或逐个设置环境变量，包括：
+///
+///   - 设备号（优先级高于环境变量 `FASTSEND_DEVICE_ID`）；
+///   - `Cursor` 的基准起始时间，见 `fastsend::set_timebase`；
+///   - 全局 `ShardFrame` 的起始时间（需启用 'sharded' feature）；
+///   - 每个线程每次领取的 `Block` 数量，见 `fastsend::set_blocks_per_claim`；
+///   - 全局发号序号的步长，见 `fastsend::set_sequence_stride`；
+///   - 对外公开 id 的全局置换，见 `fastsend::set_id_permutation`；
+///   - 具名的 `Serialer` 预设，通过 `fastsend::config::preset` 获取。
+///
+/// 设备号与起始时间只能在首次使用之前配置，因此 `apply` 应尽可能早地在程序启动时调用。启用 'config' feature
+/// 后，还可以通过 `Config::load` 从 TOML 文件（如 `fastsend.toml`）中读取配置。
+#[derive(Default)]
+pub struct Config {
+    device_id: Option<DeviceIdSetting>,
+
+    timebase: Option<SystemTime>,
+
+    blocks_per_claim: Option<usize>,
+
+    sequence_stride: Option<(u16, u16)>,
+
+    id_permutation: Option<u64>,
+
+    #[cfg(feature = "sharded")]
+    shard_epoch: Option<SystemTime>,
+
+    presets: Vec<((TypeId, String), Factory)>,
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Config");
+        debug.field("device_id", &self.device_id);
+        debug.field("timebase", &self.timebase);
+        debug.field("blocks_per_claim", &self.blocks_per_claim);
+        debug.field("sequence_stride", &self.sequence_stride);
+        // 置换的种子相当于密钥，不在调试输出中展示
+        debug.field("id_permutation", &self.id_permutation.is_some());
+        #[cfg(feature = "sharded")]
+        debug.field("shard_epoch", &self.shard_epoch);
+        debug
+            .field(
+                "presets",
+                &self
+                    .presets
+                    .iter()
+                    .map(|((_, name), _)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl Config {
+    pub fn new() -> Config {
+        Self::default()
+    }
+
+    pub fn device_id(mut self, setting: DeviceIdSetting) -> Self {
+        self.device_id = Some(setting);
+        self
+    }
+
+    pub fn timebase(mut self, timebase: SystemTime) -> Self {
+        self.timebase = Some(timebase);
+        self
+    }
+
+    pub fn blocks_per_claim(mut self, blocks: usize) -> Self {
+        self.blocks_per_claim = Some(blocks);
+        self
+    }
+
+    pub fn sequence_stride(mut self, offset: u16, step: u16) -> Self {
+        self.sequence_stride = Some((offset, step));
+        self
+    }
+
+    pub fn id_permutation(mut self, seed: u64) -> Self {
+        self.id_permutation = Some(seed);
+        self
+    }
+
+    #[cfg(feature = "sharded")]
+    pub fn shard_epoch(mut self, epoch: SystemTime) -> Self {
+        self.shard_epoch = Some(epoch);
+        self
+    }
+
+    /// 注册名为 `name` 的 `Serialer` 预设，每次通过 `preset` 获取时调用 `f` 构造一个新的 `Serialer`。不同类型的
+    /// `Serialer` 可以使用相同的预设名称。
+    pub fn preset<S, F>(mut self, name: &str, f: F) -> Self
+    where
+        S: 'static,
+        F: Fn() -> S + Send + Sync + 'static,
+    {
+        let factory: Arc<dyn Fn() -> S + Send + Sync> = Arc::new(f);
+        self.presets
+            .push(((TypeId::of::<S>(), name.to_owned()), Box::new(factory)));
+        self
+    }
+
+    /// 应用配置：设置设备号及起始时间，并注册全部预设（同名预设将被覆盖）。
+    pub fn apply(self) -> Result<(), ConfigError> {
+        if let Some(blocks) = self.blocks_per_claim {
+            let max = crate::BlockFrame::<crate::Token>::QUEUE_SIZE;
+            if !(1..=max).contains(&blocks) {
+                return Err(ConfigError::Invalid(format!(
+                    "blocks_per_claim must be within 1..={}",
+                    max
+                )));
+            }
+        }
+
+        if let Some((offset, step)) = self.sequence_stride {
+            if !(1..=crate::SequenceStride::MAX_STEP).contains(&step) || offset >= step {
+                return Err(ConfigError::Invalid(format!(
+                    "sequence_stride must satisfy offset < step <= {}",
+                    crate::SequenceStride::MAX_STEP
+                )));
+            }
+        }
+
+        if let Some(setting) = self.device_id {
+            let device_id = match setting {
+                DeviceIdSetting::Value(device_id) => device_id,
+                DeviceIdSetting::Mac => crate::device_id::from_mac(u8::BITS)
+                    .ok_or(ConfigError::DeviceId("MAC address not found".to_owned()))?,
+                DeviceIdSetting::K8s => crate::device_id::from_k8s(u8::BITS)
+                    .map_err(|error| ConfigError::DeviceId(error.to_string()))?,
+                DeviceIdSetting::Ip => crate::device_id::from_ip(0, u8::BITS)
+                    .map_err(|error| ConfigError::DeviceId(error.to_string()))?,
+            };
+
+            if !crate::set_device_id(device_id) {
+                return Err(ConfigError::AlreadyInUse("device id"));
+            }
+        }
+
+        if let Some(timebase) = self.timebase {
+            if !crate::set_timebase(timebase) {
+                return Err(ConfigError::AlreadyInUse("cursor timebase"));
+            }
+        }
+
+        if let Some((offset, step)) = self.sequence_stride {
+            if !crate::set_sequence_stride(offset, step) {
+                return Err(ConfigError::AlreadyInUse("sequence stride"));
+            }
+        }
+
+        if let Some(seed) = self.id_permutation {
+            if !crate::set_id_permutation(seed) {
+                return Err(ConfigError::AlreadyInUse("id permutation"));
+            }
+        }
+
+        if let Some(blocks) = self.blocks_per_claim {
+            crate::set_blocks_per_claim(blocks);
+        }
+
+        #[cfg(feature = "sharded")]
+        if let Some(epoch) = self.shard_epoch {
+            if !crate::shard::set_default_epoch(epoch) {
+                return Err(ConfigError::AlreadyInUse("shard epoch"));
+            }
+        }
+
+        PRESETS.write().unwrap().extend(self.presets);
+        Ok(())
+    }
+}
+
+/// 获取通过 `Config::apply` 注册的、类型为 `S` 的名为 `name` 的预设，预设不存在时返回 `None`。
+pub fn preset<S: 'static>(name: &str) -> Option<S> {
+    let presets = PRESETS.read().unwrap();
+    let factory = presets
+        .get(&(TypeId::of::<S>(), name.to_owned()))?
+        .downcast_ref::<Arc<dyn Fn() -> S + Send + Sync>>()?;
+    Some(factory())
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// 读取配置文件失败。
+    Io(std::io::Error),
+
+    /// 配置文件格式不正确。
+    Parse(String),
+
+    /// 配置项的值不合法。
+    Invalid(String),
+
+    /// 无法根据配置获取设备号。
+    DeviceId(String),
+
+    /// 配置项已经被使用（如已经生成过 id），无法再修改。
+    AlreadyInUse(&'static str),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(error) => write!(f, "failed to read config file: {}", error),
+            ConfigError::Parse(error) => write!(f, "failed to parse config file: {}", error),
+            ConfigError::Invalid(error) => write!(f, "invalid config: {}", error),
+            ConfigError::DeviceId(error) => write!(f, "failed to resolve device id: {}", error),
+            ConfigError::AlreadyInUse(what) => {
+                write!(f, "{} is already in use and cannot be changed", what)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
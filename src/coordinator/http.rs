@@ -0,0 +1,119 @@
+use super::{Coordinator, CoordinatorError};
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// ## 基于 HTTP 的协调服务客户端
+///
+/// `HttpCoordinator` 是 `Coordinator` 的参考实现，协调服务需要在 `endpoint` 下提供以下 JSON 接口：
+///
+///   - `POST /worker-id`，请求体为 `{"instance": "<instance>"}`，响应体为 `{"worker_id": 3}`；
+///   - `POST /heartbeat`，请求体为 `{"instance": "<instance>", "worker_id": 3}`，以 2xx 状态码表示成功；
+///   - `GET /time`，响应体为 `{"unix_millis": 1700000000000}`。
+///
+/// 其中 'instance' 用于在协调服务中标识当前实例，缺省为「进程号-随机数」。时间偏差按照请求往返的中点计算，
+/// 网络延迟越对称，偏差越准确。
+#[derive(Debug, Clone)]
+pub struct HttpCoordinator {
+    client: Client,
+
+    /// 协调服务的地址，如 'http://coordinator:8080'。
+    endpoint: String,
+
+    /// 当前实例的标识。
+    instance: String,
+}
+
+impl HttpCoordinator {
+    pub fn new(endpoint: &str) -> HttpCoordinator {
+        HttpCoordinator {
+            client: Client::new(),
+            endpoint: endpoint.trim_end_matches('/').to_owned(),
+            instance: format!("{}-{:016x}", std::process::id(), rand::random::<u64>()),
+        }
+    }
+
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn instance(mut self, instance: &str) -> Self {
+        self.instance = instance.to_owned();
+        self
+    }
+
+    async fn post(&self, path: &str, body: Value) -> Result<Value, reqwest::Error> {
+        let response = self
+            .client
+            .post(format!("{}{}", self.endpoint, path))
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        // 心跳接口允许返回空的响应体
+        let bytes = response.bytes().await?;
+        Ok(serde_json::from_slice(&bytes).unwrap_or_default())
+    }
+}
+
+impl Coordinator for HttpCoordinator {
+    fn worker_id(&self) -> Pin<Box<dyn Future<Output = Result<u8, CoordinatorError>> + Send + '_>> {
+        Box::pin(async move {
+            let response = self
+                .post("/worker-id", json!({ "instance": self.instance }))
+                .await?;
+
+            let worker_id = response["worker_id"]
+                .as_u64()
+                .ok_or("missing worker_id in response")?;
+            Ok(u8::try_from(worker_id)?)
+        })
+    }
+
+    fn heartbeat(
+        &self,
+        worker_id: u8,
+    ) -> Pin<Box<dyn Future<Output = Result<(), CoordinatorError>> + Send + '_>> {
+        Box::pin(async move {
+            self.post(
+                "/heartbeat",
+                json!({ "instance": self.instance, "worker_id": worker_id }),
+            )
+            .await?;
+            Ok(())
+        })
+    }
+
+    fn time_offset(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<i64, CoordinatorError>> + Send + '_>> {
+        Box::pin(async move {
+            let sent = unix_millis();
+            let response = self
+                .client
+                .get(format!("{}/time", self.endpoint))
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<Value>()
+                .await?;
+            let received = unix_millis();
+
+            let remote = response["unix_millis"]
+                .as_i64()
+                .ok_or("missing unix_millis in response")?;
+            Ok(remote - (sent + received) / 2)
+        })
+    }
+}
+
+fn unix_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time before unix epoch")
+        .as_millis() as i64
+}
@@ -0,0 +1,164 @@
+use crate::device_id::set_device_id;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+mod http;
+pub use http::HttpCoordinator;
+
+/// 与协调服务通信时出现的错误（如网络错误、响应格式错误）。
+pub type CoordinatorError = Box<dyn Error + Send + Sync + 'static>;
+
+/// `Coordinator` 代表一个中心化的「ID 协调服务」，fastsend 实例在启动时通过它分配设备号（worker id）、
+/// 校验本地时钟，并在运行期间定期上报心跳，使协调服务能够回收已经下线的实例所持有的设备号。
+///
+/// 签名与 `ConflictCheck::check` 一样使用 `Pin<Box<dyn Future>>` 来支持异步调用，fastsend 提供了基于 HTTP
+/// 的参考实现 `HttpCoordinator`，其他协议（如 gRPC）可以自行实现该 trait。
+pub trait Coordinator: Send + Sync {
+    /// 为当前实例分配设备号。
+    fn worker_id(&self) -> Pin<Box<dyn Future<Output = Result<u8, CoordinatorError>> + Send + '_>>;
+
+    /// 上报设备号 `worker_id` 仍由当前实例持有。
+    fn heartbeat(
+        &self,
+        worker_id: u8,
+    ) -> Pin<Box<dyn Future<Output = Result<(), CoordinatorError>> + Send + '_>>;
+
+    /// 获取协调服务的权威时间与本地时间的偏差（毫秒），正数代表本地时钟落后于协调服务。
+    fn time_offset(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<i64, CoordinatorError>> + Send + '_>>;
+}
+
+/// ## 通过协调服务完成启动
+///
+/// `Bootstrap` 在程序启动时依次完成：
+///
+///   1. 通过 `Coordinator::time_offset` 校验本地时钟，偏差超过 `max_clock_skew` 时拒绝启动，避免因时钟错误
+///      生成重复或乱序的 id；
+///   2. 通过 `Coordinator::worker_id` 分配设备号，并写入全局设备号（因此必须在首次生成 id 或序列号之前调用）；
+///   3. 在后台以 `heartbeat_interval` 的间隔上报心跳，心跳在 `fastsend::shutdown` 时停止。
+///
+/// 心跳注册为维护任务（见 `register_job`），在 `run` 所在的 tokio 运行时中执行，心跳失败时将在下一个周期重试。
+pub struct Bootstrap {
+    coordinator: Arc<dyn Coordinator>,
+
+    /// 允许的最大时钟偏差，缺省配置是 1 秒。
+    max_clock_skew: Duration,
+
+    /// 心跳间隔，缺省配置是 10 秒。
+    heartbeat_interval: Duration,
+}
+
+impl fmt::Debug for Bootstrap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bootstrap")
+            .field("max_clock_skew", &self.max_clock_skew)
+            .field("heartbeat_interval", &self.heartbeat_interval)
+            .finish()
+    }
+}
+
+impl Bootstrap {
+    pub fn new<C: Coordinator + 'static>(coordinator: C) -> Bootstrap {
+        Bootstrap {
+            coordinator: Arc::new(coordinator),
+            max_clock_skew: Duration::from_secs(1),
+            heartbeat_interval: Duration::from_secs(10),
+        }
+    }
+
+    pub fn max_clock_skew(mut self, max_clock_skew: Duration) -> Self {
+        self.max_clock_skew = max_clock_skew;
+        self
+    }
+
+    pub fn heartbeat_interval(mut self, heartbeat_interval: Duration) -> Self {
+        assert!(!heartbeat_interval.is_zero());
+        self.heartbeat_interval = heartbeat_interval;
+        self
+    }
+
+    /// 完成启动并返回分配到的设备号。
+    pub async fn run(self) -> Result<u8, BootstrapError> {
+        let offset = self.coordinator.time_offset().await?;
+        if offset.unsigned_abs() > self.max_clock_skew.as_millis() as u64 {
+            return Err(BootstrapError::ClockSkew(offset));
+        }
+
+        let worker_id = self.coordinator.worker_id().await?;
+        if !set_device_id(worker_id) {
+            return Err(BootstrapError::AlreadyResolved);
+        }
+
+        let coordinator = self.coordinator;
+        let heartbeat = crate::maintenance::register_runtime_job(
+            "coordinator heartbeat",
+            self.heartbeat_interval,
+            move || {
+                let coordinator = Arc::clone(&coordinator);
+                async move {
+                    let _ = coordinator.heartbeat(worker_id).await;
+                }
+            },
+        );
+        crate::init::on_shutdown(move || async move {
+            crate::remove_job(heartbeat);
+        });
+
+        Ok(worker_id)
+    }
+}
+
+#[derive(Debug)]
+pub enum BootstrapError {
+    /// 本地时钟与协调服务的偏差（毫秒）超过了允许的范围。
+    ClockSkew(i64),
+
+    /// 设备号在分配之前已经被解析（即已经生成过 id 或序列号），分配到的设备号无法生效。
+    AlreadyResolved,
+
+    /// 与协调服务通信失败。
+    Coordinator(CoordinatorError),
+}
+
+impl From<CoordinatorError> for BootstrapError {
+    fn from(error: CoordinatorError) -> Self {
+        BootstrapError::Coordinator(error)
+    }
+}
+
+impl fmt::Display for BootstrapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BootstrapError::ClockSkew(offset) => write!(
+                f,
+                "local clock is {}ms away from the coordinator",
+                offset.unsigned_abs()
+            ),
+            BootstrapError::AlreadyResolved => write!(
+                f,
+                "device id has already been resolved before bootstrapping"
+            ),
+            BootstrapError::Coordinator(error) => {
+                write!(
+                    f,
+                    "an error occurs when communicating with coordinator: {}",
+                    error
+                )
+            }
+        }
+    }
+}
+
+impl Error for BootstrapError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BootstrapError::Coordinator(error) => Some(&**error),
+            _ => None,
+        }
+    }
+}
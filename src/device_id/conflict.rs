@@ -0,0 +1,201 @@
+use futures::channel::oneshot;
+use std::error::Error;
+use std::future::Future;
+use std::net::{Ipv4Addr, UdpSocket};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 冲突检测过程中出现的错误（如网络错误），与「检测到冲突」不同，它表示无法确认是否存在冲突。
+pub type CheckError = Box<dyn Error + Send + Sync + 'static>;
+
+/// `ConflictCheck` 代表一种设备号冲突检测方式，在 `Init::run` 时被调用：返回 `Ok(true)` 代表存在另一个存活的
+/// 实例持有相同的设备号，返回 `Ok(false)` 代表未检测到冲突。
+///
+/// 签名与 `Serialer::build` 一样使用 `Pin<Box<dyn Future>>` 来支持异步检测。
+pub trait ConflictCheck: Send {
+    fn check(
+        &mut self,
+        device_id: u8,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, CheckError>> + Send + '_>>;
+}
+
+/// 探测报文前缀，探测报文格式为 'fastsend?<device_id>'，应答报文格式为 'fastsend!<device_id>:<token>'。
+const PROBE: &str = "fastsend?";
+const REPLY: &str = "fastsend!";
+
+/// 进程内常驻的应答线程，按端口登记，记录需要应答的设备号。同一端口只绑定一次，之后的检测复用该线程。
+static RESPONDERS: Mutex<Vec<(u16, Arc<AtomicU8>)>> = Mutex::new(Vec::new());
+
+/// 当前进程的应答标识，用于从收到的应答中区分自己的应答。
+fn token() -> &'static str {
+    static TOKEN: OnceLock<String> = OnceLock::new();
+    TOKEN.get_or_init(|| format!("{}-{:016x}", std::process::id(), rand::random::<u64>()))
+}
+
+/// 确保 `port` 上有应答线程对 `device_id` 的探测进行应答：首次调用时绑定端口并启动线程，同时注册 `shutdown`
+/// 时的清理任务，之后的调用只更新应答的设备号。
+fn respond(port: u16, device_id: u8) -> std::io::Result<()> {
+    let mut responders = RESPONDERS.lock().unwrap_or_else(|error| error.into_inner());
+    if let Some((_, current)) = responders.iter().find(|(bound, _)| *bound == port) {
+        current.store(device_id, Ordering::Relaxed);
+        return Ok(());
+    }
+
+    let responder = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port))?;
+    // 定期醒来检查停止标志
+    responder.set_read_timeout(Some(Duration::from_millis(100)))?;
+
+    let current = Arc::new(AtomicU8::new(device_id));
+    let stop = Arc::new(AtomicBool::new(false));
+    let (stopped, receiver) = oneshot::channel::<()>();
+
+    thread::spawn({
+        let current = Arc::clone(&current);
+        let stop = Arc::clone(&stop);
+
+        move || {
+            let mut buf = [0u8; 64];
+            while !stop.load(Ordering::Acquire) {
+                let (n, from) = match responder.recv_from(&mut buf) {
+                    Ok(received) => received,
+                    Err(error)
+                        if matches!(
+                            error.kind(),
+                            std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                        ) =>
+                    {
+                        continue
+                    }
+                    Err(_) => break,
+                };
+
+                let device_id = current.load(Ordering::Relaxed);
+                if buf[..n] == *format!("{}{}", PROBE, device_id).as_bytes() {
+                    let reply = format!("{}{}:{}", REPLY, device_id, token());
+                    let _ = responder.send_to(reply.as_bytes(), from);
+                }
+            }
+
+            // 先关闭套接字再通知，`shutdown` 返回后端口即可被重新绑定
+            drop(responder);
+            let _ = stopped.send(());
+        }
+    });
+
+    crate::init::on_shutdown(move || async move {
+        RESPONDERS
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .retain(|(bound, _)| *bound != port);
+        stop.store(true, Ordering::Release);
+        let _ = receiver.await;
+    });
+
+    responders.push((port, current));
+    Ok(())
+}
+
+/// ## 局域网 UDP 广播冲突检测
+///
+/// `UdpBroadcast` 适用于没有 Redis、etcd 等外部依赖的局域网部署：检测开始时在 `port` 上启动一个常驻的应答线程，
+/// 对其他实例的探测报文进行应答；随后向 255.255.255.255:`port` 广播探测报文，并在 `timeout` 内收集应答，收到
+/// 其他实例（token 不同）对相同设备号的应答即视为冲突。
+///
+/// 同一进程内每个端口只启动一个应答线程，多次检测（如多次调用 `Init::run`）复用该线程，`fastsend::shutdown`
+/// 时停止应答并释放端口。由于应答线程需要独占 `port`，同一台主机上只能运行一个使用 `UdpBroadcast` 的实例（同一主机上的多个实例通常
+/// 也不应共享设备号来源）。广播报文无法跨越子网，跨子网部署请使用基于 Redis 或 etcd 的心跳检测。
+#[derive(Debug, Clone)]
+pub struct UdpBroadcast {
+    /// 探测及应答使用的端口，缺省配置是 47_300。
+    port: u16,
+
+    /// 收集应答的时间，缺省配置是 1 秒。
+    timeout: Duration,
+}
+
+impl UdpBroadcast {
+    pub fn new() -> UdpBroadcast {
+        UdpBroadcast {
+            port: 47_300,
+            timeout: Duration::from_secs(1),
+        }
+    }
+
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn run(&self, device_id: u8) -> Result<bool, CheckError> {
+        respond(self.port, device_id)?;
+
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.set_broadcast(true)?;
+        socket.send_to(
+            format!("{}{}", PROBE, device_id).as_bytes(),
+            (Ipv4Addr::BROADCAST, self.port),
+        )?;
+
+        let prefix = format!("{}{}:", REPLY, device_id);
+        let deadline = Instant::now() + self.timeout;
+        let mut buf = [0u8; 64];
+
+        while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+            socket.set_read_timeout(Some(remaining.max(Duration::from_millis(1))))?;
+            let n = match socket.recv_from(&mut buf) {
+                Ok((n, _)) => n,
+                Err(error)
+                    if matches!(
+                        error.kind(),
+                        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                    ) =>
+                {
+                    break
+                }
+                Err(error) => return Err(error.into()),
+            };
+
+            // 自己的应答线程也会应答自己的广播，通过 token 区分
+            let conflicted = std::str::from_utf8(&buf[..n])
+                .ok()
+                .and_then(|reply| reply.strip_prefix(&prefix))
+                .is_some_and(|other| other != token());
+
+            if conflicted {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+impl Default for UdpBroadcast {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConflictCheck for UdpBroadcast {
+    fn check(
+        &mut self,
+        device_id: u8,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, CheckError>> + Send + '_>> {
+        // 检测过程是阻塞的（等待应答），因此放在单独的线程中执行，避免阻塞异步运行时
+        let (sender, receiver) = oneshot::channel();
+        let this = self.clone();
+        thread::spawn(move || {
+            let _ = sender.send(this.run(device_id));
+        });
+
+        Box::pin(async move { receiver.await? })
+    }
+}
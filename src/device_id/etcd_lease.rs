@@ -0,0 +1,427 @@
+use super::{set_device_id, CheckError, ConflictCheck, LeaseError};
+use crate::maintenance::{register_runtime_job, JobId};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::future::Future;
+use std::ops::RangeInclusive;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// ## etcd 设备号租约
+///
+/// `EtcdLease` 通过 etcd v3 的 gRPC-gateway（HTTP/JSON 接口，etcd 3.4 及以上版本默认开启）为每个实例分配设备
+/// 号：先申请一个有效期为 `ttl` 的 lease，再列出 `prefix` 下已被占用的设备号，按从小到大的顺序以事务的方式
+/// 「仅当键不存在时写入」，第一个写入成功的设备号即为本实例的设备号，并通过 `set_device_id` 写入全局设备号。
+///
+/// 设备号键绑定在 lease 上，lease 在后台以 `ttl / 3` 的间隔自动续期，每次续期时同时检查设备号键：一旦该键被删除
+/// 或被其他实例改写，即视为设备号冲突，`EtcdLeaseGuard::is_held` 将返回 `false`。分配失败时返回错误，而不会回退到
+/// 随机设备号。续期注册为维护任务（见 `register_job`），在 `claim` 所在的 tokio 运行时中执行。
+#[derive(Debug, Clone)]
+pub struct EtcdLease {
+    client: Client,
+
+    /// etcd 的地址，如 'http://127.0.0.1:2379'。
+    endpoint: String,
+
+    /// 设备号键的前缀，缺省配置是 'fastsend/device_id/'。
+    prefix: String,
+
+    /// 可分配的设备号范围，缺省配置是 0..=255。
+    range: RangeInclusive<u8>,
+
+    /// lease 有效期，缺省配置是 30 秒。
+    ttl: Duration,
+}
+
+impl EtcdLease {
+    pub fn new(endpoint: &str) -> EtcdLease {
+        EtcdLease {
+            client: Client::new(),
+            endpoint: endpoint.trim_end_matches('/').to_owned(),
+            prefix: "fastsend/device_id/".to_owned(),
+            range: 0..=u8::MAX,
+            ttl: Duration::from_secs(30),
+        }
+    }
+
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_owned();
+        self
+    }
+
+    pub fn range(mut self, range: RangeInclusive<u8>) -> Self {
+        self.range = range;
+        self
+    }
+
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        assert!(ttl >= Duration::from_secs(1));
+        self.ttl = ttl;
+        self
+    }
+
+    /// 申请设备号，成功后将设备号写入全局设备号并开始后台续期及冲突检测。
+    pub async fn claim(self) -> Result<EtcdLeaseGuard, LeaseError> {
+        let gateway = Gateway {
+            client: self.client.clone(),
+            endpoint: self.endpoint.clone(),
+        };
+
+        let lease = gateway
+            .call("/v3/lease/grant", json!({ "TTL": self.ttl.as_secs() }))
+            .await?;
+        let lease_id = int(&lease["ID"]).ok_or(LeaseError::Protocol("missing lease ID"))?;
+
+        match self.try_claim(&gateway, lease_id).await {
+            Ok(Some((device_id, key, revision))) => {
+                let guard =
+                    EtcdLeaseGuard::new(gateway, lease_id, key, revision, device_id, self.ttl);
+                if !set_device_id(device_id) {
+                    guard.release().await?;
+                    return Err(LeaseError::AlreadyResolved);
+                }
+                Ok(guard)
+            }
+            result => {
+                // 分配失败时主动撤销 lease，避免其在 ttl 内占用资源
+                let _ = gateway
+                    .call("/v3/lease/revoke", json!({ "ID": lease_id.to_string() }))
+                    .await;
+                result?;
+                Err(LeaseError::Exhausted)
+            }
+        }
+    }
+
+    async fn try_claim(
+        &self,
+        gateway: &Gateway,
+        lease_id: i64,
+    ) -> Result<Option<(u8, String, i64)>, LeaseError> {
+        let used = gateway
+            .call(
+                "/v3/kv/range",
+                json!({
+                    "key": STANDARD.encode(&self.prefix),
+                    "range_end": STANDARD.encode(prefix_end(self.prefix.as_bytes())),
+                    "keys_only": true,
+                }),
+            )
+            .await?["kvs"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|kv| STANDARD.decode(kv["key"].as_str()?).ok())
+            .collect::<HashSet<_>>();
+
+        let token = format!("{}-{:016x}", std::process::id(), rand::random::<u64>());
+
+        for device_id in self.range.clone() {
+            let key = format!("{}{}", self.prefix, device_id);
+            if used.contains(key.as_bytes()) {
+                continue;
+            }
+
+            let txn = gateway
+                .call(
+                    "/v3/kv/txn",
+                    json!({
+                        "compare": [{
+                            "target": "CREATE",
+                            "key": STANDARD.encode(&key),
+                            "create_revision": "0",
+                        }],
+                        "success": [{
+                            "request_put": {
+                                "key": STANDARD.encode(&key),
+                                "value": STANDARD.encode(&token),
+                                "lease": lease_id.to_string(),
+                            }
+                        }],
+                    }),
+                )
+                .await?;
+
+            // 键已被其他实例抢先写入时，继续尝试下一个设备号
+            if txn["succeeded"].as_bool().unwrap_or(false) {
+                let revision = int(&txn["header"]["revision"])
+                    .ok_or(LeaseError::Protocol("missing txn revision"))?;
+                return Ok(Some((device_id, key, revision)));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// `EtcdLease::claim` 成功后返回的租约，持有期间在后台自动续期并检测冲突。
+#[derive(Debug)]
+pub struct EtcdLeaseGuard {
+    gateway: Gateway,
+    lease_id: i64,
+    device_id: u8,
+
+    /// lease 过期或设备号键被改写时置为 true。
+    lost: Arc<AtomicBool>,
+
+    /// 续期及冲突检测任务，见 `register_job`。
+    keep_alive: JobId,
+}
+
+impl EtcdLeaseGuard {
+    fn new(
+        gateway: Gateway,
+        lease_id: i64,
+        key: String,
+        revision: i64,
+        device_id: u8,
+        ttl: Duration,
+    ) -> EtcdLeaseGuard {
+        let lost = Arc::new(AtomicBool::new(false));
+
+        let keep_alive = register_runtime_job("etcd lease keepalive", ttl / 3, {
+            let (gateway, key, lost) = (gateway.clone(), key, Arc::clone(&lost));
+
+            move || {
+                let (gateway, key, lost) = (gateway.clone(), key.clone(), Arc::clone(&lost));
+
+                async move {
+                    if lost.load(Ordering::SeqCst) {
+                        return;
+                    }
+
+                    // 网络错误时在下一个周期重试；lease 已过期，或设备号键被删除、被改写（修改版本不再是写入时的
+                    // revision）时停止续期
+                    let held = async {
+                        if !gateway.keep_alive(lease_id).await? {
+                            return Ok(false);
+                        }
+
+                        let kvs = gateway
+                            .call("/v3/kv/range", json!({ "key": STANDARD.encode(&key) }))
+                            .await?;
+                        Ok::<_, LeaseError>(int(&kvs["kvs"][0]["mod_revision"]) == Some(revision))
+                    };
+
+                    if let Ok(false) = held.await {
+                        lost.store(true, Ordering::SeqCst);
+                    }
+                }
+            }
+        });
+
+        revoke_on_shutdown(gateway.clone(), lease_id, &[keep_alive]);
+
+        EtcdLeaseGuard {
+            gateway,
+            lease_id,
+            device_id,
+            lost,
+            keep_alive,
+        }
+    }
+
+    pub fn device_id(&self) -> u8 {
+        self.device_id
+    }
+
+    /// 租约是否仍由本实例持有，返回 `false` 时设备号可能已被其他实例占用，应尽快停止生成 id。
+    pub fn is_held(&self) -> bool {
+        !self.lost.load(Ordering::SeqCst)
+    }
+
+    /// 停止续期并撤销 lease，设备号键随 lease 一并删除。
+    pub async fn release(self) -> Result<(), LeaseError> {
+        crate::remove_job(self.keep_alive);
+
+        self.gateway
+            .call(
+                "/v3/lease/revoke",
+                json!({ "ID": self.lease_id.to_string() }),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl Drop for EtcdLeaseGuard {
+    fn drop(&mut self) {
+        crate::remove_job(self.keep_alive);
+    }
+}
+
+/// ## etcd 心跳冲突检测
+///
+/// `EtcdHeartbeat` 用于设备号来自其他来源（如环境变量）时检测冲突：在 `Init::run` 时申请一个有效期为 `ttl` 的
+/// lease，并以事务的方式「仅当键不存在时」写入 `<prefix><device_id>`，写入失败即代表另一个存活的实例持有相同的
+/// 设备号；写入成功后在后台自动续期 lease，直到进程退出。续期注册为维护任务（见 `register_job`），在 `Init::run`
+/// 所在的 tokio 运行时中执行。
+#[derive(Debug, Clone)]
+pub struct EtcdHeartbeat {
+    gateway: Gateway,
+
+    /// 心跳键的前缀，缺省配置是 'fastsend/heartbeat/'。
+    prefix: String,
+
+    /// lease 有效期，缺省配置是 30 秒。
+    ttl: Duration,
+}
+
+impl EtcdHeartbeat {
+    pub fn new(endpoint: &str) -> EtcdHeartbeat {
+        EtcdHeartbeat {
+            gateway: Gateway {
+                client: Client::new(),
+                endpoint: endpoint.trim_end_matches('/').to_owned(),
+            },
+            prefix: "fastsend/heartbeat/".to_owned(),
+            ttl: Duration::from_secs(30),
+        }
+    }
+
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_owned();
+        self
+    }
+
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        assert!(ttl >= Duration::from_secs(1));
+        self.ttl = ttl;
+        self
+    }
+}
+
+impl ConflictCheck for EtcdHeartbeat {
+    fn check(
+        &mut self,
+        device_id: u8,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, CheckError>> + Send + '_>> {
+        Box::pin(async move {
+            let gateway = self.gateway.clone();
+
+            let lease = gateway
+                .call("/v3/lease/grant", json!({ "TTL": self.ttl.as_secs() }))
+                .await?;
+            let lease_id = int(&lease["ID"]).ok_or(LeaseError::Protocol("missing lease ID"))?;
+
+            let key = format!("{}{}", self.prefix, device_id);
+            let txn = gateway
+                .call(
+                    "/v3/kv/txn",
+                    json!({
+                        "compare": [{
+                            "target": "CREATE",
+                            "key": STANDARD.encode(&key),
+                            "create_revision": "0",
+                        }],
+                        "success": [{
+                            "request_put": {
+                                "key": STANDARD.encode(&key),
+                                "value": STANDARD.encode(std::process::id().to_string()),
+                                "lease": lease_id.to_string(),
+                            }
+                        }],
+                    }),
+                )
+                .await?;
+
+            if !txn["succeeded"].as_bool().unwrap_or(false) {
+                let _ = gateway
+                    .call("/v3/lease/revoke", json!({ "ID": lease_id.to_string() }))
+                    .await;
+                return Ok(true);
+            }
+
+            // lease 续期在进程的整个生命周期内持续进行，直到 `shutdown` 时停止并撤销 lease
+            let expired = Arc::new(AtomicBool::new(false));
+            let keep_alive = register_runtime_job("etcd heartbeat keepalive", self.ttl / 3, {
+                let gateway = gateway.clone();
+
+                move || {
+                    let (gateway, expired) = (gateway.clone(), Arc::clone(&expired));
+
+                    async move {
+                        if !expired.load(Ordering::SeqCst) {
+                            if let Ok(false) = gateway.keep_alive(lease_id).await {
+                                expired.store(true, Ordering::SeqCst);
+                            }
+                        }
+                    }
+                }
+            });
+            revoke_on_shutdown(gateway, lease_id, &[keep_alive]);
+
+            Ok(false)
+        })
+    }
+}
+
+/// etcd gRPC-gateway 的简单封装。
+#[derive(Debug, Clone)]
+struct Gateway {
+    client: Client,
+    endpoint: String,
+}
+
+impl Gateway {
+    async fn call(&self, path: &str, body: Value) -> Result<Value, LeaseError> {
+        Ok(self
+            .client
+            .post(format!("{}{}", self.endpoint, path))
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    /// 续期 lease，返回 `Ok(false)` 代表 lease 已过期（TTL 缺失或为 0）。
+    async fn keep_alive(&self, lease_id: i64) -> Result<bool, LeaseError> {
+        let response = self
+            .call("/v3/lease/keepalive", json!({ "ID": lease_id.to_string() }))
+            .await?;
+        Ok(int(&response["result"]["TTL"]).unwrap_or(0) > 0)
+    }
+}
+
+/// gRPC-gateway 将 int64 编码为字符串，这里同时兼容字符串与数字两种形式。
+fn int(value: &Value) -> Option<i64> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        value => value.as_i64(),
+    }
+}
+
+/// 计算前缀查询的 `range_end`：将前缀的最后一个非 0xff 字节 +1 并截断其后的字节。
+fn prefix_end(prefix: &[u8]) -> Vec<u8> {
+    let mut end = prefix.to_vec();
+    while let Some(last) = end.pop() {
+        if last < u8::MAX {
+            end.push(last + 1);
+            return end;
+        }
+    }
+    // 前缀全部为 0xff 时，使用 '\0' 代表「大于等于 key 的所有键」
+    vec![0]
+}
+
+/// 在 `shutdown` 时停止续期任务并撤销 lease，lease 撤销后其绑定的设备号键会被立即删除。
+fn revoke_on_shutdown(gateway: Gateway, lease_id: i64, jobs: &[JobId]) {
+    let jobs = jobs.to_vec();
+
+    crate::init::on_shutdown(move || async move {
+        jobs.iter().for_each(|&job| {
+            crate::remove_job(job);
+        });
+        let _ = gateway
+            .call("/v3/lease/revoke", json!({ "ID": lease_id.to_string() }))
+            .await;
+    });
+}
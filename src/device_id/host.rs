@@ -0,0 +1,175 @@
+use super::set_device_id;
+use std::env;
+use std::fmt;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io;
+use std::path::PathBuf;
+
+/// ## 同一主机上的多进程设备号划分
+///
+/// 同一台主机上的多个进程读取到的是同一个 `FASTSEND_DEVICE_ID`，仅依靠进程号、线程号派生的位来区分进程，
+/// 终究会发生碰撞。`HostSlot` 将主机的设备号（`base`）进一步划分为 2^`bits` 个槽位，每个进程通过对
+/// `dir` 下的 'slot-<n>.lock' 文件加建议锁（advisory lock）来独占一个槽位，最终的设备号为
+/// `base << bits | slot`。
+///
+/// 建议锁由操作系统在进程退出（包括异常退出）时自动释放，因此不需要额外的清理；但进程在运行期间必须一直持有
+/// `HostSlotGuard`，否则槽位会被其他进程重新占用。由于设备号只有 8 位，划分后每台主机的 `base` 必须小于
+/// 2^(8 - `bits`)。
+///
+/// 建议锁使用标准库的 `File::try_lock`，需要 Rust 1.89 及以上的版本（见 Cargo.toml 中的 `rust-version`）。
+#[derive(Debug, Clone)]
+pub struct HostSlot {
+    /// 主机的设备号，缺省配置从环境变量 `FASTSEND_DEVICE_ID`（数字）中读取，未提供时为 0。
+    base: u8,
+
+    /// 槽位的位数（1..=7），缺省配置是 2，即同一主机上最多 4 个进程。
+    bits: u32,
+
+    /// 锁文件所在的目录，缺省配置是 '/run/fastsend'。
+    dir: PathBuf,
+}
+
+impl HostSlot {
+    pub fn new() -> HostSlot {
+        HostSlot {
+            base: env::var("FASTSEND_DEVICE_ID")
+                .ok()
+                .and_then(|var| var.trim().parse().ok())
+                .unwrap_or_default(),
+            bits: 2,
+            dir: PathBuf::from("/run/fastsend"),
+        }
+    }
+
+    pub fn base(mut self, base: u8) -> Self {
+        self.base = base;
+        self
+    }
+
+    pub fn bits(mut self, bits: u32) -> Self {
+        assert!((1..u8::BITS).contains(&bits));
+        self.bits = bits;
+        self
+    }
+
+    pub fn dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.dir = dir.into();
+        self
+    }
+
+    /// 独占一个空闲的槽位，但不设置全局设备号。
+    pub fn lock(&self) -> Result<HostSlotGuard, HostSlotError> {
+        if self.base as u32 >= 1 << (u8::BITS - self.bits) {
+            return Err(HostSlotError::Overflow {
+                base: self.base,
+                bits: self.bits,
+            });
+        }
+
+        fs::create_dir_all(&self.dir)?;
+
+        for slot in 0..1u8 << self.bits {
+            let file = OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(self.dir.join(format!("slot-{}.lock", slot)))?;
+
+            match file.try_lock() {
+                Ok(()) => {
+                    return Ok(HostSlotGuard {
+                        _file: file,
+                        slot,
+                        device_id: self.base << self.bits | slot,
+                    })
+                }
+                Err(TryLockError::WouldBlock) => continue,
+                Err(TryLockError::Error(error)) => return Err(error.into()),
+            }
+        }
+
+        Err(HostSlotError::Exhausted)
+    }
+
+    /// 独占一个空闲的槽位，并将划分后的设备号写入全局设备号（因此必须在首次生成 id 或序列号之前调用）。
+    pub fn claim(&self) -> Result<HostSlotGuard, HostSlotError> {
+        let guard = self.lock()?;
+        if !set_device_id(guard.device_id) {
+            return Err(HostSlotError::AlreadyResolved);
+        }
+
+        Ok(guard)
+    }
+}
+
+impl Default for HostSlot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 持有槽位的建议锁，被释放时槽位即可被其他进程占用。
+#[derive(Debug)]
+pub struct HostSlotGuard {
+    _file: File,
+    slot: u8,
+    device_id: u8,
+}
+
+impl HostSlotGuard {
+    pub fn slot(&self) -> u8 {
+        self.slot
+    }
+
+    pub fn device_id(&self) -> u8 {
+        self.device_id
+    }
+}
+
+#[derive(Debug)]
+pub enum HostSlotError {
+    /// 创建或锁定锁文件失败。
+    Io(io::Error),
+
+    /// 所有槽位都已被其他进程占用。
+    Exhausted,
+
+    /// 主机的设备号 `base` 无法在划分出 `bits` 位槽位后放入 8 位设备号中。
+    Overflow { base: u8, bits: u32 },
+
+    /// 设备号在划分之前已经被解析（即已经生成过 id 或序列号）。
+    AlreadyResolved,
+}
+
+impl From<io::Error> for HostSlotError {
+    fn from(error: io::Error) -> Self {
+        HostSlotError::Io(error)
+    }
+}
+
+impl fmt::Display for HostSlotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HostSlotError::Io(error) => write!(f, "failed to lock host slot: {}", error),
+            HostSlotError::Exhausted => write!(f, "no free host slot left"),
+            HostSlotError::Overflow { base, bits } => write!(
+                f,
+                "device id {} leaves no room for {} bits of host slot",
+                base, bits
+            ),
+            HostSlotError::AlreadyResolved => write!(
+                f,
+                "device id has already been resolved before the host slot is claimed"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HostSlotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HostSlotError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
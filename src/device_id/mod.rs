@@ -0,0 +1,281 @@
+use lazy_static::lazy_static;
+use std::env;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+use std::sync::Mutex;
+
+mod conflict;
+pub use conflict::{CheckError, ConflictCheck, UdpBroadcast};
+
+mod host;
+pub use host::{HostSlot, HostSlotError, HostSlotGuard};
+
+#[cfg(feature = "redis_lease")]
+mod redis_lease;
+#[cfg(feature = "redis_lease")]
+pub use redis_lease::{RedisHeartbeat, RedisLease, RedisLeaseGuard};
+
+#[cfg(feature = "etcd_lease")]
+mod etcd_lease;
+#[cfg(feature = "etcd_lease")]
+pub use etcd_lease::{EtcdHeartbeat, EtcdLease, EtcdLeaseGuard};
+
+/// # 设备号来源
+///
+/// 设备号（`DEVICE_ID`）在程序中只会被解析一次，解析的优先级由高到低依次为：
+///
+///   1. 在首次使用设备号之前，通过 `set_device_id` 显式设置的设备号；
+///   2. 环境变量 `FASTSEND_DEVICE_ID`：数字表示直接使用该设备号，'mac' 表示使用 `from_mac` 从主网卡的 MAC
+///      地址中派生设备号，'k8s' 表示使用 `from_k8s` 从 StatefulSet 的 pod 序号中获取设备号，'ip' 表示使用
+///      `from_ip` 从私有 IP 地址的最后一个字节中获取设备号；
+///   3. 以上均未提供时，视为未配置设备号（`None`）。
+///
+/// 环境变量指定的来源无法解析时（如 pod 名称中没有序号）同样视为未配置设备号，并通过 'log'/'tracing' 输出一次警告。
+///
+/// 无论设备号来自哪种来源，最终都会经过 `RV` 的混淆后才被使用。
+#[derive(Debug)]
+struct State {
+    /// 设备号是否已经被解析，解析后再调用 `set_device_id` 将不再生效。
+    resolved: bool,
+
+    /// 通过 `set_device_id` 显式设置的设备号。
+    device_id: Option<u8>,
+}
+
+lazy_static! {
+    static ref STATE: Mutex<State> = Mutex::new(State {
+        resolved: false,
+        device_id: None,
+    });
+}
+
+/// 在首次使用设备号之前显式设置设备号，优先级高于环境变量 `FASTSEND_DEVICE_ID`。如果设备号已经被解析（即已经
+/// 生成过 id 或序列号），设置将不会生效并返回 `false`。
+pub fn set_device_id(device_id: u8) -> bool {
+    let mut state = STATE.lock().unwrap();
+    if state.resolved {
+        return false;
+    }
+
+    state.device_id = Some(device_id);
+    true
+}
+
+/// 解析设备号（未经过混淆），仅在 `DEVICE_ID` 初始化时调用一次。
+pub(crate) fn resolve() -> Option<u8> {
+    let mut state = STATE.lock().unwrap();
+    state.resolved = true;
+
+    state.device_id.or_else(|| {
+        let device_id = match env::var("FASTSEND_DEVICE_ID").ok()?.trim() {
+            "mac" => from_mac(u8::BITS),
+            "k8s" => from_k8s(u8::BITS).ok(),
+            "ip" => from_ip(0, u8::BITS).ok(),
+            var => return var.parse::<u8>().ok(),
+        };
+
+        // 在首次生成 id 时解析，不能 panic；显式指定的来源无法解析时输出警告并视为未配置设备号，需要在启动
+        // 时确认设备号的场合应通过 `Config::apply`（或直接调用 `from_k8s` 等）以错误的形式获取失败的原因
+        if device_id.is_none() {
+            crate::fallback::warn(crate::fallback::Fallback::DeviceIdSource);
+        }
+        device_id
+    })
+}
+
+/// 解析设备号失败时返回的错误。
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DeviceIdError {
+    /// 未找到用于派生设备号的信息，如环境变量缺失。
+    NotFound(&'static str),
+
+    /// 用于派生设备号的信息格式不正确。
+    Malformed(String),
+
+    /// 派生出的设备号超出了 `bits` 位所能表示的范围。
+    Overflow { value: u64, bits: u32 },
+}
+
+impl fmt::Display for DeviceIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeviceIdError::NotFound(what) => write!(f, "{} not found", what),
+            DeviceIdError::Malformed(value) => write!(f, "malformed value {:?}", value),
+            DeviceIdError::Overflow { value, bits } => {
+                write!(f, "device id {} exceeds {} bits", value, bits)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DeviceIdError {}
+
+/// 从 Kubernetes StatefulSet 的 pod 名称（形如 'myapp-3'）中获取序号作为宽度为 `bits`（1..=8）的设备号。
+///
+/// pod 名称优先从环境变量 `POD_NAME` 中获取（通过 downward API 注入 `metadata.name`），其次为 `HOSTNAME`
+/// （StatefulSet 中 pod 的主机名与 pod 名称一致）。序号超出 `bits` 位所能表示的范围时返回错误，此时应减少副本数
+/// 或增加设备号宽度，而不能截断序号，否则会导致设备号冲突。
+pub fn from_k8s(bits: u32) -> Result<u8, DeviceIdError> {
+    assert!((1..=u8::BITS).contains(&bits));
+
+    let name = env::var("POD_NAME")
+        .or_else(|_| env::var("HOSTNAME"))
+        .map_err(|_| DeviceIdError::NotFound("POD_NAME or HOSTNAME"))?;
+
+    let ordinal = name
+        .rsplit_once('-')
+        .and_then(|(_, ordinal)| ordinal.parse::<u64>().ok())
+        .ok_or_else(|| DeviceIdError::Malformed(name.clone()))?;
+
+    if ordinal >= 1 << bits {
+        return Err(DeviceIdError::Overflow {
+            value: ordinal,
+            bits,
+        });
+    }
+
+    Ok(ordinal as u8)
+}
+
+/// 从本机的私有 IPv4 地址中截取设备号：跳过最低的 `offset` 位后取 `bits`（1..=8）位，例如 `from_ip(0, 8)` 取
+/// 最后一个字节，`from_ip(8, 8)` 取第三个字节。这是 snowflake 部署中常见的兜底方案，适用于同一子网内主机地址
+/// 互不相同的场景，调用方需根据子网掩码选择合适的位段，确保所选位段在所有实例之间唯一。
+///
+/// 本机地址通过 `private_ip` 获取，获取不到私有 IPv4 地址时返回错误。
+pub fn from_ip(offset: u32, bits: u32) -> Result<u8, DeviceIdError> {
+    let ip = private_ip().ok_or(DeviceIdError::NotFound("private IPv4 address"))?;
+    Ok(from_ipv4(ip, offset, bits))
+}
+
+/// `from_ip` 的纯函数版本，从给定的 IPv4 地址 `ip` 中截取设备号。
+pub fn from_ipv4(ip: Ipv4Addr, offset: u32, bits: u32) -> u8 {
+    assert!((1..=u8::BITS).contains(&bits));
+    assert!(offset + bits <= 32);
+
+    ((u32::from(ip) >> offset) & ((1 << bits) - 1)) as u8
+}
+
+/// 获取本机用于对外通信的私有 IPv4 地址（10.0.0.0/8、172.16.0.0/12 或 192.168.0.0/16）。
+///
+/// 通过「连接」一个 UDP 套接字来让操作系统选择出口地址，UDP 的 `connect` 只会查询路由表而不会发送任何数据包。
+pub fn private_ip() -> Option<Ipv4Addr> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((Ipv4Addr::new(10, 255, 255, 255), 1)).ok()?;
+
+    match socket.local_addr().ok()?.ip() {
+        IpAddr::V4(ip) if ip.is_private() => Some(ip),
+        _ => None,
+    }
+}
+
+/// 从主网卡的 MAC 地址中派生宽度为 `bits`（1..=8）的设备号，适用于通过环境变量配置设备号容易出错的物理机集群。
+///
+/// MAC 地址经过 FNV-1a 哈希后折叠为 `bits` 位，因此不同设备的 MAC 地址仍有可能得到相同的设备号（概率约为
+/// 1 / 2^bits），在设备数量较多时请配合冲突检测使用。无法获取 MAC 地址时返回 `None`。
+pub fn from_mac(bits: u32) -> Option<u8> {
+    assert!((1..=u8::BITS).contains(&bits));
+
+    let hash = mac_address()?
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, &byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+
+    // 逐段异或折叠，使 MAC 地址的每一位都能影响设备号
+    let mask = (1u64 << bits) - 1;
+    let folded = (0..64)
+        .step_by(bits as usize)
+        .fold(0, |folded, shift| folded ^ (hash >> shift));
+
+    Some((folded & mask) as u8)
+}
+
+/// 获取主网卡的 MAC 地址：优先选择默认路由所在的网卡，其次按名称顺序选择第一个拥有有效 MAC 地址的非回环网卡。
+///
+/// 目前仅支持 Linux（读取 `/proc/net/route` 及 `/sys/class/net`），其他平台返回 `None`。
+pub fn mac_address() -> Option<[u8; 6]> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::fs;
+
+        let read = |name: &str| {
+            fs::read_to_string(format!("/sys/class/net/{}/address", name))
+                .ok()
+                .and_then(|address| parse_mac(address.trim()))
+                .filter(|mac| mac.iter().any(|&b| b != 0))
+        };
+
+        // `/proc/net/route` 中目标地址为 00000000 的路由即为默认路由
+        let default = fs::read_to_string("/proc/net/route")
+            .ok()
+            .and_then(|route| {
+                route.lines().skip(1).find_map(|line| {
+                    let mut fields = line.split_whitespace();
+                    let name = fields.next()?;
+                    (fields.next()? == "00000000").then(|| name.to_owned())
+                })
+            });
+
+        if let Some(mac) = default.as_deref().and_then(read) {
+            return Some(mac);
+        }
+
+        let mut names = fs::read_dir("/sys/class/net")
+            .ok()?
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter(|name| name != "lo")
+            .collect::<Vec<_>>();
+        names.sort();
+
+        names.iter().find_map(|name| read(name))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// 解析形如 '00:1a:2b:3c:4d:5e' 的 MAC 地址。
+#[cfg(target_os = "linux")]
+fn parse_mac(s: &str) -> Option<[u8; 6]> {
+    let mut mac = [0u8; 6];
+    let mut parts = s.split(':');
+
+    for byte in mac.iter_mut() {
+        *byte = u8::from_str_radix(parts.next()?, 16).ok()?;
+    }
+
+    parts.next().is_none().then_some(mac)
+}
+
+/// 通过外部系统（如 Redis、etcd）申请设备号租约时可能出现的错误。
+#[cfg(any(feature = "redis_lease", feature = "etcd_lease"))]
+#[derive(Debug, thiserror::Error)]
+pub enum LeaseError {
+    #[cfg(feature = "redis_lease")]
+    #[error("an error occurs when communicating with redis: {0}")]
+    Redis(
+        #[from]
+        #[source]
+        redis::RedisError,
+    ),
+
+    #[cfg(feature = "etcd_lease")]
+    #[error("an error occurs when communicating with etcd: {0}")]
+    Etcd(
+        #[from]
+        #[source]
+        reqwest::Error,
+    ),
+
+    #[cfg(feature = "etcd_lease")]
+    #[error("unexpected response from etcd: {0}")]
+    Protocol(&'static str),
+
+    #[error("no free device id left in the given range")]
+    Exhausted,
+
+    #[error("device id has already been resolved before the lease is claimed")]
+    AlreadyResolved,
+}
@@ -0,0 +1,318 @@
+use super::{set_device_id, CheckError, ConflictCheck, LeaseError};
+use crate::maintenance::{register_runtime_job, JobId};
+use redis::aio::MultiplexedConnection;
+use redis::{Client, Script};
+use std::future::Future;
+use std::ops::RangeInclusive;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 仅当租约仍由自己持有时才续期。
+const RENEW_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("PEXPIRE", KEYS[1], ARGV[2])
+else
+    return 0
+end
+"#;
+
+/// 仅当租约仍由自己持有时才释放。
+const RELEASE_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("DEL", KEYS[1])
+else
+    return 0
+end
+"#;
+
+/// ## Redis 设备号租约
+///
+/// `RedisLease` 通过 Redis 为每个实例分配设备号，适用于无法为每个 pod 手动配置 `FASTSEND_DEVICE_ID` 的场景：
+/// 按顺序对 `range` 内的每个设备号尝试 `SET <prefix><id> <token> NX PX <ttl>`，第一个成功写入的设备号即为本实例
+/// 的设备号，并通过 `set_device_id` 写入全局设备号。
+///
+/// 租约在后台以 `ttl / 3` 的间隔自动续期，程序退出前应调用 `RedisLeaseGuard::release` 主动释放租约，未释放的
+/// 租约将在 `ttl` 之后自动过期。续期注册为维护任务（见 `register_job`），在 `claim` 所在的 tokio 运行时中执行。
+#[derive(Debug, Clone)]
+pub struct RedisLease {
+    client: Client,
+
+    /// 租约键的前缀，缺省配置是 'fastsend:device_id:'。
+    prefix: String,
+
+    /// 可分配的设备号范围，缺省配置是 0..=255。
+    range: RangeInclusive<u8>,
+
+    /// 租约有效期，缺省配置是 30 秒。
+    ttl: Duration,
+}
+
+impl RedisLease {
+    pub fn new(client: Client) -> RedisLease {
+        RedisLease {
+            client,
+            prefix: "fastsend:device_id:".to_owned(),
+            range: 0..=u8::MAX,
+            ttl: Duration::from_secs(30),
+        }
+    }
+
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_owned();
+        self
+    }
+
+    pub fn range(mut self, range: RangeInclusive<u8>) -> Self {
+        self.range = range;
+        self
+    }
+
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        assert!(ttl >= Duration::from_secs(1));
+        self.ttl = ttl;
+        self
+    }
+
+    /// 申请设备号，成功后将设备号写入全局设备号并开始后台续期。
+    pub async fn claim(self) -> Result<RedisLeaseGuard, LeaseError> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let token = format!("{}-{:016x}", std::process::id(), rand::random::<u64>());
+        let ttl = self.ttl.as_millis() as u64;
+
+        for device_id in self.range.clone() {
+            let key = format!("{}{}", self.prefix, device_id);
+            let claimed: Option<String> = redis::cmd("SET")
+                .arg(&key)
+                .arg(&token)
+                .arg("NX")
+                .arg("PX")
+                .arg(ttl)
+                .query_async(&mut conn)
+                .await?;
+
+            if claimed.is_none() {
+                continue;
+            }
+
+            let guard = RedisLeaseGuard::new(conn, key, token, device_id, self.ttl);
+            if !set_device_id(device_id) {
+                guard.release().await?;
+                return Err(LeaseError::AlreadyResolved);
+            }
+
+            return Ok(guard);
+        }
+
+        Err(LeaseError::Exhausted)
+    }
+}
+
+/// `RedisLease::claim` 成功后返回的租约，持有期间在后台自动续期。
+#[derive(Debug)]
+pub struct RedisLeaseGuard {
+    conn: MultiplexedConnection,
+    key: String,
+    token: String,
+    device_id: u8,
+
+    /// 续期失败（租约已被他人占用或已过期）时置为 true。
+    lost: Arc<AtomicBool>,
+
+    /// 续期任务，见 `register_job`。
+    renewal: JobId,
+}
+
+impl RedisLeaseGuard {
+    fn new(
+        conn: MultiplexedConnection,
+        key: String,
+        token: String,
+        device_id: u8,
+        ttl: Duration,
+    ) -> RedisLeaseGuard {
+        let lost = Arc::new(AtomicBool::new(false));
+
+        let renewal = register_runtime_job("redis lease renewal", ttl / 3, {
+            let (conn, key, token, lost) =
+                (conn.clone(), key.clone(), token.clone(), Arc::clone(&lost));
+
+            move || {
+                let (conn, key, token, lost) =
+                    (conn.clone(), key.clone(), token.clone(), Arc::clone(&lost));
+
+                async move {
+                    // 租约已不属于自己时停止续期
+                    if lost.load(Ordering::SeqCst) {
+                        return;
+                    }
+
+                    // 网络错误时在下一个周期重试
+                    if let Ok(false) = renew(conn, &key, &token, ttl).await {
+                        lost.store(true, Ordering::SeqCst);
+                    }
+                }
+            }
+        });
+
+        release_on_shutdown(conn.clone(), key.clone(), token.clone(), renewal);
+
+        RedisLeaseGuard {
+            conn,
+            key,
+            token,
+            device_id,
+            lost,
+            renewal,
+        }
+    }
+
+    pub fn device_id(&self) -> u8 {
+        self.device_id
+    }
+
+    /// 租约是否仍由本实例持有，返回 `false` 时设备号可能已被其他实例占用，应尽快停止生成 id。
+    pub fn is_held(&self) -> bool {
+        !self.lost.load(Ordering::SeqCst)
+    }
+
+    /// 停止续期并释放租约。
+    pub async fn release(mut self) -> Result<(), LeaseError> {
+        crate::remove_job(self.renewal);
+
+        let _: i64 = Script::new(RELEASE_SCRIPT)
+            .key(&self.key)
+            .arg(&self.token)
+            .invoke_async(&mut self.conn)
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl Drop for RedisLeaseGuard {
+    fn drop(&mut self) {
+        crate::remove_job(self.renewal);
+    }
+}
+
+/// ## Redis 心跳冲突检测
+///
+/// `RedisHeartbeat` 用于设备号来自其他来源（如环境变量）时检测冲突：在 `Init::run` 时以
+/// `SET <prefix><device_id> <token> NX PX <ttl>` 写入心跳键，心跳键已被其他实例持有即视为冲突；写入成功后在后台
+/// 以 `ttl / 3` 的间隔续期，直到进程退出。续期注册为维护任务（见 `register_job`），在 `Init::run` 所在的 tokio
+/// 运行时中执行。
+#[derive(Debug, Clone)]
+pub struct RedisHeartbeat {
+    client: Client,
+
+    /// 心跳键的前缀，缺省配置是 'fastsend:heartbeat:'。
+    prefix: String,
+
+    /// 心跳有效期，缺省配置是 30 秒。
+    ttl: Duration,
+}
+
+impl RedisHeartbeat {
+    pub fn new(client: Client) -> RedisHeartbeat {
+        RedisHeartbeat {
+            client,
+            prefix: "fastsend:heartbeat:".to_owned(),
+            ttl: Duration::from_secs(30),
+        }
+    }
+
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_owned();
+        self
+    }
+
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        assert!(ttl >= Duration::from_secs(1));
+        self.ttl = ttl;
+        self
+    }
+}
+
+impl ConflictCheck for RedisHeartbeat {
+    fn check(
+        &mut self,
+        device_id: u8,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, CheckError>> + Send + '_>> {
+        Box::pin(async move {
+            let mut conn = self.client.get_multiplexed_async_connection().await?;
+            let key = format!("{}{}", self.prefix, device_id);
+            let token = format!("{}-{:016x}", std::process::id(), rand::random::<u64>());
+
+            let written: Option<String> = redis::cmd("SET")
+                .arg(&key)
+                .arg(&token)
+                .arg("NX")
+                .arg("PX")
+                .arg(self.ttl.as_millis() as u64)
+                .query_async(&mut conn)
+                .await?;
+
+            if written.is_none() {
+                return Ok(true);
+            }
+
+            // 心跳续期在进程的整个生命周期内持续进行，直到 `shutdown` 时停止并删除心跳键
+            let ttl = self.ttl;
+            let lost = Arc::new(AtomicBool::new(false));
+            let renewal = register_runtime_job("redis heartbeat renewal", ttl / 3, {
+                let (conn, key, token) = (conn.clone(), key.clone(), token.clone());
+
+                move || {
+                    let (conn, key, token, lost) =
+                        (conn.clone(), key.clone(), token.clone(), Arc::clone(&lost));
+
+                    async move {
+                        if !lost.load(Ordering::SeqCst) {
+                            if let Ok(false) = renew(conn, &key, &token, ttl).await {
+                                lost.store(true, Ordering::SeqCst);
+                            }
+                        }
+                    }
+                }
+            });
+            release_on_shutdown(conn, key, token, renewal);
+
+            Ok(false)
+        })
+    }
+}
+
+/// 将 `key` 的有效期延长至 `ttl`（仅当其仍由 `token` 持有时），返回 `Ok(false)` 代表 `key` 已不属于自己。
+async fn renew(
+    mut conn: MultiplexedConnection,
+    key: &str,
+    token: &str,
+    ttl: Duration,
+) -> redis::RedisResult<bool> {
+    let renewed: i64 = Script::new(RENEW_SCRIPT)
+        .key(key)
+        .arg(token)
+        .arg(ttl.as_millis() as u64)
+        .invoke_async(&mut conn)
+        .await?;
+    Ok(renewed != 0)
+}
+
+/// 在 `shutdown` 时停止续期并释放 `key`（仅当其仍由 `token` 持有时）。
+fn release_on_shutdown(
+    mut conn: MultiplexedConnection,
+    key: String,
+    token: String,
+    renewal: JobId,
+) {
+    crate::init::on_shutdown(move || async move {
+        crate::remove_job(renewal);
+        let _: Result<i64, _> = Script::new(RELEASE_SCRIPT)
+            .key(&key)
+            .arg(&token)
+            .invoke_async(&mut conn)
+            .await;
+    });
+}
@@ -0,0 +1,91 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+#[cfg(not(feature = "strict_order"))]
+use crate::{Block, Token};
+#[cfg(not(feature = "strict_order"))]
+use std::collections::VecDeque;
+#[cfg(not(feature = "strict_order"))]
+use std::ops::{Deref, DerefMut};
+
+/// 线程退出时随线程本地的 `Block` 一起丢弃的 `Token` 总数。
+static DISCARDED: AtomicU64 = AtomicU64::new(0);
+
+/// 通过 `on_block_dropped` 设置的钩子。
+static HOOK: RwLock<Option<Arc<dyn Fn(usize) + Send + Sync>>> = RwLock::new(None);
+
+/// 进程启动以来，因线程退出而随线程本地的 `Block` 一起丢弃（未被使用）的 `Token` 总数。
+///
+/// 每个线程最多持有 `set_blocks_per_claim` 个 `Block`，线程退出时其中尚未使用的 `Token` 不会归还给全局队列，而是
+/// 直接丢弃，因此每秒实际可用的 `Token` 数量会少于 65536。频繁创建、销毁线程的服务可以通过该计数判断是否需要调整
+/// 线程池或每次领取的 `Block` 数量。启用 'strict_order' feature 时 `Token` 不经过 `Block` 发放，计数始终为 0。
+pub fn discarded_tokens() -> u64 {
+    DISCARDED.load(Ordering::Relaxed)
+}
+
+/// 设置线程退出丢弃 `Block` 时调用的钩子（替换之前设置的钩子），参数为本次丢弃的 `Token` 数量。钩子在退出线程的
+/// 线程本地变量析构过程中调用，不应再调用 `next_token` 等依赖线程本地变量的方法。
+pub fn on_block_dropped<F>(hook: F)
+where
+    F: Fn(usize) + Send + Sync + 'static,
+{
+    *HOOK.write().unwrap_or_else(|error| error.into_inner()) = Some(Arc::new(hook));
+}
+
+/// 记录一次丢弃：累加计数、调用钩子，并在启用 'log' 或 'tracing' feature 时输出一条 debug 日志。
+#[cfg(not(feature = "strict_order"))]
+fn discard(tokens: usize) {
+    DISCARDED.fetch_add(tokens as u64, Ordering::Relaxed);
+
+    let hook = HOOK
+        .read()
+        .unwrap_or_else(|error| error.into_inner())
+        .clone();
+    if let Some(hook) = hook {
+        hook(tokens);
+    }
+
+    #[cfg(feature = "log")]
+    log::debug!(target: "fastsend", discarded = tokens; "thread exited with unused tokens");
+
+    // 与回退警告相同，同时开启两个特性时只通过 log 输出
+    #[cfg(all(feature = "tracing", not(feature = "log")))]
+    tracing::debug!(target: "fastsend", discarded = tokens, "thread exited with unused tokens");
+}
+
+/// 线程本地保存的 `Block` 队列（见 `fastsend::BLOCK`），析构（即线程退出）时统计其中剩余的 `Token`。
+#[cfg(not(feature = "strict_order"))]
+pub(crate) struct ThreadBlocks(VecDeque<Block<Token>>);
+
+#[cfg(not(feature = "strict_order"))]
+impl ThreadBlocks {
+    pub(crate) const fn new() -> Self {
+        ThreadBlocks(VecDeque::new())
+    }
+}
+
+#[cfg(not(feature = "strict_order"))]
+impl Deref for ThreadBlocks {
+    type Target = VecDeque<Block<Token>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(not(feature = "strict_order"))]
+impl DerefMut for ThreadBlocks {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(not(feature = "strict_order"))]
+impl Drop for ThreadBlocks {
+    fn drop(&mut self) {
+        let tokens = self.0.iter().map(|block| block.size_hint().0).sum();
+        if tokens > 0 {
+            discard(tokens);
+        }
+    }
+}
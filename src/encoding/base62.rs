@@ -0,0 +1,98 @@
+//! ## Base62
+//!
+//! 使用 '0'-'9'、'A'-'Z'、'a'-'z' 共 62 个字符编码 u64/u128 id，适用于 URL 等只允许字母和数字的场合：
+//!
+//! ```
+//! use fastsend::encoding::base62;
+//!
+//! assert_eq!(base62::encode_u64(61), "z");
+//! assert_eq!(base62::encode_u64_fixed(61), "0000000000z");
+//! assert_eq!(base62::decode_u64("0000000000z"), Ok(61));
+//! ```
+//!
+//! 变长模式（`encode_u64`、`encode_u128`）得到最短的字符串；定长模式（`encode_u64_fixed`、`encode_u128_fixed`）
+//! 前向填充 '0' 至 `U64_WIDTH`、`U128_WIDTH` 个字符，由于字符表按 ASCII 顺序排列，定长编码的字典序与数值大小一致。
+//! `decode_u64`、`decode_u128` 同时接受两种模式的输出；`decode_u64_fixed` 只接受恰好 `U64_WIDTH` 个字符的输入，
+//! 使每个数值只有唯一的合法表示。解码区分大小写。
+
+use super::EncodingError;
+
+/// 按 ASCII 顺序排列的 62 进制字符表。
+pub const ALPHABET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// 定长编码 u64 所需的字符数（62^11 > 2^64）。
+pub const U64_WIDTH: usize = 11;
+
+/// 定长编码 u128 所需的字符数（62^22 > 2^128）。
+pub const U128_WIDTH: usize = 22;
+
+const RADIX: u128 = 62;
+
+pub fn encode_u64(n: u64) -> String {
+    encode(n as u128, 1)
+}
+
+pub fn encode_u64_fixed(n: u64) -> String {
+    encode(n as u128, U64_WIDTH)
+}
+
+pub fn decode_u64(s: &str) -> Result<u64, EncodingError> {
+    let n = decode(s)?;
+    u64::try_from(n).map_err(|_| EncodingError::Overflow { width: s.len() })
+}
+
+/// 解码 `encode_u64_fixed` 的输出，长度不是 `U64_WIDTH` 时返回 `EncodingError::InvalidLength`。
+pub fn decode_u64_fixed(s: &str) -> Result<u64, EncodingError> {
+    if s.len() != U64_WIDTH {
+        return Err(EncodingError::InvalidLength(s.len()));
+    }
+    decode_u64(s)
+}
+
+pub fn encode_u128(n: u128) -> String {
+    encode(n, 1)
+}
+
+pub fn encode_u128_fixed(n: u128) -> String {
+    encode(n, U128_WIDTH)
+}
+
+pub fn decode_u128(s: &str) -> Result<u128, EncodingError> {
+    decode(s)
+}
+
+/// 编码并前向填充至至少 `width` 个字符。
+fn encode(mut n: u128, width: usize) -> String {
+    let alphabet = ALPHABET.as_bytes();
+
+    let mut digits = Vec::with_capacity(U128_WIDTH);
+    loop {
+        digits.push(alphabet[(n % RADIX) as usize]);
+        n /= RADIX;
+        if n == 0 {
+            break;
+        }
+    }
+
+    digits.resize(digits.len().max(width), alphabet[0]);
+    digits.iter().rev().map(|&byte| byte as char).collect()
+}
+
+fn decode(s: &str) -> Result<u128, EncodingError> {
+    if s.is_empty() {
+        return Err(EncodingError::Empty);
+    }
+
+    s.chars().try_fold(0u128, |n, c| {
+        let digit = match c {
+            '0'..='9' => c as u8 - b'0',
+            'A'..='Z' => c as u8 - b'A' + 10,
+            'a'..='z' => c as u8 - b'a' + 36,
+            _ => return Err(EncodingError::InvalidChar(c)),
+        };
+
+        n.checked_mul(RADIX)
+            .and_then(|n| n.checked_add(digit as u128))
+            .ok_or(EncodingError::Overflow { width: s.len() })
+    })
+}
@@ -0,0 +1,135 @@
+//! ## Base64url
+//!
+//! [RFC 4648](https://www.rfc-editor.org/rfc/rfc4648#section-5) 中 URL 安全的 base64 编码（不带 '=' 填充），
+//! 字符表为 'A'-'Z'、'a'-'z'、'0'-'9'、'-' 以及 '_'，16 字节的 UUID、`Flake128` 等二进制 id 只需要 22 个字符，
+//! 可以直接放入 HTTP 头或 URL：
+//!
+//! ```
+//! use fastsend::encoding::base64url;
+//!
+//! assert_eq!(base64url::encode(b"fastsend"), "ZmFzdHNlbmQ");
+//! assert_eq!(base64url::decode("ZmFzdHNlbmQ").unwrap(), b"fastsend");
+//! assert_eq!(base64url::decode_array::<16>(&base64url::encode(&[0xff; 16])), Ok([0xff; 16]));
+//! ```
+//!
+//! `BinarySerialer` 的输出可以直接通过 `encode` 编码。解码只接受规范的编码（不带填充、末尾多余的位为 0），并且
+//! 以不依赖字符内容的分支及查表完成字符校验与转换，解码耗时只与输入长度有关，可以用于解码令牌等敏感的 id。
+
+use super::EncodingError;
+
+/// base64url 字符表。
+pub const ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// 编码 16 字节（u128）所需的字符数。
+pub const U128_WIDTH: usize = 22;
+
+/// 编码为不带填充的 base64url 字符串。
+pub fn encode(bytes: &[u8]) -> String {
+    let alphabet = ALPHABET.as_bytes();
+
+    let mut output = String::with_capacity((bytes.len() * 4).div_ceil(3));
+    for chunk in bytes.chunks(3) {
+        let mut group = [0u8; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let n = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+
+        // 1 个字节编码为 2 个字符，2 个字节编码为 3 个字符，3 个字节编码为 4 个字符
+        for i in 0..=chunk.len() {
+            output.push(alphabet[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+
+    output
+}
+
+/// 解码不带填充的 base64url 字符串。
+pub fn decode(s: &str) -> Result<Vec<u8>, EncodingError> {
+    let input = s.as_bytes();
+    if input.is_empty() {
+        return Err(EncodingError::Empty);
+    }
+    if input.len() % 4 == 1 {
+        return Err(EncodingError::InvalidLength(input.len()));
+    }
+
+    let mut output = Vec::with_capacity(input.len() * 3 / 4);
+    let mut invalid = 0u8;
+    for chunk in input.chunks(4) {
+        let mut n = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let (value, valid) = decode_char(c);
+            invalid |= valid ^ 1;
+            n |= (value as u32) << (18 - 6 * i);
+        }
+
+        let bytes = n.to_be_bytes();
+        let len = chunk.len() - 1;
+        output.extend_from_slice(&bytes[1..=len]);
+
+        // 不足 4 个字符时，末尾未被使用的位必须为 0，否则同一字节序列会有多种编码
+        invalid |= ((n & (0xff_ffff >> (8 * len))) != 0) as u8;
+    }
+
+    if invalid != 0 {
+        return Err(first_invalid(s));
+    }
+
+    Ok(output)
+}
+
+/// 解码恰好 `N` 个字节的 base64url 字符串（如 16 字节的 UUID），长度不符时返回 `EncodingError::InvalidLength`。
+pub fn decode_array<const N: usize>(s: &str) -> Result<[u8; N], EncodingError> {
+    if s.len() != (N * 4).div_ceil(3) {
+        return Err(EncodingError::InvalidLength(s.len()));
+    }
+
+    let bytes = decode(s)?;
+    let mut output = [0u8; N];
+    output.copy_from_slice(&bytes);
+    Ok(output)
+}
+
+/// 以大端序编码 u128，恰好 `U128_WIDTH` 个字符。
+pub fn encode_u128(n: u128) -> String {
+    encode(&n.to_be_bytes())
+}
+
+pub fn decode_u128(s: &str) -> Result<u128, EncodingError> {
+    decode_array(s).map(u128::from_be_bytes)
+}
+
+/// 将单个字符转换为 6 位的数值，同时返回其是否合法（0 或 1）。
+///
+/// 转换过程只使用算术运算：`range` 在 `c` 落在 [lo, hi] 内时得到 -1（全 1），否则得到 0，各区间的结果通过掩码
+/// 合并，不会因为字符内容的不同而走入不同的分支。
+fn decode_char(c: u8) -> (u8, u8) {
+    fn range(c: i16, lo: u8, hi: u8) -> i16 {
+        ((lo as i16 - 1 - c) & (c - hi as i16 - 1)) >> 8
+    }
+
+    let c = c as i16;
+    let upper = range(c, b'A', b'Z');
+    let lower = range(c, b'a', b'z');
+    let digit = range(c, b'0', b'9');
+    let minus = range(c, b'-', b'-');
+    let underscore = range(c, b'_', b'_');
+
+    let value = upper & (c - b'A' as i16)
+        | lower & (c - b'a' as i16 + 26)
+        | digit & (c - b'0' as i16 + 52)
+        | minus & 62
+        | underscore & 63;
+    let valid = upper | lower | digit | minus | underscore;
+
+    (value as u8, (valid & 1) as u8)
+}
+
+/// 解码失败时定位第一个不合法的字符，字符均合法时说明末尾多余的位不为 0，归咎于最后一个字符。
+fn first_invalid(s: &str) -> EncodingError {
+    let c = s
+        .chars()
+        .find(|&c| !c.is_ascii() || decode_char(c as u8).1 == 0)
+        .or_else(|| s.chars().last())
+        .unwrap_or_default();
+    EncodingError::InvalidChar(c)
+}
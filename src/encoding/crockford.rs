@@ -0,0 +1,145 @@
+//! ## Crockford Base32
+//!
+//! [Crockford base32](https://www.crockford.com/base32.html) 编码及解码，字符表不包含容易混淆的 'I'、'L'、'O'
+//! 以及 'U'，适合需要人工抄写或口述的 id：
+//!
+//! ```
+//! use fastsend::encoding::crockford;
+//!
+//! assert_eq!(crockford::encode_u64(1234), "16J");
+//! assert_eq!(crockford::decode_u64("16j"), Ok(1234));
+//! assert_eq!(crockford::decode_u64("l6J"), Ok(1234));
+//!
+//! // 可选的校验符号（数值对 37 取模）
+//! assert_eq!(crockford::encode_u64_checked(1234), "16JD");
+//! assert_eq!(crockford::decode_u64_checked("16-jd"), Ok(1234));
+//! ```
+//!
+//! 编码输出大写字母；解码不区分大小写，忽略 '-'，并将 'I'/'L' 视为 '1'、'O' 视为 '0'。定长模式
+//! （`encode_u64_fixed`、`encode_u128_fixed`）前向填充 '0' 至 `U64_WIDTH`、`U128_WIDTH` 个字符，字典序与数值大小
+//! 一致。带有校验符号的字符串需要使用 `decode_*_checked` 解码，校验符号不匹配时返回 `EncodingError::Checksum`。
+
+use super::EncodingError;
+
+/// Crockford base32 字符表（大写）。
+pub const ALPHABET: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// 校验符号的字符表：`ALPHABET` 之后追加 5 个只用于校验符号的字符。
+pub const CHECK_ALPHABET: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U";
+
+/// 定长编码 u64 所需的字符数（64 bits 补齐为 65 bits）。
+pub const U64_WIDTH: usize = 13;
+
+/// 定长编码 u128 所需的字符数（128 bits 补齐为 130 bits）。
+pub const U128_WIDTH: usize = 26;
+
+pub fn encode_u64(n: u64) -> String {
+    encode(n as u128, 1)
+}
+
+pub fn encode_u64_fixed(n: u64) -> String {
+    encode(n as u128, U64_WIDTH)
+}
+
+/// 变长编码并追加校验符号。
+pub fn encode_u64_checked(n: u64) -> String {
+    let mut output = encode_u64(n);
+    output.push(check_symbol(n as u128));
+    output
+}
+
+pub fn decode_u64(s: &str) -> Result<u64, EncodingError> {
+    narrow(s, decode(s)?)
+}
+
+/// 解码以校验符号结尾的字符串。
+pub fn decode_u64_checked(s: &str) -> Result<u64, EncodingError> {
+    narrow(s, decode_u128_checked(s)?)
+}
+
+pub fn encode_u128(n: u128) -> String {
+    encode(n, 1)
+}
+
+pub fn encode_u128_fixed(n: u128) -> String {
+    encode(n, U128_WIDTH)
+}
+
+/// 变长编码并追加校验符号。
+pub fn encode_u128_checked(n: u128) -> String {
+    let mut output = encode_u128(n);
+    output.push(check_symbol(n));
+    output
+}
+
+pub fn decode_u128(s: &str) -> Result<u128, EncodingError> {
+    decode(s)
+}
+
+/// 解码以校验符号结尾的字符串。
+pub fn decode_u128_checked(s: &str) -> Result<u128, EncodingError> {
+    let s = s.trim_end_matches('-');
+    let (digits, check) = match s.char_indices().last() {
+        Some((index, check)) => (&s[..index], check),
+        None => return Err(EncodingError::Empty),
+    };
+
+    let n = decode(digits)?;
+    if !check.eq_ignore_ascii_case(&check_symbol(n)) {
+        return Err(EncodingError::Checksum);
+    }
+
+    Ok(n)
+}
+
+/// `n` 的校验符号，即 `CHECK_ALPHABET` 中的第 `n % 37` 个字符。
+pub fn check_symbol(n: u128) -> char {
+    CHECK_ALPHABET.as_bytes()[(n % 37) as usize] as char
+}
+
+/// 编码并前向填充至至少 `width` 个字符。
+fn encode(mut n: u128, width: usize) -> String {
+    let alphabet = ALPHABET.as_bytes();
+
+    let mut digits = Vec::with_capacity(U128_WIDTH);
+    loop {
+        digits.push(alphabet[(n & 0x1f) as usize]);
+        n >>= 5;
+        if n == 0 {
+            break;
+        }
+    }
+
+    digits.resize(digits.len().max(width), alphabet[0]);
+    digits.iter().rev().map(|&byte| byte as char).collect()
+}
+
+fn decode(s: &str) -> Result<u128, EncodingError> {
+    let mut digits = s.chars().filter(|&c| c != '-').peekable();
+    if digits.peek().is_none() {
+        return Err(EncodingError::Empty);
+    }
+
+    digits.try_fold(0u128, |n, c| {
+        let normalized = match c.to_ascii_uppercase() {
+            'I' | 'L' => '1',
+            'O' => '0',
+            c => c,
+        };
+        let digit = ALPHABET
+            .find(normalized)
+            .ok_or(EncodingError::InvalidChar(c))?;
+
+        // 左移前检查高 5 位，避免溢出的位被静默丢弃
+        if n >> (u128::BITS - 5) != 0 {
+            return Err(EncodingError::Overflow { width: s.len() });
+        }
+
+        Ok(n << 5 | digit as u128)
+    })
+}
+
+/// 将解码结果收窄为 u64。
+fn narrow(s: &str, n: u128) -> Result<u64, EncodingError> {
+    u64::try_from(n).map_err(|_| EncodingError::Overflow { width: s.len() })
+}
@@ -0,0 +1,189 @@
+use std::fmt;
+
+pub mod base62;
+pub mod base64url;
+pub mod crockford;
+
+/// 数字优先的 36 进制字符表：'0'-'9' 之后是 'A'-'Z'。
+pub const DIGITS_FIRST: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// 字母优先的 36 进制字符表：'A'-'Z' 之后是 '0'-'9'。
+pub const LETTERS_FIRST: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// ## 进制编码
+///
+/// `Radix` 使用字符表 `alphabet` 将整数编码为 N 进制字符串（N 为字符表的长度），字符在字符表中的下标即为其代表的
+/// 数值，字符表的第一个字符同时用作前向填充的字符。fastsend 中 `TicketSerialer`、`IncrSerialer` 等序列号格式都
+/// 基于 `Radix` 构建，也可以用于自定义的序列号格式：
+///
+/// ```
+/// use fastsend::encoding::Radix;
+///
+/// let radix = Radix::digits_first(36);
+/// assert_eq!(radix.encode_padded(35, 3), "00Z");
+/// assert_eq!(radix.decode("00Z"), Ok(35));
+/// assert!(radix.encode_fixed(36 * 36, 2).is_err());
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Radix<'a> {
+    alphabet: &'a [u8],
+}
+
+impl<'a> Radix<'a> {
+    /// 使用自定义的字符表，字符表只能包含 ASCII 字符、不能重复，且至少包含 2 个字符。
+    pub fn new(alphabet: &'a str) -> Result<Radix<'a>, EncodingError> {
+        let bytes = alphabet.as_bytes();
+        let unique = bytes
+            .iter()
+            .enumerate()
+            .all(|(i, byte)| !bytes[..i].contains(byte));
+
+        if bytes.len() < 2 || !alphabet.is_ascii() || !unique {
+            return Err(EncodingError::InvalidAlphabet);
+        }
+
+        Ok(Radix { alphabet: bytes })
+    }
+
+    /// `DIGITS_FIRST` 的前 `radix`（2..=36）个字符组成的 `radix` 进制。
+    pub fn digits_first(radix: usize) -> Radix<'static> {
+        assert!((2..=36).contains(&radix));
+        Radix {
+            alphabet: &DIGITS_FIRST.as_bytes()[..radix],
+        }
+    }
+
+    /// `LETTERS_FIRST` 的前 `radix`（2..=36）个字符组成的 `radix` 进制。
+    pub fn letters_first(radix: usize) -> Radix<'static> {
+        assert!((2..=36).contains(&radix));
+        Radix {
+            alphabet: &LETTERS_FIRST.as_bytes()[..radix],
+        }
+    }
+
+    pub fn radix(&self) -> usize {
+        self.alphabet.len()
+    }
+
+    /// 编码为不带填充的最短字符串（0 编码为字符表的第一个字符）。
+    pub fn encode(&self, n: u64) -> String {
+        self.encode_padded(n, 1)
+    }
+
+    /// 编码并前向填充至至少 `width` 个字符，数值超出 `width` 位时保留全部的位（结果长于 `width`），不会截断。
+    pub fn encode_padded(&self, mut n: u64, width: usize) -> String {
+        let radix = self.radix() as u64;
+
+        let mut digits = Vec::with_capacity(width.max(2));
+        loop {
+            digits.push(self.alphabet[(n % radix) as usize]);
+            n /= radix;
+            if n == 0 {
+                break;
+            }
+        }
+
+        digits.resize(digits.len().max(width), self.alphabet[0]);
+        digits.iter().rev().map(|&byte| byte as char).collect()
+    }
+
+    /// 编码为恰好 `width` 个字符，数值超出 `width` 位时返回 `EncodingError::Overflow`。
+    pub fn encode_fixed(&self, n: u64, width: usize) -> Result<String, EncodingError> {
+        let output = self.encode_padded(n, width);
+        if output.len() > width {
+            return Err(EncodingError::Overflow { width });
+        }
+
+        Ok(output)
+    }
+
+    /// `encode_fixed` 的无分配版本：将恰好 `width` 个字符追加写入 `output`，数值超出 `width` 位时返回
+    /// `EncodingError::Overflow` 且不写入任何字符。
+    pub fn encode_fixed_into(
+        &self,
+        n: u64,
+        width: usize,
+        output: &mut String,
+    ) -> Result<(), EncodingError> {
+        let radix = self.radix() as u64;
+
+        // 第 `position` 位（从 0 开始）的权重，超出 u64 时为 `None`，此时任何 u64 数值在该位上都为 0
+        let weight = |position: usize| {
+            u32::try_from(position)
+                .ok()
+                .and_then(|position| radix.checked_pow(position))
+        };
+
+        if weight(width).is_some_and(|max| n >= max) {
+            return Err(EncodingError::Overflow { width });
+        }
+
+        output.reserve(width);
+        for position in (0..width).rev() {
+            let digit = weight(position).map_or(0, |weight| n / weight % radix);
+            output.push(self.alphabet[digit as usize] as char);
+        }
+
+        Ok(())
+    }
+
+    /// 解码由 `encode` 系列方法生成的字符串，前向填充的字符会被忽略。
+    pub fn decode(&self, s: &str) -> Result<u64, EncodingError> {
+        if s.is_empty() {
+            return Err(EncodingError::Empty);
+        }
+
+        s.chars().try_fold(0u64, |n, c| {
+            let digit = self
+                .alphabet
+                .iter()
+                .position(|&byte| byte as char == c)
+                .ok_or(EncodingError::InvalidChar(c))?;
+
+            n.checked_mul(self.radix() as u64)
+                .and_then(|n| n.checked_add(digit as u64))
+                .ok_or(EncodingError::Overflow { width: s.len() })
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum EncodingError {
+    /// 字符表少于 2 个字符、包含非 ASCII 字符或包含重复的字符。
+    InvalidAlphabet,
+
+    /// 编码时数值无法放入 `width` 个字符，或解码时 `width` 个字符代表的数值超出了 u64 的范围。
+    Overflow { width: usize },
+
+    /// 解码时遇到了字符表之外的字符。
+    InvalidChar(char),
+
+    /// 解码空字符串。
+    Empty,
+
+    /// 解码时校验符号与数值不匹配，见 `crockford::decode_u64_checked`。
+    Checksum,
+
+    /// 解码时字符串的长度不合法，或与期望的字节数不符，见 `base64url::decode_array`。
+    InvalidLength(usize),
+}
+
+impl fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodingError::InvalidAlphabet => write!(
+                f,
+                "alphabet must contain at least 2 unique ascii characters"
+            ),
+            EncodingError::Overflow { width } => {
+                write!(f, "value overflows {} digits", width)
+            }
+            EncodingError::InvalidChar(c) => write!(f, "invalid character {:?}", c),
+            EncodingError::Empty => write!(f, "cannot decode empty string"),
+            EncodingError::Checksum => write!(f, "check symbol mismatch"),
+            EncodingError::InvalidLength(len) => write!(f, "invalid encoded length {}", len),
+        }
+    }
+}
+
+impl std::error::Error for EncodingError {}
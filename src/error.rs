@@ -0,0 +1,46 @@
+use std::fmt;
+
+/// `FastsendError` 是 fastsend 核心发号路径（`Cursor`、`BlockFrame` 以及 `next_token`）的错误类型，由
+/// `Cursor::try_new`、`BlockFrame::try_new`、`try_next_token` 等 `try_` 系列方法返回，对应的非 `try_` 方法
+/// 在遇到相同的错误时会 panic。
+///
+/// 长期运行的服务可以使用 `try_` 系列方法，在系统时间异常等情况下返回错误（如拒绝当前请求）而不是终止进程。
+///
+/// 之后的版本可能会增加新的错误种类，匹配时需要保留通配分支。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum FastsendError {
+    /// 当前时间早于 `Cursor::timebase()`（缺省为 '2021-12-10 12:27:33'），通常是系统时间被错误地设置到了过去。
+    ClockBeforeTimebase,
+
+    /// 当前时间超出了 `Cursor` 的 u32 表示范围（`Cursor::timebase()` 之后约 136 年，设置纪元版本时为版本位之外的
+    /// 位数，见 `set_epoch`）。
+    CursorOverflow,
+
+    /// 从 `Block` 中获取元素时 `Block` 已经耗尽。
+    DrainedBlock,
+
+    /// 在 `next_token_timeout` 指定的时间内没有获取到 `Token`，通常是补充 `Block` 的过程被阻塞（如等待下一秒）。
+    Timeout,
+
+    /// 当前时间早于 `ShardFrame` 的起始时间（见 `ShardFrame::epoch`），通常是系统时间被错误地设置到了过去。
+    ClockBeforeShardEpoch,
+}
+
+impl fmt::Display for FastsendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FastsendError::ClockBeforeTimebase => {
+                write!(f, "system time is earlier than the cursor timebase")
+            }
+            FastsendError::CursorOverflow => write!(f, "cursor overflows over u32"),
+            FastsendError::DrainedBlock => write!(f, "unexpected drained `Block` iterator"),
+            FastsendError::Timeout => write!(f, "timed out waiting for the next token"),
+            FastsendError::ClockBeforeShardEpoch => {
+                write!(f, "system time is earlier than the shard epoch")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FastsendError {}
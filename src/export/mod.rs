@@ -0,0 +1,271 @@
+use crate::Serialer;
+use std::collections::HashSet;
+use std::fmt::{self, Display};
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// 同一行连续生成重复序列号的次数上限，超出后认为 `Serialer` 无法继续生成不重复的序列号。
+const MAX_RETRIES: usize = 16;
+
+/// 导出的进度，`on_progress` 回调及 `CsvExport::write` 的返回值。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ExportProgress {
+    /// 续写时文件中已有的序列号数量。
+    pub existing: u64,
+
+    /// 本次写入的序列号数量。
+    pub written: u64,
+
+    /// 需要导出的序列号总数。
+    pub total: u64,
+}
+
+impl ExportProgress {
+    /// 文件中已有的序列号总数。
+    pub fn completed(&self) -> u64 {
+        self.existing + self.written
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    /// 读写导出文件失败。
+    Io(io::Error),
+
+    /// `Serialer` 构建序列号失败。
+    Build(String),
+
+    /// `Serialer` 连续多次生成了文件中已有的序列号。
+    Duplicate(String),
+
+    /// 已有的文件无法续写，如表头与当前配置不一致。
+    InvalidFile(String),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Io(error) => write!(f, "failed to write export file: {}", error),
+            ExportError::Build(error) => write!(f, "failed to build serial: {}", error),
+            ExportError::Duplicate(serial) => {
+                write!(
+                    f,
+                    "serialer keeps generating duplicated serial {:?}",
+                    serial
+                )
+            }
+            ExportError::InvalidFile(error) => write!(f, "cannot resume export file: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ExportError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ExportError {
+    fn from(error: io::Error) -> Self {
+        ExportError::Io(error)
+    }
+}
+
+/// ## 离线导出
+///
+/// `CsvExport` 预先生成 `n` 个序列号并写入 CSV 文件（每行一个序列号），用于设备出厂前烧录设备码、提前印刷券码等
+/// 需要在使用之前批量生成序列号的场合。每个序列号由 `factory(index)` 创建的 `Serialer` 构建（`index` 为行号，从 0
+/// 开始，可以用于 `feed`），与在线生成使用相同的 `Serialer`，因此具有相同的唯一性保证；导出过程中还会对整个文件
+/// 判重，重复的序列号会被丢弃并重新生成。
+///
+/// 导出可以中断后续写：目标文件已经存在时，先截断末尾不完整的一行，再从已有的行数继续生成，直到文件中共有 `n` 个
+/// 序列号。写入的内容每 `progress_interval` 行落盘一次并调用 `on_progress` 回调：
+///
+/// ```no_run
+/// use fastsend::export::CsvExport;
+/// use fastsend::{Serialer, TimeSerialer};
+///
+/// # futures::executor::block_on(async {
+/// let factory = |index: u64| {
+///     let mut serialer = TimeSerialer::default();
+///     serialer.feed(&index.to_be_bytes());
+///     serialer
+/// };
+///
+/// let progress = CsvExport::new(factory)
+///     .header(Some("device_code"))
+///     .on_progress(|progress| println!("{}/{}", progress.completed(), progress.total))
+///     .write(1000, "device_codes.csv")
+///     .await
+///     .unwrap();
+/// assert_eq!(progress.completed(), 1000);
+/// # });
+/// ```
+pub struct CsvExport<F> {
+    factory: F,
+
+    /// CSV 表头，缺省配置是 "id"，`None` 表示不写入表头。
+    header: Option<String>,
+
+    /// 每写入多少行落盘并回调一次进度，缺省配置是 10000。
+    progress_interval: u64,
+
+    on_progress: Option<Box<dyn FnMut(&ExportProgress) + Send>>,
+}
+
+impl<F> CsvExport<F> {
+    pub fn new(factory: F) -> Self {
+        CsvExport {
+            factory,
+            header: Some("id".to_owned()),
+            progress_interval: 10_000,
+            on_progress: None,
+        }
+    }
+
+    pub fn header(mut self, header: Option<&str>) -> Self {
+        self.header = header.map(escape);
+        self
+    }
+
+    pub fn progress_interval(mut self, interval: u64) -> Self {
+        assert!(interval > 0, "progress interval must be positive");
+        self.progress_interval = interval;
+        self
+    }
+
+    pub fn on_progress<P>(mut self, callback: P) -> Self
+    where
+        P: FnMut(&ExportProgress) + Send + 'static,
+    {
+        self.on_progress = Some(Box::new(callback));
+        self
+    }
+}
+
+impl<F, S> CsvExport<F>
+where
+    F: FnMut(u64) -> S,
+    S: Serialer,
+    S::Error: Display,
+{
+    /// 导出至 `path`，文件中已有 `n` 个及以上的序列号时不再写入。
+    pub async fn write<P: AsRef<Path>>(
+        mut self,
+        n: u64,
+        path: P,
+    ) -> Result<ExportProgress, ExportError> {
+        let path = path.as_ref();
+        let (mut seen, complete) = self.resume(path)?;
+
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.set_len(complete)?;
+
+        let mut progress = ExportProgress {
+            existing: seen.len() as u64,
+            written: 0,
+            total: n,
+        };
+
+        let mut writer = BufWriter::new(file);
+        if complete == 0 {
+            if let Some(header) = &self.header {
+                writeln!(writer, "{}", header)?;
+            }
+        }
+
+        while progress.completed() < n {
+            let line = self.generate(progress.completed(), &seen).await?;
+            writeln!(writer, "{}", line)?;
+            seen.insert(line);
+            progress.written += 1;
+
+            if progress.written.is_multiple_of(self.progress_interval) {
+                writer.flush()?;
+                self.report(&progress);
+            }
+        }
+
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        self.report(&progress);
+        Ok(progress)
+    }
+
+    /// 读取已有的文件：返回其中的序列号，以及完整行的总长度（之后的内容是中断时未写完的一行）。
+    fn resume(&self, path: &Path) -> Result<(HashSet<String>, u64), ExportError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error.into()),
+        };
+
+        let complete = content.rfind('\n').map_or(0, |i| i + 1);
+        let mut lines = content[..complete].lines();
+
+        if complete > 0 {
+            if let Some(header) = &self.header {
+                if lines.next() != Some(header.as_str()) {
+                    return Err(ExportError::InvalidFile(format!(
+                        "header mismatch, expected {:?}",
+                        header
+                    )));
+                }
+            }
+        }
+
+        Ok((lines.map(str::to_owned).collect(), complete as u64))
+    }
+
+    /// 生成第 `index` 行的序列号，与已有的序列号重复时重新生成。
+    async fn generate(
+        &mut self,
+        index: u64,
+        seen: &HashSet<String>,
+    ) -> Result<String, ExportError> {
+        let mut line = String::new();
+        for _ in 0..MAX_RETRIES {
+            let serial = (self.factory)(index)
+                .build()
+                .await
+                .map_err(|error| ExportError::Build(error.to_string()))?;
+
+            line = escape(&serial.to_string());
+            if !seen.contains(&line) {
+                return Ok(line);
+            }
+        }
+
+        Err(ExportError::Duplicate(line))
+    }
+
+    fn report(&mut self, progress: &ExportProgress) {
+        if let Some(callback) = &mut self.on_progress {
+            callback(progress);
+        }
+    }
+}
+
+/// 以 `CsvExport` 的缺省配置将 `n` 个序列号导出至 `path`，见 `CsvExport`。
+pub async fn write_csv<F, S, P>(factory: F, n: u64, path: P) -> Result<ExportProgress, ExportError>
+where
+    F: FnMut(u64) -> S,
+    S: Serialer,
+    S::Error: Display,
+    P: AsRef<Path>,
+{
+    CsvExport::new(factory).write(n, path).await
+}
+
+/// 按 RFC 4180 转义 CSV 字段：包含逗号、双引号或换行时以双引号包裹，并将双引号写为两个双引号。
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
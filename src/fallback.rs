@@ -0,0 +1,105 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// ## 静默回退
+///
+/// fastsend 在缺少配置时会静默地回退到随机值，这在单设备场景下可以减少碰撞，但在多设备场景下都是潜在的冲突
+/// 来源。开启 `log` 或 `tracing` 特性后，每种回退在第一次发生时会以 'fastsend' 为 target 输出一条警告，其中
+/// 'fallback' 字段为回退的种类；两个特性同时开启时只通过 `log` 输出，都未开启时不输出任何内容。
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum Fallback {
+    /// 编译时未提供 `FASTSEND_RANDOM_VALUE`，`RV` 使用运行时生成的随机数。
+    RandomValue,
+
+    /// 未配置设备号，`Token` 使用进程号代替设备号。
+    DeviceId,
+
+    /// 环境变量 `FASTSEND_DEVICE_ID` 指定的来源（'mac'、'k8s'、'ip'）无法解析，视为未配置设备号。
+    DeviceIdSource,
+
+    /// 未配置设备号，`TimeSerialer` 每次生成序列号时都使用随机的设备号。
+    SerialDevice,
+
+    /// 未设置全局置换，`Token::public_id` 返回未经置换的 id。
+    Permutation,
+}
+
+impl Fallback {
+    fn name(self) -> &'static str {
+        match self {
+            Fallback::RandomValue => "random_value",
+            Fallback::DeviceId => "device_id",
+            Fallback::DeviceIdSource => "device_id_source",
+            Fallback::SerialDevice => "serial_device",
+            Fallback::Permutation => "permutation",
+        }
+    }
+
+    fn message(self) -> &'static str {
+        match self {
+            Fallback::RandomValue => {
+                "FASTSEND_RANDOM_VALUE is not provided at compile time, a random RV is used, \
+                 devices built separately may derive the same device id"
+            }
+            Fallback::DeviceId => {
+                "device id is not configured, the process id is used instead, \
+                 ids generated on different devices may collide"
+            }
+            Fallback::DeviceIdSource => {
+                "the source named by FASTSEND_DEVICE_ID cannot be resolved, \
+                 the device id is treated as not configured"
+            }
+            Fallback::SerialDevice => {
+                "device id is not configured, TimeSerialer uses random device digits for \
+                 every serial, serials generated on different devices may collide"
+            }
+            Fallback::Permutation => {
+                "id permutation is not configured, Token::public_id returns the packed id, \
+                 timestamps and device ids can be read from public ids"
+            }
+        }
+    }
+
+    fn flag(self) -> &'static AtomicBool {
+        static FLAGS: [AtomicBool; 5] = [
+            AtomicBool::new(false),
+            AtomicBool::new(false),
+            AtomicBool::new(false),
+            AtomicBool::new(false),
+            AtomicBool::new(false),
+        ];
+
+        &FLAGS[self as usize]
+    }
+}
+
+/// 报告一次回退，同一种回退只会输出一次警告。
+pub(crate) fn warn(fallback: Fallback) {
+    if fallback.flag().swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    #[cfg(feature = "log")]
+    log::warn!(target: "fastsend", fallback = fallback.name(); "{}", fallback.message());
+
+    // 同时开启两个特性时只通过 log 输出，tracing 的 log 桥接会将同一条警告再转发一次
+    #[cfg(all(feature = "tracing", not(feature = "log")))]
+    tracing::warn!(target: "fastsend", fallback = fallback.name(), "{}", fallback.message());
+
+    #[cfg(not(any(feature = "log", feature = "tracing")))]
+    let _ = (fallback.name(), fallback.message());
+}
+
+/// 报告一次不应被静默忽略、但也不中断运行的异常，如按 `ConflictPolicy::Warn` 继续启动的检测失败（'conflict_policy'）、
+/// 按 `ClockErrorPolicy::Warn` 跳过的时钟来源（'clock_error_policy'）、panic 的维护任务（'maintenance_job'）以及
+/// 持久化 cursor 失败（'cursor_persistence'），'fallback' 字段为 `kind`。与 `warn` 不同，每次都会输出。
+pub(crate) fn warn_each(kind: &'static str, message: &dyn fmt::Display) {
+    #[cfg(feature = "log")]
+    log::warn!(target: "fastsend", fallback = kind; "{}", message);
+
+    #[cfg(all(feature = "tracing", not(feature = "log")))]
+    tracing::warn!(target: "fastsend", fallback = kind, "{}", message);
+
+    #[cfg(not(any(feature = "log", feature = "tracing")))]
+    let _ = (kind, message);
+}
@@ -0,0 +1,253 @@
+use crate::encoding::crockford;
+use lazy_static::lazy_static;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+
+/// `HlcTimestamp` 是混合逻辑时钟（Hybrid Logical Clock）的时间戳，由高到低依次为 48 bits 的毫秒级 Unix 时间戳
+/// （物理部分）与 16 bits 的逻辑计数器，按 u64 比较大小即为 HLC 的先后顺序。
+///
+/// 时间戳可以通过 `as_u64`、`from_u64` 在服务之间传递（如放入请求头），接收方通过 `Hlc::observe` 合并。
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct HlcTimestamp(u64);
+
+impl HlcTimestamp {
+    pub fn new(physical_millis: u64, logical: u16) -> HlcTimestamp {
+        assert!(physical_millis < 1 << 48, "physical time overflows 48 bits");
+        HlcTimestamp(physical_millis << 16 | logical as u64)
+    }
+
+    pub fn from_u64(n: u64) -> HlcTimestamp {
+        HlcTimestamp(n)
+    }
+
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    /// 物理部分：毫秒级 Unix 时间戳。
+    pub fn physical_millis(&self) -> u64 {
+        self.0 >> 16
+    }
+
+    pub fn logical(&self) -> u16 {
+        self.0 as u16
+    }
+
+    /// 同一物理时间下的下一个时间戳，逻辑计数器溢出时向后借用一毫秒。
+    fn tick(self) -> HlcTimestamp {
+        HlcTimestamp(self.0 + 1)
+    }
+}
+
+/// `Hlc::observe` 拒绝远端时间戳时返回的错误。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum HlcError {
+    /// 远端时间戳的物理部分超前本地时钟超过 `max_offset`，通常是远端的系统时间设置有误，合并后会使本地的 HLC
+    /// 长期超前于物理时间。
+    ClockOffset { offset: Duration },
+}
+
+impl fmt::Display for HlcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HlcError::ClockOffset { offset } => {
+                write!(f, "remote clock is {:?} ahead of the local clock", offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HlcError {}
+
+/// ## 混合逻辑时钟
+///
+/// `Hlc` 维护一个只增不减的 HLC 时间戳：本地事件（包括生成 id）时取物理时间与上一个时间戳的较大者并递增逻辑计数器，
+/// 接收到其他服务的时间戳时通过 `observe` 将其合并，使之后的本地时间戳大于所有已观察到的时间戳。因此沿请求链路
+/// 传递时间戳时，因果关系在后的事件总是得到更大的时间戳，即使各服务的系统时间存在偏差。
+///
+/// ```
+/// use fastsend::{Hlc, HlcTimestamp};
+///
+/// let hlc = Hlc::new();
+/// let first = hlc.now();
+/// assert!(hlc.now() > first);
+///
+/// // 观察到超前的远端时间戳后，本地时间戳不会早于它
+/// let remote = HlcTimestamp::new(first.physical_millis() + 1000, 7);
+/// hlc.observe(remote).unwrap();
+/// assert!(hlc.now() > remote);
+/// ```
+#[derive(Debug)]
+pub struct Hlc {
+    /// 上一个发出或观察到的时间戳。
+    last: Mutex<HlcTimestamp>,
+
+    /// 允许远端时间戳超前本地物理时间的最大值，缺省配置是不限制。
+    max_offset: Option<Duration>,
+}
+
+impl Hlc {
+    pub fn new() -> Hlc {
+        Hlc {
+            last: Mutex::new(HlcTimestamp(0)),
+            max_offset: None,
+        }
+    }
+
+    pub fn max_offset(mut self, max_offset: Duration) -> Self {
+        self.max_offset = Some(max_offset);
+        self
+    }
+
+    /// 本地事件：返回一个大于此前所有发出及观察到的时间戳的新时间戳。
+    pub fn now(&self) -> HlcTimestamp {
+        let physical = HlcTimestamp::new(physical_millis(), 0);
+
+        let mut last = self.last.lock().unwrap_or_else(|error| error.into_inner());
+        *last = if physical > *last {
+            physical
+        } else {
+            last.tick()
+        };
+        *last
+    }
+
+    /// 接收事件：合并远端的时间戳 `remote`，返回合并后的本地时间戳（同样大于 `remote`）。远端时间戳超前本地物理时间
+    /// 超过 `max_offset` 时返回错误，本地时钟保持不变。
+    pub fn observe(&self, remote: HlcTimestamp) -> Result<HlcTimestamp, HlcError> {
+        let now = physical_millis();
+        let ahead = remote.physical_millis().saturating_sub(now);
+        if let Some(max_offset) = self.max_offset {
+            if ahead > max_offset.as_millis() as u64 {
+                return Err(HlcError::ClockOffset {
+                    offset: Duration::from_millis(ahead),
+                });
+            }
+        }
+
+        let physical = HlcTimestamp::new(now, 0);
+
+        let mut last = self.last.lock().unwrap_or_else(|error| error.into_inner());
+        *last = if physical > *last && physical > remote {
+            physical
+        } else {
+            (*last).max(remote).tick()
+        };
+        Ok(*last)
+    }
+}
+
+impl Default for Hlc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn physical_millis() -> u64 {
+    crate::clock::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// 全局 `Hlc`，由 `next_hlc_id`、`observe_hlc` 使用，可以通过 `set_hlc` 预先设置。
+static HLC: OnceLock<Hlc> = OnceLock::new();
+
+lazy_static! {
+    /// 节点号，与 `Flake128` 相同：设备号作为高 8 位（未配置时使用随机数），低 8 位为随机数。
+    static ref NODE: u16 = u16::from_be_bytes([crate::DEVICE_ID.unwrap_or_else(rand::random), rand::random()]);
+}
+
+/// 在首次使用全局 HLC 之前设置其配置（如 `Hlc::max_offset`），已经使用过时设置不会生效并返回 `false`。
+pub fn set_hlc(hlc: Hlc) -> bool {
+    HLC.set(hlc).is_ok()
+}
+
+fn hlc() -> &'static Hlc {
+    HLC.get_or_init(Hlc::new)
+}
+
+/// 将接收到的远端时间戳（如上游请求携带的 `HlcId::timestamp`）合并到全局 HLC，之后生成的 `HlcId` 都排在其之后。
+pub fn observe_hlc(remote: HlcTimestamp) -> Result<HlcTimestamp, HlcError> {
+    hlc().observe(remote)
+}
+
+/// ## HLC id
+///
+/// 生成 128 bits 的 `HlcId`，由高到低依次为：
+///
+/// | HLC 时间戳 | 节点号 | 随机数 |
+/// |:---------:|:------:|:-----:|
+/// |  64 bits  | 16 bits | 48 bits |
+///
+/// 每个 `HlcId` 占用全局 HLC 的一个时间戳，因此同一进程内生成的 id 严格递增且不重复；不同进程之间由节点号与随机数
+/// 区分。通过 `observe_hlc` 合并了上游的时间戳之后生成的 id 总是大于上游的 id，id 的大小关系与因果关系一致。
+///
+/// ```
+/// let upstream = fastsend::next_hlc_id();
+///
+/// // 下游服务收到 upstream 后合并其时间戳
+/// fastsend::observe_hlc(upstream.timestamp()).unwrap();
+/// assert!(fastsend::next_hlc_id() > upstream);
+/// ```
+pub fn next_hlc_id() -> HlcId {
+    let timestamp = hlc().now();
+    let random = rand::random::<u64>() & 0xffff_ffff_ffff;
+    HlcId((timestamp.as_u64() as u128) << 64 | (*NODE as u128) << 48 | random as u128)
+}
+
+/// `next_hlc_id` 生成的 id，以定长 26 位 Crockford base32 字符串展示，字典序与 HLC 顺序一致。
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct HlcId(u128);
+
+impl HlcId {
+    pub fn from_u128(n: u128) -> HlcId {
+        HlcId(n)
+    }
+
+    pub fn as_u128(&self) -> u128 {
+        self.0
+    }
+
+    /// 生成 id 时的 HLC 时间戳，可以传递给下游并通过 `observe_hlc` 合并。
+    pub fn timestamp(&self) -> HlcTimestamp {
+        HlcTimestamp((self.0 >> 64) as u64)
+    }
+
+    pub fn node(&self) -> u16 {
+        (self.0 >> 48) as u16
+    }
+}
+
+impl fmt::Display for HlcId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&crockford::encode_u128_fixed(self.0))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ParseHlcIdError;
+
+impl fmt::Display for ParseHlcIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid HlcId string")
+    }
+}
+
+impl std::error::Error for ParseHlcIdError {}
+
+impl FromStr for HlcId {
+    type Err = ParseHlcIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != crockford::U128_WIDTH || s.contains('-') {
+            return Err(ParseHlcIdError);
+        }
+
+        crockford::decode_u128(s)
+            .map(HlcId)
+            .map_err(|_| ParseHlcIdError)
+    }
+}
@@ -0,0 +1,185 @@
+use crate::Serialer;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+/// 生成的 id 或序列号。
+#[derive(Copy, Clone)]
+pub enum Generated<'a> {
+    /// `Token` 等数值 id。
+    Id(u64),
+
+    /// `Serialer` 生成的序列号。
+    Serial(&'a dyn fmt::Display),
+}
+
+impl fmt::Debug for Generated<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Generated::Id(id) => f.debug_tuple("Id").field(id).finish(),
+            Generated::Serial(serial) => f
+                .debug_tuple("Serial")
+                .field(&format_args!("{}", serial))
+                .finish(),
+        }
+    }
+}
+
+impl fmt::Display for Generated<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Generated::Id(id) => write!(f, "{}", id),
+            Generated::Serial(serial) => write!(f, "{}", serial),
+        }
+    }
+}
+
+/// 每次生成 id 或序列号时传递给钩子的事件。
+#[derive(Debug, Copy, Clone)]
+pub struct GenerateEvent<'a> {
+    /// 生成器的种类，如 "Token"、"TimeSerialer"。
+    pub kind: &'static str,
+
+    /// 生成的 id 或序列号。
+    pub output: Generated<'a>,
+
+    /// 当前进程的设备号，未配置时为 `None`，见 `fastsend::DEVICE_ID`。
+    pub device: Option<u8>,
+
+    /// 生成的时间。
+    pub timestamp: SystemTime,
+}
+
+type Hook = Arc<dyn Fn(&GenerateEvent<'_>) + Send + Sync>;
+
+/// 全局钩子，以及是否存在任何全局钩子（未注册钩子时生成路径只需读取一次原子变量）。
+static HOOKS: RwLock<Vec<(HookId, Hook)>> = RwLock::new(Vec::new());
+static REGISTERED: AtomicBool = AtomicBool::new(false);
+
+/// `on_generate` 返回的钩子标识，用于通过 `remove_hook` 移除钩子。
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct HookId(u64);
+
+/// 注册全局的生成钩子：之后每次通过 `fastsend::next_token`（及 `try_next_token`）生成 `Token`，或通过
+/// `Serialer::on_generate` 包装的 `Serialer` 生成序列号时，都会以 `GenerateEvent` 调用 `hook`，用于将生成事件接入
+/// 审计日志或异常检测。`Serialer` 只需在构造处（如 `Config::preset` 的构造函数中）包装一次，无需修改各个调用方。
+///
+/// 钩子在生成 id 的线程中同步调用，应尽量轻量（如写入 channel），钩子 panic 会传播至生成 id 的调用方。
+pub fn on_generate<F>(hook: F) -> HookId
+where
+    F: Fn(&GenerateEvent<'_>) + Send + Sync + 'static,
+{
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+
+    let id = HookId(NEXT.fetch_add(1, Ordering::Relaxed));
+    let mut hooks = HOOKS.write().unwrap_or_else(|error| error.into_inner());
+    hooks.push((id, Arc::new(hook)));
+    REGISTERED.store(true, Ordering::Release);
+    id
+}
+
+/// 移除通过 `on_generate` 注册的全局钩子，钩子不存在时返回 `false`。
+pub fn remove_hook(id: HookId) -> bool {
+    let mut hooks = HOOKS.write().unwrap_or_else(|error| error.into_inner());
+    let len = hooks.len();
+    hooks.retain(|(hook, _)| *hook != id);
+    REGISTERED.store(!hooks.is_empty(), Ordering::Release);
+    hooks.len() != len
+}
+
+/// 以 `kind` 及 `output` 调用全部全局钩子，未注册钩子时不会构造事件。
+pub(crate) fn emit(kind: &'static str, output: Generated<'_>) {
+    if !REGISTERED.load(Ordering::Acquire) {
+        return;
+    }
+
+    // 先复制钩子列表再调用，钩子内部可以注册或移除钩子
+    let hooks = HOOKS
+        .read()
+        .unwrap_or_else(|error| error.into_inner())
+        .iter()
+        .map(|(_, hook)| Arc::clone(hook))
+        .collect::<Vec<_>>();
+
+    let event = event(kind, output);
+    for hook in hooks {
+        hook(&event);
+    }
+}
+
+fn event<'a>(kind: &'static str, output: Generated<'a>) -> GenerateEvent<'a> {
+    GenerateEvent {
+        kind,
+        output,
+        device: *crate::DEVICE_ID,
+        timestamp: crate::clock::now(),
+    }
+}
+
+/// 带有生成钩子的 `Serialer`，由 `Serialer::on_generate` 返回。构建成功时依次调用自身的钩子以及全局钩子，`kind`
+/// 为被包装的 `Serialer` 的类型名称。
+pub struct Hooked<S, F> {
+    serialer: S,
+    hook: F,
+}
+
+impl<S, F> Hooked<S, F> {
+    pub(crate) fn new(serialer: S, hook: F) -> Self {
+        Hooked { serialer, hook }
+    }
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for Hooked<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hooked")
+            .field("serialer", &self.serialer)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, F> Serialer for Hooked<S, F>
+where
+    S: Serialer,
+    S::Output: Send + 'static,
+    S::Error: Send + 'static,
+    F: Fn(&GenerateEvent<'_>) + Send + 'static,
+{
+    type Output = S::Output;
+
+    type Error = S::Error;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        let Hooked { serialer, hook } = self;
+        let build = serialer.build();
+
+        Box::pin(async move {
+            let output = build.await?;
+
+            let kind = kind::<S>();
+            hook(&event(kind, Generated::Serial(&output)));
+            emit(kind, Generated::Serial(&output));
+
+            Ok(output)
+        })
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.serialer.feed(data);
+    }
+
+    fn feed_field(&mut self, name: &str, data: &[u8]) {
+        self.serialer.feed_field(name, data);
+    }
+}
+
+/// `Serialer` 类型名称的最后一段（不含模块路径及泛型参数），如 "TimeSerialer"。
+fn kind<S>() -> &'static str {
+    let name = std::any::type_name::<S>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
@@ -0,0 +1,129 @@
+use crate::device_id::CheckError;
+use futures::channel::oneshot;
+use std::future::Future;
+use std::net::{Ipv4Addr, UdpSocket};
+use std::pin::Pin;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// `ClockSource` 代表一个权威时间来源（如 NTP 服务器或协调服务），在 `Init::run` 时用于校验本地时钟：返回
+/// 权威时间与本地 `SystemTime` 的偏差（毫秒），正数代表本地时钟落后。
+pub trait ClockSource: Send {
+    fn offset(&mut self) -> Pin<Box<dyn Future<Output = Result<i64, CheckError>> + Send + '_>>;
+}
+
+/// 时钟偏差超过阈值时的处理策略，`Init::cursor_file` 检测到时钟回拨时同样使用该策略：`Fail` 返回
+/// `InitError::TimeTravel`，`Pause` 等待至时钟超过持久化的游标。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ClockSkewPolicy {
+    /// `Init::run` 返回错误，调用方应拒绝生成 id（缺省策略）。
+    Fail,
+
+    /// 扩大碰撞保护：`Init::run` 等待与偏差等长的时间后再返回，使本次运行生成的 id 不会与时钟校正前（例如上一次
+    /// 运行）在偏差范围内生成的 id 重叠，作用与 `pause_on_start` 类似，但等待时间由偏差决定。
+    Pause,
+}
+
+/// 无法从时钟来源获取权威时间（如 NTP 服务器超时）时的处理策略，与设备号冲突检测的 `ConflictPolicy` 相互独立。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ClockErrorPolicy {
+    /// `Init::run` 返回 `InitError::Clock`，调用方应拒绝生成 id（缺省策略）。
+    Fail,
+
+    /// 以 'fastsend' 为 target 输出一条警告（需开启 `log` 或 `tracing` 特性），跳过该时钟来源继续启动。
+    Warn,
+}
+
+/// NTP 时间戳（1900-01-01 起）与 unix 时间戳之间相差的秒数。
+const NTP_UNIX_DELTA: u64 = 2_208_988_800;
+
+/// ## SNTP 时钟校验
+///
+/// `Ntp` 通过 SNTP（RFC 4330）向 `server` 查询时间，并按照标准的往返公式
+/// `((t2 - t1) + (t3 - t4)) / 2` 计算本地时钟偏差。
+#[derive(Debug, Clone)]
+pub struct Ntp {
+    /// NTP 服务器地址，如 'pool.ntp.org:123'。
+    server: String,
+
+    /// 等待响应的时间，缺省配置是 2 秒。
+    timeout: Duration,
+}
+
+impl Ntp {
+    pub fn new(server: &str) -> Ntp {
+        Ntp {
+            server: server.to_owned(),
+            timeout: Duration::from_secs(2),
+        }
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn query(&self) -> Result<i64, CheckError> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.set_read_timeout(Some(self.timeout))?;
+        socket.connect(&self.server)?;
+
+        // LI = 0，VN = 4，Mode = 3（client）
+        let mut request = [0u8; 48];
+        request[0] = 0x23;
+        let t1 = ntp_now();
+        request[40..48].copy_from_slice(&t1.to_be_bytes());
+        socket.send(&request)?;
+
+        let mut response = [0u8; 48];
+        let n = socket.recv(&mut response)?;
+        let t4 = ntp_now();
+
+        // Mode = 4（server），且 originate timestamp 必须与请求的 transmit timestamp 一致
+        if n < 48 || response[0] & 0x07 != 4 || response[24..32] != request[40..48] {
+            return Err("malformed ntp response".into());
+        }
+
+        let t2 = u64::from_be_bytes(response[32..40].try_into().unwrap());
+        let t3 = u64::from_be_bytes(response[40..48].try_into().unwrap());
+        let millis = |t: u64| ((t >> 32) * 1000 + (((t & 0xffff_ffff) * 1000) >> 32)) as i64;
+
+        Ok(((millis(t2) - millis(t1)) + (millis(t3) - millis(t4))) / 2)
+    }
+}
+
+impl Default for Ntp {
+    fn default() -> Self {
+        Self::new("pool.ntp.org:123")
+    }
+}
+
+impl ClockSource for Ntp {
+    fn offset(&mut self) -> Pin<Box<dyn Future<Output = Result<i64, CheckError>> + Send + '_>> {
+        // 查询过程是阻塞的（等待响应），因此放在单独的线程中执行，避免阻塞异步运行时
+        let (sender, receiver) = oneshot::channel();
+        let this = self.clone();
+        thread::spawn(move || {
+            let _ = sender.send(this.query());
+        });
+
+        Box::pin(async move { receiver.await? })
+    }
+}
+
+#[cfg(feature = "coordinator")]
+impl ClockSource for crate::coordinator::HttpCoordinator {
+    fn offset(&mut self) -> Pin<Box<dyn Future<Output = Result<i64, CheckError>> + Send + '_>> {
+        crate::coordinator::Coordinator::time_offset(self)
+    }
+}
+
+/// 以 NTP 时间戳格式（高 32 位为秒，低 32 位为秒的小数部分）表示的当前时间。
+fn ntp_now() -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time before unix epoch");
+
+    let fraction = ((now.subsec_nanos() as u64) << 32) / 1_000_000_000;
+    ((now.as_secs() + NTP_UNIX_DELTA) << 32) | fraction
+}
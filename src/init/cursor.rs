@@ -0,0 +1,65 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 读取持久化的游标高水位（秒级 Unix 时间戳），文件不存在时返回 `None`。
+///
+/// 文件中保存的是 Unix 时间戳而不是 `Cursor` 的内部计数，修改 `Cursor::timebase` 之后仍然可以正确比较。
+pub(crate) fn load(path: &Path) -> io::Result<Option<u64>> {
+    match fs::read_to_string(path) {
+        Ok(content) => content
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// 以先写入临时文件再重命名的方式保存游标高水位，避免进程在写入过程中退出时留下不完整的文件。
+fn save(path: &Path, unix: u64) -> io::Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    fs::write(&temp, unix.to_string())?;
+    fs::rename(&temp, path)
+}
+
+/// 全局生成器当前游标的秒级 Unix 时间戳，全局生成器尚未初始化时返回 `None`。
+fn high_water_mark() -> Option<u64> {
+    crate::high_water_mark().map(crate::block::epoch::unix_secs)
+}
+
+/// 注册周期任务（见 `fastsend::register_job`），每隔 `interval` 将全局生成器的游标保存到 `path`，并在 `shutdown`
+/// 时保存最后一次。保存失败时只打印警告，下一次保存会重新尝试。
+pub(crate) fn persist(path: PathBuf, interval: Duration) {
+    let saved = path.clone();
+    super::on_shutdown(move || async move {
+        if let Some(unix) = high_water_mark() {
+            let _ = save(&saved, unix);
+        }
+    });
+
+    let path = Arc::new(path);
+    let last = Arc::new(Mutex::new(None));
+    crate::register_job("cursor persistence", interval, move || {
+        let (path, last) = (Arc::clone(&path), Arc::clone(&last));
+        async move {
+            let mut last = last.lock().unwrap_or_else(|error| error.into_inner());
+            let current = high_water_mark();
+            if current.is_none() || current == *last {
+                return;
+            }
+
+            match save(&path, current.unwrap_or_default()) {
+                Ok(()) => *last = current,
+                Err(error) => crate::fallback::warn_each(
+                    "cursor_persistence",
+                    &format_args!("failed to persist cursor to {}: {}", path.display(), error),
+                ),
+            }
+        }
+    });
+}
@@ -0,0 +1,299 @@
+use crate::device_id::{CheckError, ConflictCheck};
+use lazy_static::lazy_static;
+use std::fmt;
+use std::future::Future;
+use std::mem;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+mod clock;
+pub use clock::{ClockErrorPolicy, ClockSkewPolicy, ClockSource, Ntp};
+
+mod cursor;
+
+/// 检测到设备号冲突（或无法完成检测）时的处理策略。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ConflictPolicy {
+    /// `Init::run` 返回错误，调用方应终止启动（缺省策略）。
+    Fail,
+
+    /// 以 'fastsend' 为 target 输出一条警告（需开启 `log` 或 `tracing` 特性），继续启动。
+    Warn,
+}
+
+/// ## 初始化
+///
+/// `Init` 用于在程序启动时完成 fastsend 的初始化：解析设备号（`DEVICE_ID`），并依次执行通过 `conflict_check`
+/// 注册的冲突检测，在两个实例意外共享同一个设备号时尽早暴露问题，而不是等到数据库中出现重复 id。
+///
+/// 通过 `clock_check` 注册的时钟来源（如 `Ntp`）会在冲突检测之前校验本地时钟：在未启用 `pause_on_start` 时，
+/// 时钟偏差可能导致重启前后的时间游标（`Cursor`）重叠，偏差超过 `max_clock_skew` 时按照 `ClockSkewPolicy`
+/// 拒绝启动或扩大碰撞保护；无法获取权威时间时按照 `ClockErrorPolicy` 拒绝启动或跳过该时钟来源。
+///
+/// 通过 `cursor_file` 指定的文件会持久化全局生成器的游标高水位：启动时若本地时钟不晚于上一次运行保存的游标（例如
+/// 重启前后时钟被回拨，或者在同一秒内重启），同样按照 `ClockSkewPolicy` 拒绝启动或等待时钟超过该游标，覆盖了
+/// `pause_on_start` 只等待一秒所无法处理的情形。
+///
+/// 未配置设备号时，生成 id 使用进程号代替设备号，此时不执行冲突检测。初始化不是必须的，未调用初始化时设备号将
+/// 在首次生成 id 或序列号时被解析。
+pub struct Init {
+    checks: Vec<Box<dyn ConflictCheck>>,
+
+    /// 冲突处理策略，缺省配置是 `ConflictPolicy::Fail`。
+    policy: ConflictPolicy,
+
+    clocks: Vec<Box<dyn ClockSource>>,
+
+    /// 允许的最大时钟偏差，缺省配置是 1 秒。
+    max_clock_skew: Duration,
+
+    /// 时钟偏差处理策略，缺省配置是 `ClockSkewPolicy::Fail`。
+    clock_policy: ClockSkewPolicy,
+
+    /// 无法获取权威时间时的处理策略，缺省配置是 `ClockErrorPolicy::Fail`。
+    clock_error_policy: ClockErrorPolicy,
+
+    /// 持久化游标高水位的文件，缺省配置是不持久化。
+    cursor_file: Option<PathBuf>,
+
+    /// 持久化游标高水位的间隔，缺省配置是 1 秒。
+    persist_interval: Duration,
+}
+
+impl fmt::Debug for Init {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Init")
+            .field("checks", &self.checks.len())
+            .field("policy", &self.policy)
+            .field("clocks", &self.clocks.len())
+            .field("max_clock_skew", &self.max_clock_skew)
+            .field("clock_policy", &self.clock_policy)
+            .field("cursor_file", &self.cursor_file)
+            .field("persist_interval", &self.persist_interval)
+            .finish()
+    }
+}
+
+impl Init {
+    pub fn new() -> Init {
+        Init {
+            checks: Vec::new(),
+            policy: ConflictPolicy::Fail,
+            clocks: Vec::new(),
+            max_clock_skew: Duration::from_secs(1),
+            clock_policy: ClockSkewPolicy::Fail,
+            clock_error_policy: ClockErrorPolicy::Fail,
+            cursor_file: None,
+            persist_interval: Duration::from_secs(1),
+        }
+    }
+
+    pub fn conflict_check<C: ConflictCheck + 'static>(mut self, check: C) -> Self {
+        self.checks.push(Box::new(check));
+        self
+    }
+
+    pub fn on_conflict(mut self, policy: ConflictPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn clock_check<C: ClockSource + 'static>(mut self, clock: C) -> Self {
+        self.clocks.push(Box::new(clock));
+        self
+    }
+
+    pub fn max_clock_skew(mut self, max_clock_skew: Duration) -> Self {
+        self.max_clock_skew = max_clock_skew;
+        self
+    }
+
+    pub fn on_clock_skew(mut self, policy: ClockSkewPolicy) -> Self {
+        self.clock_policy = policy;
+        self
+    }
+
+    pub fn on_clock_error(mut self, policy: ClockErrorPolicy) -> Self {
+        self.clock_error_policy = policy;
+        self
+    }
+
+    /// 启动时检查 `path` 中保存的游标高水位，并在之后每隔 `persist_interval` 以及 `shutdown` 时将全局生成器的
+    /// 游标写入 `path`。文件不存在时视为首次启动。
+    pub fn cursor_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.cursor_file = Some(path.into());
+        self
+    }
+
+    pub fn persist_interval(mut self, persist_interval: Duration) -> Self {
+        assert!(!persist_interval.is_zero());
+        self.persist_interval = persist_interval;
+        self
+    }
+
+    pub async fn run(mut self) -> Result<(), InitError> {
+        for clock in self.clocks.iter_mut() {
+            let offset = match clock.offset().await {
+                Ok(offset) => offset,
+                Err(error) => match self.clock_error_policy {
+                    ClockErrorPolicy::Fail => return Err(InitError::Clock(error)),
+                    ClockErrorPolicy::Warn => {
+                        crate::fallback::warn_each("clock_error_policy", &InitError::Clock(error));
+                        continue;
+                    }
+                },
+            };
+
+            if offset.unsigned_abs() <= self.max_clock_skew.as_millis() as u64 {
+                continue;
+            }
+
+            match self.clock_policy {
+                ClockSkewPolicy::Fail => return Err(InitError::ClockSkew(offset)),
+                ClockSkewPolicy::Pause => {
+                    crate::clock::sleep(Duration::from_millis(offset.unsigned_abs())).await
+                }
+            }
+        }
+
+        if let Some(path) = self.cursor_file.take() {
+            let stored = cursor::load(&path).map_err(|error| InitError::Check(Box::new(error)))?;
+            let now = crate::clock::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default();
+
+            // 当前时间不晚于上一次运行的游标时，本次运行的游标可能与之重叠
+            if let Some(stored) = stored.filter(|&stored| now <= stored) {
+                match self.clock_policy {
+                    ClockSkewPolicy::Fail => return Err(InitError::TimeTravel(stored - now)),
+                    ClockSkewPolicy::Pause => {
+                        let wait = Duration::from_secs(stored - now + 1);
+                        if !crate::clock::advance(wait) {
+                            crate::clock::sleep(wait).await;
+                        }
+                    }
+                }
+            }
+
+            cursor::persist(path, self.persist_interval);
+        }
+
+        let device_id = match *crate::DEVICE_ID {
+            Some(device_id) => device_id,
+            None => return Ok(()),
+        };
+
+        for check in self.checks.iter_mut() {
+            let error = match check.check(device_id).await {
+                Ok(false) => continue,
+                Ok(true) => InitError::Conflict(device_id),
+                Err(error) => InitError::Check(error),
+            };
+
+            match self.policy {
+                ConflictPolicy::Fail => return Err(error),
+                ConflictPolicy::Warn => crate::fallback::warn_each("conflict_policy", &error),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Init {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
+pub enum InitError {
+    /// 另一个存活的实例持有相同的设备号。
+    Conflict(u8),
+
+    /// 冲突检测本身失败，无法确认是否存在冲突。
+    Check(CheckError),
+
+    /// 无法从时钟来源获取权威时间，无法校验本地时钟。
+    Clock(CheckError),
+
+    /// 本地时钟与权威时间的偏差（毫秒）超过了允许的范围。
+    ClockSkew(i64),
+
+    /// 本地时钟不晚于上一次运行持久化的游标高水位，值为落后的秒数（同一秒内重启时为 0），见 `Init::cursor_file`。
+    TimeTravel(u64),
+}
+
+impl fmt::Display for InitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InitError::Conflict(device_id) => write!(
+                f,
+                "device id {} is held by another live instance",
+                device_id
+            ),
+            InitError::Check(error) => write!(f, "device id conflict check failed: {}", error),
+            InitError::Clock(error) => {
+                write!(f, "failed to query the authoritative time: {}", error)
+            }
+            InitError::ClockSkew(offset) => write!(
+                f,
+                "local clock is {}ms away from the authoritative time",
+                offset.unsigned_abs()
+            ),
+            InitError::TimeTravel(behind) => write!(
+                f,
+                "local clock is {}s behind the last persisted cursor",
+                behind
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InitError::Conflict(_) | InitError::ClockSkew(_) | InitError::TimeTravel(_) => None,
+            InitError::Check(error) | InitError::Clock(error) => Some(&**error),
+        }
+    }
+}
+
+/// 使用缺省配置完成初始化（解析设备号，不执行冲突检测），等价于 `Init::new().run()`。
+pub async fn init() -> Result<(), InitError> {
+    Init::new().run().await
+}
+
+/// `ShutdownHook` 是在 `shutdown` 时执行的清理任务，例如撤销设备号租约。
+type ShutdownHook = Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send>;
+
+lazy_static! {
+    static ref SHUTDOWN_HOOKS: Mutex<Vec<ShutdownHook>> = Mutex::new(Vec::new());
+}
+
+/// 注册一个在 `shutdown` 时执行的清理任务，清理任务按注册顺序的逆序执行。
+pub(crate) fn on_shutdown<F, Fut>(f: F)
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    SHUTDOWN_HOOKS
+        .lock()
+        .unwrap()
+        .push(Box::new(move || Box::pin(f())));
+}
+
+/// 优雅退出：停止后台续期并立即撤销通过 `RedisLease`、`EtcdLease` 申请的设备号租约以及
+/// `RedisHeartbeat`、`EtcdHeartbeat` 写入的心跳，使设备号可以被替换实例立即复用，而不必等待租约过期（这对于
+/// 0..=255 这样较小的设备号范围尤为重要）。
+///
+/// 清理过程中的错误将被忽略（租约最终仍会在 ttl 之后过期）。`shutdown` 之后不应再生成 id。
+pub async fn shutdown() {
+    let hooks = mem::take(&mut *SHUTDOWN_HOOKS.lock().unwrap());
+    for hook in hooks.into_iter().rev() {
+        hook().await;
+    }
+}
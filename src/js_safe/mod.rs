@@ -0,0 +1,108 @@
+//! ## JavaScript 安全的 JSON 表示
+//!
+//! JavaScript 的 `Number` 只能精确表示 2^53 以内的整数，而 fastsend 生成的 64 位 id 通常都超出了这个范围，
+//! 以数字形式输出到 JSON 后会在 JavaScript 客户端中被静默地截断。本模块提供了以十进制字符串表示 id 的方式，
+//! id 在 Rust 中仍然保持为数值类型：
+//!
+//!   - 单个字段：使用 `#[serde(with = "fastsend::js_safe")]` 标注 `u64` 或 `Token` 类型的字段；
+//!   - 整个类型：将字段类型声明为 `JsSafeId<T>`，例如在对外的 DTO 中统一使用 `JsSafeId` 代替 `Token`。
+//!
+//! 两种方式都需要显式地选择，`Token` 自身的 `Serialize` 实现始终输出数字，启用 'js_safe' feature 不会改变依赖
+//! 同一个 fastsend 的其他 crate 的序列化结果。反序列化时同时接受字符串和数字两种形式，因此可以平滑地从数字表示
+//! 迁移到字符串表示。
+
+use crate::Token;
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+use std::ops::Deref;
+
+/// 可以以 JavaScript 安全的形式表示的 id 类型，即可以与 `u64` 无损互相转换的类型。
+pub trait JsSafe: Copy {
+    fn to_u64(self) -> u64;
+
+    fn from_u64(n: u64) -> Self;
+}
+
+impl JsSafe for u64 {
+    fn to_u64(self) -> u64 {
+        self
+    }
+
+    fn from_u64(n: u64) -> Self {
+        n
+    }
+}
+
+impl JsSafe for Token {
+    fn to_u64(self) -> u64 {
+        crate::ID::id(self)
+    }
+
+    fn from_u64(n: u64) -> Self {
+        Token::from_id(n)
+    }
+}
+
+/// 以十进制字符串的形式序列化 id，用于 `#[serde(with = "fastsend::js_safe")]`。
+pub fn serialize<T: JsSafe, S: Serializer>(id: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&id.to_u64())
+}
+
+/// 从十进制字符串或非负整数反序列化 id，用于 `#[serde(with = "fastsend::js_safe")]`。
+pub fn deserialize<'de, T: JsSafe, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+    deserializer.deserialize_any(IdVisitor).map(T::from_u64)
+}
+
+/// 以十进制字符串的形式进行序列化的 id 包装类型，反序列化时同时接受字符串和数字。
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct JsSafeId<T = Token>(pub T);
+
+impl<T> Deref for JsSafeId<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> From<T> for JsSafeId<T> {
+    fn from(id: T) -> Self {
+        JsSafeId(id)
+    }
+}
+
+impl<T: JsSafe> Serialize for JsSafeId<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T: JsSafe> Deserialize<'de> for JsSafeId<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer).map(JsSafeId)
+    }
+}
+
+struct IdVisitor;
+
+impl Visitor<'_> for IdVisitor {
+    type Value = u64;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a 64-bit unsigned integer or its decimal string")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        u64::try_from(v).map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse()
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+}
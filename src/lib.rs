@@ -1,30 +1,238 @@
 //! 关于 fastsend 的详细说明，请查看 README.md
 #![doc = include_str!("../README.md")]
+#![allow(clippy::type_complexity)]
 
 #[doc(hidden)]
 pub mod block;
-pub use block::{Block, BlockFrame, ConstructBlock, Cursor};
+pub use block::{
+    epoch_of, exclude_ids, is_excluded, register_epoch, set_epoch, set_timebase, Block, BlockFrame,
+    BlockFuture, ConstructBlock, Cursor, NextBlock, SequenceStride,
+};
 
 #[doc(hidden)]
 pub mod token;
-pub use token::Token;
+pub use token::{
+    inspect_id, partition_of, set_entropy_bits, set_sequence_stride, set_tag_bits, shard_of,
+    Granularity, IdInfo, IdRange, PartitionKey, Token,
+};
 
 #[doc(hidden)]
 pub mod serial;
-pub use serial::{Serial, Serialer, TimeSerialer};
+pub use serial::{
+    field_digest, BinarySerialer, LocalSerialer, Serial, Serialer, SipHasher13, StableState,
+    SyncSerialer, TimeSerialer, TimeSlot,
+};
+
+#[doc(hidden)]
+pub mod error;
+pub use error::FastsendError;
+
+mod compat;
+
+mod fallback;
+
+mod clock;
+
+mod discard;
+pub use discard::{discarded_tokens, on_block_dropped};
+
+#[doc(hidden)]
+pub mod typed;
+pub use typed::{next_for, register_frame, try_next_for};
+
+#[doc(hidden)]
+pub mod newtype;
+pub use newtype::ParseIdError;
+
+#[doc(hidden)]
+pub mod source;
+pub use source::{IdSource, IdValue, SerialerSource, TokenSource};
+
+pub mod encoding;
+
+pub mod analysis;
+
+#[doc(hidden)]
+pub mod obfuscate;
+pub use obfuscate::{set_id_permutation, BitPermutation, Obfuscated, Obfuscator};
+
+#[doc(hidden)]
+pub mod device_id;
+pub use device_id::set_device_id;
+
+#[doc(hidden)]
+pub mod maintenance;
+pub use maintenance::{maintenance_jobs, register_job, remove_job, JobId};
+#[cfg(feature = "maintenance")]
+pub use maintenance::{spawn_maintenance, MaintenanceHandle};
+
+#[doc(hidden)]
+pub mod init;
+pub use init::{
+    init, shutdown, ClockErrorPolicy, ClockSkewPolicy, ClockSource, ConflictPolicy, Init,
+    InitError, Ntp,
+};
+
+#[doc(hidden)]
+pub mod hook;
+pub use hook::{on_generate, remove_hook, GenerateEvent, Generated, HookId, Hooked};
+
+#[doc(hidden)]
+pub mod config;
+pub use config::{Config, ConfigError, DeviceIdSetting};
+
+#[cfg(feature = "coordinator")]
+#[doc(hidden)]
+pub mod coordinator;
+#[cfg(feature = "coordinator")]
+pub use coordinator::{Bootstrap, BootstrapError, Coordinator, CoordinatorError, HttpCoordinator};
+
+#[cfg(feature = "rate_limit")]
+#[doc(hidden)]
+pub mod limit;
+#[cfg(feature = "rate_limit")]
+pub use limit::{LimitError, Limited, RateLimited, RateLimiter};
+
+#[cfg(any(feature = "axum", feature = "actix"))]
+#[doc(hidden)]
+pub mod request_id;
+#[cfg(any(feature = "axum", feature = "actix"))]
+pub use request_id::RequestId;
+#[cfg(feature = "axum")]
+pub use request_id::{NextRequestId, RequestIdLayer, RequestIdService};
+#[cfg(feature = "actix")]
+pub use request_id::{RequestIdMiddleware, RequestIdMiddlewareService};
+
+#[cfg(feature = "tower")]
+#[doc(hidden)]
+pub mod stamp;
+#[cfg(feature = "tower")]
+pub use stamp::{IdLayer, IdService, MakeId, NextToken};
+
+#[cfg(feature = "js_safe")]
+pub mod js_safe;
+#[cfg(feature = "js_safe")]
+pub use js_safe::JsSafeId;
+
+#[cfg(feature = "test-util")]
+pub mod testing;
+
+#[cfg(feature = "test-util")]
+pub mod simulate;
+
+#[cfg(feature = "support_code")]
+#[doc(hidden)]
+pub mod support;
+#[cfg(feature = "support_code")]
+pub use support::{
+    parse_support_code, support_code, support_hash, SupportCodeError, SUPPORT_HASH_BITS,
+};
+
+#[cfg(feature = "trace_context")]
+#[doc(hidden)]
+pub mod trace;
+#[cfg(feature = "trace_context")]
+pub use trace::{next_span_id, next_trace_id, traceparent};
+
+#[cfg(feature = "hlc")]
+#[doc(hidden)]
+pub mod hlc;
+#[cfg(feature = "hlc")]
+pub use hlc::{
+    next_hlc_id, observe_hlc, set_hlc, Hlc, HlcError, HlcId, HlcTimestamp, ParseHlcIdError,
+};
+
+#[cfg(feature = "export")]
+pub mod export;
+
+#[cfg(feature = "prost")]
+#[doc(hidden)]
+pub mod proto;
+#[cfg(feature = "prost")]
+pub use proto::IdProto;
+
+#[cfg(feature = "sharded")]
+#[doc(hidden)]
+pub mod shard;
+#[cfg(feature = "sharded")]
+pub use shard::{next_sharded_id, set_default_epoch, try_next_sharded_id, ShardFrame, ShardedId};
+
+#[cfg(feature = "tenant")]
+#[doc(hidden)]
+pub mod tenant;
+#[cfg(feature = "tenant")]
+pub use tenant::{next_token_for, TenantFrame, TenantToken};
 
 #[cfg(feature = "ticket")]
-pub use serial::{ticket::TicketSerialError, ticket::TicketSerialer};
+pub use serial::ticket::{DegradePolicy, QueueFnMut, Ticket, TicketSerialError, TicketSerialer};
 
 #[cfg(feature = "uuid")]
-pub use serial::uuid::{UUIDSerialer, UUID};
+pub use serial::uuid::{ParseUUIDError, UUIDSerialer, UUID};
 
 #[cfg(feature = "auto_increment")]
-pub use serial::auto_increment::{AutoIncrement, IncrSerialer, IncrState, IncrStateBuilder};
+pub use serial::auto_increment::{
+    AutoIncrement, IncrSerialer, IncrState, IncrStateBuilder, NoStore, SequenceMap,
+    SequenceMapBuilder, SequenceStore,
+};
 
 #[cfg(feature = "random62")]
 pub use serial::random62::Random62Serialer;
 
+#[cfg(feature = "cuid2")]
+pub use serial::cuid2::Cuid2Serialer;
+
+#[cfg(feature = "typeid")]
+pub use serial::typeid::{TypeId, TypeIdError, TypeIdSerialer};
+
+#[cfg(feature = "push_id")]
+pub use serial::push_id::PushIdSerialer;
+
+#[cfg(feature = "sqids")]
+pub use serial::sqids::{Sqids, SqidsSerialer};
+
+#[cfg(feature = "fpe")]
+pub use serial::fpe::{FpeCipher, FpeSerialError, FpeSerialer};
+
+#[cfg(feature = "pronounce")]
+pub use serial::pronounce::{Pronounce, PronounceSerialer};
+
+#[cfg(feature = "coupon")]
+pub use serial::coupon::CouponSerialer;
+
+#[cfg(feature = "gift_card")]
+pub use serial::gift_card::{GiftCardError, GiftCardSerialer};
+
+#[cfg(feature = "invoice")]
+pub use serial::invoice::{
+    InvoiceCounter, InvoiceSerialer, InvoiceState, MemoryCounter, Reservation,
+};
+
+#[cfg(feature = "order")]
+pub use serial::order::OrderSerialer;
+
+#[cfg(feature = "gs1")]
+pub use serial::gs1::{Gs1Error, Gs1Format, Gs1Serialer};
+
+#[cfg(feature = "license")]
+pub use serial::license::{validate as validate_license, LicenseSerialer, LicenseVerifier};
+
+#[cfg(feature = "crypto")]
+pub use serial::signed::{HmacSigner, SignedSerialer};
+
+#[cfg(feature = "expiring")]
+pub use serial::expiring::{
+    verify as verify_expiring, ExpiringClaims, ExpiringError, ExpiringSerialer,
+};
+
+#[cfg(feature = "slug")]
+pub use serial::slug::{SlugSerialError, SlugSerialer};
+
+#[cfg(feature = "flake128")]
+pub use serial::flake128::{Flake128, Flake128Serialer, ParseFlake128Error};
+
+#[cfg(feature = "ordered_key")]
+pub use serial::ordered_key::{OrderedKeySerialer, OrderedKeys};
+
 /// `ID` 是 fastsend 的核心 trait，用于生成不重复的 id，其表示形式为 64 位无符号整数，可用作数据库的主键。
 /// 其生成方法会消耗自身所有权，目的是确保一个 `ID` 实例只生成一个 id，多次生成在某些特定场景下会造成 id 冲突
 /// 的情况，例如因为代码逻辑错误导致多次调用 `id` 方法（但实际上如果 `ID` 是 Copy 的，这种情况也很难避免）。
@@ -48,30 +256,355 @@ pub trait ID {
     fn id(self) -> u64;
 }
 
+use futures::future::Either;
 use lazy_static::lazy_static;
+#[cfg(not(feature = "strict_order"))]
 use std::cell::RefCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(not(feature = "strict_order"))]
+use std::sync::OnceLock;
+use std::task::Poll;
+use std::time::Duration;
 
-lazy_static! {
-    /// 用作全局变量的 `BlockFrame` 支持在多线程环境下持续生成 `Block`，并会提前缓存一部分预生成的 `Block`，
-    /// 通常而言一个程序仅需要一个全局 `BlockFrame`，`fastsend::next_token` 的实现中就依赖于这个包裹在
-    /// `lazy_static` 里的全局生成器。全局生成器不需要（也不应该）直接被调用方使用，其会通过一个 `thread_local`
-    /// 暴露给使用者（fastsend 的 `thread_local` 就被包裹在 `with_block` 函数内），关于 `thread_local`
-    /// 的相关信息，详见 `with_block` 内的说明。
-    static ref FRAME: BlockFrame<Token> = BlockFrame::new();
+/// 用作全局变量的 `BlockFrame` 支持在多线程环境下持续生成 `Block`，并会提前缓存一部分预生成的 `Block`，
+/// 通常而言一个程序仅需要一个全局 `BlockFrame`，`fastsend::next_token` 的实现中就依赖于这个全局生成器。全局
+/// 生成器不需要（也不应该）直接被调用方使用，其会通过一个 `thread_local` 暴露给使用者（fastsend 的
+/// `thread_local` 即 `BLOCK`，由 `with_block` 使用），关于 `thread_local` 的相关信息，详见 `BLOCK` 及
+/// `with_block` 的说明。
+///
+/// 与 `lazy_static` 不同，全局生成器初始化失败（获取 `Cursor` 失败）时不会被缓存，下一次调用会重新尝试初始化。
+#[cfg(not(feature = "strict_order"))]
+fn frame() -> Result<&'static BlockFrame<Token>, FastsendError> {
+    if let Some(frame) = FRAME.get() {
+        return Ok(frame);
+    }
+
+    let stride = token::sequence_stride();
+    let frame = BlockFrame::try_new()?
+        .sequence_stride(stride.offset(), stride.step())
+        .entropy_bits(stride.entropy_bits());
+    Ok(FRAME.get_or_init(|| frame))
+}
+
+/// 全局生成器正在使用的 `Cursor`，全局生成器尚未初始化时返回 `None`，见 `Init::cursor_file`。
+pub(crate) fn high_water_mark() -> Option<Cursor> {
+    #[cfg(feature = "strict_order")]
+    return token::ordered::cursor();
+
+    #[cfg(not(feature = "strict_order"))]
+    FRAME.get().map(BlockFrame::cursor)
+}
+
+/// 全局 `BlockFrame`，由 `frame` 在首次使用时初始化，或由 `set_global_frame` 预先设置。
+#[cfg(not(feature = "strict_order"))]
+static FRAME: OnceLock<BlockFrame<Token>> = OnceLock::new();
+
+/// 在首次调用 `next_token` 之前将自行构造的 `BlockFrame` 设置为全局生成器，之后 `next_token`、`reserve_range`
+/// 等全局方法都使用该生成器发号。以下情况设置不会生效并返回 `false`：
+///
+///   - 全局生成器已经初始化（或已经设置过）；
+///   - 已经通过 `set_sequence_stride` 或 `set_entropy_bits` 设置了全局配置，这些配置不会被 `frame` 静默覆盖，
+///     需要时应通过 `BlockFrame::sequence_stride`、`BlockFrame::entropy_bits` 在 `frame` 上设置；
+///   - 开启了 'strict_order' feature，此时全局生成器不是 `BlockFrame`。
+///
+/// 设置成功后 `set_sequence_stride` 与 `set_entropy_bits` 同样返回 `false`。
+///
+/// ```
+/// use fastsend::{BlockFrame, Token};
+///
+/// let frame = BlockFrame::<Token>::new().sequence_stride(1, 2);
+/// assert_eq!(fastsend::set_global_frame(frame), !cfg!(feature = "strict_order"));
+/// ```
+pub fn set_global_frame(frame: BlockFrame<Token>) -> bool {
+    #[cfg(feature = "strict_order")]
+    {
+        let _ = frame;
+        false
+    }
+
+    #[cfg(not(feature = "strict_order"))]
+    {
+        token::claim_sequence_stride() && FRAME.set(frame).is_ok()
+    }
+}
+
+/// 每个线程每次从全局 `BlockFrame` 领取的 `Block` 数量，见 `set_blocks_per_claim`。
+static BLOCKS_PER_CLAIM: AtomicUsize = AtomicUsize::new(1);
+
+/// 设置每个线程每次从全局 `BlockFrame` 领取的 `Block` 数量（缺省为 1），之后的领取立即生效。
+///
+/// 每个 `Block` 仅包含 8 个 `Token`，高吞吐的线程每隔几微秒就需要重新访问全局队列，一次领取多个 `Block` 并保存在
+/// 线程本地可以减少访问全局队列的次数。代价是线程持有的 `Block` 更多：领取到的 `Token` 在该线程用完之前不会被其他
+/// 线程使用，同一秒内 `Token` 的分配也会更集中于少数线程。全局队列中剩余的 `Block` 不足时只领取剩余的部分，不会为此
+/// 等待补充。启用 'strict_order' feature 时 `Token` 不经过 `Block` 发放，该设置不生效。
+///
+/// # Panics
+///
+/// `blocks` 为 0 或超过 `BlockFrame::<Token>::QUEUE_SIZE` 时 panic。
+pub fn set_blocks_per_claim(blocks: usize) {
+    assert!(
+        (1..=BlockFrame::<Token>::QUEUE_SIZE).contains(&blocks),
+        "blocks per claim must be within 1..={}",
+        BlockFrame::<Token>::QUEUE_SIZE
+    );
+    BLOCKS_PER_CLAIM.store(blocks, Ordering::Relaxed);
 }
 
 /// `next_token` 是 fastsend 中获取 `Token` 的主要方式，其会从当前线程持有的 `Block` 中获取一个 `Token` 并
 /// 返回给调用方，由于 `with_block` 使用了 `thread_local`，因此 `next_block` 方法是线程安全且无锁竞争的（这里
 /// 对一个函数强调了线程安全，是因为在函数实现的内部使用了全局变量，即 `BlockFrame`）。
+///
+/// 系统时间异常（早于 `Cursor::timebase()` 或超出 `Cursor` 的范围）时 `next_token` 会 panic，不希望因此终止
+/// 进程的场合请使用 `try_next_token`。
+///
+/// # 取消安全
+///
+/// `next_token` 系列方法都是取消安全（cancellation safe）的，可以在 `tokio::select!`、超时等场合中随时丢弃：
+/// 唯一的等待点是等待 `BlockFrame` 补充 `Block`，此时尚未从全局队列中取出任何 `Block`，也没有借用线程本地的
+/// `Block`；取出 `Block` 并放入线程本地之间没有等待点，因此丢弃正在等待的 `Future` 不会丢失 `Token`，也不会使
+/// 线程本地的状态处于借用之中。被丢弃的等待者登记的 `Waker` 会在补充完成时被唤醒一次，不会产生其他影响。
 pub async fn next_token() -> Token {
-    with_block(|block| {
-        // 对 `Block` 可用性的额外保障，确保 `Block` 仍然可以生成 `Token`。
-        // （`<Block as Iterator>::size_hint` 用于表明 `Block` 剩余可生成的元素数量）
-        debug_assert!(block.size_hint().0 > 0);
+    try_next_token()
+        .await
+        .unwrap_or_else(|error| panic!("{} on next_token()", error))
+}
 
-        block.next().expect("unexpected drained `Block` iterator")
-    })
-    .await
+/// `next_token` 的非 panic 版本，系统时间异常时返回 `FastsendError`，时间恢复正常后的调用可以继续获取 `Token`。
+pub async fn try_next_token() -> Result<Token, FastsendError> {
+    try_next_token_tagged(0).await
+}
+
+/// 在 `timeout` 内获取下一个 `Token`，超时（如补充 `Block` 的过程被阻塞）时返回 `FastsendError::Timeout`，其余
+/// 错误与 `try_next_token` 相同。超时后放弃等待是安全的，见 `next_token` 中关于取消安全的说明。
+///
+/// 线程本地的 `Block` 中仍有 `Token` 时直接返回，只有需要等待补充时才会启动计时。计时由 fastsend 内部共用的一个
+/// 计时线程完成，获取到 `Token` 或被丢弃时立即撤销，不会为每次调用占用线程。
+pub async fn next_token_timeout(timeout: Duration) -> Result<Token, FastsendError> {
+    let token = try_next_token();
+    futures::pin_mut!(token);
+
+    if let Poll::Ready(result) = futures::poll!(&mut token) {
+        return result;
+    }
+
+    let delay = clock::sleep(timeout);
+    futures::pin_mut!(delay);
+
+    match futures::future::select(token, delay).await {
+        Either::Left((result, _)) => result,
+        Either::Right(_) => Err(FastsendError::Timeout),
+    }
+}
+
+/// `next_token_with_priority` 的发号优先级。
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Priority {
+    /// 交互请求等不能等待的调用方，缺省不保留余量。
+    High,
+
+    /// 与 `next_token` 相同，缺省不保留余量。
+    Normal,
+
+    /// 后台任务等可以推迟的调用方，缺省在全局队列剩余不足 1/4 时等待下一秒。
+    Low,
+}
+
+/// 各优先级的保留余量（`Block` 数量），依次为 `High`、`Normal`、`Low`，见 `set_priority_reserve`。
+static PRIORITY_RESERVE: [AtomicUsize; 3] = [
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(BlockFrame::<Token>::QUEUE_SIZE / 4),
+];
+
+/// 设置优先级 `priority` 的保留余量：该优先级的调用方需要从全局队列领取 `Block` 时，如果当前 `Cursor` 下剩余的
+/// `Block` 少于 `blocks` 个，则先等待至下一秒再领取，把剩余的 `Token` 留给优先级更高的调用方。设置为 0 表示从不
+/// 等待，之后的调用立即生效。
+pub fn set_priority_reserve(priority: Priority, blocks: usize) {
+    PRIORITY_RESERVE[priority as usize].store(blocks, Ordering::Relaxed);
+}
+
+/// 按优先级生成 `Token`：当前线程缓存的 `Block` 中仍有 `Token` 时与 `next_token` 相同；需要从全局队列领取新的
+/// `Block` 且剩余的 `Block` 少于该优先级的保留余量（见 `set_priority_reserve`）时，先等待至下一秒（新的 `Cursor`
+/// 通常已经可用）再领取。等待只发生一次，等待后不再检查余量，因此低优先级的调用方不会无限期地等待。
+///
+/// 用于在每秒的发号量接近上限时让后台任务让位于交互请求。开启 'strict_order' feature 时优先级不生效。
+///
+/// # Panics
+///
+/// 系统时间异常时 panic，见 `try_next_token_with_priority`。
+pub async fn next_token_with_priority(priority: Priority) -> Token {
+    try_next_token_with_priority(priority)
+        .await
+        .unwrap_or_else(|error| panic!("{} on next_token_with_priority()", error))
+}
+
+/// `next_token_with_priority` 的非 panic 版本，系统时间异常时返回 `FastsendError`。
+pub async fn try_next_token_with_priority(priority: Priority) -> Result<Token, FastsendError> {
+    #[cfg(not(feature = "strict_order"))]
+    {
+        let reserve = PRIORITY_RESERVE[priority as usize].load(Ordering::Relaxed);
+        let should_claim = BLOCK.with(|block| block.borrow().is_empty());
+
+        // 等待发生在领取 `Block` 之前，与 `next_token` 一样是取消安全的
+        if reserve > 0 && should_claim && frame()?.remaining_blocks() < reserve {
+            let wait = clock::until_next_second();
+            if !clock::advance(wait) {
+                clock::sleep(wait).await;
+            }
+        }
+    }
+
+    #[cfg(feature = "strict_order")]
+    let _ = priority;
+
+    try_next_token().await
+}
+
+/// 生成嵌入了标签 `tag` 的 `Token`，标签可以通过 `Token::tag` 读取，需要事先通过 `set_tag_bits` 预留标签位数：
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// assert!(fastsend::set_tag_bits(2));
+/// let token = fastsend::next_token_tagged(3).await;
+/// assert_eq!(token.tag(), 3);
+/// # });
+/// ```
+///
+/// # Panics
+///
+/// `tag` 无法以标签位数表示时 panic（未设置标签位数时只接受 0），系统时间异常时同 `next_token`。
+pub async fn next_token_tagged(tag: u8) -> Token {
+    try_next_token_tagged(tag)
+        .await
+        .unwrap_or_else(|error| panic!("{} on next_token_tagged()", error))
+}
+
+/// `next_token_tagged` 的非 panic 版本，系统时间异常时返回 `FastsendError`，`tag` 超出标签位数时仍然 panic。
+pub async fn try_next_token_tagged(tag: u8) -> Result<Token, FastsendError> {
+    let bits = token::tag_bits();
+    assert!(
+        (tag as u32) < 1 << bits,
+        "tag {} exceeds {} bits",
+        tag,
+        bits
+    );
+
+    loop {
+        // 'strict_order' 模式下所有 `Token` 由同一个计数器按顺序发放，不经过 `Block`（见 `token::ordered`）
+        #[cfg(feature = "strict_order")]
+        let token = token::ordered::next_token()?;
+
+        #[cfg(not(feature = "strict_order"))]
+        let token = with_block(|block| {
+            // 对 `Block` 可用性的额外保障，确保 `Block` 仍然可以生成 `Token`。
+            // （`<Block as Iterator>::size_hint` 用于表明 `Block` 剩余可生成的元素数量）
+            debug_assert!(block.size_hint().0 > 0);
+
+            block.next().ok_or(FastsendError::DrainedBlock)
+        })
+        .await??;
+
+        // 发放时的检查针对写入标签之前的 id，写入标签之后的 id 可能落入登记的区间，此时改用下一个 `Token`
+        let token = token.with_tag(tag, bits);
+        if bits > 0 && is_excluded(token.id()) {
+            continue;
+        }
+
+        hook::emit("Token", Generated::Id(token.id()));
+        return Ok(token);
+    }
+}
+
+/// 预留一段包含 `count` 个 id 的连续区间，用于数据回填（ETL backfill）等需要批量分配 id 的场景，同一进程之后通过
+/// `next_token` 生成的 id 不会与该区间重叠，预留过的区间也不会被再次预留。
+///
+/// 预留以 `Cursor`（秒级游标）为单位，每个 `Cursor` 包含 2^32 个 id，预留的 `Cursor` 数量为 `count` 按 2^32 向上
+/// 取整，未使用的部分会被浪费。开启 'pause_on_start' 时预留需要等待至下一秒，且预留多个 `Cursor` 后 `next_token`
+/// 需要等待时间追上预留的最后一个 `Cursor`。需要注意的是，预留的区间覆盖了该秒内 `Token` 低 32 位的全部取值（包括
+/// 设备号 `c`），多个设备共用同一个 id 空间时，其他设备在同一秒内生成的 id 可能落入该区间，因此预留的区间应由单独的
+/// 设备（或不生成 `Token` 的设备）使用。
+///
+/// # Panics
+///
+/// `count` 为 0 或获取 `Cursor` 失败时 panic，不希望因此终止进程的场合请使用 `try_reserve_range`。
+pub fn reserve_range(count: u64) -> IdRange {
+    try_reserve_range(count).unwrap_or_else(|error| panic!("{} on reserve_range()", error))
+}
+
+/// `reserve_range` 的非 panic 版本，获取 `Cursor` 失败或区间超出 `Cursor` 的范围时返回 `FastsendError`。
+///
+/// # Panics
+///
+/// `count` 为 0 时 panic。
+pub fn try_reserve_range(count: u64) -> Result<IdRange, FastsendError> {
+    assert!(count > 0, "must reserve at least one id");
+
+    let cursors =
+        u32::try_from(count.div_ceil(1 << 32)).map_err(|_| FastsendError::CursorOverflow)?;
+
+    #[cfg(feature = "strict_order")]
+    let first = token::ordered::reserve_cursors(cursors)?;
+    #[cfg(not(feature = "strict_order"))]
+    let first = frame()?.reserve_cursors(cursors)?;
+
+    IdRange::new(first, count).ok_or(FastsendError::CursorOverflow)
+}
+
+#[cfg(not(feature = "strict_order"))]
+thread_local! {
+    /// `BLOCK` 是对 `Token` 的第二次预分配行为，此次预分配是各线程各自的预分配，即在 `thread_local`
+    /// 里获取 `BLOCK`，此时再次从 `Block` 里获取元素便不需要加锁，避免了锁竞争。
+    ///
+    /// 这里使用了 `RefCell` 来实现内部可变性，由于从 `Block` 中获取元素以及更新 `Block` 都需要可变引用，
+    /// 因此不得不套一层 `RefCell`，虽然有性能损耗，但从宏观上说这也是必须要有的消耗，也避免了使用 unsafe。
+    ///
+    /// `BLOCK` 以队列的形式保存本线程领取的全部 `Block`（见 `set_blocks_per_claim`），队首为正在使用的
+    /// `Block`，队列中不会保存已经耗尽的 `Block`。
+    ///
+    /// 线程退出时 `BLOCK` 中剩余的 `Token` 会被丢弃，见 `fastsend::discarded_tokens`。
+    static BLOCK: RefCell<discard::ThreadBlocks> = const { RefCell::new(discard::ThreadBlocks::new()) };
+}
+
+/// 预热全局生成器：初始化全局 `BlockFrame`（包括开启 'pause_on_start' 时等待至下一秒）、等待首次补充完成，并为
+/// 当前线程预先领取足以生成 `n_tokens` 个 `Token` 的 `Block`（最多为一个 `Cursor` 下的全部 `Token`），使之后的
+/// `next_token` 不再承担初始化的开销。对延迟敏感的服务可以在就绪检查（readiness probe）通过之前调用。
+///
+/// 预先领取的 `Block` 保存在调用 `warm_up` 的线程中，多线程运行时中的其他线程仍会在首次调用 `next_token` 时各自
+/// 领取 `Block`（此时全局队列已经补充完毕，无需等待）。启用 'strict_order' feature 时只初始化全局计数器。
+pub async fn warm_up(n_tokens: usize) -> Result<(), FastsendError> {
+    #[cfg(feature = "strict_order")]
+    {
+        let _ = n_tokens;
+        token::ordered::init()
+    }
+
+    #[cfg(not(feature = "strict_order"))]
+    {
+        // 初始化全局生成器并等待首次补充完成，同时为当前线程领取第一个 `Block`
+        with_block(|_| ()).await?;
+
+        let frame = frame()?;
+        let n_tokens = n_tokens.min(BlockFrame::<Token>::ELEMENT_CAP);
+        loop {
+            let held = BLOCK.with(|block| {
+                block
+                    .borrow()
+                    .iter()
+                    .map(|block| block.size_hint().0)
+                    .sum::<usize>()
+            });
+            if held >= n_tokens {
+                return Ok(());
+            }
+
+            // 与 `with_block` 相同，`borrow_mut` 需要在异步任务断点之后调用
+            let next_block = match frame.pop_block() {
+                Some(next_block) => next_block,
+                None => frame.try_next_block().await?,
+            };
+            BLOCK.with(|block| block.borrow_mut().push_back(next_block));
+        }
+    }
 }
 
 /// `with_block` 是一个辅助方法，用于从 thread_local 中获取本线程拥有的 `Block`，由于是使用了 `RefCell` 来
@@ -79,32 +612,16 @@ pub async fn next_token() -> Token {
 /// 引用 `&mut T` 由于生命周期约束的原因，无法移动到函数外部），因此这是一种对 `&mut Block` 的折中的使用方式。
 ///
 /// `with_block` 可以保证传递给 `f` 的 `Block` 一定是包含可用元素的，即调用 `<Block as Iterator>::next` 方
-/// 法时，返回的一定是 `Some`。
-async fn with_block<T, F>(f: F) -> T
+/// 法时，返回的一定是 `Some`；无法获取新的 `Block` 时返回 `FastsendError`。
+#[cfg(not(feature = "strict_order"))]
+async fn with_block<T, F>(f: F) -> Result<T, FastsendError>
 where
     F: FnOnce(&mut Block<Token>) -> T,
 {
-    thread_local! {
-        /// `BLOCK` 是对 `Token` 的第二次预分配行为，此次预分配是各线程各自的预分配，即在 `thread_local`
-        /// 里获取 `BLOCK`，此时再次从 `Block` 里获取元素便不需要加锁，避免了锁竞争。
-        ///
-        /// 这里使用了 `RefCell` 来实现内部可变性，由于从 `Block` 中获取元素以及更新 `Block` 都需要可变引用，
-        /// 因此不得不套一层 `RefCell`，虽然有性能损耗，但从宏观上说这也是必须要有的消耗，也避免了使用 unsafe。
-        static BLOCK: RefCell<Option<Block<Token>>> = RefCell::new(None);
-    }
-
     // 在两种情况下需要重新从 `BlockFrame` 获取新生成的 `Block`：
-    //     - 当前线程的 `BLOCK` 尚未初始化时，即 `BLOCK` 内部为 `None` 时
+    //     - 当前线程的 `BLOCK` 尚未初始化时，即 `BLOCK` 内部为空时
     //     - 当前线程的 `BLOCK` 内部的 `Token` 已全部消耗完毕时，需要重新获取
-    let block_should_assign = BLOCK.with(|block| {
-        0 == block
-            .borrow()
-            .as_ref()
-            // 通过 `size_hint` 来判断剩余可生成的 `Token` 数量
-            .map(|block| block.size_hint().0)
-            // 当 `BLOCK` 为 `None` 时，`unwrap_or_default` 同样会返回 0
-            .unwrap_or_default()
-    });
+    let block_should_assign = BLOCK.with(|block| block.borrow().is_empty());
 
     if block_should_assign {
         // `next_block` 作为异步函数的分隔点，函数过程将在此处被阻断并保留，因此如果在此之前已经出现了对
@@ -114,17 +631,33 @@ where
         // 为避免这个问题，将 `borrow_mut` 的调用延后至 `next_block` 之后，在异步任务断点之前不会有任何
         // `Future` 抢占可变借用，确保该异步函数过程顺利完成。
         // （由于异步任务的可调度性，以上问题在同一个线程中也同样会出现。）
-        let next_block = FRAME.next_block().await;
+        let frame = frame()?;
+        let next_block = frame.try_next_block().await?;
 
         // ===============================================================
 
-        BLOCK.with(|block| *block.borrow_mut() = Some(next_block));
+        // 在已经获得一个 `Block` 的前提下，不等待补充地额外领取 `BLOCKS_PER_CLAIM - 1` 个 `Block`
+        let extra = BLOCKS_PER_CLAIM.load(Ordering::Relaxed) - 1;
+        BLOCK.with(|block| {
+            let mut block = block.borrow_mut();
+            block.push_back(next_block);
+            block.extend((0..extra).map_while(|_| frame.pop_block()));
+        });
     }
 
-    BLOCK.with(|block| f(block.borrow_mut().as_mut().unwrap()))
-}
+    BLOCK.with(|block| {
+        let mut block = block.borrow_mut();
+        let current = block.front_mut().ok_or(FastsendError::DrainedBlock)?;
+        let output = f(current);
 
-use std::env;
+        // 通过 `size_hint` 来判断剩余可生成的 `Token` 数量，耗尽的 `Block` 从队列中移除
+        if current.size_hint().0 == 0 {
+            block.pop_front();
+        }
+
+        Ok(output)
+    })
+}
 
 lazy_static! {
     /// `RV` 是用于对设备号进行混淆的参数，通常而言由编译时的环境变量 `FASTSEND_RANDOM_VALUE` 控制，如果未提供
@@ -133,19 +666,28 @@ lazy_static! {
     static ref RV: u8 = option_env!("FASTSEND_RANDOM_VALUE")
         .map(|var| var.parse::<u8>().ok())
         .flatten()
-        .unwrap_or_else(rand::random);
+        .unwrap_or_else(|| {
+            #[cfg(feature = "test-util")]
+            if let Some(rv) = testing::with_rng(rand::Rng::gen) {
+                return rv;
+            }
+
+            fallback::warn(fallback::Fallback::RandomValue);
+            rand::random()
+        });
 
-    /// 用于定位设备的设备号（添加了随机要素 `RV`），从环境变量中获取，在 id 和 serial 生成的场景用来避免多设备
-    /// 冲突，使用 `lazy_static` 来确保环境变量在整个程序周期只会被获取一次。
+    /// 用于定位设备的设备号（添加了随机要素 `RV`），通常从环境变量中获取（完整的来源及优先级见 `device_id` 模块），
+    /// 在 id 和 serial 生成的场景用来避免多设备冲突，使用 `lazy_static` 来确保设备号在整个程序周期只会被解析一次。
     #[doc(hidden)]
-    pub static ref DEVICE_ID: Option<u8> = env::var("FASTSEND_DEVICE_ID")
-        .map(|var| var.parse::<u8>().ok())
-        .ok()
-        .flatten()
+    pub static ref DEVICE_ID: Option<u8> = device_id::resolve()
         .map(|mut id| {
             id = id.rotate_left(3) ^ (*RV);
             id = id.rotate_left(3) ^ (*RV);
             id = id.rotate_left(3) ^ (*RV);
             id
+        })
+        .or_else(|| {
+            fallback::warn(fallback::Fallback::DeviceId);
+            None
         });
 }
@@ -0,0 +1,192 @@
+use crate::{FastsendError, Serialer, SyncSerialer, Token};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// ## 发号限流
+///
+/// `RateLimiter` 使用令牌桶限制 id 及序列号的发放速率：桶的容量为 `capacity`，每隔 `period / capacity` 补充一个
+/// 令牌，每次发放消耗一个令牌，令牌不足时返回 `RateLimited`。初始时桶是满的，因此允许最多 `capacity` 次的突发。
+///
+/// `RateLimiter` 可以包装全局的 `next_token`（见 `RateLimiter::next_token`），也可以包装任意 `Serialer`（见
+/// `RateLimiter::limit`）。例如限制每个进程每分钟最多发放 100 个优惠券码：
+///
+/// ```ignore
+/// use fastsend::{CouponSerialer, RateLimiter, Serialer};
+/// use std::time::Duration;
+///
+/// let limiter = RateLimiter::new(100, Duration::from_secs(60));
+/// let code = limiter.limit(CouponSerialer::new()).build().await?;
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// 桶的容量。
+    capacity: u32,
+
+    /// 补充一个令牌所需的时间。
+    interval: Duration,
+
+    /// 当前的令牌数量（可以是小数）以及上一次补充的时间。
+    bucket: Mutex<(f64, SystemTime)>,
+}
+
+impl RateLimiter {
+    /// 每 `period` 最多发放 `capacity` 次。
+    ///
+    /// # Panics
+    ///
+    /// `capacity` 为 0 或 `period` 为 0 时 panic。
+    pub fn new(capacity: u32, period: Duration) -> Self {
+        assert!(capacity > 0, "rate limit capacity must be positive");
+        assert!(!period.is_zero(), "rate limit period must be positive");
+
+        RateLimiter {
+            capacity,
+            interval: period / capacity,
+            bucket: Mutex::new((capacity as f64, crate::clock::now())),
+        }
+    }
+
+    /// 尝试消耗一个令牌，令牌不足时返回 `RateLimited`，其中包含需要等待的时间。
+    pub fn try_acquire(&self) -> Result<(), RateLimited> {
+        let mut bucket = self
+            .bucket
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        let (tokens, last) = &mut *bucket;
+
+        // 系统时间回拨时不补充令牌
+        let now = crate::clock::now();
+        let elapsed = now.duration_since(*last).unwrap_or_default();
+        *tokens = (*tokens + elapsed.as_secs_f64() / self.interval.as_secs_f64())
+            .min(self.capacity as f64);
+        *last = now.max(*last);
+
+        if *tokens < 1.0 {
+            return Err(RateLimited {
+                retry_after: self.interval.mul_f64(1.0 - *tokens),
+            });
+        }
+
+        *tokens -= 1.0;
+        Ok(())
+    }
+
+    /// 受限流保护的 `fastsend::try_next_token`。
+    pub async fn next_token(&self) -> Result<Token, LimitError<FastsendError>> {
+        self.try_acquire()?;
+        crate::try_next_token().await.map_err(LimitError::Inner)
+    }
+
+    /// 使用当前 `RateLimiter` 包装 `serialer`，令牌在调用 `build` 时消耗。
+    pub fn limit<S: Serialer>(&self, serialer: S) -> Limited<'_, S> {
+        Limited {
+            limiter: self,
+            serialer,
+        }
+    }
+}
+
+/// 受限流保护的 `Serialer`，由 `RateLimiter::limit` 返回。
+#[derive(Debug)]
+pub struct Limited<'a, S> {
+    limiter: &'a RateLimiter,
+    serialer: S,
+}
+
+impl<'a, S> Serialer for Limited<'a, S>
+where
+    S: Serialer,
+    S::Output: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Output = S::Output;
+
+    type Error = LimitError<S::Error>;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        if let Err(error) = self.limiter.try_acquire() {
+            return Box::pin(std::future::ready(Err(error.into())));
+        }
+
+        let build = self.serialer.build();
+        Box::pin(async move { build.await.map_err(LimitError::Inner) })
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.serialer.feed(data);
+    }
+
+    fn feed_field(&mut self, name: &str, data: &[u8]) {
+        self.serialer.feed_field(name, data);
+    }
+}
+
+impl<'a, S> SyncSerialer for Limited<'a, S>
+where
+    S: SyncSerialer,
+    S::Output: Send + 'static,
+    S::Error: Send + 'static,
+{
+    fn build_sync(self) -> Result<Self::Output, Self::Error> {
+        self.limiter.try_acquire()?;
+        self.serialer.build_sync().map_err(LimitError::Inner)
+    }
+}
+
+/// 超出 `RateLimiter` 限制的速率时返回的错误。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct RateLimited {
+    retry_after: Duration,
+}
+
+impl RateLimited {
+    /// 距离下一个令牌可用所需等待的时间。
+    pub fn retry_after(&self) -> Duration {
+        self.retry_after
+    }
+}
+
+impl fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rate limited, retry after {:?}", self.retry_after)
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// 受限流保护的发号路径返回的错误：被限流，或被包装的发号路径本身返回的错误。
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum LimitError<E> {
+    RateLimited(RateLimited),
+
+    Inner(E),
+}
+
+impl<E> From<RateLimited> for LimitError<E> {
+    fn from(error: RateLimited) -> Self {
+        LimitError::RateLimited(error)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for LimitError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitError::RateLimited(error) => write!(f, "{}", error),
+            LimitError::Inner(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for LimitError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LimitError::RateLimited(error) => Some(error),
+            LimitError::Inner(error) => Some(error),
+        }
+    }
+}
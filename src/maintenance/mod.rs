@@ -0,0 +1,281 @@
+use futures::FutureExt;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 维护任务检查到期任务的最长间隔，`defer` 的任务最迟在一个间隔之后执行。
+const TICK: Duration = Duration::from_millis(100);
+
+/// 尚无维护任务运行。
+const IDLE: u8 = 0;
+
+/// 由 fastsend 自行启动的后台线程执行。
+const THREAD: u8 = 1;
+
+/// 由 `spawn_maintenance` 在应用的运行时中执行。
+#[cfg(feature = "maintenance")]
+const RUNTIME: u8 = 2;
+
+/// 当前执行周期任务的方式，见 `IDLE`、`THREAD`、`RUNTIME`。
+static DRIVER: AtomicU8 = AtomicU8::new(IDLE);
+
+type Run = Box<dyn FnMut() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send>;
+
+struct Job {
+    id: JobId,
+    name: &'static str,
+
+    /// `None` 表示只执行一次的任务（见 `defer`）。
+    interval: Option<Duration>,
+
+    /// 下一次执行的时间，`None` 表示正在执行。
+    due: Option<Instant>,
+
+    run: Run,
+}
+
+static JOBS: Mutex<Vec<Job>> = Mutex::new(Vec::new());
+
+/// `register_job` 返回的任务标识，用于通过 `remove_job` 移除任务。
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct JobId(u64);
+
+/// 注册一个每隔 `interval` 执行一次的周期任务，首次执行在注册的 `interval` 之后。
+///
+/// fastsend 内部需要周期执行的工作（如 `Init::cursor_file` 按 `Init::persist_interval` 进行的游标持久化、
+/// `TimeSerialer` 的 slot 清理）都注册在同一个维护任务中，而不是各自启动后台线程。应用在启动时通过 `spawn_maintenance` 将维护任务交给自己的运行时执行；
+/// 未调用时 fastsend 会在首次注册任务时启动一个后台线程执行全部任务（此时任务的 `Future` 通过
+/// `futures::executor::block_on` 执行，不能依赖特定运行时提供的计时器或 IO）。
+///
+/// `job` 在持有任务列表的锁时调用，只应构造 `Future`，实际的工作放在返回的 `Future` 中进行，也不应在其中注册或移除
+/// 任务；各任务依次执行，执行时间较长的任务会推迟之后的任务。任务 panic 时输出一条警告（需开启 `log` 或 `tracing`
+/// 特性），任务照常在 `interval` 之后再次执行，不影响其他任务。
+///
+/// # Panics
+///
+/// `interval` 为 0 时 panic。
+pub fn register_job<F, Fut>(name: &'static str, interval: Duration, mut job: F) -> JobId
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    assert!(!interval.is_zero(), "job interval must be positive");
+    push(
+        name,
+        Some(interval),
+        Instant::now() + interval,
+        Box::new(move || Box::pin(job())),
+    )
+}
+
+/// 与 `register_job` 相同，但 `job` 返回的 `Future` 在注册时所在的 tokio 运行时中执行，用于依赖运行时的 IO 或计时器
+/// 的任务（如租约续期），维护任务由后台线程执行时也不会脱离运行时。
+///
+/// # Panics
+///
+/// 不在 tokio 运行时中调用时 panic。
+#[cfg(any(
+    feature = "redis_lease",
+    feature = "etcd_lease",
+    feature = "coordinator"
+))]
+pub(crate) fn register_runtime_job<F, Fut>(
+    name: &'static str,
+    interval: Duration,
+    mut job: F,
+) -> JobId
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let handle = tokio::runtime::Handle::current();
+    register_job(name, interval, move || {
+        let (handle, future) = (handle.clone(), job());
+        async move {
+            // 任务 panic 时 `JoinHandle` 返回错误，与直接执行时一样报告
+            if let Err(error) = handle.spawn(future).await {
+                if error.is_panic() {
+                    std::panic::resume_unwind(error.into_panic());
+                }
+            }
+        }
+    })
+}
+
+/// 移除通过 `register_job` 注册的任务，正在执行的任务会在本次执行结束后移除，任务不存在时返回 `false`。
+pub fn remove_job(id: JobId) -> bool {
+    let mut jobs = lock();
+    let len = jobs.len();
+    jobs.retain(|job| job.id != id);
+    jobs.len() != len
+}
+
+/// 当前注册的周期任务的名称，按注册顺序排列。
+pub fn maintenance_jobs() -> Vec<&'static str> {
+    lock()
+        .iter()
+        .filter(|job| job.interval.is_some())
+        .map(|job| job.name)
+        .collect()
+}
+
+/// 在维护任务中尽快执行一次 `future`，用于不希望阻塞调用方的清理工作。
+pub(crate) fn defer<Fut>(name: &'static str, future: Fut)
+where
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let mut future = Some(future);
+    push(
+        name,
+        None,
+        Instant::now(),
+        Box::new(move || match future.take() {
+            Some(future) => Box::pin(future),
+            None => Box::pin(std::future::ready(())),
+        }),
+    );
+}
+
+fn push(name: &'static str, interval: Option<Duration>, due: Instant, run: Run) -> JobId {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+
+    let id = JobId(NEXT.fetch_add(1, Ordering::Relaxed));
+    lock().push(Job {
+        id,
+        name,
+        interval,
+        due: Some(due),
+        run,
+    });
+
+    ensure_driver();
+    id
+}
+
+/// 获取锁并忽略 poison，任务 panic 不应导致之后的注册全部失败。
+fn lock() -> MutexGuard<'static, Vec<Job>> {
+    JOBS.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+/// 尚无维护任务运行时启动后台线程。
+fn ensure_driver() {
+    if DRIVER
+        .compare_exchange(IDLE, THREAD, Ordering::AcqRel, Ordering::Acquire)
+        .is_err()
+    {
+        return;
+    }
+
+    thread::spawn(|| {
+        // `spawn_maintenance` 接管之后退出
+        while DRIVER.load(Ordering::Acquire) == THREAD {
+            let wait = futures::executor::block_on(run_due());
+            thread::sleep(wait);
+        }
+    });
+}
+
+/// 依次执行全部到期的任务，返回距离下一次检查的时间。
+async fn run_due() -> Duration {
+    let now = Instant::now();
+    let due = lock()
+        .iter_mut()
+        .filter(|job| job.due.is_some_and(|due| due <= now))
+        .map(|job| {
+            job.due = None;
+            (job.id, job.name, (job.run)())
+        })
+        .collect::<Vec<_>>();
+
+    for (id, name, future) in due {
+        if AssertUnwindSafe(future).catch_unwind().await.is_err() {
+            crate::fallback::warn_each(
+                "maintenance_job",
+                &format_args!("maintenance job {:?} panicked", name),
+            );
+        }
+
+        let mut jobs = lock();
+        if let Some(index) = jobs.iter().position(|job| job.id == id) {
+            match jobs[index].interval {
+                Some(interval) => jobs[index].due = Some(Instant::now() + interval),
+                None => {
+                    jobs.remove(index);
+                }
+            }
+        }
+    }
+
+    let now = Instant::now();
+    lock()
+        .iter()
+        .filter_map(|job| job.due)
+        .map(|due| due.saturating_duration_since(now))
+        .min()
+        .unwrap_or(TICK)
+        .min(TICK)
+}
+
+/// 在 tokio 运行时 `handle` 中启动维护任务，之后 fastsend 的周期任务（见 `register_job`）都在该运行时中执行，
+/// 不再使用后台线程。应用只需在启动时调用一次：
+///
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// let maintenance = fastsend::spawn_maintenance(&tokio::runtime::Handle::current()).unwrap();
+/// assert!(fastsend::spawn_maintenance(&tokio::runtime::Handle::current()).is_none());
+///
+/// // 停止后周期任务重新由后台线程执行
+/// maintenance.stop();
+/// # }
+/// ```
+///
+/// 维护任务已经在运行时返回 `None`。运行时关闭后维护任务随之结束，此时应调用 `MaintenanceHandle::stop`（或在新的
+/// 运行时中重新启动），否则周期任务将不再执行。
+#[cfg(feature = "maintenance")]
+pub fn spawn_maintenance(handle: &tokio::runtime::Handle) -> Option<MaintenanceHandle> {
+    if DRIVER.swap(RUNTIME, Ordering::AcqRel) == RUNTIME {
+        return None;
+    }
+
+    let task = handle.spawn(async {
+        loop {
+            let wait = run_due().await;
+            tokio::time::sleep(wait).await;
+        }
+    });
+
+    Some(MaintenanceHandle { task })
+}
+
+/// `spawn_maintenance` 启动的维护任务，丢弃时维护任务继续运行。
+#[cfg(feature = "maintenance")]
+#[derive(Debug)]
+pub struct MaintenanceHandle {
+    task: tokio::task::JoinHandle<()>,
+}
+
+#[cfg(feature = "maintenance")]
+impl MaintenanceHandle {
+    /// 停止维护任务，之后的周期任务重新由后台线程执行。
+    pub fn stop(self) {
+        self.task.abort();
+        DRIVER.store(IDLE, Ordering::Release);
+
+        // 被中断的任务重新安排执行
+        let mut jobs = lock();
+        let now = Instant::now();
+        for job in jobs.iter_mut().filter(|job| job.due.is_none()) {
+            job.due = Some(now);
+        }
+
+        if !jobs.is_empty() {
+            drop(jobs);
+            ensure_driver();
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use crate::encoding::{base62, EncodingError};
+use std::fmt;
+
+/// ## 类型化的 id
+///
+/// `define_id!` 为一种实体定义包装 u64 的 id 类型，避免在不同实体之间直接传递裸的 u64：
+///
+/// ```
+/// fastsend::define_id!(pub OrderId, prefix = "ord");
+/// fastsend::define_id!(pub UserId, prefix = "usr");
+///
+/// # futures::executor::block_on(async {
+/// let order = OrderId::next().await;
+/// let text = order.to_string();
+/// assert!(text.starts_with("ord_"));
+/// assert_eq!(text.parse::<OrderId>(), Ok(order));
+///
+/// // 前缀不符的字符串无法解析为其他类型的 id
+/// assert!(text.parse::<UserId>().is_err());
+/// # });
+/// ```
+///
+/// 生成的类型：
+///
+///   - 实现了 `ID`（取出内部的 u64）与 `Serial`（以大端序 feed 给 `Serialer`），`next` 从全局生成器获取新的 id；
+///   - 以 `前缀_定长 base62` 的形式实现 `Display`/`FromStr`，定长编码的字典序与数值大小一致，解析时检查前缀，
+///     并只接受定长的编码，因此每个 id 只有唯一的字符串表示；
+///   - 没有实现 `From<u64>` 及与其他 id 类型之间的转换，只能通过 `from_id` 显式地由 u64 构造；
+///   - 启用 'serde' feature 后以 `Display` 的字符串形式序列化，启用 'sqlx' feature 后与 `Token` 一样以 BIGINT 存储。
+///
+/// 前缀只能包含小写字母和数字，且以字母开头，不合法的前缀在编译时报错。
+///
+/// ```compile_fail
+/// fastsend::define_id!(OrderId, prefix = "Order");
+/// ```
+#[macro_export]
+macro_rules! define_id {
+    ($(#[$meta:meta])* $vis:vis $name:ident, prefix = $prefix:literal $(,)?) => {
+        $(#[$meta])*
+        #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+        $vis struct $name(u64);
+
+        const _: () = assert!(
+            $crate::newtype::is_valid_prefix($prefix),
+            "id prefix must be lowercase ascii letters or digits starting with a letter"
+        );
+
+        impl $name {
+            pub const PREFIX: &'static str = $prefix;
+
+            pub const fn from_id(id: u64) -> Self {
+                $name(id)
+            }
+
+            /// 从全局生成器获取新的 id，见 `fastsend::next_token`。
+            pub async fn next() -> Self {
+                $name($crate::ID::id($crate::next_token().await))
+            }
+        }
+
+        impl $crate::ID for $name {
+            fn id(self) -> u64 {
+                self.0
+            }
+        }
+
+        impl $crate::Serial for $name {
+            fn serial<S: $crate::Serialer>(self, serialer: &mut S) {
+                serialer.feed(&self.0.to_be_bytes());
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                $crate::newtype::fmt_id(f, Self::PREFIX, self.0)
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = $crate::ParseIdError;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                $crate::newtype::parse_id(s, Self::PREFIX).map($name)
+            }
+        }
+
+        $crate::__define_id_serde!($name);
+        $crate::__define_id_sqlx!($name);
+    };
+}
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_id_serde {
+    ($name:ident) => {
+        impl $crate::newtype::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::newtype::serde::Serializer,
+            {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> $crate::newtype::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: $crate::newtype::serde::Deserializer<'de>,
+            {
+                $crate::newtype::deserialize_id(deserializer, Self::PREFIX).map($name)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_id_serde {
+    ($name:ident) => {};
+}
+
+#[cfg(feature = "sqlx")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_id_sqlx {
+    ($name:ident) => {
+        impl<DB: $crate::newtype::sqlx::Database> $crate::newtype::sqlx::Type<DB> for $name
+        where
+            i64: $crate::newtype::sqlx::Type<DB>,
+        {
+            fn type_info() -> DB::TypeInfo {
+                <i64 as $crate::newtype::sqlx::Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <i64 as $crate::newtype::sqlx::Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q, DB: $crate::newtype::sqlx::Database> $crate::newtype::sqlx::Encode<'q, DB>
+            for $name
+        where
+            i64: $crate::newtype::sqlx::Encode<'q, DB>,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <DB as $crate::newtype::sqlx::Database>::ArgumentBuffer<'q>,
+            ) -> ::std::result::Result<
+                $crate::newtype::sqlx::encode::IsNull,
+                $crate::newtype::sqlx::error::BoxDynError,
+            > {
+                <i64 as $crate::newtype::sqlx::Encode<DB>>::encode_by_ref(&(self.0 as i64), buf)
+            }
+        }
+
+        impl<'r, DB: $crate::newtype::sqlx::Database> $crate::newtype::sqlx::Decode<'r, DB>
+            for $name
+        where
+            i64: $crate::newtype::sqlx::Decode<'r, DB>,
+        {
+            fn decode(
+                value: <DB as $crate::newtype::sqlx::Database>::ValueRef<'r>,
+            ) -> ::std::result::Result<Self, $crate::newtype::sqlx::error::BoxDynError> {
+                Ok($name(
+                    <i64 as $crate::newtype::sqlx::Decode<DB>>::decode(value)? as u64,
+                ))
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "sqlx"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_id_sqlx {
+    ($name:ident) => {};
+}
+
+// 供 `define_id!` 展开的代码使用，调用方不需要直接依赖 serde 或 sqlx
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde;
+#[cfg(feature = "sqlx")]
+#[doc(hidden)]
+pub use sqlx;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParseIdError {
+    /// 字符串不以 `expected` 及 '_' 开头，例如将其他类型的 id 解析为当前类型。
+    Prefix { expected: &'static str },
+
+    /// 前缀之后的部分不是合法的定长 base62 编码，例如长度不是 11 个字符。
+    Encoding(EncodingError),
+}
+
+impl fmt::Display for ParseIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseIdError::Prefix { expected } => {
+                write!(f, "id should start with prefix {:?}", expected)
+            }
+            ParseIdError::Encoding(error) => write!(f, "invalid id: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for ParseIdError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseIdError::Encoding(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[doc(hidden)]
+pub const fn is_valid_prefix(prefix: &str) -> bool {
+    let bytes = prefix.as_bytes();
+    if bytes.is_empty() || !bytes[0].is_ascii_lowercase() {
+        return false;
+    }
+
+    let mut i = 1;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_lowercase() && !bytes[i].is_ascii_digit() {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+#[doc(hidden)]
+pub fn fmt_id(f: &mut fmt::Formatter<'_>, prefix: &str, id: u64) -> fmt::Result {
+    write!(f, "{}_{}", prefix, base62::encode_u64_fixed(id))
+}
+
+#[doc(hidden)]
+pub fn parse_id(s: &str, prefix: &'static str) -> Result<u64, ParseIdError> {
+    let encoded = s
+        .strip_prefix(prefix)
+        .and_then(|s| s.strip_prefix('_'))
+        .ok_or(ParseIdError::Prefix { expected: prefix })?;
+
+    base62::decode_u64_fixed(encoded).map_err(ParseIdError::Encoding)
+}
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub fn deserialize_id<'de, D>(deserializer: D, prefix: &'static str) -> Result<u64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = <std::borrow::Cow<'de, str> as serde::Deserialize>::deserialize(deserializer)?;
+    parse_id(&s, prefix).map_err(serde::de::Error::custom)
+}
@@ -0,0 +1,111 @@
+use crate::ID;
+
+mod permute;
+pub use permute::{set_id_permutation, BitPermutation};
+
+/// ## id 混淆
+///
+/// `Obfuscator` 是 u64 上的可逆置换（类似 Optimus），用于将内部大致递增的 id 转换为对外不透明的 id，同时可以从
+/// 对外的 id 还原内部 id。编码依次进行以下三步，每一步都是可逆的：
+///
+///   1. 乘以奇数 `multiplier`（模 2^64），解码时乘以其模逆元；
+///   2. 高 32 位异或到低 32 位（`x ^ x >> 32`），解码时重复一次即可还原；
+///   3. 异或 `mask`。
+///
+/// 相同的密钥（`multiplier`、`mask`）总是得到相同的结果，因此密钥需要在部署之间保持不变并妥善保管。混淆只用于隐藏
+/// id 的规律（如发号量、生成时间），不能代替加密：已知足够多的明文、密文对时密钥是可以被推算出来的。
+///
+/// ```
+/// use fastsend::Obfuscator;
+///
+/// let obfuscator = Obfuscator::from_seed(0x5EED);
+/// let public = obfuscator.encode(42);
+/// assert_ne!(public, 42);
+/// assert_eq!(obfuscator.decode(public), 42);
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Obfuscator {
+    multiplier: u64,
+
+    /// `multiplier` 模 2^64 的逆元。
+    inverse: u64,
+
+    mask: u64,
+}
+
+impl Obfuscator {
+    /// 使用指定的密钥构造 `Obfuscator`。
+    ///
+    /// # Panics
+    ///
+    /// `multiplier` 为偶数（模 2^64 不可逆）时 panic。
+    pub fn new(multiplier: u64, mask: u64) -> Self {
+        assert!(multiplier % 2 == 1, "multiplier must be odd");
+
+        // 牛顿迭代求模逆元，每次迭代正确的位数翻倍，初始值 `multiplier` 对于奇数已有 3 位正确
+        let mut inverse = multiplier;
+        for _ in 0..5 {
+            inverse = inverse.wrapping_mul(2u64.wrapping_sub(multiplier.wrapping_mul(inverse)));
+        }
+
+        Obfuscator {
+            multiplier,
+            inverse,
+            mask,
+        }
+    }
+
+    /// 由 `seed` 派生密钥（使用 SplitMix64，结果在不同平台及版本之间保持一致）。
+    pub fn from_seed(seed: u64) -> Self {
+        let mut state = seed;
+        let multiplier = splitmix64(&mut state) | 1;
+        let mask = splitmix64(&mut state);
+        Self::new(multiplier, mask)
+    }
+
+    pub fn encode(&self, id: u64) -> u64 {
+        let x = id.wrapping_mul(self.multiplier);
+        (x ^ x >> 32) ^ self.mask
+    }
+
+    pub fn decode(&self, id: u64) -> u64 {
+        let x = id ^ self.mask;
+        (x ^ x >> 32).wrapping_mul(self.inverse)
+    }
+
+    /// 包装 `id`，使其 `ID::id` 返回混淆后的 id。
+    pub fn wrap<T: ID>(&self, id: T) -> Obfuscated<T> {
+        Obfuscated {
+            inner: id,
+            obfuscator: *self,
+        }
+    }
+}
+
+/// `Obfuscator::wrap` 返回的 `ID` 适配器，`id()` 返回内部 id 经过 `Obfuscator::encode` 混淆后的结果。
+#[derive(Debug, Copy, Clone)]
+pub struct Obfuscated<T> {
+    inner: T,
+    obfuscator: Obfuscator,
+}
+
+impl<T> Obfuscated<T> {
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: ID> ID for Obfuscated<T> {
+    fn id(self) -> u64 {
+        self.obfuscator.encode(self.inner.id())
+    }
+}
+
+/// SplitMix64 伪随机数生成器，用于由种子派生密钥，结果在不同平台及版本之间保持一致。
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
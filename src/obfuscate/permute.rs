@@ -0,0 +1,105 @@
+use super::splitmix64;
+use crate::fallback::{self, Fallback};
+use crate::{Token, ID};
+use std::sync::OnceLock;
+
+/// ## 位置换
+///
+/// `BitPermutation` 由密钥派生 u64 中 64 个位的置换（Fisher–Yates 洗牌），置换后再异或掩码，编码与解码都只需要
+/// 按位搬运，开销很低。与 `Obfuscator` 一样，置换是可逆的，但不能代替加密；其作用是打散 `Token` 的位布局，使外部
+/// 观察者无法直接从 id 中读出游标（生成时间）、设备号等字段：
+///
+/// ```
+/// use fastsend::BitPermutation;
+///
+/// let permutation = BitPermutation::from_seed(0x5EED);
+/// let public = permutation.encode(42);
+/// assert_ne!(public, 42);
+/// assert_eq!(permutation.decode(public), 42);
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct BitPermutation {
+    /// 第 i 位移动到第 `positions[i]` 位。
+    positions: [u8; 64],
+
+    mask: u64,
+}
+
+impl BitPermutation {
+    /// 由 `seed` 派生置换及掩码（使用 SplitMix64，结果在不同平台及版本之间保持一致）。
+    pub fn from_seed(seed: u64) -> Self {
+        let mut state = seed;
+
+        let mut positions = [0; 64];
+        for (i, position) in positions.iter_mut().enumerate() {
+            *position = i as u8;
+        }
+        for i in (1..positions.len()).rev() {
+            let j = (splitmix64(&mut state) % (i as u64 + 1)) as usize;
+            positions.swap(i, j);
+        }
+
+        BitPermutation {
+            positions,
+            mask: splitmix64(&mut state),
+        }
+    }
+
+    pub fn encode(&self, id: u64) -> u64 {
+        let permuted = self
+            .positions
+            .iter()
+            .enumerate()
+            .fold(0, |output, (i, &position)| {
+                output | (id >> i & 1) << position
+            });
+
+        permuted ^ self.mask
+    }
+
+    pub fn decode(&self, id: u64) -> u64 {
+        let permuted = id ^ self.mask;
+        self.positions
+            .iter()
+            .enumerate()
+            .fold(0, |output, (i, &position)| {
+                output | (permuted >> position & 1) << i
+            })
+    }
+}
+
+/// 通过 `set_id_permutation` 设置的全局置换。
+static PERMUTATION: OnceLock<BitPermutation> = OnceLock::new();
+
+/// 在程序启动时设置 `Token::public_id` 使用的全局置换，由 `seed` 派生，见 `BitPermutation::from_seed`。全局置换
+/// 只能设置一次，已经设置过时返回 `false`。
+///
+/// 同一个 id 空间内的所有服务需要使用相同的 `seed`，并且 `seed` 在整个生命周期内不能修改，否则已经公开的 id 将
+/// 无法通过 `Token::from_public_id` 还原。
+pub fn set_id_permutation(seed: u64) -> bool {
+    PERMUTATION.set(BitPermutation::from_seed(seed)).is_ok()
+}
+
+impl Token {
+    /// 对外公开的 id：`ID::id` 经过全局置换（见 `set_id_permutation`）后的结果，外部无法从中直接读出生成时间及设备号。
+    /// 数据库等内部存储仍然使用 `ID::id`，在对外的边界（如 API 响应）处转换。
+    ///
+    /// 未设置全局置换时返回 `ID::id` 本身，并在启用 'log' 或 'tracing' feature 时输出一次警告。
+    pub fn public_id(self) -> u64 {
+        match PERMUTATION.get() {
+            Some(permutation) => permutation.encode(self.id()),
+            None => {
+                fallback::warn(Fallback::Permutation);
+                self.id()
+            }
+        }
+    }
+
+    /// 从 `public_id` 还原 `Token`，是 `public_id` 的逆过程。
+    pub fn from_public_id(id: u64) -> Token {
+        let id = PERMUTATION
+            .get()
+            .map_or(id, |permutation| permutation.decode(id));
+        Token::from_id(id)
+    }
+}
@@ -0,0 +1,73 @@
+//! ## Protobuf 集成
+//!
+//! 在 gRPC 服务之间传递 id 时，推荐使用 `fixed64` 类型（id 的高位通常不为 0，`uint64` 的变长编码反而需要
+//! 10 个字节），在 prost 生成的代码中对应 `u64`，可以通过 `u64::from(token)` 与 `Token::from(id)` 相互转换。
+//!
+//! 如果需要在不同服务之间保留 id 的结构，可以使用 `IdProto`，其对应的 proto 定义为：
+//!
+//! ```proto
+//! message IdProto {
+//!   fixed64 id = 1;
+//!   uint64 timestamp = 2;
+//!   uint32 device = 3;
+//!   uint32 thread = 4;
+//!   uint32 sequence = 5;
+//! }
+//! ```
+
+use crate::{Cursor, Token, ID};
+
+impl From<Token> for u64 {
+    fn from(token: Token) -> Self {
+        token.id()
+    }
+}
+
+impl From<u64> for Token {
+    fn from(id: u64) -> Self {
+        Token::from_id(id)
+    }
+}
+
+/// 携带结构信息的 id 消息，`id` 是唯一的权威字段，其余字段均由 `id` 拆解而来，仅供观察和调试使用，从
+/// `IdProto` 还原 `Token` 时会被忽略。
+#[derive(Clone, Copy, PartialEq, Eq, prost::Message)]
+pub struct IdProto {
+    #[prost(fixed64, tag = "1")]
+    pub id: u64,
+
+    /// 生成 id 时的秒级 UNIX 时间戳（由时间游标换算而来，仅精确到进程启动时的时间）。
+    #[prost(uint64, tag = "2")]
+    pub timestamp: u64,
+
+    /// 经过混淆后的设备号，未配置设备号时为进程号的低 8 位。
+    #[prost(uint32, tag = "3")]
+    pub device: u32,
+
+    /// 线程号的哈希值的低 8 位。
+    #[prost(uint32, tag = "4")]
+    pub thread: u32,
+
+    /// 同一时间游标内的发号序号。
+    #[prost(uint32, tag = "5")]
+    pub sequence: u32,
+}
+
+impl From<Token> for IdProto {
+    fn from(token: Token) -> Self {
+        let id = token.id();
+        IdProto {
+            id,
+            timestamp: crate::block::epoch::unix_secs(Cursor::from_inner((id >> 32) as u32)),
+            device: (id >> 8) as u8 as u32,
+            thread: id as u8 as u32,
+            sequence: (id >> 16) as u16 as u32,
+        }
+    }
+}
+
+impl From<IdProto> for Token {
+    fn from(proto: IdProto) -> Self {
+        Token::from_id(proto.id)
+    }
+}
@@ -0,0 +1,129 @@
+use super::{RequestId, REQUEST_ID_HEADER};
+use crate::ID;
+use actix_web::dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::error::ErrorInternalServerError;
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::{Error, FromRequest, HttpMessage, HttpRequest};
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+
+impl FromRequest for RequestId {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(
+            request
+                .extensions()
+                .get::<RequestId>()
+                .copied()
+                .ok_or_else(|| {
+                    ErrorInternalServerError(
+                        "request id is missing, is `RequestIdMiddleware` installed?",
+                    )
+                }),
+        )
+    }
+}
+
+/// ## actix-web 请求 ID 中间件
+///
+/// 与 axum 的 `RequestIdLayer` 一致，`RequestIdMiddleware` 为每个请求通过 `fastsend::next_token` 分配一个
+/// `Token` 作为请求 ID，写入请求的 extensions（通过 `HttpRequest::extensions` 或 `RequestId` 提取器读取）
+/// 以及请求头和响应头（缺省为 'x-request-id'）。
+///
+/// ```no_run
+/// use actix_web::App;
+///
+/// let app = App::new().wrap(fastsend::RequestIdMiddleware::new());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RequestIdMiddleware {
+    /// 请求 ID 写入的请求头与响应头，缺省为 'x-request-id'。
+    header: HeaderName,
+}
+
+impl RequestIdMiddleware {
+    pub fn new() -> RequestIdMiddleware {
+        RequestIdMiddleware {
+            header: HeaderName::from_static(REQUEST_ID_HEADER),
+        }
+    }
+
+    pub fn header(mut self, header: HeaderName) -> Self {
+        self.header = header;
+        self
+    }
+}
+
+impl Default for RequestIdMiddleware {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequestIdMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestIdMiddlewareService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestIdMiddlewareService {
+            service: Rc::new(service),
+            header: self.header.clone(),
+        }))
+    }
+}
+
+/// 由 `RequestIdMiddleware` 包装的服务。
+#[derive(Debug)]
+pub struct RequestIdMiddlewareService<S> {
+    service: Rc<S>,
+    header: HeaderName,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestIdMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    // actix-web 的服务运行在单线程的 worker 中，因此返回的 `Future` 不需要满足 `Send`
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut request: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let header = self.header.clone();
+
+        Box::pin(async move {
+            let token = crate::next_token().await;
+            let value = HeaderValue::from(token.id());
+
+            request.extensions_mut().insert(RequestId(token));
+            request.headers_mut().insert(header.clone(), value.clone());
+
+            #[cfg(feature = "tracing")]
+            let mut response = {
+                use tracing::Instrument;
+                let span = tracing::info_span!("request", request_id = token.id());
+                service.call(request).instrument(span).await?
+            };
+
+            #[cfg(not(feature = "tracing"))]
+            let mut response = service.call(request).await?;
+
+            response.headers_mut().insert(header, value);
+            Ok(response)
+        })
+    }
+}
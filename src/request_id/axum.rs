@@ -0,0 +1,82 @@
+use super::{RequestId, REQUEST_ID_HEADER};
+use crate::stamp::{IdLayer, IdService, MakeId};
+use crate::ID;
+use axum::extract::FromRequestParts;
+use axum::http::header::HeaderName;
+use axum::http::request::Parts;
+use axum::http::{HeaderValue, StatusCode};
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use tower_layer::Layer;
+
+impl<S: Send + Sync> FromRequestParts<S> for RequestId {
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        parts.extensions.get::<RequestId>().copied().ok_or((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "request id is missing, is `RequestIdLayer` installed?",
+        ))
+    }
+}
+
+/// 通过 `fastsend::next_token` 为每个请求生成 `RequestId`，是 `RequestIdLayer` 使用的 `MakeId`。
+#[derive(Debug, Copy, Clone, Default)]
+pub struct NextRequestId;
+
+impl MakeId for NextRequestId {
+    type Id = RequestId;
+
+    type Error = Infallible;
+
+    fn make_id(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Id, Self::Error>> + Send + 'static>> {
+        Box::pin(async { Ok(RequestId(crate::next_token().await)) })
+    }
+}
+
+/// 请求 ID 在请求头与响应头中以十进制数字表示。
+fn header_value(request_id: &RequestId) -> HeaderValue {
+    HeaderValue::from(request_id.id())
+}
+
+/// ## 请求 ID 中间件
+///
+/// `RequestIdLayer` 为每个请求通过 `fastsend::next_token` 分配一个 `Token` 作为请求 ID：写入请求的 extensions
+/// （供 `RequestId` 提取器读取）以及请求头和响应头（缺省为 'x-request-id'，以十进制数字表示），使各个服务之间的
+/// 日志能够通过同一个 ID 关联起来。同时开启 'tracing' feature 时，下游服务的调用会包含在一个携带 `request_id`
+/// 字段的 span 中。
+///
+/// `RequestIdLayer` 是以 `NextRequestId` 生成 id 并指定了请求头的 `IdLayer`。
+#[derive(Debug, Clone)]
+pub struct RequestIdLayer(IdLayer<NextRequestId>);
+
+impl RequestIdLayer {
+    pub fn new() -> RequestIdLayer {
+        RequestIdLayer::default().header(HeaderName::from_static(REQUEST_ID_HEADER))
+    }
+
+    /// 请求 ID 写入的请求头与响应头，缺省为 'x-request-id'。
+    pub fn header(self, header: HeaderName) -> Self {
+        RequestIdLayer(self.0.header(header, header_value))
+    }
+}
+
+impl Default for RequestIdLayer {
+    fn default() -> Self {
+        RequestIdLayer(IdLayer::with(NextRequestId))
+    }
+}
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        self.0.layer(inner)
+    }
+}
+
+/// 由 `RequestIdLayer` 包装的服务。
+pub type RequestIdService<S> = IdService<S, NextRequestId>;
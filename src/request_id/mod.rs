@@ -0,0 +1,46 @@
+use crate::{Token, ID};
+use std::ops::Deref;
+
+#[cfg(feature = "axum")]
+mod axum;
+#[cfg(feature = "axum")]
+pub use self::axum::{NextRequestId, RequestIdLayer, RequestIdService};
+
+#[cfg(feature = "actix")]
+mod actix;
+#[cfg(feature = "actix")]
+pub use self::actix::{RequestIdMiddleware, RequestIdMiddlewareService};
+
+/// 缺省的请求 ID 请求头。
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// 通过 `RequestIdLayer`（axum）或 `RequestIdMiddleware`（actix-web）分配给请求的 ID，保存在请求的 extensions
+/// 中，也可以在 handler 中直接作为提取器使用：
+///
+/// ```no_run
+/// async fn handler(request_id: fastsend::RequestId) -> String {
+///     format!("{}", request_id.as_u64())
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct RequestId(pub Token);
+
+impl RequestId {
+    pub fn as_u64(&self) -> u64 {
+        self.0.id()
+    }
+}
+
+impl Deref for RequestId {
+    type Target = Token;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<RequestId> for Token {
+    fn from(request_id: RequestId) -> Self {
+        request_id.0
+    }
+}
@@ -1,4 +1,4 @@
-use super::to_string_radix;
+use crate::encoding::Radix;
 use crate::{Serialer, RV};
 use lazy_static::lazy_static;
 use std::cmp;
@@ -15,6 +15,9 @@ pub const UNINITIALIZED: i64 = -1;
 /// 代表增长失败的常量，获取到该值表明 STATE 在自增过程中出现了错误导致失败
 pub const FAILED: i64 = i64::MIN;
 
+mod sequence_map;
+pub use sequence_map::{NoStore, SequenceMap, SequenceMapBuilder, SequenceStore};
+
 lazy_static! {
     static ref COUNTER: AtomicU8 = AtomicU8::new(*RV);
 }
@@ -51,13 +54,20 @@ impl<AI: AutoIncrement> IncrState<AI> {
         IncrStateBuilder::new()
     }
 
-    pub fn incr(&self) -> IncrSerialer {
+    pub fn incr(&self) -> IncrSerialer<'_> {
         assert!(!self.engine.is_poisoned());
         IncrSerialer {
             ident: {
                 let mut engine = self.engine.lock().unwrap();
-                let old = self.last.load(Ordering::SeqCst);
-                let new = engine.incr(old);
+                let mut old = self.last.load(Ordering::SeqCst);
+                let mut new = engine.incr(old);
+
+                // 跳过 `exclude_ids` 登记的区间，跳过的值同样需要保持递增
+                while new > old && crate::is_excluded(new as u64) {
+                    old = new;
+                    new = engine.incr(old);
+                }
+
                 if new == UNINITIALIZED {
                     UNINITIALIZED
                 } else {
@@ -108,15 +118,15 @@ impl<'a> Serialer for IncrSerialer<'a> {
     fn build(
         self,
     ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        // 编号超出 `padding` 位时保留全部的位，而不是截断
+        let radix = Radix::digits_first(self.radix);
         let output = format!(
             "{prefix}{ident}{suffix}",
-            ident = to_string_radix(self.ident as usize, self.radix, self.padding, true),
+            ident = radix.encode_padded(self.ident as u64, self.padding),
             prefix = self.prefix.unwrap_or_default(),
-            suffix = to_string_radix(
-                self.suffix as usize % cmp::min(self.radix.pow(2), u8::MAX as usize),
-                self.radix,
-                2,
-                true
+            suffix = radix.encode_padded(
+                (self.suffix as usize % cmp::min(self.radix.pow(2), u8::MAX as usize)) as u64,
+                2
             ),
         );
 
@@ -170,6 +180,12 @@ pub struct IncrStateBuilder {
     padding: Option<usize>,
 }
 
+impl Default for IncrStateBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl IncrStateBuilder {
     pub fn new() -> IncrStateBuilder {
         IncrStateBuilder {
@@ -187,7 +203,7 @@ impl IncrStateBuilder {
     }
 
     pub fn with_radix(mut self, radix: usize) -> IncrStateBuilder {
-        assert!(radix >= 2 && radix <= 36);
+        assert!((2..=36).contains(&radix));
         self.radix = Some(radix);
         self
     }
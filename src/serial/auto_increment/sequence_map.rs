@@ -0,0 +1,203 @@
+use super::{AutoIncrement, FAILED};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+
+/// `SequenceStore` 是 `SequenceMap` 的持久化接口：每次生成新的序号后通过 `save` 写入，内存中不存在某个键（首次使用
+/// 或已被淘汰）时通过 `load` 读取该键最后一次生成的序号。
+pub trait SequenceStore<K> {
+    fn load(&mut self, key: &K) -> Option<i64>;
+
+    fn save(&mut self, key: &K, value: i64);
+}
+
+/// 不进行持久化，被淘汰的键再次使用时会从起始值重新开始。
+#[derive(Debug, Default, Copy, Clone)]
+pub struct NoStore;
+
+impl<K> SequenceStore<K> for NoStore {
+    fn load(&mut self, _: &K) -> Option<i64> {
+        None
+    }
+
+    fn save(&mut self, _: &K, _: i64) {}
+}
+
+/// 以 `HashMap` 作为存储，适用于测试或在进程内保留全部键的最后序号。
+impl<K: Hash + Eq + Clone> SequenceStore<K> for HashMap<K, i64> {
+    fn load(&mut self, key: &K) -> Option<i64> {
+        self.get(key).copied()
+    }
+
+    fn save(&mut self, key: &K, value: i64) {
+        self.insert(key.clone(), value);
+    }
+}
+
+/// ## 按键自增序列
+///
+/// `SequenceMap` 为每个键（如会话 id）各自维护一个单调递增的序号（如会话内的消息序号），与 `IncrState` 共用
+/// `AutoIncrement` 引擎：每个键的下一个序号由 `engine.incr(当前序号)` 得到，新序号不大于当前序号时视为失败并返回
+/// `Err(FAILED)`。
+///
+/// 内存中的键数量超过 `capacity` 时淘汰最久未使用的键，被淘汰的键再次使用时从 `SequenceStore` 中读取最后的序号；
+/// 使用缺省的 `NoStore` 时被淘汰的键会从起始值重新开始，此时请确保 `capacity` 足以容纳全部活跃的键。
+pub struct SequenceMap<K, AI = fn(i64) -> i64, S = NoStore> {
+    inner: Mutex<Inner<K, AI, S>>,
+
+    /// 键首次使用时的当前序号，即首个序号为 `engine.incr(start)`。
+    start: i64,
+
+    /// 内存中最多保存的键数量，`None` 表示不淘汰。
+    capacity: Option<usize>,
+}
+
+struct Inner<K, AI, S> {
+    engine: AI,
+
+    store: S,
+
+    /// 各键的当前序号，以及最后一次使用的时刻（用于淘汰）。
+    entries: HashMap<K, (i64, u64)>,
+
+    /// 单调递增的逻辑时钟，每次访问键时 +1。
+    tick: u64,
+}
+
+impl<K: Hash + Eq + Clone> SequenceMap<K> {
+    /// 从 0 开始、每次 +1、不淘汰也不持久化的 `SequenceMap`，首个序号为 1。
+    pub fn new() -> Self {
+        SequenceMapBuilder::new().build(increment as fn(i64) -> i64)
+    }
+}
+
+fn increment(current: i64) -> i64 {
+    current + 1
+}
+
+impl<K: Hash + Eq + Clone> Default for SequenceMap<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, AI: AutoIncrement, S: SequenceStore<K>> SequenceMap<K, AI, S> {
+    /// 生成 `key` 的下一个序号并写入 `SequenceStore`。
+    pub fn next(&self, key: &K) -> Result<i64, i64> {
+        let mut inner = self.inner.lock().unwrap();
+        let inner = &mut *inner;
+        inner.tick += 1;
+
+        let current = match inner.entries.get(key) {
+            Some(&(current, _)) => current,
+            None => inner.store.load(key).unwrap_or(self.start),
+        };
+
+        let new = inner.engine.incr(current);
+        if new <= current {
+            return Err(FAILED);
+        }
+
+        inner.store.save(key, new);
+        inner.entries.insert(key.clone(), (new, inner.tick));
+
+        if let Some(capacity) = self.capacity {
+            while inner.entries.len() > capacity {
+                let oldest = inner
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, &(_, used))| used)
+                    .map(|(key, _)| key.clone());
+
+                match oldest {
+                    Some(oldest) => inner.entries.remove(&oldest),
+                    None => break,
+                };
+            }
+        }
+
+        Ok(new)
+    }
+
+    /// `key` 最后一次生成的序号，尚未生成过时返回 `None`。
+    pub fn current(&self, key: &K) -> Option<i64> {
+        let mut inner = self.inner.lock().unwrap();
+        match inner.entries.get(key) {
+            Some(&(current, _)) => Some(current),
+            None => inner.store.load(key),
+        }
+    }
+
+    /// 将 `key` 从内存中移除（不影响 `SequenceStore` 中保存的序号）。
+    pub fn evict(&self, key: &K) {
+        self.inner.lock().unwrap().entries.remove(key);
+    }
+
+    /// 内存中保存的键数量。
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+pub struct SequenceMapBuilder<S = NoStore> {
+    start: i64,
+    capacity: Option<usize>,
+    store: S,
+}
+
+impl SequenceMapBuilder {
+    pub fn new() -> SequenceMapBuilder {
+        SequenceMapBuilder {
+            start: 0,
+            capacity: None,
+            store: NoStore,
+        }
+    }
+}
+
+impl Default for SequenceMapBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> SequenceMapBuilder<S> {
+    /// 键首次使用时的当前序号，缺省为 0。
+    pub fn with_start(mut self, value: i64) -> Self {
+        assert!(value >= 0);
+        self.start = value;
+        self
+    }
+
+    /// 内存中最多保存的键数量，缺省不淘汰。
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0);
+        self.capacity = Some(capacity);
+        self
+    }
+
+    pub fn with_store<T>(self, store: T) -> SequenceMapBuilder<T> {
+        SequenceMapBuilder {
+            start: self.start,
+            capacity: self.capacity,
+            store,
+        }
+    }
+
+    pub fn build<K, AI: AutoIncrement>(self, engine: AI) -> SequenceMap<K, AI, S> {
+        SequenceMap {
+            inner: Mutex::new(Inner {
+                engine,
+                store: self.store,
+                entries: HashMap::new(),
+                tick: 0,
+            }),
+            start: self.start,
+            capacity: self.capacity,
+        }
+    }
+}
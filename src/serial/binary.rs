@@ -0,0 +1,65 @@
+use std::future::Future;
+use std::pin::Pin;
+
+/// `BinarySerialer` 是输出原始字节的 `Serialer`：`Output` 只需要能以 `&[u8]` 的形式读取（如 `[u8; 16]`、
+/// `Vec<u8>`），而不需要实现 `Display`，适用于二进制协议、以 `BYTEA`/`BINARY(16)` 存储的列等不需要字符串形式的
+/// 场合，省去了先格式化为字符串再解析回字节的开销。
+///
+/// 除了对 `Output` 的约束之外，`BinarySerialer` 与 `Serialer` 的定义（每个实例构建出的序列号都应具有唯一性）相同。
+/// `UUIDSerialer`、`Flake128Serialer` 同时实现了两者，分别输出 16 字节的 UUID 及大端序的 `Flake128`；两个 trait
+/// 同时引入作用域时，需要以 `BinarySerialer::build(serialer)` 的形式指明所调用的方法。
+///
+/// ```
+/// use fastsend::BinarySerialer;
+/// use std::convert::Infallible;
+/// use std::future::Future;
+/// use std::pin::Pin;
+///
+/// /// 将 feed 的数据按 8 字节对齐打包的序列号生成器。
+/// struct Packed(Vec<u8>);
+///
+/// impl BinarySerialer for Packed {
+///     type Output = Vec<u8>;
+///     type Error = Infallible;
+///
+///     fn build(self) -> Pin<Box<dyn Future<Output = Result<Vec<u8>, Infallible>> + Send + 'static>> {
+///         let mut bytes = self.0;
+///         bytes.resize(bytes.len().next_multiple_of(8), 0);
+///         Box::pin(async move { Ok(bytes) })
+///     }
+///
+///     fn feed(&mut self, data: &[u8]) {
+///         self.0.extend_from_slice(data);
+///     }
+/// }
+///
+/// let serial = futures::executor::block_on(Packed(Vec::new()).oneshot(b"fastsend"));
+/// assert_eq!(serial.unwrap(), b"fastsend");
+/// ```
+pub trait BinarySerialer {
+    /// 代表序列号的字节序列，见 `Serialer::Output`。
+    type Output: AsRef<[u8]>;
+
+    /// 见 `Serialer::Error`。
+    type Error;
+
+    /// 消耗自身构建出序列号，返回的 `Future` 与 `Serialer::build` 相同，需要满足 `Send` + `'static` 约束。
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>>;
+
+    /// 见 `Serialer::feed`。
+    fn feed(&mut self, data: &[u8]);
+
+    /// `feed` + `build` 的快捷方式。
+    fn oneshot(
+        mut self,
+        data: &[u8],
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>>
+    where
+        Self: Sized,
+    {
+        self.feed(data);
+        self.build()
+    }
+}
@@ -0,0 +1,165 @@
+use super::{luhn_check_char, luhn_validate};
+use crate::{Serialer, SyncSerialer};
+use rand::Rng;
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::future::{self, Future};
+use std::pin::Pin;
+
+/// 去除了易混淆字符（'0'/'O'、'1'/'I'）的 32 字符字符表，是 `CouponSerialer` 的缺省字符表。
+pub const COUPON_ALPHABET: &str = "23456789ABCDEFGHJKLMNPQRSTUVWXYZ";
+
+/// ## 优惠券码
+///
+/// `CouponSerialer` 是面向优惠券场景的预设序列号生成器，生成形如 'SPRING-K7QM-X3PT-N' 的券码，由以下部分组成：
+///
+/// - 活动前缀（可选）：用于区分不同的营销活动；
+/// - 券码主体：使用密码学安全的随机数从无歧义字符表中选取字符，长度可配置，缺省为 8；
+/// - 校验位：对券码主体使用 Luhn mod N 算法计算得出，用于在核销时快速拦截输入错误或伪造的券码。
+///
+/// 券码主体完全由随机数生成，因此 `feed` 提供的数据将被忽略；全局唯一性需要由调用方借助外部存储保证，但通过
+/// `batch` 批量生成时，同一批次内的券码保证互不重复。
+#[derive(Debug, Clone)]
+pub struct CouponSerialer {
+    /// 活动前缀，缺省为空。
+    prefix: Option<Box<str>>,
+
+    /// 字符表，缺省为 `COUPON_ALPHABET`。
+    alphabet: Box<[u8]>,
+
+    /// 券码主体长度（不包含前缀与校验位），缺省配置是 8。
+    length: usize,
+
+    /// 分组长度：券码主体每 `group` 个字符使用 '-' 分隔，0 代表不分组，缺省配置是 4。
+    group: usize,
+}
+
+impl Default for CouponSerialer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CouponSerialer {
+    pub fn new() -> CouponSerialer {
+        CouponSerialer {
+            prefix: None,
+            alphabet: COUPON_ALPHABET.as_bytes().into(),
+            length: 8,
+            group: 4,
+        }
+    }
+
+    /// 活动前缀仅能包含 ASCII 字母和数字。
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        assert!(prefix.bytes().all(|c| c.is_ascii_alphanumeric()));
+        self.prefix = Some(prefix.to_ascii_uppercase().into_boxed_str());
+        self
+    }
+
+    /// 自定义字符表，字符表应至少包含 2 个互不相同的 ASCII 字母或数字（将统一转换为大写）。
+    pub fn alphabet(mut self, alphabet: &str) -> Self {
+        let alphabet = alphabet.to_ascii_uppercase().into_bytes();
+        assert!(alphabet.len() >= 2);
+        assert!(alphabet.iter().all(|c| c.is_ascii_alphanumeric()));
+        assert_eq!(
+            alphabet.iter().collect::<HashSet<_>>().len(),
+            alphabet.len()
+        );
+
+        self.alphabet = alphabet.into_boxed_slice();
+        self
+    }
+
+    pub fn length(mut self, length: usize) -> Self {
+        assert!(length > 0);
+        self.length = length;
+        self
+    }
+
+    pub fn group(mut self, group: usize) -> Self {
+        self.group = group;
+        self
+    }
+
+    /// 批量生成 `n` 个券码，同一批次内的券码保证互不重复。当 `n` 超过券码空间大小时会 panic。
+    pub fn batch(&self, n: usize) -> Vec<String> {
+        let space = (self.alphabet.len() as f64).powi(self.length as i32);
+        assert!((n as f64) <= space, "coupon space is smaller than {}", n);
+
+        let mut seen = HashSet::with_capacity(n);
+        let mut output = Vec::with_capacity(n);
+        while output.len() < n {
+            let coupon = self.generate();
+            if seen.insert(coupon.clone()) {
+                output.push(coupon);
+            }
+        }
+
+        output
+    }
+
+    /// 校验券码格式：前缀、字符表以及校验位均正确时返回 true，忽略大小写与分隔符位置。
+    pub fn validate(&self, coupon: &str) -> bool {
+        let mut coupon = coupon.to_ascii_uppercase();
+        coupon.retain(|c| c != '-');
+
+        let body = match &self.prefix {
+            Some(prefix) => match coupon.strip_prefix(&**prefix) {
+                Some(body) => body,
+                None => return false,
+            },
+            None => &coupon,
+        };
+
+        body.len() == self.length + 1 && luhn_validate(body, &self.alphabet)
+    }
+
+    fn generate(&self) -> String {
+        let mut rng = rand::thread_rng();
+        let body = (0..self.length)
+            .map(|_| self.alphabet[rng.gen_range(0..self.alphabet.len())] as char)
+            .collect::<String>();
+        let check = luhn_check_char(&body, &self.alphabet).unwrap();
+
+        let mut parts = self
+            .prefix
+            .iter()
+            .map(|prefix| prefix.to_string())
+            .collect::<Vec<_>>();
+
+        if self.group == 0 {
+            parts.push(format!("{}{}", body, check));
+        } else {
+            parts.extend(
+                body.as_bytes()
+                    .chunks(self.group)
+                    .map(|chunk| String::from_utf8_lossy(chunk).into_owned()),
+            );
+            parts.push(check.to_string());
+        }
+
+        parts.join("-")
+    }
+}
+
+impl Serialer for CouponSerialer {
+    type Output = String;
+
+    type Error = Infallible;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        Box::pin(future::ready(self.build_sync()))
+    }
+
+    fn feed(&mut self, _: &[u8]) {}
+}
+
+impl SyncSerialer for CouponSerialer {
+    fn build_sync(self) -> Result<Self::Output, Self::Error> {
+        let output = self.generate();
+        Ok(output)
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{Serialer, SyncSerialer};
+use lazy_static::lazy_static;
+use rand::Rng;
+use sha3::{Digest, Sha3_512};
+use std::convert::Infallible;
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+
+/// cuid2 中计数器初始值的上限，与 cuid2 官方实现保持一致。
+const INITIAL_COUNT_MAX: u64 = 476782367;
+
+/// cuid2 中指纹及熵的长度上限，同时也是 `Cuid2Serialer` 所能生成的序列号的最大长度。
+const BIG_LENGTH: usize = 32;
+
+lazy_static! {
+    /// 全局计数器，以随机数作为起点，每生成一个 cuid2 自增 1。
+    static ref COUNTER: AtomicU64 = AtomicU64::new(rand::thread_rng().gen_range(0..INITIAL_COUNT_MAX));
+
+    /// 主机指纹，由进程 ID、设备号以及一段随机熵哈希而成，在整个程序周期内只计算一次。
+    static ref FINGERPRINT: String = {
+        let source = format!(
+            "{}{}{}",
+            process::id(),
+            crate::DEVICE_ID.map(|id| id.to_string()).unwrap_or_default(),
+            entropy(BIG_LENGTH)
+        );
+
+        let mut fingerprint = hash(source.as_bytes());
+        fingerprint.truncate(BIG_LENGTH);
+        fingerprint
+    };
+}
+
+/// ## CUID2
+///
+/// 按照 cuid2 的构造方式生成序列号：随机小写字母开头，后接由「时间 + 熵 + 计数器 + 主机指纹」经过 SHA3-512
+/// 哈希后的 36 进制字符串，生成的序列号与前端 cuid2 库生成的序列号格式一致，可以互相替换使用。
+///
+/// 通过 `feed` 提供的数据将作为额外的哈希输入，不影响序列号的格式。
+#[derive(Debug)]
+pub struct Cuid2Serialer {
+    /// 序列号长度，缺省配置是 24，取值范围为 2..=32。
+    length: usize,
+
+    data: Vec<u8>,
+}
+
+impl Cuid2Serialer {
+    pub const DEFAULT_LENGTH: usize = 24;
+
+    pub fn new() -> Cuid2Serialer {
+        Cuid2Serialer {
+            length: Self::DEFAULT_LENGTH,
+            data: Vec::with_capacity(8),
+        }
+    }
+
+    pub fn length(mut self, length: usize) -> Self {
+        assert!((2..=BIG_LENGTH).contains(&length));
+        self.length = length;
+        self
+    }
+}
+
+impl Default for Cuid2Serialer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Serialer for Cuid2Serialer {
+    type Output = String;
+
+    type Error = Infallible;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        Box::pin(future::ready(self.build_sync()))
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.data.extend_from_slice(data);
+    }
+}
+
+impl SyncSerialer for Cuid2Serialer {
+    fn build_sync(self) -> Result<Self::Output, Self::Error> {
+        let first_letter = char::from(b'a' + rand::thread_rng().gen_range(0..26));
+
+        let time = crate::clock::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_millis())
+            .unwrap_or_default();
+
+        let count = COUNTER.fetch_add(1, Ordering::SeqCst);
+
+        let mut input = format!(
+            "{}{}{}{}",
+            to_base36(&time.to_be_bytes()),
+            entropy(self.length),
+            to_base36(&count.to_be_bytes()),
+            *FINGERPRINT
+        )
+        .into_bytes();
+        input.extend_from_slice(&self.data);
+
+        // 与官方实现一致，取哈希值的 [1, length) 部分拼接在首字母之后
+        let output = hash(&input).chars().skip(1).take(self.length - 1).fold(
+            String::from(first_letter),
+            |mut output, c| {
+                output.push(c);
+                output
+            },
+        );
+
+        Ok(output)
+    }
+}
+
+/// 生成长度为 `length` 的 36 进制随机字符串。
+fn entropy(length: usize) -> String {
+    let mut rng = rand::thread_rng();
+    (0..length)
+        .map(|_| char::from_digit(rng.gen_range(0..36), 36).unwrap())
+        .collect()
+}
+
+/// 对输入进行 SHA3-512 哈希，转换为 36 进制字符串后丢弃首个字符（首字符分布不均匀）。
+fn hash(input: &[u8]) -> String {
+    let digest = Sha3_512::digest(input);
+    to_base36(digest.as_slice()).split_off(1)
+}
+
+/// 将大端序的字节数组视作一个大整数，并将其转换为 36 进制的小写字符串。
+fn to_base36(bytes: &[u8]) -> String {
+    let mut digits = bytes.to_vec();
+    let mut output = Vec::with_capacity(bytes.len() * 2);
+
+    while digits.iter().any(|&b| b != 0) {
+        // 对大整数逐字节做长除法，得到余数作为当前位
+        let mut remainder = 0u32;
+        for byte in digits.iter_mut() {
+            let acc = (remainder << 8) | *byte as u32;
+            *byte = (acc / 36) as u8;
+            remainder = acc % 36;
+        }
+        output.push(char::from_digit(remainder, 36).unwrap());
+    }
+
+    if output.is_empty() {
+        output.push('0');
+    }
+
+    output.iter().rev().collect()
+}
@@ -0,0 +1,155 @@
+use crate::encoding::Radix;
+use crate::{HmacSigner, Serialer};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 内部序列号、过期时间与签名之间的分隔符。
+const SEPARATOR: char = '.';
+
+/// ## 限时一次性令牌
+///
+/// `ExpiringSerialer` 在内部序列号之后追加过期时间（秒级 Unix 时间戳的 36 进制表示）以及对两者计算的 HMAC-SHA256
+/// 签名，形如 '20211209113031...K4X2PQ.9f86d081884c7d65'。持有密钥的一方可以通过 `verify` 在不查询任何存储的
+/// 情况下校验签名与有效期并取回内部序列号，适用于密码重置链接、邮箱验证码等场景。
+///
+/// 签名只能防篡改而不能防泄露，内部序列号与过期时间均以明文出现在结果中；令牌需要不可猜测时，请使用随机的内部
+/// `Serialer`（如 `Random62Serialer`）。「一次性」需要由调用方在使用后记录内部序列号来保证，令牌本身在过期之前
+/// 可以被重复校验通过。
+#[derive(Debug)]
+pub struct ExpiringSerialer<S> {
+    signer: HmacSigner,
+
+    /// 令牌的有效期。
+    ttl: Duration,
+
+    inner: S,
+}
+
+impl<S: Serialer> ExpiringSerialer<S> {
+    /// 使用密钥 `key` 签名、有效期为 `ttl` 的令牌，`feed` 的数据将原样交给内部 `Serialer`。
+    pub fn new(key: &[u8], ttl: Duration, inner: S) -> Self {
+        ExpiringSerialer {
+            signer: HmacSigner::new(key),
+            ttl,
+            inner,
+        }
+    }
+
+    /// 截取的签名长度（字节），缺省配置是 8，见 `HmacSigner::tag_len`。
+    pub fn tag_len(mut self, tag_len: usize) -> Self {
+        self.signer = self.signer.tag_len(tag_len);
+        self
+    }
+}
+
+impl<S> Serialer for ExpiringSerialer<S>
+where
+    S: Serialer,
+    S::Output: 'static,
+    S::Error: 'static,
+{
+    type Output = String;
+
+    type Error = S::Error;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        let signer = self.signer;
+        let expires_at = crate::clock::now() + self.ttl;
+        let inner = self.inner.build();
+
+        Box::pin(async move {
+            let expires_at = expires_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let payload = format!(
+                "{}{}{}",
+                inner.await?,
+                SEPARATOR,
+                Radix::digits_first(36).encode(expires_at)
+            );
+            Ok(signer.sign(&payload))
+        })
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.inner.feed(data);
+    }
+
+    fn feed_field(&mut self, name: &str, data: &[u8]) {
+        self.inner.feed_field(name, data);
+    }
+}
+
+/// `verify` 校验成功时返回的令牌内容。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ExpiringClaims<'a> {
+    /// 内部序列号。
+    pub serial: &'a str,
+
+    /// 过期时间（精确到秒）。
+    pub expires_at: SystemTime,
+}
+
+/// 校验由 `ExpiringSerialer`（使用缺省的签名长度）生成的令牌：签名正确且 `now` 早于过期时间时返回令牌内容。
+pub fn verify<'a>(
+    token: &'a str,
+    key: &[u8],
+    now: SystemTime,
+) -> Result<ExpiringClaims<'a>, ExpiringError> {
+    verify_with(token, &HmacSigner::new(key), now)
+}
+
+/// 与 `verify` 相同，但使用指定的 `HmacSigner` 校验签名，用于自定义签名长度的场合。
+pub fn verify_with<'a>(
+    token: &'a str,
+    signer: &HmacSigner,
+    now: SystemTime,
+) -> Result<ExpiringClaims<'a>, ExpiringError> {
+    let payload = signer.verify(token).ok_or(ExpiringError::Invalid)?;
+    let (serial, expires_at) = payload
+        .rsplit_once(SEPARATOR)
+        .ok_or(ExpiringError::Invalid)?;
+    let expires_at = Radix::digits_first(36)
+        .decode(expires_at)
+        .map_err(|_| ExpiringError::Invalid)?;
+    let expires_at = UNIX_EPOCH
+        .checked_add(Duration::from_secs(expires_at))
+        .ok_or(ExpiringError::Invalid)?;
+
+    if now >= expires_at {
+        return Err(ExpiringError::Expired { expires_at });
+    }
+
+    Ok(ExpiringClaims { serial, expires_at })
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ExpiringError {
+    /// 格式错误或签名不正确（令牌被篡改或使用了错误的密钥）。
+    Invalid,
+
+    /// 签名正确，但令牌已于 `expires_at` 过期。
+    Expired { expires_at: SystemTime },
+}
+
+impl fmt::Display for ExpiringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpiringError::Invalid => write!(f, "invalid token"),
+            ExpiringError::Expired { expires_at } => {
+                let secs = expires_at
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                write!(f, "token expired at unix time {}", secs)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExpiringError {}
@@ -0,0 +1,157 @@
+use crate::Serialer;
+use ::fpe::ff1::{FlexibleNumeralString, FF1};
+use aes::Aes256;
+use std::fmt::{self, Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// FF1 要求 radix^minlen >= 1,000,000，对于十进制而言，最小长度为 6。
+const MIN_LENGTH: usize = 6;
+
+/// ## FF1 格式保留加密
+///
+/// `FpeCipher` 使用 AES-256 作为底层分组密码，通过 FF1 算法在固定长度的十进制数字域内进行加密，加密结果与明文
+/// 长度一致且同为纯数字，适用于「对外展示的编号需保持既有格式（如 12 位数字），但不能暴露发放顺序」的场景。
+///
+/// FF1 是一个置换：在同一密钥和 tweak 下，不同的明文必然得到不同的密文，因此只要明文（如 `IncrSerialer` 生成的
+/// 自增序列）唯一，密文也必然唯一；持有密钥的一方可以通过 `decrypt` 还原出原始编号。
+#[derive(Clone)]
+pub struct FpeCipher {
+    cipher: Arc<FF1<Aes256>>,
+
+    /// 数字域长度：明文将被左侧补零至该长度后加密，超过该长度的明文视为越界。
+    length: usize,
+
+    /// tweak：类似于盐值，不同的 tweak 会产生不同的置换，缺省为空。
+    tweak: Arc<[u8]>,
+}
+
+impl Debug for FpeCipher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FpeCipher")
+            .field("cipher", &"FF1<Aes256>")
+            .field("length", &self.length)
+            .field("tweak", &self.tweak)
+            .finish()
+    }
+}
+
+impl FpeCipher {
+    /// 使用 32 字节的密钥构建 `FpeCipher`，`length` 为数字域长度，最小为 6。
+    pub fn new(key: &[u8; 32], length: usize) -> FpeCipher {
+        assert!(
+            length >= MIN_LENGTH,
+            "FF1 domain requires at least 6 digits"
+        );
+
+        FpeCipher {
+            cipher: Arc::new(FF1::new(key, 10).expect("radix 10 is always valid for FF1")),
+            length,
+            tweak: Arc::from(Vec::new()),
+        }
+    }
+
+    pub fn tweak(mut self, tweak: &[u8]) -> Self {
+        self.tweak = Arc::from(tweak);
+        self
+    }
+
+    /// 加密十进制数字串 `digits`，返回长度为 `length` 的密文，当 `digits` 不是纯数字或超过 `length` 位时返回
+    /// `None`。
+    pub fn encrypt(&self, digits: &str) -> Option<String> {
+        let numerals = self.to_numerals(digits)?;
+        let output = self.cipher.encrypt(&self.tweak, &numerals).ok()?;
+        Some(from_numerals(output))
+    }
+
+    /// 解密由 `encrypt` 生成的密文，返回补零至 `length` 位的明文。
+    pub fn decrypt(&self, digits: &str) -> Option<String> {
+        let numerals = self.to_numerals(digits)?;
+        let output = self.cipher.decrypt(&self.tweak, &numerals).ok()?;
+        Some(from_numerals(output))
+    }
+
+    /// 使用当前密钥包装一个内部 `Serialer`，内部序列号将被加密后输出。
+    pub fn wrap<S: Serialer>(&self, inner: S) -> FpeSerialer<S> {
+        FpeSerialer {
+            cipher: self.clone(),
+            inner,
+        }
+    }
+
+    fn to_numerals(&self, digits: &str) -> Option<FlexibleNumeralString> {
+        if digits.is_empty() || digits.len() > self.length {
+            return None;
+        }
+
+        let mut numerals = vec![0u16; self.length - digits.len()];
+        for c in digits.chars() {
+            numerals.push(c.to_digit(10)? as u16);
+        }
+
+        Some(FlexibleNumeralString::from(numerals))
+    }
+}
+
+fn from_numerals(numerals: FlexibleNumeralString) -> String {
+    Vec::<u16>::from(numerals)
+        .into_iter()
+        .map(|n| char::from(b'0' + n as u8))
+        .collect()
+}
+
+#[derive(Debug, Error)]
+pub enum FpeSerialError<E> {
+    #[error("an error occurs when building inner serial: {0}")]
+    InnerFailed(
+        #[from]
+        #[source]
+        E,
+    ),
+
+    #[error("inner serial {0:?} is out of the FF1 digit domain")]
+    OutOfDomain(String),
+}
+
+/// `FpeSerialer` 是对内部 `Serialer` 的包装，`feed` 的数据将原样交给内部 `Serialer`，在 `build` 时先构建
+/// 内部序列号，再使用 `FpeCipher` 将其加密为固定长度的数字串。内部序列号必须是位数不超过数字域长度的纯数字。
+#[derive(Debug)]
+pub struct FpeSerialer<S> {
+    cipher: FpeCipher,
+    inner: S,
+}
+
+impl<S> Serialer for FpeSerialer<S>
+where
+    S: Serialer,
+    S::Output: 'static,
+    S::Error: 'static,
+{
+    type Output = String;
+
+    type Error = FpeSerialError<S::Error>;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        let cipher = self.cipher;
+        let inner = self.inner.build();
+
+        Box::pin(async move {
+            let plain = inner.await?.to_string();
+            cipher
+                .encrypt(&plain)
+                .ok_or(FpeSerialError::OutOfDomain(plain))
+        })
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.inner.feed(data);
+    }
+
+    fn feed_field(&mut self, name: &str, data: &[u8]) {
+        self.inner.feed_field(name, data);
+    }
+}
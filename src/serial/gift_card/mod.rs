@@ -0,0 +1,139 @@
+use super::{luhn_check_char, luhn_validate};
+use crate::{Serialer, SyncSerialer};
+use rand::Rng;
+use std::fmt::{self, Debug, Formatter};
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+const DIGITS: &[u8; 10] = b"0123456789";
+
+/// `CounterFnMut` 是计数器来源的快捷方式，计数器需要在多个 `GiftCardSerialer` 之间共享，因此使用
+/// `Arc<Mutex<..>>` 包装。
+type CounterFnMut = Arc<Mutex<dyn FnMut() -> u64 + Send + 'static>>;
+
+/// 卡号中间部分的来源。
+#[derive(Clone)]
+enum Source {
+    /// 使用密码学安全的随机数填充
+    Entropy,
+
+    /// 使用调用方提供的计数器填充，计数器的值将在左侧补零至固定宽度
+    Counter(CounterFnMut),
+}
+
+/// ## 礼品卡号
+///
+/// 生成形如 '6035 1234 5678 9012 3' 的纯数字礼品卡号：以固定的地区/BIN 前缀开头，中间部分由随机数或计数器填充，
+/// 最后一位是 Luhn 校验位，生成的卡号可以通过常规的 Luhn 校验，卡号总长度为 16 至 19 位。
+///
+/// `GiftCardSerialer` 实现了 `Clone`，使用计数器时，所有克隆出的实例共享同一个计数器。`feed` 提供的数据将被
+/// 忽略。
+#[derive(Clone)]
+pub struct GiftCardSerialer {
+    prefix: Box<str>,
+    length: usize,
+    source: Source,
+}
+
+impl Debug for GiftCardSerialer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GiftCardSerialer")
+            .field("prefix", &self.prefix)
+            .field("length", &self.length)
+            .field(
+                "source",
+                &match self.source {
+                    Source::Entropy => "Entropy",
+                    Source::Counter(_) => "Counter(FnMut() -> u64)",
+                },
+            )
+            .finish()
+    }
+}
+
+impl GiftCardSerialer {
+    /// 使用纯数字前缀 `prefix` 和卡号总长度 `length`（包含前缀与校验位）构建 `GiftCardSerialer`，缺省使用随机
+    /// 数填充中间部分。
+    pub fn new(prefix: &str, length: usize) -> GiftCardSerialer {
+        assert!((16..=19).contains(&length), "length must be within 16..=19");
+        assert!(prefix.bytes().all(|c| c.is_ascii_digit()));
+        assert!(prefix.len() < length - 1, "prefix is too long");
+
+        GiftCardSerialer {
+            prefix: prefix.to_owned().into_boxed_str(),
+            length,
+            source: Source::Entropy,
+        }
+    }
+
+    /// 使用计数器填充中间部分，计数器的值超出中间部分的宽度时，`build` 将返回 `CounterOverflow` 错误。
+    pub fn counter<F>(mut self, f: F) -> Self
+    where
+        F: FnMut() -> u64 + Send + 'static,
+    {
+        self.source = Source::Counter(Arc::new(Mutex::new(f)));
+        self
+    }
+
+    /// 使用 Luhn 算法校验卡号。
+    pub fn validate(number: &str) -> bool {
+        number.len() > 1 && luhn_validate(number, DIGITS)
+    }
+
+    fn width(&self) -> usize {
+        self.length - self.prefix.len() - 1
+    }
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum GiftCardError {
+    #[error("counter value {0} does not fit into the gift card number")]
+    CounterOverflow(u64),
+}
+
+impl Serialer for GiftCardSerialer {
+    type Output = String;
+
+    type Error = GiftCardError;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        Box::pin(future::ready(self.build_sync()))
+    }
+
+    fn feed(&mut self, _: &[u8]) {}
+}
+
+impl SyncSerialer for GiftCardSerialer {
+    fn build_sync(self) -> Result<Self::Output, Self::Error> {
+        let width = self.width();
+        let middle = match &self.source {
+            Source::Entropy => {
+                let mut rng = rand::thread_rng();
+                Ok((0..width)
+                    .map(|_| char::from(b'0' + rng.gen_range(0..10)))
+                    .collect::<String>())
+            }
+            Source::Counter(counter) => {
+                let value = (counter.lock().unwrap())();
+                let middle = format!("{:0width$}", value, width = width);
+                if middle.len() > width {
+                    Err(GiftCardError::CounterOverflow(value))
+                } else {
+                    Ok(middle)
+                }
+            }
+        };
+
+        let output = middle.map(|middle| {
+            let mut number = format!("{}{}", self.prefix, middle);
+            number.push(luhn_check_char(&number, DIGITS).unwrap());
+            number
+        });
+
+        output
+    }
+}
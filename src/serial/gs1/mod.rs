@@ -0,0 +1,129 @@
+use crate::{Serialer, SyncSerialer};
+use std::fmt::{self, Debug, Formatter};
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+/// `ItemCounterFnMut` 是商品项目代码来源的快捷方式，需要在多个 `Gs1Serialer` 之间共享，因此使用
+/// `Arc<Mutex<..>>` 包装。
+type ItemCounterFnMut = Arc<Mutex<dyn FnMut() -> u64 + Send + 'static>>;
+
+/// GS1 商品条码的编码格式，值为包含校验位在内的总位数。
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Gs1Format {
+    Ean8 = 8,
+    UpcA = 12,
+    Ean13 = 13,
+    Gtin14 = 14,
+}
+
+/// ## GS1 商品条码
+///
+/// `Gs1Serialer` 生成与 EAN/UPC 兼容的纯数字商品条码，由「厂商识别代码 + 商品项目代码 + 校验位」组成，其中商品
+/// 项目代码来自调用方提供的计数器（在左侧补零至固定宽度），校验位使用 GS1 标准的模 10 算法（自右向左以 3、1 交替
+/// 加权）计算得出。
+///
+/// `Gs1Serialer` 实现了 `Clone`，所有克隆出的实例共享同一个计数器，`feed` 提供的数据将被忽略。
+#[derive(Clone)]
+pub struct Gs1Serialer {
+    format: Gs1Format,
+    company_prefix: Box<str>,
+    counter: ItemCounterFnMut,
+}
+
+impl Debug for Gs1Serialer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Gs1Serialer")
+            .field("format", &self.format)
+            .field("company_prefix", &self.company_prefix)
+            .field("counter", &"FnMut() -> u64")
+            .finish()
+    }
+}
+
+impl Gs1Serialer {
+    /// 使用编码格式 `format`、厂商识别代码 `company_prefix` 以及商品项目代码计数器 `counter` 构建 `Gs1Serialer`。
+    pub fn new<F>(format: Gs1Format, company_prefix: &str, counter: F) -> Gs1Serialer
+    where
+        F: FnMut() -> u64 + Send + 'static,
+    {
+        assert!(company_prefix.bytes().all(|c| c.is_ascii_digit()));
+        assert!(
+            company_prefix.len() < format as usize - 1,
+            "company prefix is too long for {:?}",
+            format
+        );
+
+        Gs1Serialer {
+            format,
+            company_prefix: company_prefix.into(),
+            counter: Arc::new(Mutex::new(counter)),
+        }
+    }
+
+    /// 校验条码的模 10 校验位，适用于所有 GS1 数字条码，包含非 ASCII 字符的输入直接视为无效。
+    pub fn validate(code: &str) -> bool {
+        if !code.is_ascii() {
+            return false;
+        }
+
+        match code.split_at(code.len().saturating_sub(1)) {
+            ("", _) => false,
+            (payload, check) => check_digit(payload).map(|c| c.to_string()) == Some(check.into()),
+        }
+    }
+}
+
+/// 计算 GS1 模 10 校验位：自右向左，奇数位乘以 3，偶数位乘以 1，校验位为使总和成为 10 的倍数的最小值。
+fn check_digit(payload: &str) -> Option<char> {
+    let sum = payload
+        .bytes()
+        .rev()
+        .enumerate()
+        .try_fold(0, |sum, (i, c)| {
+            let digit = (c as char).to_digit(10)?;
+            Some(sum + if i % 2 == 0 { digit * 3 } else { digit })
+        })?;
+
+    char::from_digit((10 - sum % 10) % 10, 10)
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum Gs1Error {
+    #[error("item reference {0} does not fit into the GS1 code")]
+    ItemReferenceOverflow(u64),
+}
+
+impl Serialer for Gs1Serialer {
+    type Output = String;
+
+    type Error = Gs1Error;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        Box::pin(future::ready(self.build_sync()))
+    }
+
+    fn feed(&mut self, _: &[u8]) {}
+}
+
+impl SyncSerialer for Gs1Serialer {
+    fn build_sync(self) -> Result<Self::Output, Self::Error> {
+        let width = self.format as usize - 1 - self.company_prefix.len();
+        let item = (self.counter.lock().unwrap())();
+
+        let output = {
+            let mut code = format!("{}{:0width$}", self.company_prefix, item, width = width);
+            if code.len() == self.format as usize - 1 {
+                code.push(check_digit(&code).unwrap());
+                Ok(code)
+            } else {
+                Err(Gs1Error::ItemReferenceOverflow(item))
+            }
+        };
+
+        output
+    }
+}
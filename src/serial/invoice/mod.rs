@@ -0,0 +1,230 @@
+use crate::{Serialer, SyncSerialer};
+use chrono::{Datelike, NaiveDate};
+use futures_locks::{Mutex as AsyncMutex, MutexGuard as AsyncMutexGuard};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::sync::Mutex;
+
+/// `InvoiceCounter` 是发票号的计数器引擎，按财年维护各自独立的计数，通常由调用方基于数据库等外部系统实现以完成
+/// 持久化。与 `AutoIncrement` 一样，由于 `InvoiceState` 通常作为全局变量存在，因此未设计成异步 trait。
+pub trait InvoiceCounter {
+    /// 递增 `fiscal_year` 的计数并返回新的编号，每个财年的第一个编号应为 1。
+    fn incr(&mut self, fiscal_year: i32) -> u64;
+
+    /// 严格连号模式下，编号被确认使用时调用，可用于提交持久化事务。
+    fn confirm(&mut self, _fiscal_year: i32, _number: u64) {}
+
+    /// 严格连号模式下，编号被放弃时调用，引擎应撤销 `number` 对应的那一次 `incr`，以便下次重新发放该编号。
+    fn rollback(&mut self, fiscal_year: i32, number: u64);
+}
+
+/// 基于内存的 `InvoiceCounter` 实现，程序重启后计数将丢失，仅适用于测试或单次运行的场景。
+#[derive(Debug, Default)]
+pub struct MemoryCounter(HashMap<i32, u64>);
+
+impl InvoiceCounter for MemoryCounter {
+    fn incr(&mut self, fiscal_year: i32) -> u64 {
+        let current = self.0.entry(fiscal_year).or_default();
+        *current += 1;
+        *current
+    }
+
+    fn rollback(&mut self, fiscal_year: i32, number: u64) {
+        if let Some(current) = self.0.get_mut(&fiscal_year) {
+            if *current == number {
+                *current -= 1;
+            }
+        }
+    }
+}
+
+/// ## 发票号
+///
+/// `InvoiceState` 生成形如 'INV-2024-000123' 的发票号，由「前缀 + 财年 + 财年内序号」组成，序号在每个财年重新
+/// 从 1 开始。财年的起始月份可配置（缺省为 1 月），财年以其起始日期所在的公历年份命名，例如起始月份为 4 时，
+/// 2025 年 3 月开具的发票属于 2024 财年。
+///
+/// 与 `IncrState` 类似，`InvoiceState` 相当于一个 Builder，通过 `serialer` 方法不断生成新的 `InvoiceSerialer`；
+/// 当需要严格连号（税务要求不允许出现断号）时，使用 `reserve` 方法预留编号，并在业务完成后 `confirm` 或
+/// `rollback`，同一时间只允许存在一个未决的预留编号，被放弃的编号将会重新发放。两种方式共用同一把锁，存在未决的
+/// 预留编号时 `serialer` 同样需要等待。
+pub struct InvoiceState<C: InvoiceCounter> {
+    counter: Mutex<C>,
+
+    /// 严格连号模式下用于串行化预留过程的异步锁
+    gapless: AsyncMutex<()>,
+
+    /// 发票号前缀，缺省为 'INV'
+    prefix: Box<str>,
+
+    /// 财年起始月份，取值为 1..=12
+    start_month: u32,
+
+    /// 序号的最小宽度，不足时在左侧补零，缺省为 6
+    width: usize,
+}
+
+impl<C: InvoiceCounter> fmt::Debug for InvoiceState<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InvoiceState")
+            .field("prefix", &self.prefix)
+            .field("start_month", &self.start_month)
+            .field("width", &self.width)
+            .finish()
+    }
+}
+
+impl<C: InvoiceCounter> InvoiceState<C> {
+    pub fn new(counter: C) -> InvoiceState<C> {
+        InvoiceState {
+            counter: Mutex::new(counter),
+            gapless: AsyncMutex::new(()),
+            prefix: "INV".into(),
+            start_month: 1,
+            width: 6,
+        }
+    }
+
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    pub fn start_month(mut self, month: u32) -> Self {
+        assert!((1..=12).contains(&month));
+        self.start_month = month;
+        self
+    }
+
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// 计算 `date` 所属的财年。
+    pub fn fiscal_year(&self, date: NaiveDate) -> i32 {
+        if date.month() >= self.start_month {
+            date.year()
+        } else {
+            date.year() - 1
+        }
+    }
+
+    /// 生成一个新的 `InvoiceSerialer`，编号在调用时即被占用（非严格连号模式）。存在未决的预留编号（见 `reserve`）时
+    /// 会等待其被确认或放弃，避免被放弃的编号因计数器已经前进而无法重新发放。
+    pub async fn serialer(&self) -> InvoiceSerialer {
+        let _guard = self.gapless.lock().await;
+        let fiscal_year = self.fiscal_year(crate::clock::now_local().date_naive());
+        let number = self.counter.lock().unwrap().incr(fiscal_year);
+        InvoiceSerialer {
+            output: self.format(fiscal_year, number),
+        }
+    }
+
+    /// 严格连号模式：预留下一个编号，在上一个预留编号被确认或放弃之前，该方法会一直等待。
+    pub async fn reserve(&self) -> Reservation<'_, C> {
+        let guard = self.gapless.lock().await;
+        let fiscal_year = self.fiscal_year(crate::clock::now_local().date_naive());
+        let number = self.counter.lock().unwrap().incr(fiscal_year);
+
+        Reservation {
+            state: self,
+            fiscal_year,
+            number,
+            output: self.format(fiscal_year, number),
+            guard: Some(guard),
+        }
+    }
+
+    fn format(&self, fiscal_year: i32, number: u64) -> String {
+        format!(
+            "{}-{}-{:0width$}",
+            self.prefix,
+            fiscal_year,
+            number,
+            width = self.width
+        )
+    }
+}
+
+/// 由 `InvoiceState::serialer` 生成的发票号序列号生成器，编号已在创建时确定，`feed` 提供的数据将被忽略。
+#[derive(Debug)]
+pub struct InvoiceSerialer {
+    output: String,
+}
+
+impl Serialer for InvoiceSerialer {
+    type Output = String;
+
+    type Error = Infallible;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        Box::pin(future::ready(self.build_sync()))
+    }
+
+    fn feed(&mut self, _: &[u8]) {}
+}
+
+impl SyncSerialer for InvoiceSerialer {
+    fn build_sync(self) -> Result<Self::Output, Self::Error> {
+        Ok(self.output)
+    }
+}
+
+/// 严格连号模式下预留的发票号，必须调用 `confirm` 确认使用；未确认就被 drop 时等同于调用 `rollback`。
+pub struct Reservation<'a, C: InvoiceCounter> {
+    state: &'a InvoiceState<C>,
+    fiscal_year: i32,
+    number: u64,
+    output: String,
+    guard: Option<AsyncMutexGuard<()>>,
+}
+
+impl<'a, C: InvoiceCounter> fmt::Debug for Reservation<'a, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reservation")
+            .field("fiscal_year", &self.fiscal_year)
+            .field("number", &self.number)
+            .field("output", &self.output)
+            .finish()
+    }
+}
+
+impl<'a, C: InvoiceCounter> Reservation<'a, C> {
+    pub fn as_str(&self) -> &str {
+        &self.output
+    }
+
+    /// 确认使用该编号，并返回发票号。
+    pub fn confirm(mut self) -> String {
+        self.state
+            .counter
+            .lock()
+            .unwrap()
+            .confirm(self.fiscal_year, self.number);
+        self.guard.take();
+        std::mem::take(&mut self.output)
+    }
+
+    /// 放弃该编号，该编号将在下一次预留时重新发放。
+    pub fn rollback(self) {
+        // 由 `Drop` 完成回滚
+    }
+}
+
+impl<'a, C: InvoiceCounter> Drop for Reservation<'a, C> {
+    fn drop(&mut self) {
+        if self.guard.take().is_some() {
+            self.state
+                .counter
+                .lock()
+                .unwrap()
+                .rollback(self.fiscal_year, self.number);
+        }
+    }
+}
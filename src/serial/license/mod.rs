@@ -0,0 +1,219 @@
+use crate::{Serialer, SyncSerialer};
+use ed25519_dalek::{Signature, Signer as _, SigningKey, Verifier as _, VerifyingKey};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha256;
+use std::convert::{Infallible, TryInto};
+use std::fmt::{self, Debug, Formatter};
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// 许可证密钥所使用的 Crockford base32 字符表（大写），不包含易混淆的 'I'、'L'、'O'、'U'。
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// HMAC 模式下截取的签名长度（字节），缺省配置下「5 字节载荷 + 7 字节签名」恰好编码为 20 个字符。
+const HMAC_TAG_LEN: usize = 7;
+
+/// ed25519 签名的长度（字节）。
+const ED25519_TAG_LEN: usize = 64;
+
+/// 每组字符的数量。
+const GROUP: usize = 5;
+
+/// 许可证密钥的签名方式。
+#[derive(Clone)]
+enum Signer {
+    /// 使用 HMAC-SHA256 签名并截取前 `HMAC_TAG_LEN` 个字节，签发与校验使用同一个密钥，生成的许可证密钥较短
+    Hmac(Arc<[u8]>),
+
+    /// 使用 ed25519 签名，签发使用私钥，离线校验仅需要公钥，但完整的签名会使许可证密钥变长
+    Ed25519(Arc<SigningKey>),
+}
+
+/// 离线校验许可证密钥时使用的凭据，需与签发时的 `Signer` 对应。
+#[derive(Debug, Copy, Clone)]
+pub enum LicenseVerifier<'a> {
+    Hmac(&'a [u8]),
+    Ed25519(&'a VerifyingKey),
+}
+
+/// ## 许可证密钥
+///
+/// `LicenseSerialer` 生成形如 'XXXXX-XXXXX-XXXXX-XXXXX' 的分组许可证密钥，密钥由「随机载荷 + 签名」经过 Crockford
+/// base32 编码而成，其中签名覆盖了载荷以及通过 `feed` 提供的调用方数据（如客户邮箱、产品编号），调用方数据本身不会
+/// 出现在密钥中，校验时需要再次提供。
+///
+/// 使用 `validate` 可以在不查询任何存储的情况下离线校验许可证密钥。
+#[derive(Clone)]
+pub struct LicenseSerialer {
+    signer: Signer,
+
+    /// 随机载荷的长度（字节），缺省配置是 5。
+    payload_len: usize,
+
+    data: Vec<u8>,
+}
+
+impl Debug for LicenseSerialer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LicenseSerialer")
+            .field(
+                "signer",
+                &match self.signer {
+                    Signer::Hmac(_) => "Hmac",
+                    Signer::Ed25519(_) => "Ed25519",
+                },
+            )
+            .field("payload_len", &self.payload_len)
+            .field("data", &self.data)
+            .finish()
+    }
+}
+
+impl LicenseSerialer {
+    pub fn hmac(secret: &[u8]) -> LicenseSerialer {
+        LicenseSerialer {
+            signer: Signer::Hmac(Arc::from(secret)),
+            payload_len: 5,
+            data: Vec::with_capacity(32),
+        }
+    }
+
+    pub fn ed25519(key: &SigningKey) -> LicenseSerialer {
+        LicenseSerialer {
+            signer: Signer::Ed25519(Arc::new(key.clone())),
+            payload_len: 5,
+            data: Vec::with_capacity(32),
+        }
+    }
+
+    pub fn payload_len(mut self, n: usize) -> Self {
+        assert!(n > 0);
+        self.payload_len = n;
+        self
+    }
+}
+
+impl Serialer for LicenseSerialer {
+    type Output = String;
+
+    type Error = Infallible;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        Box::pin(future::ready(self.build_sync()))
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.data.extend_from_slice(data);
+    }
+}
+
+impl SyncSerialer for LicenseSerialer {
+    fn build_sync(self) -> Result<Self::Output, Self::Error> {
+        let mut payload = vec![0; self.payload_len];
+        rand::thread_rng().fill_bytes(&mut payload);
+
+        let message = [&payload[..], &self.data[..]].concat();
+        let tag = match &self.signer {
+            Signer::Hmac(secret) => hmac_tag(secret, &message),
+            Signer::Ed25519(key) => key.sign(&message).to_bytes().to_vec(),
+        };
+
+        let output = group(&encode(&[payload, tag].concat()));
+        Ok(output)
+    }
+}
+
+/// 离线校验许可证密钥 `key`，`data` 为签发时通过 `feed` 提供的调用方数据，校验成功时返回密钥中的随机载荷。
+/// 校验时忽略大小写与分隔符，并按照 Crockford base32 的规则将 'I'/'L' 视为 '1'、'O' 视为 '0'。
+pub fn validate(key: &str, verifier: LicenseVerifier<'_>, data: &[u8]) -> Option<Vec<u8>> {
+    let bytes = decode(key)?;
+    let tag_len = match verifier {
+        LicenseVerifier::Hmac(_) => HMAC_TAG_LEN,
+        LicenseVerifier::Ed25519(_) => ED25519_TAG_LEN,
+    };
+
+    if bytes.len() <= tag_len {
+        return None;
+    }
+
+    let (payload, tag) = bytes.split_at(bytes.len() - tag_len);
+    let message = [payload, data].concat();
+
+    let valid = match verifier {
+        LicenseVerifier::Hmac(secret) => {
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret).ok()?;
+            mac.update(&message);
+            mac.verify_truncated_left(tag).is_ok()
+        }
+        LicenseVerifier::Ed25519(public_key) => {
+            let signature = Signature::from_bytes(tag.try_into().ok()?);
+            public_key.verify(&message, &signature).is_ok()
+        }
+    };
+
+    valid.then(|| payload.to_vec())
+}
+
+fn hmac_tag(secret: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(message);
+    mac.finalize().into_bytes()[..HMAC_TAG_LEN].to_vec()
+}
+
+/// 将字节序列按大端位序每 5 bits 编码为一个字符，末尾不足 5 bits 时补零。
+fn encode(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity((bytes.len() * 8).div_ceil(5));
+    let (mut buffer, mut bits) = (0u16, 0);
+
+    for &byte in bytes {
+        buffer = buffer << 8 | byte as u16;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            output.push(ALPHABET[(buffer >> bits) as usize & 0x1f] as char);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+
+    if bits > 0 {
+        output.push(ALPHABET[(buffer << (5 - bits)) as usize & 0x1f] as char);
+    }
+
+    output
+}
+
+/// `encode` 的逆操作，末尾不足 8 bits 的填充位将被丢弃。
+fn decode(key: &str) -> Option<Vec<u8>> {
+    let mut output = Vec::with_capacity(key.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u16, 0);
+
+    for c in key.bytes().filter(|&c| c != b'-') {
+        let c = match c.to_ascii_uppercase() {
+            b'I' | b'L' => b'1',
+            b'O' => b'0',
+            c => c,
+        };
+
+        buffer = buffer << 5 | ALPHABET.iter().position(|&x| x == c)? as u16;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+
+    Some(output)
+}
+
+fn group(s: &str) -> String {
+    s.as_bytes()
+        .chunks(GROUP)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap())
+        .collect::<Vec<_>>()
+        .join("-")
+}
@@ -0,0 +1,67 @@
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+
+/// `LocalSerialer` 是 `Serialer` 在单线程运行时中的对应版本：`build` 返回的 `Future` 不要求 `Send`，适用于只在
+/// tokio 的 `LocalSet`、`futures::executor::LocalPool` 等单线程执行器中运行、构建过程需要持有 `Rc`、`RefCell`
+/// 或非 `Send` 的客户端的场合。
+///
+/// 除了对 `Send` 的约束之外，`LocalSerialer` 与 `Serialer` 的定义（每个实例构建出的序列号都应具有唯一性）相同。
+/// fastsend 内置的 `Serialer` 返回的 `Future` 都满足 `Send`，在单线程运行时中可以直接使用，不需要实现
+/// `LocalSerialer`。
+///
+/// ```
+/// use fastsend::LocalSerialer;
+/// use std::cell::Cell;
+/// use std::convert::Infallible;
+/// use std::future::Future;
+/// use std::pin::Pin;
+/// use std::rc::Rc;
+///
+/// /// 在单线程内共享计数器的序列号生成器。
+/// struct Counter(Rc<Cell<u64>>);
+///
+/// impl LocalSerialer for Counter {
+///     type Output = u64;
+///     type Error = Infallible;
+///
+///     fn build(self) -> Pin<Box<dyn Future<Output = Result<u64, Infallible>> + 'static>> {
+///         let counter = self.0;
+///         Box::pin(async move {
+///             counter.set(counter.get() + 1);
+///             Ok(counter.get())
+///         })
+///     }
+///
+///     fn feed(&mut self, _: &[u8]) {}
+/// }
+///
+/// let counter = Rc::new(Cell::new(0));
+/// let serial = futures::executor::block_on(Counter(Rc::clone(&counter)).build());
+/// assert_eq!(serial, Ok(1));
+/// ```
+pub trait LocalSerialer {
+    /// 见 `Serialer::Output`。
+    type Output: Display;
+
+    /// 见 `Serialer::Error`。
+    type Error;
+
+    /// 消耗自身构建出序列号，返回的 `Future` 只需要满足 `'static` 约束。
+    fn build(self) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + 'static>>;
+
+    /// 见 `Serialer::feed`。
+    fn feed(&mut self, data: &[u8]);
+
+    /// `feed` + `build` 的快捷方式。
+    fn oneshot(
+        mut self,
+        data: &[u8],
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + 'static>>
+    where
+        Self: Sized,
+    {
+        self.feed(data);
+        self.build()
+    }
+}
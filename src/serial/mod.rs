@@ -1,17 +1,28 @@
-use chrono::Local;
+use crossbeam::atomic::AtomicCell;
 use crossbeam::utils::Backoff;
 // 使用 `futures_locks` 的读写锁来提供对（`Serialer`）异步任务的支持
-use futures::executor;
+use chrono::{DateTime, Datelike, Local, Timelike, Utc};
 use futures_locks::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use lazy_static::lazy_static;
-use std::collections::hash_map::DefaultHasher;
+use smallvec::SmallVec;
 use std::collections::{HashMap, HashSet};
 use std::convert::Infallible;
-use std::fmt::{Display, Write};
+use std::fmt::{self, Display};
 use std::future::Future;
-use std::hash::{Hash, Hasher};
+use std::hash::{BuildHasher, Hasher};
 use std::pin::Pin;
-use std::thread;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+mod local;
+pub use local::LocalSerialer;
+
+mod binary;
+pub use binary::BinarySerialer;
+
+mod sip;
+pub use sip::{SipHasher13, StableState};
 
 /// `Serial` 类似于 `Hash` trait，消耗自身，将有关数据喂给 `Serialer`。
 pub trait Serial {
@@ -56,6 +67,34 @@ pub trait Serialer {
     /// `feed` 用于向 `Serialer` 提供用于生成序列号的必要信息，类似于 `Hasher` trait 中的 `write` 方法。
     fn feed(&mut self, data: &[u8]);
 
+    /// `feed_field` 以带标签的形式提供一个字段：`name` 为字段名，`data` 为字段的值。缺省实现将字段名与值的摘要
+    /// （`field_digest`，8 个字节）通过 `feed` 提供给 `Serialer`，`Serialer` 也可以覆盖该方法以自行处理标签。
+    ///
+    /// 与直接 `feed` 字段的值相比，每个字段的数据都与字段名绑定且彼此之间有明确的边界：结构体新增或重命名字段时
+    /// 序列号的变化是可预期的（新增的可选字段在缺省时不提供，已有数据的序列号保持不变），排查问题时也可以通过
+    /// `field_digest` 逐个核对各字段提供的数据：
+    ///
+    /// ```
+    /// use fastsend::{Serial, Serialer};
+    ///
+    /// struct Order {
+    ///     user_id: u64,
+    ///     coupon: Option<String>,
+    /// }
+    ///
+    /// impl Serial for Order {
+    ///     fn serial<S: Serialer>(self, serialer: &mut S) {
+    ///         serialer.feed_field("user_id", &self.user_id.to_be_bytes());
+    ///         if let Some(coupon) = &self.coupon {
+    ///             serialer.feed_field("coupon", coupon.as_bytes());
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn feed_field(&mut self, name: &str, data: &[u8]) {
+        self.feed(&field_digest(name, data).to_be_bytes());
+    }
+
     /// `oneshot` 用于直接根据提供的数据 `data` 构建序列号，是 'feed+build' 的快捷方式，省去了需要先调用
     /// `feed` 再调用 `build` 的麻烦，能更好地进行链式调用（当你已有一个 'Serialer' 实例时，可以直接调用
     /// `serialer.oneshot.await` 完成序列号构建）。
@@ -69,8 +108,110 @@ pub trait Serialer {
         data.serial(&mut self);
         self.build()
     }
+
+    /// `oneshot_many` 以 `self` 为原型，为 `items` 中的每一项克隆一个 `Serialer` 并分别构建序列号，按 `items` 的
+    /// 顺序返回各自的结果。整批构建只返回一个 `Future`；支持批量处理的 `Serialer`（如 `TimeSerialer`）会覆盖该方法，
+    /// 省去为每个序列号分配的 `Pin<Box<dyn Future>>`，不依赖外部系统的 `Serialer` 也可以使用
+    /// `SyncSerialer::oneshot_many_sync`。
+    ///
+    /// ```
+    /// use fastsend::{Serialer, TimeSerialer};
+    ///
+    /// # futures::executor::block_on(async {
+    /// let mut tokens = Vec::new();
+    /// for _ in 0..3 {
+    ///     tokens.push(fastsend::next_token().await);
+    /// }
+    ///
+    /// let serials = TimeSerialer::new().oneshot_many(tokens).await;
+    /// assert_eq!(serials.len(), 3);
+    /// # });
+    /// ```
+    fn oneshot_many<I>(
+        self,
+        items: I,
+    ) -> Pin<Box<dyn Future<Output = Vec<Result<Self::Output, Self::Error>>> + Send + 'static>>
+    where
+        Self: Clone + Send + Sized + 'static,
+        Self::Output: Send,
+        Self::Error: Send,
+        I: IntoIterator,
+        I::Item: Serial,
+    {
+        let serialers = prototype_many(self, items);
+        Box::pin(async move {
+            let mut outputs = Vec::with_capacity(serialers.len());
+            for serialer in serialers {
+                outputs.push(serialer.build().await);
+            }
+            outputs
+        })
+    }
+    /// `on_generate` 为当前 `Serialer` 添加生成钩子：构建成功时以 `GenerateEvent` 调用 `hook`，随后调用通过
+    /// `fastsend::on_generate` 注册的全局钩子（只需要全局钩子时 `hook` 可以为空函数）。
+    fn on_generate<F>(self, hook: F) -> crate::Hooked<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&crate::GenerateEvent<'_>) + Send + 'static,
+    {
+        crate::Hooked::new(self, hook)
+    }
+}
+
+/// `SyncSerialer` 表示不依赖外部系统、可以同步完成构建的 `Serialer`。`Serialer::build` 为了支持异步的外部系统
+/// 需要为每次构建分配一个 `Pin<Box<dyn Future>>`，对于高吞吐量的场合，可以直接调用 `build_sync` 获得序列号以避免
+/// 这次堆内存分配，也可以通过 `S: SyncSerialer` 约束编写不依赖异步运行时的泛型代码。
+///
+/// 实现了 `SyncSerialer` 的 `Serialer`，其 `build` 与 `build_sync` 生成的序列号应当遵循相同的规则。
+pub trait SyncSerialer: Serialer {
+    fn build_sync(self) -> Result<Self::Output, Self::Error>;
+
+    /// `Serialer::oneshot_many` 的同步版本，整批构建不需要分配任何 `Future`。
+    fn oneshot_many_sync<I>(self, items: I) -> Vec<Result<Self::Output, Self::Error>>
+    where
+        Self: Clone + Sized,
+        I: IntoIterator,
+        I::Item: Serial,
+    {
+        prototype_many(self, items)
+            .into_iter()
+            .map(SyncSerialer::build_sync)
+            .collect()
+    }
 }
 
+/// 字段 `name` 取值为 `data` 时的摘要，即 `Serialer::feed_field` 缺省实现提供的数据：以 `StableState` 依次写入
+/// 小端序 u64 表示的字段名长度、字段名、值的长度以及值本身，结果不随 Rust 版本及平台变化。
+pub fn field_digest(name: &str, data: &[u8]) -> u64 {
+    let mut hasher = StableState::new().build_hasher();
+    hasher.write(&(name.len() as u64).to_le_bytes());
+    hasher.write(name.as_bytes());
+    hasher.write(&(data.len() as u64).to_le_bytes());
+    hasher.write(data);
+    hasher.finish()
+}
+
+/// 以 `prototype` 为原型，为 `items` 中的每一项克隆一个 `Serialer` 并喂入该项的数据。
+fn prototype_many<S, I>(prototype: S, items: I) -> Vec<S>
+where
+    S: Serialer + Clone,
+    I: IntoIterator,
+    I::Item: Serial,
+{
+    items
+        .into_iter()
+        .map(|item| {
+            let mut serialer = prototype.clone();
+            item.serial(&mut serialer);
+            serialer
+        })
+        .collect()
+}
+
+/// `feed` 数据的缓冲区，通常 `feed` 的数据（如 `Token` 的 8 个字节）不超过 16 个字节，此时数据直接保存在
+/// `Serialer` 内部，不需要分配堆内存。
+pub(crate) type FeedBuffer = SmallVec<[u8; 16]>;
+
 /// `TimeSerialer` 是基于时间的序列号生成器，该序列号由纯数字组成，其特点在于可以从序列号一眼看出生成的 时间节
 /// 点（精确到秒）。
 ///
@@ -81,222 +222,451 @@ pub trait Serialer {
 /// `TimeSerialer` 具有对全局 slot 的定时清理功能，当 slot 存储的序列号超过一定阈值时会触发清理任务，将在额外的
 /// 线程完成对 slot 的清理，最早时间节点创建的序列号将从 slot 中丢弃，因为它们（指这些被丢弃的序列号）已经被证实不
 /// 会再次出现。
-#[derive(Debug)]
-pub struct TimeSerialer(Vec<u8>);
+///
+/// 缺省情况下所有 `TimeSerialer` 共用同一个全局 slot。彼此无关的序列号（如订单号与退款单号，分别保存且互不比较）
+/// 可以通过 `TimeSerialer::namespace` 或 `TimeSerialer::slot` 使用各自的 slot，避免在同一秒内互相争抢 9999 个后缀
+/// 以及清理时互相驱逐；使用不同 slot 的 `TimeSerialer` 之间不再判重，生成的序列号可能相同。
+///
+/// 序列号的后 4 位由 `feed` 的数据经过哈希得到，相同的数据得到相同的后 4 位。缺省使用固定密钥的 SipHash-1-3
+/// （`StableState`），其算法不会随 Rust 版本变化；需要其他哈希算法或密钥时可以通过 `TimeSerialer::with_hasher`
+/// 提供自定义的 `BuildHasher`，哈希器依次写入小端序 u64 表示的数据长度以及数据本身：
+///
+/// ```
+/// use fastsend::{Serialer, StableState, TimeSerialer};
+///
+/// # futures::executor::block_on(async {
+/// let mut serialer = TimeSerialer::with_hasher(StableState::with_keys(7, 11));
+/// serialer.feed(b"order-1");
+/// let serial = serialer.build().await.unwrap();
+/// assert_eq!(serial.len(), 21);
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeSerialer<S = StableState> {
+    data: FeedBuffer,
+
+    /// 计算后 4 位使用的哈希器。
+    hasher: S,
+
+    /// 用于判重的 slot，`None` 表示使用全局 slot。
+    slot: Option<TimeSlot>,
+}
 
 impl TimeSerialer {
     const GLOBAL_SLOT_SIZE: usize = 9999;
 
     pub fn new() -> Self {
-        TimeSerialer(Vec::with_capacity(8))
+        Self::with_hasher(StableState::new())
     }
 }
 
-impl Default for TimeSerialer {
-    fn default() -> Self {
-        Self::new()
+impl<S: BuildHasher> TimeSerialer<S> {
+    pub fn with_hasher(hasher: S) -> Self {
+        TimeSerialer {
+            data: FeedBuffer::new(),
+            hasher,
+            slot: None,
+        }
     }
-}
 
-impl Serialer for TimeSerialer {
-    type Output = String;
+    /// 使用名为 `name` 的 slot 判重，同名的 `TimeSerialer` 共用同一个 slot，见 `TimeSlot::named`。
+    pub fn namespace(mut self, name: &str) -> Self {
+        self.slot = Some(TimeSlot::named(name));
+        self
+    }
 
-    type Error = Infallible;
+    /// 使用指定的 slot 判重，持有同一个 `TimeSlot`（及其克隆）的 `TimeSerialer` 共用同一个 slot。
+    pub fn slot(mut self, slot: TimeSlot) -> Self {
+        self.slot = Some(slot);
+        self
+    }
 
-    fn build(
-        self,
-    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+    /// `build` 的无分配版本：将序列号追加写入 `output`，`output` 可以是复用的 `String`，也可以是栈上的定长字符串
+    /// 等任意实现了 `fmt::Write` 的类型。序列号固定为 21 位，`output` 写入失败（如容量不足）时返回错误，此时该
+    /// 序列号已被占用，不会再次生成。
+    pub async fn build_into<W: fmt::Write>(self, output: &mut W) -> fmt::Result {
+        let ident = self.ident();
+        TimeSerialer::next_serial(ident, self.slot)
+            .await
+            .write_to(output)
+    }
+
+    /// 序列号的后 4 位，由 `feed` 带来的字节序列经过哈希后对 10000 取模生成，为保证序列号尽可能短，碰撞的情况是
+    /// 不可避免的，但通常而言，一秒钟内生成 9999 个序列号已经能满足大部分场景的需求。
+    fn ident(&self) -> u32 {
+        // 数据长度固定以小端序 u64 写入，而非 `Hash for [u8]` 使用的平台相关的 usize，确保相同的 `feed`
+        // 在任何平台上都能得到相同的后 4 位。
+        let mut hasher = self.hasher.build_hasher();
+        hasher.write(&(self.data.len() as u64).to_le_bytes());
+        hasher.write(&self.data);
+        let sum = hasher.finish();
+        ((sum ^ (sum >> 32)) % 10000) as u32
+    }
+}
+
+impl TimeSerialer {
+    /// 以后 4 位 `ident` 生成下一个在 `slot`（`None` 表示全局 slot）中不重复的序列号。
+    async fn next_serial(ident: u32, mut slot: Option<TimeSlot>) -> TimeSerial {
         lazy_static! {
             /// 全局 `SLOT` 容器，用于存储在一定时间段内生成的序列号，用于判断是否重复。
-            static ref SLOT: RwLock<HashMap<String, i64>> = {
-                // 使用 `Cursor` 来保证在程序短时间内多次重启时，生成的序列号能保证唯一性。
+            static ref SLOT: TimeSlot = {
+                // 使用 `Cursor` 来保证在程序短时间内多次重启时，生成的序列号能保证唯一性（序列号本身并不依赖
+                // `Cursor`，因此系统时间异常导致获取 `Cursor` 失败时跳过停顿，而不是 panic）。
                 #[allow(unused)]
                 #[cfg(feature = "pause_on_start")]
-                let cursor = crate::Cursor::new().next();
+                let cursor = crate::Cursor::try_new().and_then(crate::Cursor::try_next);
 
-                RwLock::new(HashMap::with_capacity(TimeSerialer::GLOBAL_SLOT_SIZE))
+                TimeSlot::new()
             };
         }
 
         let backoff = Backoff::new();
 
-        Box::pin(async move {
-            loop {
-                // 时间不仅要用来构建序列号，还需要用来定位序列号生成的时间，用于定时清空全局 HashMap 的元素
-                let now = Local::now();
-
-                // 使用填充法构建序列号
-                let serial = {
-                    // 预留 14+3+4=21 的空间用于填充序列号，`TimeSerialer` 生成长度为 21 的纯数字序列号。
-                    // "XXXXXXXXXXXXXXXXXXXXX"
-                    let mut buffer = String::with_capacity(14 + 3 + 4);
-
-                    // 序列号的前 14 位，由精确到秒的具有人类可读性的时间序列组成，其格式类似于 '20211209113031'。
-                    buffer
-                        .write_fmt(format_args!("{:014}", now.format("%Y%m%d%H%M%S")))
-                        .expect("error writing datetime into string buffer");
-
-                    // 序列号的中间 3 位，由设备 ID 决定，设备 ID 源于环境变量 `FASTSEND_DEVICE_ID`，如果未提供
-                    // 环境变量，则使用随机生成的 u8 整数（8-bit）值（在单设备环境下，可以更好地减少序列号碰撞）。
-                    buffer
-                        .write_fmt(format_args!(
-                            "{:03}",
-                            crate::DEVICE_ID.unwrap_or_else(rand::random)
-                        ))
-                        .expect("error writing first byte(u8) into string buffer");
-
-                    // 序列号的后 5 位，由 `feed` 带来的字节序列经过哈希后对 10000 取模生成，为保证序列号尽可能短，
-                    // 碰撞的情况是不可避免的，但通常而言，一秒钟内生成 9999 个序列号已经能满足大部分场景的需求。
-                    let ident = {
-                        // 直接构造 `DefaultHasher` 而非使用 `RandomState` 是为了确保相同的 `feed` 能产生相同
-                        // 的哈希值，进而确保 `serial` 的后 4 位能保持一致。
-                        let mut hasher = DefaultHasher::new();
-                        self.0.hash(&mut hasher);
-                        let sum = hasher.finish();
-                        (sum ^ (sum >> 32)) % 10000
-                    };
-
-                    buffer
-                        .write_fmt(format_args!("{:04}", ident))
-                        .expect("error writing bytes(u16) into string buffer");
-
-                    buffer
-                };
-
-                // 优先使用 `read-lock` 来判断序列号是否重复，如果重复，则在 `snooze` 后重新获取序列号，在序列号
-                // 冲突的时间点内（秒），使用 `read-lock` 能在很大程度上提升性能。
-                {
-                    let locked_slot: RwLockReadGuard<HashMap<String, i64>> =
-                        RwLock::read(&*SLOT).await;
+        loop {
+            // 时间不仅要用来构建序列号，还需要用来定位序列号生成的时间，用于定时清空全局 HashMap 的元素
+            // （序列号的前 14 位，由精确到秒的具有人类可读性的时间序列组成，其格式类似于 '20211209113031'，
+            // 同一秒内的前缀相同，因此按秒缓存，见 `TimePrefix`）
+            let prefix = TimePrefix::of(crate::clock::now());
+
+            // 以数值的形式构建序列号的后 7 位，全局 slot 中保存的是整个序列号对应的数值，避免了为每个序列号
+            // 分配 `String`。
+            let suffix = {
+                // 序列号的中间 3 位，由设备 ID 决定，设备 ID 源于环境变量 `FASTSEND_DEVICE_ID`，如果未提供
+                // 环境变量，则使用随机生成的 u8 整数（8-bit）值（在单设备环境下，可以更好地减少序列号碰撞）。
+                let device = crate::DEVICE_ID.unwrap_or_else(|| {
+                    crate::fallback::warn(crate::fallback::Fallback::SerialDevice);
+                    rand::random()
+                });
+                let suffix = device as u32 * 10000;
+
+                // 序列号的后 4 位，见 `TimeSerialer::ident`
+                suffix + ident
+            };
+            let serial = TimeSerial { prefix, suffix };
+
+            // 在首次判重时初始化全局 `SLOT`，使用独立的 slot 时同样需要完成初始化时的停顿
+            lazy_static::initialize(&SLOT);
+            let slot = slot.get_or_insert_with(|| SLOT.clone());
 
-                    if locked_slot.contains_key(&serial) {
+            // 优先使用 `read-lock` 来判断序列号是否重复，如果重复，则在 `snooze` 后重新获取序列号，在序列号
+            // 冲突的时间点内（秒），使用 `read-lock` 能在很大程度上提升性能。
+            {
+                let locked_slot: RwLockReadGuard<HashMap<u128, i64>> =
+                    RwLock::read(&slot.serials).await;
+
+                if locked_slot.contains_key(&serial.key()) {
+                    // 使用虚拟时间时直接推进到下一秒，否则在同一秒内会一直重复
+                    if !crate::clock::advance(Duration::from_secs(1)) {
                         backoff.snooze();
-                        continue;
                     }
+                    continue;
                 }
+            }
 
-                // 当前序列号是唯一序列号，此时需要把序列号保存到全局 `HashMap` 中用于判断唯一性，如果全局 `HashMap`
-                // 容量已经超过单秒内所能产生的所有序列号（9999 个），则需要对 `HashMap` 进行清理。
-                {
-                    let mut locked_slot_mut: RwLockWriteGuard<HashMap<String, i64>> =
-                        RwLock::write(&*SLOT).await;
+            // 当前序列号是唯一序列号，此时需要把序列号保存到全局 `HashMap` 中用于判断唯一性，如果全局 `HashMap`
+            // 容量已经超过单秒内所能产生的所有序列号（9999 个），则需要对 `HashMap` 进行清理。
+            {
+                let mut locked_slot_mut: RwLockWriteGuard<HashMap<u128, i64>> =
+                    RwLock::write(&slot.serials).await;
 
-                    // 双锁判断，确保在读写锁之间出现序列号冲突的情况
-                    if locked_slot_mut.contains_key(&serial) {
+                // 双锁判断，确保在读写锁之间出现序列号冲突的情况
+                if locked_slot_mut.contains_key(&serial.key()) {
+                    if !crate::clock::advance(Duration::from_secs(1)) {
                         backoff.snooze();
-                        continue;
-                    }
-
-                    // 在将序列号保存到全局 `HashMap` 时，需要同时保存时间戳（作为 value）用于后续清理时判断该序列号
-                    // 是否需要被清理。
-                    locked_slot_mut.insert(serial.clone(), now.timestamp());
-
-                    // 当 slot 的容量超过 `GLOBAL_SLOT_SIZE` 时，开始清理工作
-                    if locked_slot_mut.len() > TimeSerialer::GLOBAL_SLOT_SIZE {
-                        // 新起一个线程来执行清理任务，以便能快速返回生成的序列号，减少阻塞时间
-                        thread::spawn(|| {
-                            // 由于是在新的线程中完成对 slot 的清理，因此使用 `block_on` 方法阻塞式地执行
-                            // `Future` 并不会影响全局异步任务（Runtime）的进行。
-                            executor::block_on(async move {
-                                // 在新的线程执行异步任务，需要重新获取 `locked_slot_mut` 来执行清理动作
-                                let mut locked_slot_mut: RwLockWriteGuard<HashMap<String, i64>> =
-                                    RwLock::write(&*SLOT).await;
-
-                                // `sorted_list` 是用于判断哪个时间点前的序列号需要被清理的一个辅助工具，
-                                // 通过取出 slot 中所有的时间戳构成。
-                                let sorted_list = {
-                                    let mut list = locked_slot_mut
-                                        // 取出所有的时间戳
-                                        .values()
-                                        .copied()
-                                        // 将时间戳去重
-                                        .collect::<HashSet<i64>>()
-                                        .into_iter()
-                                        // 最后构造成 list
-                                        .collect::<Vec<i64>>();
-
-                                    // 对 list 进行排序，在这种无关排序稳定性的情况下，使用 `sort_unstable`
-                                    // 比使用 `sort` 要快不少（来自 cargo-clippy 的指点）。
-                                    list.sort_unstable();
-                                    list
-                                };
-
-                                // 当且仅当 list 的元素数量大于 1 时（list 已经去重）才进行 slot 清理，当
-                                // list 中的元素数量小于等于 1 时，进行清理会将 slot 中的所有元素都删除，
-                                // 这会导致重复判定机制失效。
-                                if sorted_list.len() > 1 {
-                                    // `mid` 代表 `HashMap` 中所有时间戳的中位数，它应至少是 `sorted_list`
-                                    // 中的第二个元素，所有小于 `mid` 时间戳的序列号均应被删除，因为当前时间已
-                                    // 经大于该时间戳，新生成的序列号永远不会与 `mid` 时间戳之前生成的序列号重
-                                    // 复。
-                                    //
-                                    // （其实从原理上来讲，`mid` 完全可以使用 `sorted_list` 的最后一个元素，
-                                    // 但此处使用 `sorted_list` 长度的一半作为索引获取 `mid`，是处于性能考
-                                    // 虑，一次性删除过多的元素会导致长时间的阻塞，因此此处试图减少删除的元素来
-                                    // 降低锁阻塞的时间。）
-                                    let mid = sorted_list[sorted_list.len() / 2];
-
-                                    // 将小于 `mid` 时间戳的序列号从 slot 中删除，并用新生成的 `HashMap`
-                                    // 代替原来的 slot
-                                    *locked_slot_mut = locked_slot_mut
-                                        .iter()
-                                        // `filter` 出大于等于 `mid` 的序列号留下，其余小于 `mid` 的序列号
-                                        // 通通丢弃
-                                        .filter(|(_, t)| **t >= mid)
-                                        .map(|(s, t)| (s.clone(), *t))
-                                        .collect();
-                                }
-                            })
-                        });
                     }
+                    continue;
                 }
 
-                return Ok(serial);
+                // 在将序列号保存到全局 `HashMap` 时，需要同时保存时间戳（作为 value）用于后续清理时判断该序列号
+                // 是否需要被清理。
+                locked_slot_mut.insert(serial.key(), prefix.second);
+
+                // 当 slot 的容量超过 `GLOBAL_SLOT_SIZE` 时，开始清理工作
+                // （同一个 slot 同时只安排一次清理）
+                if locked_slot_mut.len() > TimeSerialer::GLOBAL_SLOT_SIZE
+                    && !slot.cleaning.swap(true, Ordering::AcqRel)
+                {
+                    // 在维护任务中执行清理（见 `fastsend::register_job`），以便能快速返回生成的序列号，减少阻塞时间
+                    let slot = slot.clone();
+                    crate::maintenance::defer("time slot cleanup", async move {
+                        {
+                            // 在维护任务中执行，需要重新获取 `locked_slot_mut` 来执行清理动作
+                            let mut locked_slot_mut: RwLockWriteGuard<HashMap<u128, i64>> =
+                                RwLock::write(&slot.serials).await;
+
+                            // `sorted_list` 是用于判断哪个时间点前的序列号需要被清理的一个辅助工具，
+                            // 通过取出 slot 中所有的时间戳构成。
+                            let sorted_list = {
+                                let mut list = locked_slot_mut
+                                    // 取出所有的时间戳
+                                    .values()
+                                    .copied()
+                                    // 将时间戳去重
+                                    .collect::<HashSet<i64>>()
+                                    .into_iter()
+                                    // 最后构造成 list
+                                    .collect::<Vec<i64>>();
+
+                                // 对 list 进行排序，在这种无关排序稳定性的情况下，使用 `sort_unstable`
+                                // 比使用 `sort` 要快不少（来自 cargo-clippy 的指点）。
+                                list.sort_unstable();
+                                list
+                            };
+
+                            // 当且仅当 list 的元素数量大于 1 时（list 已经去重）才进行 slot 清理，当
+                            // list 中的元素数量小于等于 1 时，进行清理会将 slot 中的所有元素都删除，
+                            // 这会导致重复判定机制失效。
+                            if sorted_list.len() > 1 {
+                                // `mid` 代表 `HashMap` 中所有时间戳的中位数，它应至少是 `sorted_list`
+                                // 中的第二个元素，所有小于 `mid` 时间戳的序列号均应被删除，因为当前时间已
+                                // 经大于该时间戳，新生成的序列号永远不会与 `mid` 时间戳之前生成的序列号重
+                                // 复。
+                                //
+                                // （其实从原理上来讲，`mid` 完全可以使用 `sorted_list` 的最后一个元素，
+                                // 但此处使用 `sorted_list` 长度的一半作为索引获取 `mid`，是处于性能考
+                                // 虑，一次性删除过多的元素会导致长时间的阻塞，因此此处试图减少删除的元素来
+                                // 降低锁阻塞的时间。）
+                                let mid = sorted_list[sorted_list.len() / 2];
+
+                                // 将小于 `mid` 时间戳的序列号从 slot 中删除，并用新生成的 `HashMap`
+                                // 代替原来的 slot
+                                *locked_slot_mut = locked_slot_mut
+                                    .iter()
+                                    // `filter` 出大于等于 `mid` 的序列号留下，其余小于 `mid` 的序列号
+                                    // 通通丢弃
+                                    .filter(|(_, t)| **t >= mid)
+                                    .map(|(s, t)| (*s, *t))
+                                    .collect();
+                            }
+                        }
+
+                        slot.cleaning.store(false, Ordering::Release);
+                    });
+                }
             }
-        })
+
+            return serial;
+        }
     }
+}
 
-    fn feed(&mut self, data: &[u8]) {
-        self.0.extend_from_slice(data);
+/// `TimeSlot` 是 `TimeSerialer` 用于判重的 slot，保存最近生成的序列号，克隆得到的 `TimeSlot` 与原来的 `TimeSlot`
+/// 共用同一个 slot：
+///
+/// ```
+/// use fastsend::{Serialer, TimeSerialer, TimeSlot};
+///
+/// # futures::executor::block_on(async {
+/// let refunds = TimeSlot::new();
+/// let serial = TimeSerialer::new().slot(refunds.clone()).build().await.unwrap();
+/// assert_eq!(serial.len(), 21);
+///
+/// // 同名的 slot 在进程内只有一个
+/// let orders = TimeSerialer::new().namespace("orders").build().await.unwrap();
+/// assert_eq!(orders.len(), 21);
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct TimeSlot {
+    /// 最近生成的序列号及其生成时间（秒级 Unix 时间戳）。
+    serials: RwLock<HashMap<u128, i64>>,
+
+    /// 是否已经安排了清理。
+    cleaning: Arc<AtomicBool>,
+}
+
+impl TimeSlot {
+    /// 创建一个新的、不与其他 `TimeSerialer` 共用的 slot。
+    pub fn new() -> TimeSlot {
+        TimeSlot {
+            serials: RwLock::new(HashMap::with_capacity(TimeSerialer::GLOBAL_SLOT_SIZE)),
+            cleaning: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// 名为 `name` 的 slot，首次使用时创建，之后同名的调用返回同一个 slot（不会被释放）。
+    pub fn named(name: &str) -> TimeSlot {
+        lazy_static! {
+            static ref NAMESPACES: std::sync::Mutex<HashMap<String, TimeSlot>> =
+                std::sync::Mutex::new(HashMap::new());
+        }
+
+        let mut namespaces = NAMESPACES.lock().unwrap_or_else(|error| error.into_inner());
+        namespaces.entry(name.to_owned()).or_default().clone()
     }
 }
 
-fn to_string_radix(mut n: usize, radix: usize, size: usize, digit_first: bool) -> String {
-    assert!(radix >= 2 && radix <= 36);
-
-    let bytes_table: [char; 36] = if digit_first {
-        [
-            '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
-            'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X',
-            'Y', 'Z',
-        ]
-    } else {
-        [
-            'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q',
-            'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '0', '1', '2', '3', '4', '5', '6', '7',
-            '8', '9',
-        ]
-    };
-
-    let mut buf = String::with_capacity(2);
-
-    loop {
-        let m = n % radix;
-        n = n / radix;
-        buf.push(bytes_table[m]);
-        if n == 0 {
-            break;
+impl Default for TimeSlot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for TimeSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimeSlot").finish_non_exhaustive()
+    }
+}
+
+/// `TimeSerialer` 序列号的时间前缀（本地时间 '%Y%m%d%H%M%S'），同一秒内生成的序列号共享同一个前缀，因此
+/// 全局缓存最近一秒的前缀，只有在进入新的一秒时才需要重新进行时区换算及格式化。
+#[derive(Debug, Copy, Clone)]
+struct TimePrefix {
+    /// 前缀对应的秒级 Unix 时间戳。
+    second: i64,
+
+    /// 前缀的数值形式。
+    value: u64,
+
+    /// 前缀的字符串形式（14 个 ASCII 数字）。
+    rendered: [u8; 14],
+}
+
+impl TimePrefix {
+    fn of(now: SystemTime) -> TimePrefix {
+        // `AtomicCell` 在不支持 40 字节原子操作的平台上使用 seqlock，读取依然不需要加锁
+        static CACHE: AtomicCell<TimePrefix> = AtomicCell::new(TimePrefix {
+            second: i64::MIN,
+            value: 0,
+            rendered: [b'0'; 14],
+        });
+
+        let second = DateTime::<Utc>::from(now).timestamp();
+        let cached = CACHE.load();
+        if cached.second == second {
+            return cached;
+        }
+
+        // 直接使用各个整数字段，避免了 `DateTime::format` 可能产生的格式化错误
+        let local = DateTime::<Local>::from(now);
+        let mut value = local.year() as u64;
+        for field in [
+            local.month(),
+            local.day(),
+            local.hour(),
+            local.minute(),
+            local.second(),
+        ] {
+            value = value * 100 + field as u64;
         }
+
+        let mut rendered = [b'0'; 14];
+        let mut n = value;
+        for digit in rendered.iter_mut().rev() {
+            *digit = b'0' + (n % 10) as u8;
+            n /= 10;
+        }
+
+        let prefix = TimePrefix {
+            second,
+            value,
+            rendered,
+        };
+        CACHE.store(prefix);
+        prefix
+    }
+}
+
+/// `TimeSerialer` 生成的序列号：14 位时间前缀以及由 3 位设备号和 4 位 feed 哈希组成的 7 位后缀。
+struct TimeSerial {
+    prefix: TimePrefix,
+    suffix: u32,
+}
+
+impl TimeSerial {
+    /// 整个序列号对应的数值，用作全局 slot 的键。
+    fn key(&self) -> u128 {
+        self.prefix.value as u128 * 10_000_000 + self.suffix as u128
     }
 
-    use std::cmp;
-    (0..(size - cmp::min(size, buf.len()))).for_each(|_| buf.push(bytes_table[0]));
+    fn write_to<W: fmt::Write>(&self, output: &mut W) -> fmt::Result {
+        // `rendered` 只包含 ASCII 数字
+        let prefix = std::str::from_utf8(&self.prefix.rendered).map_err(|_| fmt::Error)?;
+        output.write_str(prefix)?;
+        write!(output, "{:07}", self.suffix)
+    }
+}
 
-    unsafe {
-        buf.as_mut_vec().reverse();
+impl Display for TimeSerial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_to(f)
     }
+}
 
-    buf
+impl Default for TimeSerialer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: BuildHasher + Clone> Serialer for TimeSerialer<S> {
+    type Output = String;
+
+    type Error = Infallible;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        let ident = self.ident();
+        let slot = self.slot;
+        Box::pin(async move { Ok(TimeSerialer::next_serial(ident, slot).await.to_string()) })
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.data.extend_from_slice(data);
+    }
+
+    /// 整批序列号在同一个 `Future` 中依次生成，省去了逐个构建时为每个序列号分配的 `Future`。
+    fn oneshot_many<I>(
+        self,
+        items: I,
+    ) -> Pin<Box<dyn Future<Output = Vec<Result<Self::Output, Self::Error>>> + Send + 'static>>
+    where
+        I: IntoIterator,
+        I::Item: Serial,
+    {
+        let slot = self.slot.clone();
+        let idents = prototype_many(self, items)
+            .iter()
+            .map(TimeSerialer::ident)
+            .collect::<Vec<_>>();
+
+        Box::pin(async move {
+            let mut outputs = Vec::with_capacity(idents.len());
+            for ident in idents {
+                let serial = TimeSerialer::next_serial(ident, slot.clone()).await;
+                outputs.push(Ok(serial.to_string()));
+            }
+            outputs
+        })
+    }
+}
+
+/// 使用 Luhn mod N 算法计算校验位，`alphabet` 中字符的下标即为该字符代表的数值，N 为 `alphabet` 的长度，
+/// `payload` 中的所有字符都必须在 `alphabet` 中（当 `alphabet` 为 '0'-'9' 时即为标准的 Luhn 算法）。
+#[cfg(any(feature = "coupon", feature = "gift_card", feature = "order"))]
+fn luhn_check_char(payload: &str, alphabet: &[u8]) -> Option<char> {
+    let n = alphabet.len();
+    let sum = luhn_sum(payload, alphabet, 2)?;
+    Some(alphabet[(n - sum % n) % n] as char)
+}
+
+/// 校验以 Luhn mod N 校验位结尾的字符串。
+#[cfg(any(feature = "coupon", feature = "gift_card"))]
+fn luhn_validate(code: &str, alphabet: &[u8]) -> bool {
+    luhn_sum(code, alphabet, 1).is_some_and(|sum| sum % alphabet.len() == 0)
+}
+
+/// Luhn mod N 的核心计算：从最右侧字符开始，交替乘以 `factor` 和 1（计算校验位时从 2 开始，校验时从 1 开始），
+/// 并将乘积按 N 进制的各位数字求和。
+#[cfg(any(feature = "coupon", feature = "gift_card", feature = "order"))]
+fn luhn_sum(code: &str, alphabet: &[u8], mut factor: usize) -> Option<usize> {
+    let n = alphabet.len();
+    code.bytes().rev().try_fold(0, |sum, c| {
+        let addend = factor * alphabet.iter().position(|&x| x == c)?;
+        factor = if factor == 2 { 1 } else { 2 };
+        Some(sum + addend / n + addend % n)
+    })
 }
 
 #[cfg(feature = "ticket")]
@@ -310,3 +680,54 @@ pub mod auto_increment;
 
 #[cfg(feature = "random62")]
 pub mod random62;
+
+#[cfg(feature = "cuid2")]
+pub mod cuid2;
+
+#[cfg(feature = "typeid")]
+pub mod typeid;
+
+#[cfg(feature = "push_id")]
+pub mod push_id;
+
+#[cfg(feature = "sqids")]
+pub mod sqids;
+
+#[cfg(feature = "fpe")]
+pub mod fpe;
+
+#[cfg(feature = "pronounce")]
+pub mod pronounce;
+
+#[cfg(feature = "coupon")]
+pub mod coupon;
+
+#[cfg(feature = "gift_card")]
+pub mod gift_card;
+
+#[cfg(feature = "invoice")]
+pub mod invoice;
+
+#[cfg(feature = "order")]
+pub mod order;
+
+#[cfg(feature = "gs1")]
+pub mod gs1;
+
+#[cfg(feature = "license")]
+pub mod license;
+
+#[cfg(feature = "crypto")]
+pub mod signed;
+
+#[cfg(feature = "expiring")]
+pub mod expiring;
+
+#[cfg(feature = "slug")]
+pub mod slug;
+
+#[cfg(feature = "flake128")]
+pub mod flake128;
+
+#[cfg(feature = "ordered_key")]
+pub mod ordered_key;
@@ -0,0 +1,137 @@
+use crate::{Cursor, Serialer, SyncSerialer};
+use std::convert::Infallible;
+use std::fs::{self, File};
+use std::future::{self, Future};
+use std::io::{self, Write};
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+/// Crockford base32 字符表（大写），按 ASCII 顺序排列，确保定长编码后的字典序与数值大小一致。
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// 96 bits 补齐为 100 bits 后，每 5 bits 编码为一个字符。
+const LENGTH: usize = 20;
+
+/// ## 有序字符串键
+///
+/// `OrderedKeys` 生成适用于 RocksDB、FoundationDB 等有序存储的定长字符串键，由高到低依次为：
+///
+/// |  纪元  | 游标（`Cursor`） | 序列号 |
+/// |:-----:|:--------------:|:-----:|
+/// | 32 bits |    32 bits    | 32 bits |
+///
+/// 96 bits 以大端序编码为 20 位 Crockford base32 字符串。
+///
+/// # 全序保证
+///
+/// 对于同一个纪元存储（`open` 使用的文件）而言，后生成的键的字典序一定大于先生成的键，即使程序发生了重启或时钟回拨：
+///
+///   1. 纪元在每次 `open` 时持久化地 +1，因此重启后生成的键一定排在重启前生成的键之后；
+///   2. 同一纪元内，`Cursor` 基于单调时钟计算，不会因为系统时钟回拨而倒退；
+///   3. 同一纪元、同一游标内，序列号严格递增，序列号耗尽时向后借用一个游标。
+///
+/// 以上保证要求同一时刻只有一个 `OrderedKeys` 实例使用同一个纪元存储，多个进程共享同一个纪元文件将破坏全序保证。
+#[derive(Debug, Clone)]
+pub struct OrderedKeys {
+    epoch: u32,
+
+    /// 上一次生成键时的游标及序列号。
+    last: Arc<Mutex<(u32, u32)>>,
+}
+
+impl OrderedKeys {
+    /// 从文件 `path` 中读取纪元并持久化地 +1，文件不存在时从纪元 0 开始。纪元文件以「写入临时文件后重命名」的方式
+    /// 原子更新。
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<OrderedKeys> {
+        let path = path.as_ref();
+
+        let epoch = match fs::read_to_string(path) {
+            Ok(content) => content
+                .trim()
+                .parse::<u32>()
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?
+                .checked_add(1)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "epoch overflows"))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => 0,
+            Err(error) => return Err(error),
+        };
+
+        let tmp = path.with_extension("tmp");
+        {
+            let mut file = File::create(&tmp)?;
+            write!(file, "{}", epoch)?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp, path)?;
+
+        Ok(Self::with_epoch(epoch))
+    }
+
+    /// 使用调用方自行持久化的纪元构建 `OrderedKeys`，调用方需保证每次启动时的纪元严格大于上一次启动时的纪元。
+    pub fn with_epoch(epoch: u32) -> OrderedKeys {
+        OrderedKeys {
+            epoch,
+            last: Arc::new(Mutex::new((0, 0))),
+        }
+    }
+
+    pub fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    /// 生成下一个键。
+    pub fn next_key(&self) -> String {
+        let cursor = Cursor::new().into_inner();
+
+        let (cursor, sequence) = {
+            let mut last = self.last.lock().unwrap();
+
+            *last = if cursor > last.0 {
+                (cursor, 0)
+            } else if last.1 == u32::MAX {
+                (last.0 + 1, 0)
+            } else {
+                (last.0, last.1 + 1)
+            };
+
+            *last
+        };
+
+        let n = (self.epoch as u128) << 64 | (cursor as u128) << 32 | sequence as u128;
+        (0..LENGTH)
+            .map(|i| ALPHABET[((n >> (5 * (LENGTH - 1 - i))) & 0x1f) as usize] as char)
+            .collect()
+    }
+
+    pub fn serialer(&self) -> OrderedKeySerialer {
+        OrderedKeySerialer { keys: self.clone() }
+    }
+}
+
+/// `OrderedKeySerialer` 是 `OrderedKeys` 的 `Serialer` 形式，`feed` 提供的数据将被忽略。
+#[derive(Debug)]
+pub struct OrderedKeySerialer {
+    keys: OrderedKeys,
+}
+
+impl Serialer for OrderedKeySerialer {
+    type Output = String;
+
+    type Error = Infallible;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        Box::pin(future::ready(self.build_sync()))
+    }
+
+    fn feed(&mut self, _: &[u8]) {}
+}
+
+impl SyncSerialer for OrderedKeySerialer {
+    fn build_sync(self) -> Result<Self::Output, Self::Error> {
+        let output = self.keys.next_key();
+        Ok(output)
+    }
+}
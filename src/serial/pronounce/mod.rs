@@ -0,0 +1,170 @@
+use crate::{Serialer, SyncSerialer};
+use lazy_static::lazy_static;
+use std::convert::{Infallible, TryInto};
+use std::future::{self, Future};
+use std::pin::Pin;
+
+/// proquint 中代表 4 bits 的 16 个辅音字母。
+const CONSONANTS: &[u8; 16] = b"bdfghjklmnprstvz";
+
+/// proquint 中代表 2 bits 的 4 个元音字母。
+const VOWELS: &[u8; 4] = b"aiou";
+
+lazy_static! {
+    /// 内置词表，共 256 个易于口述、互不相同的英文单词，每个单词代表一个字节。
+    static ref WORDS: Vec<&'static str> = include_str!("words.txt").lines().collect();
+}
+
+/// ## 可读 ID
+///
+/// `Pronounce` 用于将数值 ID 渲染为便于口述的形式，适用于客服在电话中核对 ID 的场景（随机的 base62 字符串在
+/// 口述时非常痛苦）。目前提供两种风格，两种风格均使用 '-' 连接各部分，并且都可以无损地解码回原始数值：
+///
+/// - `Proquint`：每 16 bits 渲染为一个「辅音-元音-辅音-元音-辅音」结构的五字母单元，如 'lusab-babad'；
+/// - `Words`：每 8 bits 渲染为内置词表中的一个单词，如 'otter-castle-maple'。
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Pronounce {
+    Proquint,
+    Words,
+}
+
+impl Pronounce {
+    /// 构建当前风格的 `PronounceSerialer`。
+    pub fn serialer(self) -> PronounceSerialer {
+        PronounceSerialer {
+            style: self,
+            data: Vec::with_capacity(8),
+        }
+    }
+
+    /// 按大端序渲染字节序列，`Proquint` 风格下奇数长度的字节序列会在头部补齐一个 0 字节。
+    pub fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            Pronounce::Proquint => {
+                let padded;
+                let bytes = if bytes.len() % 2 == 1 {
+                    padded = [&[0], bytes].concat();
+                    &padded[..]
+                } else {
+                    bytes
+                };
+
+                bytes
+                    .chunks(2)
+                    .map(|chunk| to_quint(u16::from_be_bytes([chunk[0], chunk[1]])))
+                    .collect::<Vec<_>>()
+                    .join("-")
+            }
+            Pronounce::Words => bytes
+                .iter()
+                .map(|&b| WORDS[b as usize])
+                .collect::<Vec<_>>()
+                .join("-"),
+        }
+    }
+
+    /// `encode` 的逆操作，无法识别的输入返回 `None`，解码时忽略大小写。
+    pub fn decode(self, s: &str) -> Option<Vec<u8>> {
+        let s = s.to_ascii_lowercase();
+        match self {
+            Pronounce::Proquint => s.split('-').try_fold(Vec::new(), |mut bytes, quint| {
+                bytes.extend_from_slice(&from_quint(quint)?.to_be_bytes());
+                Some(bytes)
+            }),
+            Pronounce::Words => s
+                .split('-')
+                .map(|word| WORDS.binary_search(&word).ok().map(|index| index as u8))
+                .collect(),
+        }
+    }
+
+    pub fn encode_u64(self, n: u64) -> String {
+        self.encode(&n.to_be_bytes())
+    }
+
+    pub fn decode_u64(self, s: &str) -> Option<u64> {
+        Some(u64::from_be_bytes(pad(self.decode(s)?)?))
+    }
+
+    pub fn encode_u128(self, n: u128) -> String {
+        self.encode(&n.to_be_bytes())
+    }
+
+    pub fn decode_u128(self, s: &str) -> Option<u128> {
+        Some(u128::from_be_bytes(pad(self.decode(s)?)?))
+    }
+}
+
+/// 将解码得到的字节序列在头部补零至 N 个字节，超过 N 个字节的非零数据视为溢出。
+fn pad<const N: usize>(bytes: Vec<u8>) -> Option<[u8; N]> {
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    let significant = &bytes[start..];
+    if significant.len() > N {
+        return None;
+    }
+
+    let mut padded = vec![0; N - significant.len()];
+    padded.extend_from_slice(significant);
+    padded.try_into().ok()
+}
+
+fn to_quint(n: u16) -> String {
+    let n = n as usize;
+    [
+        CONSONANTS[(n >> 12) & 0x0f],
+        VOWELS[(n >> 10) & 0x03],
+        CONSONANTS[(n >> 6) & 0x0f],
+        VOWELS[(n >> 4) & 0x03],
+        CONSONANTS[n & 0x0f],
+    ]
+    .iter()
+    .map(|&c| c as char)
+    .collect()
+}
+
+fn from_quint(quint: &str) -> Option<u16> {
+    if quint.len() != 5 {
+        return None;
+    }
+
+    quint.bytes().enumerate().try_fold(0u16, |n, (i, c)| {
+        if i % 2 == 0 {
+            let value = CONSONANTS.iter().position(|&x| x == c)? as u16;
+            Some(n << 4 | value)
+        } else {
+            let value = VOWELS.iter().position(|&x| x == c)? as u16;
+            Some(n << 2 | value)
+        }
+    })
+}
+
+/// `PronounceSerialer` 将 `feed` 提供的字节序列按 `Pronounce` 的风格渲染为序列号，对于 `Token` 而言，解码
+/// 后的数值即为 `Token::id` 的值（可使用 `Pronounce::decode_u64` 还原）。
+#[derive(Debug)]
+pub struct PronounceSerialer {
+    style: Pronounce,
+    data: Vec<u8>,
+}
+
+impl Serialer for PronounceSerialer {
+    type Output = String;
+
+    type Error = Infallible;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        Box::pin(future::ready(self.build_sync()))
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.data.extend_from_slice(data);
+    }
+}
+
+impl SyncSerialer for PronounceSerialer {
+    fn build_sync(self) -> Result<Self::Output, Self::Error> {
+        let output = self.style.encode(&self.data);
+        Ok(output)
+    }
+}
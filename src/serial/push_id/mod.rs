@@ -0,0 +1,90 @@
+use crate::{Serialer, SyncSerialer};
+use lazy_static::lazy_static;
+use rand::Rng;
+use std::convert::Infallible;
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Firebase push ID 所使用的 64 个字符，按 ASCII 顺序排列，确保生成的序列号按字典序排列即按时间排列。
+const PUSH_CHARS: &[u8; 64] = b"-0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ_abcdefghijklmnopqrstuvwxyz";
+
+lazy_static! {
+    /// 记录上一次生成序列号的毫秒时间戳以及 12 个随机字符（以 `PUSH_CHARS` 的下标表示），同一毫秒内再次生成
+    /// 序列号时，会在上一次的随机字符基础上 +1，以保证同一毫秒内生成的序列号依然有序且不重复。
+    static ref LAST: Mutex<(u64, [u8; 12])> = Mutex::new((0, [0; 12]));
+}
+
+/// ## Firebase Push ID
+///
+/// 生成与 Firebase push ID 格式一致的 20 个字符长度的序列号，前 8 个字符由毫秒级时间戳编码而成，后 12 个字符
+/// （72 bits）为随机字符，当同一毫秒内多次生成序列号时，随机部分将在上一次的基础上自增，因此生成的序列号严格按照
+/// 字典序递增。
+///
+/// `PushIdSerialer` 不依赖任何外部数据，`feed` 提供的数据将被忽略。
+#[derive(Debug, Default)]
+pub struct PushIdSerialer;
+
+impl PushIdSerialer {
+    pub fn new() -> PushIdSerialer {
+        PushIdSerialer
+    }
+}
+
+impl Serialer for PushIdSerialer {
+    type Output = String;
+
+    type Error = Infallible;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        Box::pin(future::ready(self.build_sync()))
+    }
+
+    fn feed(&mut self, _: &[u8]) {}
+}
+
+impl SyncSerialer for PushIdSerialer {
+    fn build_sync(self) -> Result<Self::Output, Self::Error> {
+        let now = crate::clock::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default();
+
+        let (timestamp, random) = {
+            let mut last = LAST.lock().unwrap();
+
+            // 时钟回拨时沿用上一次的时间戳，避免生成的序列号出现倒序
+            let timestamp = if now > last.0 {
+                let mut rng = rand::thread_rng();
+                last.1.iter_mut().for_each(|c| *c = rng.gen_range(0..64));
+                now
+            } else {
+                // 从最低位开始进位：值为 63 的字符归零，并对第一个非 63 的字符 +1
+                for c in last.1.iter_mut().rev() {
+                    if *c == 63 {
+                        *c = 0;
+                    } else {
+                        *c += 1;
+                        break;
+                    }
+                }
+                last.0
+            };
+
+            last.0 = timestamp;
+            (timestamp, last.1)
+        };
+
+        let mut output = String::with_capacity(20);
+        (0..8)
+            .rev()
+            .map(|i| PUSH_CHARS[((timestamp >> (6 * i)) & 0x3f) as usize])
+            .chain(random.iter().map(|&c| PUSH_CHARS[c as usize]))
+            .for_each(|c| output.push(c as char));
+
+        Ok(output)
+    }
+}
@@ -1,14 +1,14 @@
-use crate::Serialer;
+use crate::{Serialer, SyncSerialer};
 use rand::{distributions::Alphanumeric, prelude::*};
 use rand_chacha::{rand_core::block::BlockRng, ChaCha20Core};
-use std::convert::{Infallible, TryInto};
-use std::future::Future;
-use std::iter;
+use smallvec::SmallVec;
+use std::convert::Infallible;
+use std::future::{self, Future};
 use std::pin::Pin;
 
 #[derive(Debug)]
 pub struct Random62Serialer {
-    seed: Vec<u8>,
+    seed: SmallVec<[u8; 32]>,
 }
 
 /// ## Random-ID-62
@@ -17,11 +17,17 @@ pub struct Random62Serialer {
 impl Random62Serialer {
     pub fn new() -> Random62Serialer {
         Random62Serialer {
-            seed: Vec::with_capacity(32),
+            seed: SmallVec::new(),
         }
     }
 }
 
+impl Default for Random62Serialer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Serialer for Random62Serialer {
     type Output = String;
 
@@ -30,15 +36,20 @@ impl Serialer for Random62Serialer {
     fn build(
         self,
     ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
-        let seed: [u8; 32] = self
-            .seed
-            .iter()
-            .copied()
-            .chain(iter::repeat(0))
-            .take(32)
-            .collect::<Vec<_>>()
-            .try_into()
-            .unwrap();
+        Box::pin(future::ready(self.build_sync()))
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.seed.extend_from_slice(data);
+    }
+}
+
+impl SyncSerialer for Random62Serialer {
+    fn build_sync(self) -> Result<Self::Output, Self::Error> {
+        // 不足 32 个字节的部分补 0，超出的部分被忽略
+        let mut seed = [0; 32];
+        let len = self.seed.len().min(32);
+        seed[..len].copy_from_slice(&self.seed[..len]);
 
         let output = BlockRng::new(ChaCha20Core::from_seed(seed))
             .sample_iter(Alphanumeric)
@@ -46,10 +57,6 @@ impl Serialer for Random62Serialer {
             .map(char::from)
             .collect();
 
-        Box::pin(async move { Ok(output) })
-    }
-
-    fn feed(&mut self, data: &[u8]) {
-        self.seed.extend_from_slice(data);
+        Ok(output)
     }
 }
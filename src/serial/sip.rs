@@ -0,0 +1,119 @@
+use std::hash::{BuildHasher, Hasher};
+
+/// `SipHasher13` 是 SipHash-1-3 算法的实现，与标准库当前的 `DefaultHasher` 使用相同的算法，但算法本身固定在
+/// fastsend 中，不会随 Rust 版本变化，因此相同的密钥和输入在任何版本下都会得到相同的哈希值。
+///
+/// SipHash 的抗碰撞能力依赖于密钥保密，固定密钥时只适用于需要稳定输出的场合（如 `TimeSerialer` 的尾号），不能用于
+/// 抵御哈希洪水攻击。
+#[derive(Debug, Clone)]
+pub struct SipHasher13 {
+    v0: u64,
+    v1: u64,
+    v2: u64,
+    v3: u64,
+
+    /// 尚未凑满 8 个字节的输入，按小端序保存。
+    tail: u64,
+
+    /// `tail` 中的字节数。
+    ntail: usize,
+
+    /// 已写入的总字节数。
+    length: usize,
+}
+
+impl SipHasher13 {
+    pub fn new_with_keys(k0: u64, k1: u64) -> SipHasher13 {
+        SipHasher13 {
+            v0: k0 ^ 0x736f_6d65_7073_6575,
+            v1: k1 ^ 0x646f_7261_6e64_6f6d,
+            v2: k0 ^ 0x6c79_6765_6e65_7261,
+            v3: k1 ^ 0x7465_6462_7974_6573,
+            tail: 0,
+            ntail: 0,
+            length: 0,
+        }
+    }
+
+    fn round(&mut self) {
+        self.v0 = self.v0.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(13) ^ self.v0;
+        self.v0 = self.v0.rotate_left(32);
+        self.v2 = self.v2.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(16) ^ self.v2;
+        self.v0 = self.v0.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(21) ^ self.v0;
+        self.v2 = self.v2.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(17) ^ self.v2;
+        self.v2 = self.v2.rotate_left(32);
+    }
+
+    /// 压缩一个 8 字节的分组（1 轮）。
+    fn compress(&mut self, m: u64) {
+        self.v3 ^= m;
+        self.round();
+        self.v0 ^= m;
+    }
+}
+
+impl Default for SipHasher13 {
+    /// 密钥为 (0, 0)。
+    fn default() -> Self {
+        Self::new_with_keys(0, 0)
+    }
+}
+
+impl Hasher for SipHasher13 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.length += bytes.len();
+        for &byte in bytes {
+            self.tail |= (byte as u64) << (8 * self.ntail);
+            self.ntail += 1;
+            if self.ntail == 8 {
+                self.compress(self.tail);
+                self.tail = 0;
+                self.ntail = 0;
+            }
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        let mut state = self.clone();
+        let b = ((self.length as u64 & 0xff) << 56) | self.tail;
+
+        state.compress(b);
+        state.v2 ^= 0xff;
+        for _ in 0..3 {
+            state.round();
+        }
+
+        state.v0 ^ state.v1 ^ state.v2 ^ state.v3
+    }
+}
+
+/// `StableState` 是以固定密钥构建 `SipHasher13` 的 `BuildHasher`，`TimeSerialer` 缺省使用密钥 (0, 0)，与替换之前
+/// 使用的 `DefaultHasher::new()` 在当前的 Rust 版本下输出相同。
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct StableState {
+    k0: u64,
+    k1: u64,
+}
+
+impl StableState {
+    pub fn new() -> StableState {
+        Self::default()
+    }
+
+    /// 使用自定义的密钥，不同的密钥会得到不同的尾号，部署之间需要保持不变。
+    pub fn with_keys(k0: u64, k1: u64) -> StableState {
+        StableState { k0, k1 }
+    }
+}
+
+impl BuildHasher for StableState {
+    type Hasher = SipHasher13;
+
+    fn build_hasher(&self) -> SipHasher13 {
+        SipHasher13::new_with_keys(self.k0, self.k1)
+    }
+}
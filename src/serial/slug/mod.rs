@@ -0,0 +1,196 @@
+use crate::Serialer;
+use std::convert::Infallible;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use thiserror::Error;
+
+/// 小写 base36 字符表。
+const BASE36: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// base58 字符表（比特币风格），不包含易混淆的 '0'、'O'、'I'、'l'。
+const BASE58: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// `InspectFnMut` 是 Inspect 方法的快捷方式（alias），签名与 `TicketSerialer` 的 Inspect 方法一致：返回
+/// `true` 代表序列号已存在（重复），返回 `false` 代表序列号可用。
+pub type InspectFnMut<E> = Box<
+    dyn FnMut(&str) -> Pin<Box<dyn Future<Output = Result<bool, E>> + Send + 'static>>
+        + Send
+        + 'static,
+>;
+
+/// ## 短链接 Slug
+///
+/// `SlugSerialer` 生成适用于短链接的 6~10 位短序列号，缺省使用小写 base36 字符，也可切换为 base58 字符。序列号
+/// 由 `feed` 提供的数据（如 `Token`、自增计数器）经过混淆后截取而来，未提供数据时使用随机数。
+///
+/// 由于截取后的序列号空间较小，`SlugSerialer` 支持与 `TicketSerialer` 相同的 `inspect` 方法，借助外部系统（如
+/// 短链接存储）校验序列号是否重复，重复时将混入重试次数重新生成，重试 `retry_times` 次后仍重复则返回 `MaxRetry`
+/// 错误。未配置 `inspect` 方法时不做重复校验。
+pub struct SlugSerialer<E = Infallible> {
+    /// 序列号长度，缺省配置是 8，取值范围为 6..=10。
+    length: usize,
+
+    /// base58：是否使用 base58 字符构建序列号，缺省配置是 false（即使用小写 base36 字符）。
+    base58: bool,
+
+    /// 重试次数：调用 `inspect` 方法校验序列号唯一性的重试次数，缺省配置是 10。
+    retry_times: usize,
+
+    data: Vec<u8>,
+
+    inspect: Option<InspectFnMut<E>>,
+}
+
+impl<E> Debug for SlugSerialer<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SlugSerialer")
+            .field("length", &self.length)
+            .field("base58", &self.base58)
+            .field("retry_times", &self.retry_times)
+            .field("data", &self.data)
+            .field(
+                "inspect",
+                &self
+                    .inspect
+                    .as_ref()
+                    .map(|_| "FnMut(&str) -> impl Future<Output = Result<bool, E>>"),
+            )
+            .finish()
+    }
+}
+
+impl SlugSerialer {
+    pub const DEFAULT_LENGTH: usize = 8;
+
+    pub fn new() -> SlugSerialer {
+        SlugSerialer {
+            length: Self::DEFAULT_LENGTH,
+            base58: false,
+            retry_times: 10,
+            data: Vec::with_capacity(8),
+            inspect: None,
+        }
+    }
+}
+
+impl Default for SlugSerialer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> SlugSerialer<E> {
+    pub fn length(mut self, length: usize) -> Self {
+        assert!((6..=10).contains(&length));
+        self.length = length;
+        self
+    }
+
+    pub fn base58(mut self) -> Self {
+        self.base58 = true;
+        self
+    }
+
+    pub fn retry_times(mut self, n: usize) -> Self {
+        self.retry_times = n;
+        self
+    }
+
+    pub fn inspect<T, F>(self, f: F) -> SlugSerialer<T>
+    where
+        F: FnMut(&str) -> Pin<Box<dyn Future<Output = Result<bool, T>> + Send + 'static>>
+            + Send
+            + 'static,
+    {
+        SlugSerialer {
+            length: self.length,
+            base58: self.base58,
+            retry_times: self.retry_times,
+            data: self.data,
+            inspect: Some(Box::new(f)),
+        }
+    }
+
+    /// 根据 `data` 以及重试次数 `attempt` 生成一个 64 位的混淆值，相同的输入一定得到相同的输出。
+    fn seed(&self, attempt: u64) -> u64 {
+        if self.data.is_empty() {
+            return rand::random();
+        }
+
+        let h = self.data.chunks(8).fold(0xcbf2_9ce4_8422_2325, |h, chunk| {
+            let mut bytes = [0u8; 8];
+            bytes[..chunk.len()].copy_from_slice(chunk);
+            mix(h ^ u64::from_be_bytes(bytes))
+        });
+
+        mix(h ^ attempt)
+    }
+
+    fn render(&self, mut n: u64) -> String {
+        let alphabet: &[u8] = if self.base58 { BASE58 } else { BASE36 };
+        let radix = alphabet.len() as u64;
+
+        let mut output = vec![0u8; self.length];
+        for c in output.iter_mut().rev() {
+            *c = alphabet[(n % radix) as usize];
+            n /= radix;
+        }
+
+        String::from_utf8(output).unwrap()
+    }
+}
+
+/// splitmix64 的混淆函数，是 u64 上的双射。
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[derive(Debug, Error)]
+pub enum SlugSerialError<E> {
+    #[error("an error occurs when inspecting new-generated slug: {0}")]
+    InspectFailed(
+        #[from]
+        #[source]
+        E,
+    ),
+
+    #[error("reach max retry times while generating slug")]
+    MaxRetry,
+}
+
+impl<E> Serialer for SlugSerialer<E>
+where
+    E: 'static,
+{
+    type Output = String;
+
+    type Error = SlugSerialError<E>;
+
+    fn build(
+        mut self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        Box::pin(async move {
+            for attempt in 0..self.retry_times.max(1) as u64 {
+                let output = self.render(self.seed(attempt));
+
+                let duplicated = match self.inspect.as_mut() {
+                    Some(inspect) => inspect(&output).await?,
+                    None => false,
+                };
+
+                if !duplicated {
+                    return Ok(output);
+                }
+            }
+
+            Err(SlugSerialError::MaxRetry)
+        })
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.data.extend_from_slice(data);
+    }
+}
@@ -0,0 +1,305 @@
+use crate::{Serialer, SyncSerialer};
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Sqids 缺省使用的字符表。
+pub const DEFAULT_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// ## Sqids
+///
+/// 按照 Sqids 算法将一个或多个 u64 编码为短小且可逆的字符串，常用于在 URL 中隐藏 `Token::id` 等数值 ID，
+/// 避免 ID 被枚举。字符表决定了编码结果，使用不同字符表的 `Sqids` 之间无法互相解码，因此可以通过自定义字符表
+/// 来实现简单的混淆（注意：这并不是加密）。
+///
+/// `Sqids` 的配置方式与 `TicketSerialer` 类似，通过链式调用完成，字符表不合法时会 panic：
+///
+/// ```ignore
+/// let sqids = Sqids::new().min_length(10);
+/// let id = sqids.encode(&[1, 2, 3]);
+/// assert_eq!(sqids.decode(&id), vec![1, 2, 3]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sqids {
+    /// 经过打乱的字符表
+    alphabet: Vec<char>,
+
+    /// 最小长度：编码结果不足该长度时会进行填充，缺省配置是 0，最大为 255。
+    min_length: usize,
+
+    /// 屏蔽词：编码结果中包含屏蔽词时会重新编码，缺省配置为空。
+    blocklist: HashSet<String>,
+}
+
+impl Default for Sqids {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sqids {
+    pub fn new() -> Sqids {
+        Sqids {
+            alphabet: shuffle(&DEFAULT_ALPHABET.chars().collect::<Vec<_>>()),
+            min_length: 0,
+            blocklist: HashSet::new(),
+        }
+    }
+
+    /// 使用自定义字符表，字符表应至少包含 3 个互不相同的 ASCII 字符。
+    pub fn alphabet(mut self, alphabet: &str) -> Self {
+        let chars = alphabet.chars().collect::<Vec<_>>();
+        assert!(
+            alphabet.is_ascii(),
+            "alphabet must only contain ASCII chars"
+        );
+        assert!(chars.len() >= 3, "alphabet length must be at least 3");
+        assert_eq!(
+            chars.iter().collect::<HashSet<_>>().len(),
+            chars.len(),
+            "alphabet must contain unique chars"
+        );
+
+        self.alphabet = shuffle(&chars);
+        self
+    }
+
+    pub fn min_length(mut self, min_length: usize) -> Self {
+        assert!(min_length <= 255, "min_length must be at most 255");
+        self.min_length = min_length;
+        self
+    }
+
+    pub fn blocklist<I, S>(mut self, blocklist: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        // 与官方实现一致，丢弃长度小于 3 以及包含字符表以外字符的屏蔽词
+        let alphabet = self
+            .alphabet
+            .iter()
+            .map(|c| c.to_ascii_lowercase())
+            .collect::<HashSet<_>>();
+
+        self.blocklist = blocklist
+            .into_iter()
+            .map(|word| word.as_ref().to_lowercase())
+            .filter(|word| word.len() >= 3 && word.chars().all(|c| alphabet.contains(&c)))
+            .collect();
+        self
+    }
+
+    /// 构建使用当前配置的 `SqidsSerialer`。
+    pub fn serialer(&self) -> SqidsSerialer {
+        SqidsSerialer {
+            sqids: Arc::new(self.clone()),
+            data: Vec::with_capacity(8),
+        }
+    }
+
+    /// 将 `numbers` 编码为字符串，`numbers` 为空时返回空字符串。
+    pub fn encode(&self, numbers: &[u64]) -> String {
+        if numbers.is_empty() {
+            return String::new();
+        }
+
+        self.encode_numbers(numbers, 0)
+    }
+
+    fn encode_numbers(&self, numbers: &[u64], increment: usize) -> String {
+        // 屏蔽词导致的重新编码次数不会超过字符表长度，超过时说明屏蔽词配置不合理
+        assert!(
+            increment <= self.alphabet.len(),
+            "reached max attempts to re-generate the ID"
+        );
+
+        let len = self.alphabet.len();
+        let offset = numbers
+            .iter()
+            .enumerate()
+            .fold(numbers.len(), |acc, (i, &n)| {
+                self.alphabet[(n % len as u64) as usize] as usize + i + acc
+            })
+            % len;
+        let offset = (offset + increment) % len;
+
+        let mut alphabet = self.alphabet.clone();
+        alphabet.rotate_left(offset);
+        let prefix = alphabet[0];
+        alphabet.reverse();
+
+        let mut id = vec![prefix];
+        for (i, &n) in numbers.iter().enumerate() {
+            id.extend(to_id(n, &alphabet[1..]));
+
+            if i < numbers.len() - 1 {
+                id.push(alphabet[0]);
+                alphabet = shuffle(&alphabet);
+            }
+        }
+
+        if self.min_length > id.len() {
+            id.push(alphabet[0]);
+
+            while self.min_length > id.len() {
+                alphabet = shuffle(&alphabet);
+                let n = (self.min_length - id.len()).min(alphabet.len());
+                id.extend_from_slice(&alphabet[..n]);
+            }
+        }
+
+        let id = id.into_iter().collect::<String>();
+        if self.is_blocked(&id) {
+            self.encode_numbers(numbers, increment + 1)
+        } else {
+            id
+        }
+    }
+
+    /// 将字符串解码为 u64 序列，`id` 不是由当前配置编码而成时返回空 `Vec`。
+    pub fn decode(&self, id: &str) -> Vec<u64> {
+        let mut numbers = Vec::new();
+
+        let mut chars = id.chars();
+        let prefix = match chars.next() {
+            Some(prefix) => prefix,
+            None => return numbers,
+        };
+
+        if !id.chars().all(|c| self.alphabet.contains(&c)) {
+            return numbers;
+        }
+
+        let offset = self.alphabet.iter().position(|&c| c == prefix).unwrap();
+        let mut alphabet = self.alphabet.clone();
+        alphabet.rotate_left(offset);
+        alphabet.reverse();
+
+        let mut rest = chars.as_str();
+        while !rest.is_empty() {
+            let separator = alphabet[0];
+            let (chunk, next) = match rest.split_once(separator) {
+                Some((chunk, next)) => (chunk, Some(next)),
+                None => (rest, None),
+            };
+
+            if chunk.is_empty() {
+                break;
+            }
+
+            match to_number(chunk, &alphabet[1..]) {
+                Some(n) => numbers.push(n),
+                None => return Vec::new(),
+            }
+
+            match next {
+                Some(next) => {
+                    alphabet = shuffle(&alphabet);
+                    rest = next;
+                }
+                None => break,
+            }
+        }
+
+        // 仅接受规范编码：重新编码后应与输入完全一致，避免同一组数值对应多个字符串
+        if self.encode(&numbers) != id {
+            return Vec::new();
+        }
+
+        numbers
+    }
+
+    fn is_blocked(&self, id: &str) -> bool {
+        let id = id.to_lowercase();
+        self.blocklist.iter().any(|word| {
+            if word.len() > id.len() {
+                false
+            } else if id.len() <= 3 || word.len() <= 3 {
+                id == *word
+            } else if word.chars().any(|c| c.is_ascii_digit()) {
+                id.starts_with(word.as_str()) || id.ends_with(word.as_str())
+            } else {
+                id.contains(word.as_str())
+            }
+        })
+    }
+}
+
+/// `SqidsSerialer` 将 `feed` 提供的字节按大端序每 8 个字节组成一个 u64（不足 8 个字节的尾部同样按大端序组成
+/// u64），再使用 `Sqids` 编码为序列号，因此对于 `Token` 而言，生成的序列号解码后即为 `Token::id` 的值。
+#[derive(Debug)]
+pub struct SqidsSerialer {
+    sqids: Arc<Sqids>,
+    data: Vec<u8>,
+}
+
+impl Serialer for SqidsSerialer {
+    type Output = String;
+
+    type Error = Infallible;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        Box::pin(future::ready(self.build_sync()))
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.data.extend_from_slice(data);
+    }
+}
+
+impl SyncSerialer for SqidsSerialer {
+    fn build_sync(self) -> Result<Self::Output, Self::Error> {
+        let numbers = self
+            .data
+            .chunks(8)
+            .map(|chunk| chunk.iter().fold(0u64, |n, &b| n << 8 | b as u64))
+            .collect::<Vec<_>>();
+
+        let output = self.sqids.encode(&numbers);
+        Ok(output)
+    }
+}
+
+fn shuffle(alphabet: &[char]) -> Vec<char> {
+    let mut chars = alphabet.to_vec();
+    let len = chars.len();
+
+    let (mut i, mut j) = (0, len - 1);
+    while j > 0 {
+        let r = (i * j + chars[i] as usize + chars[j] as usize) % len;
+        chars.swap(i, r);
+        i += 1;
+        j -= 1;
+    }
+
+    chars
+}
+
+fn to_id(mut n: u64, alphabet: &[char]) -> Vec<char> {
+    let len = alphabet.len() as u64;
+    let mut id = Vec::new();
+
+    loop {
+        id.push(alphabet[(n % len) as usize]);
+        n /= len;
+        if n == 0 {
+            break;
+        }
+    }
+
+    id.reverse();
+    id
+}
+
+fn to_number(id: &str, alphabet: &[char]) -> Option<u64> {
+    let len = alphabet.len() as u64;
+    id.chars().try_fold(0u64, |n, c| {
+        let index = alphabet.iter().position(|&x| x == c)? as u64;
+        n.checked_mul(len)?.checked_add(index)
+    })
+}
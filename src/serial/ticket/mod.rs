@@ -1,9 +1,11 @@
-use super::to_string_radix;
+use super::FeedBuffer;
+use crate::encoding::Radix;
 use crate::Serialer;
 use chrono::{DateTime, Datelike, Duration, Local, TimeZone, Timelike};
-use std::fmt::{Debug, Formatter};
+use std::fmt::{self, Debug, Display, Formatter};
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU16, Ordering};
 use thiserror::Error;
 
 /// `InspectFnMut` 是 Inspect 方法的快捷方式（alias），由于需要兼顾异步任务及多线程场景，因此 Inspect
@@ -14,6 +16,59 @@ pub type InspectFnMut<E> = Box<
         + 'static,
 >;
 
+/// `QueueFnMut` 接收降级发放的序列号及 `inspect` 返回的错误，见 `DegradePolicy::Queue`。
+pub type QueueFnMut<E> = Box<dyn FnMut(&str, &E) + Send + 'static>;
+
+/// `inspect` 返回错误（如外部系统不可用）时 `TicketSerialer` 的处理方式，缺省为 `Fail`。
+///
+/// 降级发放的序列号未经外部系统校验，在末尾追加一个由进程内的计数器与随机数组成的部分（十进制下共 10 个字符），
+/// 以较宽的熵在本地保证唯一性：同一进程在同一秒内由相同数据生成的序列号在计数器循环之前不会重复，不同进程之间则
+/// 依赖随机数。降级发放的序列号比正常的序列号更长，`Ticket::verified` 为 `false`。
+pub enum DegradePolicy<E> {
+    /// 返回 `TicketSerialError::InspectFailed`。
+    Fail,
+
+    /// 不经校验地发放序列号。
+    Local,
+
+    /// 与 `Local` 相同，并将序列号交给回调（如写入队列），以便外部系统恢复之后再进行校验。
+    Queue(QueueFnMut<E>),
+}
+
+impl<E> DegradePolicy<E> {
+    pub fn queue<F>(f: F) -> Self
+    where
+        F: FnMut(&str, &E) + Send + 'static,
+    {
+        DegradePolicy::Queue(Box::new(f))
+    }
+}
+
+impl<E> Debug for DegradePolicy<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DegradePolicy::Fail => f.write_str("Fail"),
+            DegradePolicy::Local => f.write_str("Local"),
+            DegradePolicy::Queue(_) => f.write_str("Queue(FnMut(&str, &E))"),
+        }
+    }
+}
+
+/// `TicketSerialer::build_ticket` 生成的序列号。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Ticket {
+    pub serial: String,
+
+    /// 序列号是否经过 `inspect` 的校验，按 `DegradePolicy` 降级发放时为 `false`。
+    pub verified: bool,
+}
+
+impl Display for Ticket {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.serial)
+    }
+}
+
 /// `TicketSerialer` 是一个可配置的、生成字母+数字组合的序列号生成器，可用于生成各类编码，如设备、资产、事件等。
 /// 其借助外部系统来确保序列号的唯一性，当通过 `inspect` 方法校验序列号为重复时，`TicketSerialer` 会向后借用
 /// 一秒来重新构建序列号，以期找到唯一序列号值，这个过程会重复 `retry_times` 次，若仍未找到唯一序列号，则会返回
@@ -39,7 +94,7 @@ pub struct TicketSerialer<E> {
 
     /// ============ 临时存储 ===============
     /// 调用 `feed` 方法时，数据临时存储于 `data` 字段，在调用 `init` 方法后将生成下方的序列号构建参数。
-    data: Vec<u8>,
+    data: FeedBuffer,
 
     /// ============ InspectFnMut ===============
     /// `inspect` 用于校验生成的序列号是否是合法的序列号（即是否是唯一的，是否与已经生成的序列号重复），借助外部
@@ -48,6 +103,9 @@ pub struct TicketSerialer<E> {
     /// （使用 `FnMut` 的原因在于外部实现有可能需要引用环境中的可变变量，如 sqlx 中的 `Executor`）
     inspect: InspectFnMut<E>,
 
+    /// `inspect` 返回错误时的处理方式，缺省配置是 `DegradePolicy::Fail`。
+    degrade: DegradePolicy<E>,
+
     /// ============ 生成参数 ===============
 
     /// 日期：用于生成序列好的头两个部分，分别为代表年月日的四字符部分（'XXXX'）和代表时分秒的五字符部分（'XXXXX'）
@@ -58,7 +116,7 @@ pub struct TicketSerialer<E> {
 
     /// 尾部数字序列：处于尾部的字节将被收集到尾部数字序列中，将会以两个字节一组合成 u16 形式的数字，并默认转化为纯
     /// 数字十进制字符，没有数量限制。
-    decimal_digit_part2: Vec<u8>,
+    decimal_digit_part2: FeedBuffer,
 
     /// 校验码
     auth: u8,
@@ -77,6 +135,7 @@ impl<E> Debug for TicketSerialer<E> {
                 "inspect",
                 &"FnMut(&str) -> impl Future<Output = Result<(), E>>",
             )
+            .field("degrade", &self.degrade)
             .field("datetime", &self.datetime)
             .field("decimal_digit_part1", &self.decimal_digit_part1)
             .field("decimal_digit_part2", &self.decimal_digit_part2)
@@ -98,11 +157,12 @@ impl<E> TicketSerialer<E> {
             lowercase: false,
             decimal_only: true,
             retry_times: 10,
-            data: Vec::with_capacity(8),
+            data: FeedBuffer::new(),
             inspect: Box::new(f),
+            degrade: DegradePolicy::Fail,
             datetime: None,
             decimal_digit_part1: None,
-            decimal_digit_part2: Vec::with_capacity(5),
+            decimal_digit_part2: FeedBuffer::new(),
             auth: u8::MAX,
         }
     }
@@ -132,6 +192,11 @@ impl<E> TicketSerialer<E> {
         self
     }
 
+    pub fn degrade(mut self, policy: DegradePolicy<E>) -> Self {
+        self.degrade = policy;
+        self
+    }
+
     /// `init` 方法将保存在 `data` 中的数据转换为对应的构建参数，需要注意的是，如果 `data` 中的字节数不足 8 个
     /// 字节，那么 `init` 方法会强行按八个字节进行构建，缺少的部分将被缺省地补充为 0，因此请务必保证 feed 超过 8
     /// 个字节的数据，不然生成的序列号有可能重复（极大概率）。
@@ -160,7 +225,7 @@ impl<E> TicketSerialer<E> {
                 next(&mut iter, &mut self.auth),
             ]);
 
-            Some(Local.timestamp(ts as i64, 0))
+            Some(Local.timestamp_opt(ts as i64, 0).unwrap())
         };
 
         self.decimal_digit_part1 = {
@@ -179,7 +244,7 @@ impl<E> TicketSerialer<E> {
             .push(next(&mut iter, &mut self.auth));
 
         // 多余部分直接 append 到 `decimal_digit_part2` 末尾
-        while let Some(&item) = iter.next() {
+        for &item in iter {
             mix(&mut self.auth, item);
             self.decimal_digit_part2.push(item);
         }
@@ -202,19 +267,47 @@ pub enum TicketSerialError<E> {
     DataNotEnough,
 }
 
-impl<E> Serialer for TicketSerialer<E>
+impl<E> TicketSerialer<E>
 where
     E: 'static,
 {
-    type Output = String;
-
-    type Error = TicketSerialError<E>;
-
-    fn build(
+    /// 与 `build` 相同，同时返回序列号是否经过了 `inspect` 的校验（见 `DegradePolicy`）。
+    pub fn build_ticket(
         mut self,
-    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+    ) -> Pin<Box<dyn Future<Output = Result<Ticket, TicketSerialError<E>>> + Send + 'static>> {
         self.init();
         Box::pin(async move {
+            let sep = if self.minus_sep { "-" } else { "" };
+
+            // 序列号中除时间以外的部分（`rest`）在重试时保持不变，只需构建一次；每次重试仅在复用的 `output`
+            // 中重新写入代表时间的前两个部分。
+            let datetime = self.datetime.ok_or(TicketSerialError::DataNotEnough)?;
+            let rest = {
+                let right = self
+                    .decimal_digit_part1
+                    .map(|n| format_u16(n, self.decimal_only))
+                    .ok_or(TicketSerialError::DataNotEnough)?;
+
+                if self.decimal_digit_part2.is_empty() {
+                    return Err(TicketSerialError::DataNotEnough);
+                }
+
+                if !self.short_repr {
+                    let tail = self
+                        .decimal_digit_part2
+                        .chunks(2)
+                        .map(to_u16)
+                        .map(|n| format_u16(n, self.decimal_only))
+                        .fold(String::with_capacity(5), |prev, next| prev + &next);
+                    let auth = encode((self.auth % u8::MAX) as u64, Radix::letters_first(26), 2);
+
+                    [&*right, &*tail, &*auth].join(sep)
+                } else {
+                    right
+                }
+            };
+
+            let mut output = String::with_capacity(4 + 5 + 2 * sep.len() + rest.len());
             let mut cnt = 0;
             let mut secs = 0;
             loop {
@@ -223,56 +316,70 @@ where
                     return Err(TicketSerialError::MaxRetry);
                 }
 
-                (!self.decimal_digit_part2.is_empty())
-                    .then(|| ())
-                    .ok_or_else(|| TicketSerialError::DataNotEnough)?;
-
-                let dt = self
-                    .datetime
-                    .as_ref()
-                    .cloned()
-                    .map(|old| old + Duration::seconds(secs))
-                    .ok_or_else(|| TicketSerialError::DataNotEnough)?;
-
-                let (head, left, right, tail, auth) = (
-                    build_head(&dt),
-                    build_left(&dt),
-                    self.decimal_digit_part1
-                        .map(|n| format_u16(n, self.decimal_only))
-                        .ok_or_else(|| TicketSerialError::DataNotEnough)?,
-                    self.decimal_digit_part2
-                        .chunks(2)
-                        .map(to_u16)
-                        .map(|n| format_u16(n, self.decimal_only))
-                        .fold(String::with_capacity(5), |prev, next| prev + &next),
-                    to_string_radix((self.auth % u8::MAX) as usize, 26, 2, false),
-                );
-
-                let sep = if self.minus_sep { "-" } else { "" };
+                let dt = datetime + Duration::seconds(secs);
 
-                let mut output: String = if !self.short_repr {
-                    [&*head, &*left, &*right, &*tail, &*auth].join(sep)
-                } else {
-                    [&*head, &*left, &*right].join(sep)
-                };
+                output.clear();
+                push_head(&mut output, &dt);
+                output.push_str(sep);
+                push_left(&mut output, &dt);
+                output.push_str(sep);
+                output.push_str(&rest);
 
                 if self.lowercase {
                     output.make_ascii_lowercase();
                 }
 
-                match (self.inspect)(&*output).await {
+                match (self.inspect)(&output).await {
                     Ok(duplicated) if duplicated => {
                         secs += 1;
                         continue;
                     }
                     Ok(_) => (),
-                    Err(e) => return Err(e.into()),
+                    Err(e) => {
+                        if let DegradePolicy::Fail = self.degrade {
+                            return Err(e.into());
+                        }
+
+                        output.push_str(sep);
+                        push_local(&mut output, self.decimal_only);
+                        if self.lowercase {
+                            output.make_ascii_lowercase();
+                        }
+
+                        if let DegradePolicy::Queue(queue) = &mut self.degrade {
+                            queue(&output, &e);
+                        }
+
+                        return Ok(Ticket {
+                            serial: output,
+                            verified: false,
+                        });
+                    }
                 }
 
-                return Ok(output);
+                return Ok(Ticket {
+                    serial: output,
+                    verified: true,
+                });
             }
         })
     }
+}
+
+impl<E> Serialer for TicketSerialer<E>
+where
+    E: 'static,
+{
+    type Output = String;
+
+    type Error = TicketSerialError<E>;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        let ticket = self.build_ticket();
+        Box::pin(async move { ticket.await.map(|ticket| ticket.serial) })
+    }
 
     fn feed(&mut self, data: &[u8]) {
         self.data.extend_from_slice(data);
@@ -281,7 +388,7 @@ where
 
 #[inline]
 fn to_u16(s: &[u8]) -> u16 {
-    assert!(s.len() > 0 && s.len() <= 2);
+    assert!(!s.is_empty() && s.len() <= 2);
     if s.len() == 2 {
         u16::from_be_bytes([s[0], s[1]])
     } else {
@@ -294,25 +401,51 @@ fn format_u16(n: u16, decimal_only: bool) -> String {
     if decimal_only {
         format!("{:05}", n)
     } else {
-        to_string_radix(n as usize, 36, 4, true)
+        encode(n as u64, Radix::digits_first(36), 4)
     }
 }
 
-fn build_head(dt: &DateTime<Local>) -> String {
+/// 写入代表年月日的四字符部分（'XXXX'）。
+fn push_head(output: &mut String, dt: &DateTime<Local>) {
     const OFFSET: i32 = 1918;
-    format!(
-        "{}{}{}",
-        to_string_radix((dt.year() - OFFSET) as usize, 26, 2, false),
-        to_string_radix((dt.month() - 1) as usize, 12, 1, true),
-        to_string_radix((dt.day() - 1) as usize, 31, 1, true)
-    )
+    push(
+        output,
+        (dt.year() - OFFSET) as u64,
+        Radix::letters_first(26),
+        2,
+    );
+    push(output, (dt.month() - 1) as u64, Radix::digits_first(12), 1);
+    push(output, (dt.day() - 1) as u64, Radix::digits_first(31), 1);
+}
+
+/// 写入代表时分秒的五字符部分（'XXXXX'）。
+fn push_left(output: &mut String, dt: &DateTime<Local>) {
+    push(output, dt.hour() as u64, Radix::digits_first(24), 1);
+    push(output, dt.minute() as u64, Radix::digits_first(36), 2);
+    push(output, dt.second() as u64, Radix::digits_first(36), 2);
+}
+
+/// 写入降级发放时追加的部分：进程内的计数器与随机数各占一个 u16。
+fn push_local(output: &mut String, decimal_only: bool) {
+    static COUNTER: AtomicU16 = AtomicU16::new(0);
+
+    output.push_str(&format_u16(
+        COUNTER.fetch_add(1, Ordering::Relaxed),
+        decimal_only,
+    ));
+    output.push_str(&format_u16(rand::random(), decimal_only));
+}
+
+/// 序列号的各个部分在构造时都保证了数值不超过其宽度（如月份 < 12，u16 < 36^4），因此溢出代表程序逻辑错误。
+fn encode(n: u64, radix: Radix, width: usize) -> String {
+    radix
+        .encode_fixed(n, width)
+        .expect("ticket part overflows its width")
 }
 
-fn build_left(dt: &DateTime<Local>) -> String {
-    format!(
-        "{}{}{}",
-        to_string_radix(dt.hour() as usize, 24, 1, true),
-        to_string_radix(dt.minute() as usize, 36, 2, true),
-        to_string_radix(dt.second() as usize, 36, 2, true)
-    )
+/// `encode` 的无分配版本，直接写入 `output`。
+fn push(output: &mut String, n: u64, radix: Radix, width: usize) {
+    radix
+        .encode_fixed_into(n, width, output)
+        .expect("ticket part overflows its width")
 }
@@ -0,0 +1,161 @@
+use crate::{Serialer, UUIDSerialer, UUID};
+use std::convert::Infallible;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// TypeID 后缀所使用的 Crockford base32 字符表（小写）。
+const ALPHABET: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+
+/// TypeID 后缀的固定长度：128 bits 的 UUID 补齐为 130 bits 后，每 5 bits 编码为一个字符。
+const SUFFIX_LENGTH: usize = 26;
+
+/// TypeID 前缀的最大长度。
+const PREFIX_MAX_LENGTH: usize = 63;
+
+/// ## TypeID
+///
+/// 按照 TypeID 规范生成形如 'user_01h455vb4pex5vsknk084sn02q' 的序列号，由「类型前缀 + '_' + base32 编码的
+/// V7 版本 UUID」组成，前缀为空时省略分隔符。由于 V7 版本的 UUID 以毫秒级时间戳开头，因此相同前缀的 TypeID 按
+/// 字典序排列即按生成时间排列。
+///
+/// `TypeIdSerialer` 生成的 UUID 采用随机数填充，因此 `feed` 提供的数据将被忽略。
+#[derive(Debug)]
+pub struct TypeIdSerialer {
+    prefix: Box<str>,
+}
+
+impl TypeIdSerialer {
+    /// 使用类型前缀 `prefix` 构建 `TypeIdSerialer`，前缀仅能包含小写字母和下划线，且不能以下划线开头或结尾，
+    /// 长度不超过 63 个字符，不合法的前缀会导致 panic。
+    pub fn new(prefix: &str) -> TypeIdSerialer {
+        assert!(
+            validate_prefix(prefix),
+            "invalid TypeID prefix: {:?}",
+            prefix
+        );
+
+        TypeIdSerialer {
+            prefix: prefix.to_owned().into_boxed_str(),
+        }
+    }
+}
+
+impl Serialer for TypeIdSerialer {
+    type Output = TypeId;
+
+    type Error = Infallible;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        let prefix = self.prefix;
+        Box::pin(async move {
+            let uuid = UUIDSerialer::new_v7().build().await?;
+            Ok(TypeId {
+                prefix,
+                bytes: uuid.to_bytes(),
+            })
+        })
+    }
+
+    fn feed(&mut self, _: &[u8]) {}
+}
+
+/// `TypeId` 是 `TypeIdSerialer` 生成的序列号，同时支持通过 `FromStr` 从字符串中解析，解析逻辑与生成逻辑共用
+/// 同一套前缀校验及 base32 编码规则。
+///
+/// 按照 TypeID 规范，后缀可以是任意 UUID（包括 nil UUID 及 V7 以外的版本），解析时只校验前缀及 base32 编码，
+/// 后缀的 16 个字节原样保存。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TypeId {
+    prefix: Box<str>,
+    bytes: [u8; 16],
+}
+
+impl TypeId {
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// 后缀对应的 `UUID`，`UUID` 仅支持 V3、V4、V5、V7，其余版本（包括 nil UUID）返回 `None`。
+    pub fn uuid(&self) -> Option<UUID> {
+        UUID::from_bytes(self.bytes)
+    }
+
+    /// 后缀对应的 16 个字节。
+    pub fn to_bytes(&self) -> [u8; 16] {
+        self.bytes
+    }
+}
+
+impl fmt::Display for TypeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.prefix.is_empty() {
+            write!(f, "{}_", self.prefix)?;
+        }
+
+        let n = u128::from_be_bytes(self.bytes);
+        (0..SUFFIX_LENGTH)
+            .map(|i| ALPHABET[((n >> (5 * (SUFFIX_LENGTH - 1 - i))) & 0x1f) as usize])
+            .try_for_each(|c| write!(f, "{}", c as char))
+    }
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum TypeIdError {
+    #[error("invalid TypeID prefix")]
+    InvalidPrefix,
+
+    #[error("invalid TypeID suffix")]
+    InvalidSuffix,
+}
+
+impl FromStr for TypeId {
+    type Err = TypeIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // 前缀中可以包含下划线，因此使用最后一个下划线作为分隔符
+        let (prefix, suffix) = match s.rsplit_once('_') {
+            Some(("", _)) => return Err(TypeIdError::InvalidPrefix),
+            Some((prefix, suffix)) => (prefix, suffix),
+            None => ("", s),
+        };
+
+        if !validate_prefix(prefix) {
+            return Err(TypeIdError::InvalidPrefix);
+        }
+
+        if suffix.len() != SUFFIX_LENGTH {
+            return Err(TypeIdError::InvalidSuffix);
+        }
+
+        let n = suffix.bytes().enumerate().try_fold(0u128, |n, (i, c)| {
+            let value = ALPHABET
+                .iter()
+                .position(|&x| x == c)
+                .ok_or(TypeIdError::InvalidSuffix)? as u128;
+
+            // 首个字符只能编码 3 bits，超过 '7' 即代表溢出
+            if i == 0 && value > 7 {
+                return Err(TypeIdError::InvalidSuffix);
+            }
+
+            Ok(n << 5 | value)
+        })?;
+
+        Ok(TypeId {
+            prefix: prefix.to_owned().into_boxed_str(),
+            bytes: n.to_be_bytes(),
+        })
+    }
+}
+
+fn validate_prefix(prefix: &str) -> bool {
+    prefix.len() <= PREFIX_MAX_LENGTH
+        && prefix.bytes().all(|c| c.is_ascii_lowercase() || c == b'_')
+        && !prefix.starts_with('_')
+        && !prefix.ends_with('_')
+}
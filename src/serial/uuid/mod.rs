@@ -1,14 +1,17 @@
-use crate::Serialer;
+use crate::encoding::base64url;
+use crate::{BinarySerialer, Serialer, SyncSerialer};
 use rand::prelude::*;
 use rand_chacha::{rand_core::block::BlockRng, ChaCha20Core};
 use sha1::{Digest as Sha1Digest, Sha1};
 use std::cell::RefCell;
 use std::convert::{Infallible, TryInto};
 use std::fmt;
-use std::future::Future;
+use std::future::{self, Future};
 use std::ops::Index;
 use std::pin::Pin;
 use std::rc::Rc;
+use std::str::FromStr;
+use std::time::SystemTime;
 
 /// ## UUID
 ///
@@ -18,11 +21,14 @@ use std::rc::Rc;
 /// 尽管如此，还是简单地实现了 V4 和 V5 版本的 UUID，作为备选。V4 版本的 UUID 采用了密码学安全的 chacha20
 /// 随机数生成算法；V5 版本的 UUID 采用 sha-1 作为哈希算法（这些都是符合 UUID 版本标准的）。其中，由于 sha-1
 /// 生成的摘要信息超过 128-bit，因此仅截取前 128 bits 作为 UUID 值。
-#[derive(Debug)]
+///
+/// V7 版本的 UUID 以 48-bit 的毫秒级 UNIX 时间戳开头，其余部分由随机数填充，生成的 UUID 按时间有序，适合作为
+/// 数据库索引使用（TypeID 也是基于 V7 版本的 UUID 构建的）。
+#[derive(Debug, Clone)]
 pub struct UUIDSerialer {
     data: Vec<u8>,
 
-    /// 版本号，仅支持 V3、V4、V5
+    /// 版本号，仅支持 V3、V4、V5、V7
     version: Version,
 }
 
@@ -47,6 +53,13 @@ impl UUIDSerialer {
             version: Version::V5,
         }
     }
+
+    pub fn new_v7() -> UUIDSerialer {
+        UUIDSerialer {
+            data: Vec::with_capacity(0),
+            version: Version::V7,
+        }
+    }
 }
 
 impl Serialer for UUIDSerialer {
@@ -57,6 +70,38 @@ impl Serialer for UUIDSerialer {
     fn build(
         self,
     ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        Box::pin(future::ready(self.build_sync()))
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        // V4 和 V7 版本的 UUID 采用密码学安全的随机数生成，因此不需要提供任何额外数据
+        if !matches!(self.version, Version::V4 | Version::V7) {
+            self.data.extend_from_slice(data);
+        }
+    }
+}
+
+/// 输出符合 UUID 标准的 16 字节表示，与 `UUID::to_bytes` 相同。
+impl BinarySerialer for UUIDSerialer {
+    type Output = [u8; 16];
+
+    type Error = Infallible;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        Box::pin(future::ready(
+            self.build_sync().map(|uuid: UUID| uuid.to_bytes()),
+        ))
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        Serialer::feed(self, data)
+    }
+}
+
+impl SyncSerialer for UUIDSerialer {
+    fn build_sync(self) -> Result<Self::Output, Self::Error> {
         let uuid = match self.version {
             Version::V3 => {
                 let digest = md5::compute(self.data);
@@ -67,16 +112,26 @@ impl Serialer for UUIDSerialer {
                 }
             }
             Version::V4 => {
-                thread_local! {
-                    static RNG: Rc<RefCell<BlockRng<ChaCha20Core>>> = Rc::new(RefCell::new(BlockRng::new(ChaCha20Core::from_entropy())));
-                }
+                assert!(self.data.is_empty());
 
+                UUID {
+                    bytes: random_bytes(),
+                    version: self.version,
+                }
+            }
+            Version::V7 => {
                 assert!(self.data.is_empty());
-                let rng = RNG.with(|rng| rng.clone());
-                let mut mut_rng = rng.borrow_mut();
+                let mut bytes = random_bytes();
+
+                // 前 48 bits 为毫秒级的 UNIX 时间戳（大端序），其余部分保留随机数
+                let millis = crate::clock::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .map(|duration| duration.as_millis() as u64)
+                    .unwrap_or_default();
+                bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
 
                 UUID {
-                    bytes: mut_rng.gen(),
+                    bytes,
                     version: self.version,
                 }
             }
@@ -97,15 +152,30 @@ impl Serialer for UUIDSerialer {
             }
         };
 
-        Box::pin(async move { Ok(uuid) })
+        // 统一写入版本号与变体标记，确保 `UUID` 与其标准字节表示一一对应
+        let uuid = UUID {
+            bytes: uuid.to_bytes(),
+            ..uuid
+        };
+
+        Ok(uuid)
     }
+}
 
-    fn feed(&mut self, data: &[u8]) {
-        // V4 版本的 UUID 采用密码学安全的随机数生成，因此不需要提供任何额外数据
-        if self.version != Version::V4 {
-            self.data.extend_from_slice(data);
-        }
+thread_local! {
+    static RNG: Rc<RefCell<BlockRng<ChaCha20Core>>> = Rc::new(RefCell::new(BlockRng::new(ChaCha20Core::from_entropy())));
+}
+
+/// 生成 V4、V7 版本 `UUID` 的随机部分，确定性模式（`fastsend::testing::seed`）下使用全局的随机数生成器。
+fn random_bytes() -> [u8; 16] {
+    #[cfg(feature = "test-util")]
+    if let Some(bytes) = crate::testing::with_rng(Rng::gen) {
+        return bytes;
     }
+
+    let rng = RNG.with(|rng| rng.clone());
+    let bytes = rng.borrow_mut().gen();
+    bytes
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -113,6 +183,7 @@ enum Version {
     V3 = 3,
     V4 = 4,
     V5 = 5,
+    V7 = 7,
 }
 
 impl fmt::LowerHex for Version {
@@ -128,11 +199,89 @@ impl fmt::UpperHex for Version {
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "diesel-postgres",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Uuid)
+)]
 pub struct UUID {
     bytes: [u8; 16],
     version: Version,
 }
 
+impl UUID {
+    /// 返回符合 UUID 标准的 16 字节表示，即已写入版本号与变体标记的字节数组（与 `Display` 输出的内容一致）。
+    pub fn to_bytes(&self) -> [u8; 16] {
+        let mut bytes = self.bytes;
+        bytes[6] = (self.version as u8) << 4 | (bytes[6] & 0x0f);
+        bytes[8] = bytes[8] & 0x3f | 0x80;
+        bytes
+    }
+
+    /// 从符合 UUID 标准的 16 字节表示构建 `UUID`，版本号取自第 7 个字节的高 4 位，仅支持 V3、V4、V5、V7，
+    /// 其余版本返回 `None`。
+    pub fn from_bytes(bytes: [u8; 16]) -> Option<UUID> {
+        let version = match bytes[6] >> 4 {
+            3 => Version::V3,
+            4 => Version::V4,
+            5 => Version::V5,
+            7 => Version::V7,
+            _ => return None,
+        };
+
+        Some(UUID { bytes, version })
+    }
+
+    /// `to_bytes` 的 base64url 表示（22 个字符，不带填充），见 `encoding::base64url`。
+    pub fn to_base64url(&self) -> String {
+        base64url::encode(&self.to_bytes())
+    }
+
+    /// 解析 `to_base64url` 的输出，与 `UUID::from_bytes` 一样仅支持 V3、V4、V5、V7。
+    pub fn from_base64url(s: &str) -> Result<UUID, ParseUUIDError> {
+        let bytes = base64url::decode_array(s).map_err(|_| ParseUUIDError)?;
+        UUID::from_bytes(bytes).ok_or(ParseUUIDError)
+    }
+}
+
+/// 解析 `UUID` 失败时返回的错误。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ParseUUIDError;
+
+impl fmt::Display for ParseUUIDError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid UUID string")
+    }
+}
+
+impl std::error::Error for ParseUUIDError {}
+
+impl FromStr for UUID {
+    type Err = ParseUUIDError;
+
+    /// 支持带连字符（'xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx'）与不带连字符的两种形式，忽略大小写，与
+    /// `UUID::from_bytes` 一样仅支持 V3、V4、V5、V7。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = match s.len() {
+            32 => s.to_owned(),
+            36 if [8, 13, 18, 23].iter().all(|&i| s.as_bytes()[i] == b'-') => s.replace('-', ""),
+            _ => return Err(ParseUUIDError),
+        };
+
+        // `from_str_radix` 允许 '+' 前缀，因此需要预先校验
+        if !hex.bytes().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ParseUUIDError);
+        }
+
+        let mut bytes = [0u8; 16];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| ParseUUIDError)?;
+        }
+
+        UUID::from_bytes(bytes).ok_or(ParseUUIDError)
+    }
+}
+
 impl fmt::Display for UUID {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         to_uuid(f, self.bytes.into_iter(), self.version, false)
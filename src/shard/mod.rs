@@ -0,0 +1,203 @@
+use crate::{FastsendError, ID};
+use lazy_static::lazy_static;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+
+/// # 分片 id
+///
+/// `ShardFrame` 参照 Instagram 的分片 id 方案生成 64 位 id，由高到低依次为：
+///
+/// | 毫秒级时间戳（相对 `epoch`） | 分片号 | 分片内序列号 |
+/// |:-------------------------:|:-----:|:---------:|
+/// |          41 bits          | 13 bits |  10 bits  |
+///
+/// 分片号由调用方提供（例如 `user_id % 4096`），每个分片各自维护一个序列号，因此不同分片之间的 id 生成互不干扰，
+/// 且数据库分片可以直接从 id 中还原（见 `ShardedId::shard`）。41 bits 的时间戳从 `epoch` 起算约可使用 69 年。
+///
+/// 同一分片在同一毫秒内最多生成 1024 个 id，超过后将向后借用一毫秒，因此单个分片内生成的 id 严格递增。
+///
+/// # 注意
+///
+/// 分片内的序列号只在当前 `ShardFrame` 中维护，id 中也不包含设备号，因此两个进程（或同一进程中的两个
+/// `ShardFrame`）使用相同的起始时间为同一个分片号生成 id 时，同一毫秒内得到的 id 会发生冲突。同一时刻每个分片号
+/// 只能由一个 `ShardFrame` 负责，例如按分片号将请求路由到固定的实例。
+#[derive(Debug)]
+pub struct ShardFrame {
+    /// 起始时间（Unix 毫秒时间戳），缺省配置是 `ShardFrame::DEFAULT_EPOCH`，即 2020-01-01T00:00:00Z。
+    epoch: u64,
+
+    /// 各分片的状态，以「时间戳 << SEQUENCE_BITS | 序列号」的形式存储，通过 CAS 更新，不需要加锁。
+    slots: Box<[AtomicU64]>,
+}
+
+impl ShardFrame {
+    pub const TIMESTAMP_BITS: u32 = 41;
+    pub const SHARD_BITS: u32 = 13;
+    pub const SEQUENCE_BITS: u32 = 10;
+
+    /// 分片号的上限（不含），即 8192。
+    pub const SHARD_CAP: u16 = 1 << Self::SHARD_BITS;
+
+    pub const DEFAULT_EPOCH: u64 = 1_577_836_800_000;
+
+    pub fn new() -> ShardFrame {
+        ShardFrame {
+            epoch: Self::DEFAULT_EPOCH,
+            slots: (0..Self::SHARD_CAP).map(|_| AtomicU64::new(0)).collect(),
+        }
+    }
+
+    /// 设置起始时间，`epoch` 不能晚于当前时间。需要注意的是，`epoch` 一经使用便不能再修改，否则会导致 id 冲突。
+    pub fn epoch(mut self, epoch: SystemTime) -> Self {
+        self.epoch = epoch_millis(epoch);
+        self
+    }
+
+    /// 为分片 `shard` 生成下一个 id，`shard` 必须小于 `ShardFrame::SHARD_CAP`。当前时间早于起始时间时 panic，
+    /// 见 `try_next`。
+    pub fn next(&self, shard: u16) -> ShardedId {
+        self.try_next(shard)
+            .unwrap_or_else(|error| panic!("{} on ShardFrame::next()", error))
+    }
+
+    /// `next` 方法的非 panic 版本，当前时间早于起始时间（如系统时间被回拨到 `epoch` 之前）时返回
+    /// `FastsendError::ClockBeforeShardEpoch`。
+    pub fn try_next(&self, shard: u16) -> Result<ShardedId, FastsendError> {
+        assert!(
+            shard < Self::SHARD_CAP,
+            "shard {} exceeds {} bits",
+            shard,
+            Self::SHARD_BITS
+        );
+
+        let now = (now().as_millis() as u64)
+            .checked_sub(self.epoch)
+            .ok_or(FastsendError::ClockBeforeShardEpoch)?;
+        let sequence_mask = (1 << Self::SEQUENCE_BITS) - 1;
+
+        let slot = &self.slots[shard as usize];
+        let mut current = slot.load(Ordering::Acquire);
+        loop {
+            let (timestamp, sequence) = (current >> Self::SEQUENCE_BITS, current & sequence_mask);
+
+            // 时钟回拨时沿用上一次的时间戳，序列号耗尽时向后借用一毫秒
+            let next = if now > timestamp {
+                now << Self::SEQUENCE_BITS
+            } else if sequence == sequence_mask {
+                (timestamp + 1) << Self::SEQUENCE_BITS
+            } else {
+                current + 1
+            };
+
+            match slot.compare_exchange_weak(current, next, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => {
+                    return Ok(ShardedId {
+                        timestamp: next >> Self::SEQUENCE_BITS,
+                        shard,
+                        sequence: (next & sequence_mask) as u16,
+                    })
+                }
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+impl Default for ShardFrame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn epoch_millis(epoch: SystemTime) -> u64 {
+    let epoch = epoch
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("epoch must be later than UNIX_EPOCH");
+    assert!(epoch <= now());
+
+    epoch.as_millis() as u64
+}
+
+fn now() -> Duration {
+    crate::clock::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// `ShardedId` 是 `ShardFrame` 生成的 id，通过 `ID::id` 转化为 u64，也可以通过 `from_id` 从 u64 中还原。
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ShardedId {
+    timestamp: u64,
+    shard: u16,
+    sequence: u16,
+}
+
+impl ShardedId {
+    pub fn from_id(id: u64) -> ShardedId {
+        ShardedId {
+            timestamp: id >> (ShardFrame::SHARD_BITS + ShardFrame::SEQUENCE_BITS),
+            shard: ((id >> ShardFrame::SEQUENCE_BITS) & (ShardFrame::SHARD_CAP as u64 - 1)) as u16,
+            sequence: (id & ((1 << ShardFrame::SEQUENCE_BITS) - 1)) as u16,
+        }
+    }
+
+    /// 相对于 `ShardFrame` 起始时间的毫秒数。
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn shard(&self) -> u16 {
+        self.shard
+    }
+
+    pub fn sequence(&self) -> u16 {
+        self.sequence
+    }
+}
+
+impl ID for ShardedId {
+    fn id(self) -> u64 {
+        (self.timestamp & ((1 << ShardFrame::TIMESTAMP_BITS) - 1))
+            << (ShardFrame::SHARD_BITS + ShardFrame::SEQUENCE_BITS)
+            | (self.shard as u64) << ShardFrame::SEQUENCE_BITS
+            | self.sequence as u64
+    }
+}
+
+/// 全局 `ShardFrame` 的起始时间，可以在首次使用全局 `ShardFrame` 之前通过 `set_default_epoch` 修改。
+static DEFAULT_EPOCH: AtomicU64 = AtomicU64::new(ShardFrame::DEFAULT_EPOCH);
+
+/// 全局 `ShardFrame` 是否已经被初始化，初始化后起始时间不能再修改。
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    /// 全局 `ShardFrame`，使用 `DEFAULT_EPOCH` 作为起始时间。
+    static ref SHARD_FRAME: ShardFrame = {
+        INITIALIZED.store(true, Ordering::SeqCst);
+        ShardFrame {
+            epoch: DEFAULT_EPOCH.load(Ordering::SeqCst),
+            ..ShardFrame::new()
+        }
+    };
+}
+
+/// 在首次调用 `next_sharded_id` 之前修改全局 `ShardFrame` 的起始时间，全局 `ShardFrame` 已经被使用时修改不会
+/// 生效并返回 `false`。
+pub fn set_default_epoch(epoch: SystemTime) -> bool {
+    if INITIALIZED.load(Ordering::SeqCst) {
+        return false;
+    }
+
+    DEFAULT_EPOCH.store(epoch_millis(epoch), Ordering::SeqCst);
+    true
+}
+
+/// 使用全局 `ShardFrame` 为分片 `shard` 生成下一个 id。
+pub fn next_sharded_id(shard: u16) -> ShardedId {
+    SHARD_FRAME.next(shard)
+}
+
+/// `next_sharded_id` 的非 panic 版本，见 `ShardFrame::try_next`。
+pub fn try_next_sharded_id(shard: u16) -> Result<ShardedId, FastsendError> {
+    SHARD_FRAME.try_next(shard)
+}
@@ -0,0 +1,333 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+use std::fmt;
+
+/// 参与模拟的生成器种类。
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum GeneratorKind {
+    /// `fastsend::next_token` 生成的 `Token`。
+    Token,
+
+    /// `TimeSerialer` 生成的序列号（feed 的数据视为随机数据）。
+    TimeSerial,
+}
+
+impl fmt::Display for GeneratorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeneratorKind::Token => write!(f, "Token"),
+            GeneratorKind::TimeSerial => write!(f, "TimeSerialer"),
+        }
+    }
+}
+
+/// ## 冲突模拟
+///
+/// `Simulation` 在虚拟时间上模拟 `devices` 台设备、每台设备 `threads` 个线程、每个线程每秒生成 `rate` 个 id，
+/// 持续 `seconds` 秒，期间每台设备随机重启 `restarts` 次（重启后的进程与重启前处于同一秒），最终统计各生成器
+/// 产生的 id 中重复的数量，用于在上线之前评估设备数量、设备号配置等选择是否安全。
+///
+/// 模拟按照 fastsend 的实际行为建模，但不依赖任何全局状态，也不会真正等待时间流逝：
+///
+///   - `Token` 的高 32 位为秒级游标，低 32 位依次为 16 位发号序号（每个游标 65536 个，按 `Block` 打乱顺序）、
+///     8 位设备号（未配置时为进程号的后 8 位）以及 8 位线程号（补充 `Block` 的线程，视为随机）；开启
+///     `pause_on_start` 时进程启动及游标耗尽时会等待下一秒，期间的请求顺延，否则游标直接递增（可能超前于时间）；
+///   - `TimeSerialer` 的序列号由秒级时间、3 位设备号（未配置时每次随机）以及 4 位 feed 哈希组成，进程内重复时
+///     顺延到下一秒（未配置设备号时重新随机设备号），不同进程之间不做判重。
+///
+/// 第 `n` 台设备的设备号为 `n % 256`，因此设备数量超过 256 时会出现设备号重复。相同的 `seed` 得到相同的结果。
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    /// 设备数量，缺省配置是 4。
+    devices: usize,
+
+    /// 每台设备的线程数量，缺省配置是 4。
+    threads: u64,
+
+    /// 每个线程每秒生成的 id 数量，缺省配置是 1000。
+    rate: u64,
+
+    /// 模拟的时长（秒），缺省配置是 60。
+    seconds: u64,
+
+    /// 每台设备重启的次数，缺省配置是 0。
+    restarts: usize,
+
+    /// 是否为每台设备配置了设备号，缺省配置是 true。
+    device_ids: bool,
+
+    /// 是否模拟 'pause_on_start' 特性，缺省配置与当前编译时的特性一致。
+    pause_on_start: bool,
+
+    /// 参与模拟的生成器，缺省配置是全部生成器。
+    kinds: Vec<GeneratorKind>,
+
+    /// 随机数种子，缺省配置是 0。
+    seed: u64,
+}
+
+impl Simulation {
+    pub fn new() -> Simulation {
+        Simulation {
+            devices: 4,
+            threads: 4,
+            rate: 1000,
+            seconds: 60,
+            restarts: 0,
+            device_ids: true,
+            pause_on_start: cfg!(feature = "pause_on_start"),
+            kinds: vec![GeneratorKind::Token, GeneratorKind::TimeSerial],
+            seed: 0,
+        }
+    }
+
+    pub fn devices(mut self, devices: usize) -> Self {
+        self.devices = devices;
+        self
+    }
+
+    pub fn threads(mut self, threads: u64) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    pub fn rate(mut self, rate: u64) -> Self {
+        self.rate = rate;
+        self
+    }
+
+    pub fn seconds(mut self, seconds: u64) -> Self {
+        assert!(seconds > 0);
+        self.seconds = seconds;
+        self
+    }
+
+    pub fn restarts(mut self, restarts: usize) -> Self {
+        self.restarts = restarts;
+        self
+    }
+
+    pub fn device_ids(mut self, device_ids: bool) -> Self {
+        self.device_ids = device_ids;
+        self
+    }
+
+    pub fn pause_on_start(mut self, pause_on_start: bool) -> Self {
+        self.pause_on_start = pause_on_start;
+        self
+    }
+
+    pub fn kinds(mut self, kinds: &[GeneratorKind]) -> Self {
+        self.kinds = kinds.to_vec();
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn run(&self) -> Report {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        let outcomes = self
+            .kinds
+            .iter()
+            .map(|&kind| {
+                let mut seen = HashSet::new();
+                let mut generated = 0;
+
+                for device in 0..self.devices {
+                    for (start, end) in self.lifetimes(&mut rng) {
+                        let mut process = Process::new(self, device, start);
+                        let ids = match kind {
+                            GeneratorKind::Token => process.tokens(end, &mut rng),
+                            GeneratorKind::TimeSerial => process.serials(end, &mut rng),
+                        };
+
+                        generated += ids.len() as u64;
+                        seen.extend(ids);
+                    }
+                }
+
+                Outcome {
+                    kind,
+                    generated,
+                    collisions: generated - seen.len() as u64,
+                }
+            })
+            .collect();
+
+        Report { outcomes }
+    }
+
+    /// 将模拟时长按随机的重启时间点划分为各个进程的生命周期 [start, end)。
+    fn lifetimes(&self, rng: &mut StdRng) -> Vec<(u64, u64)> {
+        let mut points = (0..self.restarts)
+            .map(|_| rng.gen_range(0..self.seconds))
+            .collect::<Vec<_>>();
+        points.push(0);
+        points.push(self.seconds);
+        points.sort_unstable();
+
+        points.windows(2).map(|pair| (pair[0], pair[1])).collect()
+    }
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 一个进程在生命周期内的状态。
+struct Process<'a> {
+    simulation: &'a Simulation,
+
+    /// 进程启动的时间（秒）。
+    start: u64,
+
+    /// 设备号，未配置设备号时为 `None`。
+    device: Option<u8>,
+}
+
+impl<'a> Process<'a> {
+    fn new(simulation: &'a Simulation, device: usize, start: u64) -> Self {
+        Process {
+            simulation,
+            start,
+            device: simulation.device_ids.then_some(device as u8),
+        }
+    }
+
+    fn demand(&self) -> u64 {
+        self.simulation.threads * self.simulation.rate
+    }
+
+    fn tokens(&mut self, end: u64, rng: &mut StdRng) -> Vec<u64> {
+        const SEQUENCES: u32 = 1 << 16;
+        const BLOCK: u32 = 8;
+
+        // 未配置设备号时使用进程号的后 8 位，每次重启都会变化
+        let c = self.device.unwrap_or_else(|| rng.gen()) as u64;
+        let pause = self.simulation.pause_on_start;
+
+        // `BlockFrame::new` 时的游标，开启 `pause_on_start` 时等待至下一秒
+        let mut cursor = self.start + pause as u64;
+        let mut order = Vec::new();
+        let mut used = SEQUENCES;
+        let mut d = 0;
+
+        let mut ids = Vec::new();
+        let mut backlog = 0;
+        for now in self.start..end {
+            backlog += self.demand();
+
+            while backlog > 0 {
+                if used == SEQUENCES {
+                    // `Cursor::next` 需要等待时间超过当前游标，否则在本秒内停顿；`Cursor::incr` 直接递增
+                    cursor = if !pause {
+                        cursor + 1
+                    } else if now > cursor {
+                        now
+                    } else {
+                        break;
+                    };
+
+                    order = (0..SEQUENCES / BLOCK).collect();
+                    order.shuffle(rng);
+                    used = 0;
+                    d = rng.gen::<u8>() as u64;
+                }
+
+                let sequence = order[(used / BLOCK) as usize] * BLOCK + used % BLOCK;
+                ids.push(cursor << 32 | (sequence as u64) << 16 | c << 8 | d);
+                used += 1;
+                backlog -= 1;
+            }
+        }
+
+        ids
+    }
+
+    fn serials(&mut self, end: u64, rng: &mut StdRng) -> Vec<u64> {
+        // 全局 slot 初始化时，开启 `pause_on_start` 会等待至下一秒
+        let first = self.start + self.simulation.pause_on_start as u64;
+
+        let mut slot = HashSet::new();
+        let mut ids = Vec::new();
+
+        // 尚未完成的请求（feed 的哈希值），重复的序列号需要等到下一秒才能重新生成
+        let mut backlog = Vec::new();
+        for now in self.start..end {
+            backlog.extend((0..self.demand()).map(|_| rng.gen_range(0..10000u64)));
+            if now < first {
+                continue;
+            }
+
+            backlog.retain(|&bucket| loop {
+                let digits = self.device.unwrap_or_else(|| rng.gen()) as u64;
+                let id = now << 32 | digits << 16 | bucket;
+                if slot.insert(id) {
+                    ids.push(id);
+                    break false;
+                }
+
+                // 设备号固定时同一秒内只会得到相同的序列号，未配置时重新随机设备号
+                if self.device.is_some() {
+                    break true;
+                }
+            });
+        }
+
+        ids
+    }
+}
+
+/// 单个生成器的模拟结果。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Outcome {
+    pub kind: GeneratorKind,
+
+    /// 生成的 id 数量。
+    pub generated: u64,
+
+    /// 重复的 id 数量（与之前生成的某个 id 相同即计为一次）。
+    pub collisions: u64,
+}
+
+/// `Simulation::run` 的结果，按生成器分别统计。
+#[derive(Debug, Clone)]
+pub struct Report {
+    outcomes: Vec<Outcome>,
+}
+
+impl Report {
+    pub fn outcomes(&self) -> &[Outcome] {
+        &self.outcomes
+    }
+
+    pub fn outcome(&self, kind: GeneratorKind) -> Option<&Outcome> {
+        self.outcomes.iter().find(|outcome| outcome.kind == kind)
+    }
+
+    /// 所有生成器的重复总数。
+    pub fn collisions(&self) -> u64 {
+        self.outcomes.iter().map(|outcome| outcome.collisions).sum()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for outcome in &self.outcomes {
+            writeln!(
+                f,
+                "{}: {} generated, {} collisions",
+                outcome.kind, outcome.generated, outcome.collisions
+            )?;
+        }
+        Ok(())
+    }
+}
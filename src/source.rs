@@ -0,0 +1,189 @@
+use crate::{Serialer, Token, ID};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+/// `IdSource` 生成的 id，按生成方案的不同可能是 64 位整数、128 位整数或字符串。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum IdValue {
+    /// 64 位的 id，如 `Token`。
+    U64(u64),
+
+    /// 128 位的 id，如 UUID、`Flake128`。
+    U128(u128),
+
+    /// 字符串形式的序列号，如 `TimeSerialer`、`TypeId`。
+    String(String),
+}
+
+impl IdValue {
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            IdValue::U64(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// 整数形式的 id，64 位的 id 会被扩展为 u128，字符串返回 `None`。
+    pub fn as_u128(&self) -> Option<u128> {
+        match self {
+            IdValue::U64(n) => Some(*n as u128),
+            IdValue::U128(n) => Some(*n),
+            IdValue::String(_) => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            IdValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// 整数以十进制展示，字符串原样展示。
+impl fmt::Display for IdValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdValue::U64(n) => write!(f, "{}", n),
+            IdValue::U128(n) => write!(f, "{}", n),
+            IdValue::String(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<u64> for IdValue {
+    fn from(n: u64) -> Self {
+        IdValue::U64(n)
+    }
+}
+
+impl From<u128> for IdValue {
+    fn from(n: u128) -> Self {
+        IdValue::U128(n)
+    }
+}
+
+impl From<String> for IdValue {
+    fn from(s: String) -> Self {
+        IdValue::String(s)
+    }
+}
+
+impl From<Token> for IdValue {
+    fn from(token: Token) -> Self {
+        IdValue::U64(token.id())
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<crate::serial::uuid::UUID> for IdValue {
+    fn from(uuid: crate::serial::uuid::UUID) -> Self {
+        IdValue::U128(u128::from_be_bytes(uuid.to_bytes()))
+    }
+}
+
+#[cfg(feature = "typeid")]
+impl From<crate::serial::typeid::TypeId> for IdValue {
+    fn from(id: crate::serial::typeid::TypeId) -> Self {
+        IdValue::String(id.to_string())
+    }
+}
+
+#[cfg(feature = "flake128")]
+impl From<crate::serial::flake128::Flake128> for IdValue {
+    fn from(flake: crate::serial::flake128::Flake128) -> Self {
+        IdValue::U128(flake.as_u128())
+    }
+}
+
+#[cfg(feature = "hlc")]
+impl From<crate::hlc::HlcId> for IdValue {
+    fn from(id: crate::hlc::HlcId) -> Self {
+        IdValue::U128(id.as_u128())
+    }
+}
+
+/// ## 统一的 id 生成接口
+///
+/// `IdSource` 将不同的 id 生成方案统一为同一个接口，应用代码可以对 `IdSource` 泛型（或持有 `Box<dyn IdSource>`），
+/// 按部署环境选择使用 `Token`、`Flake128` 还是 UUIDv7 等方案，而不必修改生成 id 的代码：
+///
+/// ```
+/// use fastsend::{IdSource, IdValue, SerialerSource, TimeSerialer, TokenSource};
+///
+/// # futures::executor::block_on(async {
+/// let sources: Vec<Box<dyn IdSource>> = vec![
+///     Box::new(TokenSource),
+///     Box::new(SerialerSource::new(TimeSerialer::default())),
+/// ];
+///
+/// for source in &sources {
+///     match source.next().await {
+///         IdValue::U64(id) => assert!(id > 0),
+///         IdValue::String(serial) => assert!(!serial.is_empty()),
+///         IdValue::U128(_) => unreachable!(),
+///     }
+/// }
+/// # });
+/// ```
+///
+/// 与 `next_token` 相同，`next` 不返回错误，生成失败时 panic；需要处理错误时应直接使用各方案的 `try_` 系列方法。
+pub trait IdSource: Send + Sync {
+    fn next(&self) -> Pin<Box<dyn Future<Output = IdValue> + Send + '_>>;
+}
+
+impl<T: IdSource + ?Sized> IdSource for Box<T> {
+    fn next(&self) -> Pin<Box<dyn Future<Output = IdValue> + Send + '_>> {
+        (**self).next()
+    }
+}
+
+impl<T: IdSource + ?Sized> IdSource for std::sync::Arc<T> {
+    fn next(&self) -> Pin<Box<dyn Future<Output = IdValue> + Send + '_>> {
+        (**self).next()
+    }
+}
+
+/// 全局生成器（`next_token`）生成的 `Token`，输出 `IdValue::U64`。
+#[derive(Debug, Default, Copy, Clone)]
+pub struct TokenSource;
+
+impl IdSource for TokenSource {
+    fn next(&self) -> Pin<Box<dyn Future<Output = IdValue> + Send + '_>> {
+        Box::pin(async { crate::next_token().await.into() })
+    }
+}
+
+/// 以 `serialer` 为原型，每次调用 `next` 时克隆一个 `Serialer` 并构建序列号（不提供 `feed` 数据），适用于 `TimeSerialer`、
+/// `UUIDSerialer`（V7）、`Flake128Serialer` 等不依赖外部数据的序列号。需要 `feed` 数据时可以在原型上预先提供，之后的
+/// 每次构建都会使用相同的数据。
+#[derive(Debug, Clone)]
+pub struct SerialerSource<S> {
+    prototype: S,
+}
+
+impl<S> SerialerSource<S> {
+    pub fn new(serialer: S) -> SerialerSource<S> {
+        SerialerSource {
+            prototype: serialer,
+        }
+    }
+}
+
+impl<S> IdSource for SerialerSource<S>
+where
+    S: Serialer + Clone + Send + Sync + 'static,
+    S::Output: Into<IdValue>,
+    S::Error: fmt::Debug,
+{
+    fn next(&self) -> Pin<Box<dyn Future<Output = IdValue> + Send + '_>> {
+        let build = self.prototype.clone().build();
+        Box::pin(async move {
+            build
+                .await
+                .map(Into::into)
+                .unwrap_or_else(|error| panic!("{:?} on IdSource::next()", error))
+        })
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{Serialer, Token};
+use http::header::HeaderName;
+use http::{HeaderValue, Request, Response};
+use std::convert::Infallible;
+use std::fmt::{self, Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// `MakeId` 代表为每个请求生成 id 的方式，生成的 id 会被写入请求的 extensions 中。
+///
+/// fastsend 为 `NextToken`（通过 `fastsend::next_token` 生成 `Token`）以及所有返回 `Serialer` 的函数
+/// （如 `UUIDSerialer::new_v7`、`TimeSerialer::new`）实现了该 trait，此时写入 extensions 的是
+/// `Serialer::Output`。
+pub trait MakeId: Clone + Send + 'static {
+    type Id: Clone + Send + Sync + 'static;
+
+    /// 生成 id 失败时的错误，需要能够转换为内部服务的错误类型。
+    type Error;
+
+    fn make_id(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Id, Self::Error>> + Send + 'static>>;
+}
+
+/// 通过 `fastsend::next_token` 为每个请求生成 `Token`，与 `next_token` 一样不会失败，因此可以用于错误类型为
+/// `Infallible` 的服务（如 axum）。
+#[derive(Debug, Copy, Clone, Default)]
+pub struct NextToken;
+
+impl MakeId for NextToken {
+    type Id = Token;
+
+    type Error = Infallible;
+
+    fn make_id(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Id, Self::Error>> + Send + 'static>> {
+        Box::pin(async { Ok(crate::next_token().await) })
+    }
+}
+
+impl<F, S> MakeId for F
+where
+    F: Fn() -> S + Clone + Send + 'static,
+    S: Serialer,
+    S::Output: Clone + Send + Sync + 'static,
+{
+    type Id = S::Output;
+
+    type Error = S::Error;
+
+    fn make_id(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Id, Self::Error>> + Send + 'static>> {
+        self().build()
+    }
+}
+
+/// 写入 id 的请求头以及由 id 得到请求头取值的方式。
+struct Header<I> {
+    name: HeaderName,
+    value: fn(&I) -> HeaderValue,
+}
+
+impl<I> Clone for Header<I> {
+    fn clone(&self) -> Self {
+        Header {
+            name: self.name.clone(),
+            value: self.value,
+        }
+    }
+}
+
+impl<I> Debug for Header<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Header")
+            .field("name", &self.name)
+            .field("value", &"fn(&Id) -> HeaderValue")
+            .finish()
+    }
+}
+
+/// ## 为请求附加 id 的 tower 中间件
+///
+/// `IdLayer` 为每个 `http::Request` 生成一个新的 id（由 `MakeId` 决定）并写入请求的 extensions，下游服务可以通过
+/// `request.extensions().get::<Token>()`（或对应的 id 类型）读取。`IdLayer` 只依赖 `tower-layer`、
+/// `tower-service` 和 `http`，不依赖具体的异步运行时或 web 框架，因此可以同时用于 tonic、hyper 等技术栈。
+///
+/// 通过 `header` 指定请求头后，id 还会同时写入请求头和响应头（同时开启 'tracing' feature 时，下游服务的调用会包含
+/// 在一个携带 `request_id` 字段的 span 中），axum 的 `RequestIdLayer` 即是以此实现的。
+///
+/// id 生成失败时（仅可能发生在 `Serialer` 上），请求不会被转发给下游服务，错误转换为内部服务的错误类型后返回。
+#[derive(Debug, Clone)]
+pub struct IdLayer<M: MakeId = NextToken> {
+    make_id: M,
+    header: Option<Header<M::Id>>,
+}
+
+impl IdLayer {
+    /// 使用 `fastsend::next_token` 为每个请求生成 `Token`。
+    pub fn new() -> IdLayer {
+        IdLayer::with(NextToken)
+    }
+}
+
+impl<M: MakeId> IdLayer<M> {
+    /// 使用指定的 `MakeId` 生成 id，例如 `IdLayer::with(UUIDSerialer::new_v7)`。
+    pub fn with(make_id: M) -> IdLayer<M> {
+        IdLayer {
+            make_id,
+            header: None,
+        }
+    }
+
+    /// 将 id 以 `value` 转换后的取值写入请求头及响应头 `header`，例如
+    /// `IdLayer::new().header(name, |token| HeaderValue::from(token.id()))`。
+    pub fn header(mut self, header: HeaderName, value: fn(&M::Id) -> HeaderValue) -> Self {
+        self.header = Some(Header {
+            name: header,
+            value,
+        });
+        self
+    }
+}
+
+impl Default for IdLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, M: MakeId> Layer<S> for IdLayer<M> {
+    type Service = IdService<S, M>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        IdService {
+            inner,
+            make_id: self.make_id.clone(),
+            header: self.header.clone(),
+        }
+    }
+}
+
+/// 由 `IdLayer` 包装的服务。
+#[derive(Debug, Clone)]
+pub struct IdService<S, M: MakeId = NextToken> {
+    inner: S,
+    make_id: M,
+    header: Option<Header<M::Id>>,
+}
+
+impl<S, M, B, R> Service<Request<B>> for IdService<S, M>
+where
+    S: Service<Request<B>, Response = Response<R>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    M: MakeId,
+    M::Error: Into<S::Error>,
+    B: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        // `poll_ready` 只保证了当前的 `inner` 已就绪，因此将其换出用于本次请求，并留下一个克隆供后续请求使用
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let id = self.make_id.make_id();
+        let header = self.header.clone();
+
+        Box::pin(async move {
+            let id = id.await.map_err(Into::into)?;
+            let header = header.map(|header| ((header.value)(&id), header.name));
+            if let Some((value, name)) = &header {
+                request.headers_mut().insert(name.clone(), value.clone());
+            }
+            request.extensions_mut().insert(id);
+
+            #[cfg(feature = "tracing")]
+            let mut response = match &header {
+                Some((value, _)) => {
+                    use tracing::Instrument;
+                    let request_id = value.to_str().unwrap_or_default();
+                    let span = tracing::info_span!("request", request_id);
+                    inner.call(request).instrument(span).await?
+                }
+                None => inner.call(request).await?,
+            };
+
+            #[cfg(not(feature = "tracing"))]
+            let mut response = inner.call(request).await?;
+
+            if let Some((value, name)) = header {
+                response.headers_mut().insert(name, value);
+            }
+            Ok(response)
+        })
+    }
+}
@@ -0,0 +1,136 @@
+use crate::encoding::crockford::ALPHABET;
+use crate::encoding::Radix;
+use std::fmt;
+
+/// 支持码中哈希部分的字符数，每个字符 5 bits。
+const HASH_LEN: usize = 7;
+
+/// 支持码中哈希部分的位数。
+pub const SUPPORT_HASH_BITS: u32 = 5 * HASH_LEN as u32;
+
+/// ## 支持码
+///
+/// 由 id 确定性地生成 8 位的短码（7 位哈希 + 1 位校验位），便于客服或用户在电话、工单中口头引用某条记录，而数据库中
+/// 仍然保存完整的 u64 id：
+///
+/// ```
+/// let id = 0x61b2_d3a5_0042_7f1e;
+/// let code = fastsend::support_code(id);
+/// assert_eq!(code.len(), 8);
+///
+/// // 数据库中额外保存 `support_hash(id)`（并建立索引），通过支持码查询时先解析出哈希值
+/// let hash = fastsend::parse_support_code(&code.to_lowercase()).unwrap();
+/// assert_eq!(hash, fastsend::support_hash(id));
+/// ```
+///
+/// 支持码使用 Crockford base32 字符表，解析时不区分大小写，忽略 '-' 和空白字符，并将 'O' 视为 '0'、'I'/'L' 视为
+/// '1'；校验位可以发现单个字符的错误以及绝大多数相邻字符的颠倒。
+///
+/// 支持码只保留 id 哈希值中的 35 bits，不能还原出 id，不同的 id 也可能得到相同的支持码（约 18 万条记录时出现重复的
+/// 概率达到一半），因此通过支持码查询时可能匹配到多条记录，需要结合其他信息（如用户）确认。哈希算法是固定的，在不同
+/// 进程、不同版本之间保持一致。
+pub fn support_code(id: u64) -> String {
+    let hash = support_hash(id);
+    let mut code = radix()
+        .encode_fixed(hash, HASH_LEN)
+        .expect("support hash must fit in HASH_LEN digits");
+    code.push(ALPHABET.as_bytes()[check_digit(hash) as usize] as char);
+    code
+}
+
+/// `support_code` 中使用的哈希值（低 35 bits），用于在数据库中建立反查索引。
+pub fn support_hash(id: u64) -> u64 {
+    // murmur3 的 64 位终结混淆（fmix64），取高位作为哈希值
+    let mut hash = id;
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^= hash >> 33;
+
+    hash >> (u64::BITS - SUPPORT_HASH_BITS)
+}
+
+/// 解析 `support_code` 生成的支持码并校验校验位，返回与 `support_hash` 一致的哈希值。
+pub fn parse_support_code(code: &str) -> Result<u64, SupportCodeError> {
+    let normalized = code
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| match c.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        })
+        .collect::<String>();
+
+    if normalized.len() != HASH_LEN + 1 || !normalized.is_ascii() {
+        return Err(SupportCodeError::InvalidLength);
+    }
+
+    let (digits, check) = normalized.split_at(HASH_LEN);
+    let hash = radix()
+        .decode(digits)
+        .map_err(|_| SupportCodeError::InvalidChar(invalid_char(digits)))?;
+    let check = radix()
+        .decode(check)
+        .map_err(|_| SupportCodeError::InvalidChar(invalid_char(check)))?;
+
+    if check != check_digit(hash) {
+        return Err(SupportCodeError::Checksum);
+    }
+
+    Ok(hash)
+}
+
+fn radix() -> Radix<'static> {
+    Radix::new(ALPHABET).expect("ALPHABET must be a valid alphabet")
+}
+
+/// `s` 中第一个不在字符表中的字符。
+fn invalid_char(s: &str) -> char {
+    s.chars()
+        .find(|c| !ALPHABET.contains(*c))
+        .unwrap_or_default()
+}
+
+/// 以 32 为模的 Luhn 校验位（Luhn mod N），计算范围为 `hash` 的 `HASH_LEN` 个 base32 数字。
+fn check_digit(hash: u64) -> u64 {
+    const N: u64 = 32;
+
+    let sum = (0..HASH_LEN)
+        .map(|position| {
+            // 从最低位起，偶数位置的数字加倍
+            let digit = hash >> (5 * position) & (N - 1);
+            let addend = if position % 2 == 0 { digit * 2 } else { digit };
+            addend / N + addend % N
+        })
+        .sum::<u64>();
+
+    (N - sum % N) % N
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SupportCodeError {
+    /// 去除分隔符后长度不是 8 个字符。
+    InvalidLength,
+
+    /// 包含字符表之外的字符。
+    InvalidChar(char),
+
+    /// 校验位不匹配，通常是输入时写错了某个字符。
+    Checksum,
+}
+
+impl fmt::Display for SupportCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SupportCodeError::InvalidLength => {
+                write!(f, "support code must contain {} characters", HASH_LEN + 1)
+            }
+            SupportCodeError::InvalidChar(c) => write!(f, "invalid character {:?}", c),
+            SupportCodeError::Checksum => write!(f, "support code checksum mismatch"),
+        }
+    }
+}
+
+impl std::error::Error for SupportCodeError {}
@@ -0,0 +1,162 @@
+use crate::{Cursor, Serial, Serialer, ID};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::process;
+use std::sync::Mutex;
+
+/// # 租户隔离的 id
+///
+/// `TenantFrame` 为每个租户维护独立的序列号，并在 id 中预留 `tenant_bits` 位用于存放租户号，由高到低依次为：
+///
+/// | 游标（`Cursor`） | 租户号 | 设备号 | 租户内序列号 |
+/// |:--------------:|:-----:|:-----:|:----------:|
+/// |    32 bits     | `tenant_bits` | 8 bits | 24 - `tenant_bits` bits |
+///
+/// 由于租户号占据 id 中固定的位段，不同租户的 id 空间天然互不重叠，即使某个租户的 id 泄露也无法推测其他租户的
+/// id 发放情况。租户号位数越多，单个租户每秒可生成的 id 越少（`2^(24 - tenant_bits)` 个），超出后将向后借用一秒；
+/// 与 `BlockFrame` 一样，开启 'pause_on_start' feature 时 `TenantFrame::new` 会等待至下一秒，之后生成的 id 的
+/// 游标都晚于构建时的游标，以保证程序在一秒内重启时不会与之前生成的 id 冲突（借用的游标超前较多时仍可能冲突）；
+/// 未开启时由调用方保证重启之间的唯一性。
+///
+/// 每个租户的状态在首次生成 id 时创建且不会被移除，状态数量以 `2^tenant_bits` 为上限（最多 65536 个）。
+#[derive(Debug)]
+pub struct TenantFrame {
+    tenant_bits: u32,
+
+    /// 可以使用的最早的游标，开启 'pause_on_start' 时为构建之后的下一秒。
+    start: u32,
+
+    /// 各租户上一次生成 id 时的游标及序列号。
+    states: Mutex<HashMap<u32, (u32, u32)>>,
+}
+
+impl TenantFrame {
+    /// 设备号位数，与 `Token` 中的设备号保持一致。
+    pub const DEVICE_BITS: u32 = 8;
+
+    /// 游标以下可供租户号和序列号分配的位数。
+    const FREE_BITS: u32 = 32 - Self::DEVICE_BITS;
+
+    /// 使用 `tenant_bits`（1..=16）位存放租户号构建 `TenantFrame`，开启 'pause_on_start' 时等待至下一秒。
+    pub fn new(tenant_bits: u32) -> TenantFrame {
+        assert!((1..=16).contains(&tenant_bits));
+
+        #[cfg(feature = "pause_on_start")]
+        let start = Cursor::new().next().into_inner();
+        #[cfg(not(feature = "pause_on_start"))]
+        let start = 0;
+
+        TenantFrame {
+            tenant_bits,
+            start,
+            states: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn tenant_bits(&self) -> u32 {
+        self.tenant_bits
+    }
+
+    /// 为租户 `tenant` 生成下一个 `TenantToken`，`tenant` 超出 `tenant_bits` 位所能表示的范围时会 panic。
+    pub fn next_token_for(&self, tenant: u32) -> TenantToken {
+        assert!(
+            tenant < 1 << self.tenant_bits,
+            "tenant {} exceeds {} bits",
+            tenant,
+            self.tenant_bits
+        );
+
+        let sequence_max = (1u32 << (Self::FREE_BITS - self.tenant_bits)) - 1;
+        let cursor = Cursor::new().into_inner().max(self.start);
+
+        let (cursor, sequence) = {
+            let mut states = self.states.lock().unwrap();
+            let last = states.entry(tenant).or_insert((0, 0));
+
+            *last = if cursor > last.0 {
+                (cursor, 0)
+            } else if last.1 == sequence_max {
+                (last.0 + 1, 0)
+            } else {
+                (last.0, last.1 + 1)
+            };
+
+            *last
+        };
+
+        TenantToken {
+            cursor,
+            tenant,
+            tenant_bits: self.tenant_bits,
+            device: crate::DEVICE_ID.unwrap_or(process::id() as u8),
+            sequence,
+        }
+    }
+}
+
+/// `TenantToken` 是 `TenantFrame` 生成的标记，与 `Token` 一样可以通过 `ID::id` 转化为 u64，或作为 `Serial`
+/// 提供给 `Serialer`。
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TenantToken {
+    cursor: u32,
+    tenant: u32,
+    tenant_bits: u32,
+    device: u8,
+    sequence: u32,
+}
+
+impl TenantToken {
+    /// 从 u64 id 中还原 `TenantToken`，`tenant_bits` 需与生成时的 `TenantFrame` 一致。
+    pub fn from_id(id: u64, tenant_bits: u32) -> TenantToken {
+        assert!((1..=16).contains(&tenant_bits));
+
+        let sequence_bits = TenantFrame::FREE_BITS - tenant_bits;
+        let low = id as u32;
+
+        TenantToken {
+            cursor: (id >> 32) as u32,
+            tenant: low >> (32 - tenant_bits),
+            tenant_bits,
+            device: (low >> sequence_bits) as u8,
+            sequence: low & ((1 << sequence_bits) - 1),
+        }
+    }
+
+    pub fn tenant(&self) -> u32 {
+        self.tenant
+    }
+
+    pub fn sequence(&self) -> u32 {
+        self.sequence
+    }
+}
+
+impl ID for TenantToken {
+    fn id(self) -> u64 {
+        let sequence_bits = TenantFrame::FREE_BITS - self.tenant_bits;
+        let low = self.tenant << (32 - self.tenant_bits)
+            | (self.device as u32) << sequence_bits
+            | self.sequence;
+
+        (self.cursor as u64) << 32 | low as u64
+    }
+}
+
+impl Serial for TenantToken {
+    fn serial<S: Serialer>(self, serialer: &mut S) {
+        serialer.feed(&self.id().to_be_bytes());
+    }
+}
+
+lazy_static! {
+    /// 全局 `TenantFrame`，使用 `DEFAULT_TENANT_BITS` 位存放租户号。
+    static ref TENANT_FRAME: TenantFrame = TenantFrame::new(DEFAULT_TENANT_BITS);
+}
+
+/// 全局 `TenantFrame` 的租户号位数，即最多支持 256 个租户，单个租户每秒最多生成 65536 个 id。
+pub const DEFAULT_TENANT_BITS: u32 = 8;
+
+/// 使用全局 `TenantFrame` 为租户 `tenant` 生成下一个 `TenantToken`，与 `next_token` 相对应。
+pub async fn next_token_for(tenant: u32) -> TenantToken {
+    TENANT_FRAME.next_token_for(tenant)
+}
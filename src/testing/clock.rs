@@ -0,0 +1,65 @@
+use chrono::{DateTime, TimeZone};
+use lazy_static::lazy_static;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+
+/// `Clock` 代表 fastsend 中所有基于时间的组件所使用的时间来源，通过 `set_clock` 替换后，`Cursor`、
+/// `TimeSerialer` 以及各个按时间排序的 `Serialer` 都将使用 `Clock::now` 作为当前时间。
+pub trait Clock: Send + Sync + 'static {
+    fn now(&self) -> SystemTime;
+
+    /// 组件需要等待时间流逝时（如 `Cursor::next` 等待下一秒）调用，虚拟时钟应将时间推进至少 `duration`，否则组件
+    /// 会一直自旋等待。
+    fn advance(&self, duration: Duration);
+}
+
+/// 被冻结的虚拟时钟，只有在调用 `advance` 时才会推进。
+#[derive(Debug)]
+struct FrozenClock(Mutex<SystemTime>);
+
+impl Clock for FrozenClock {
+    fn now(&self) -> SystemTime {
+        *self.0.lock().unwrap()
+    }
+
+    fn advance(&self, duration: Duration) {
+        *self.0.lock().unwrap() += duration;
+    }
+}
+
+lazy_static! {
+    static ref CLOCK: RwLock<Option<Arc<dyn Clock>>> = RwLock::new(None);
+}
+
+/// 使用自定义的 `Clock` 作为时间来源。
+pub fn set_clock<C: Clock>(clock: C) {
+    *CLOCK.write().unwrap() = Some(Arc::new(clock));
+}
+
+/// 将时间冻结在 `datetime`，此后时间只会通过 `advance` 推进。
+///
+/// 组件需要等待时间流逝时（如 `pause_on_start` 等待下一秒，或 `TimeSerialer` 在同一秒内遇到重复的序列号）不会
+/// 阻塞，而是直接推进冻结的时间。由于 `Cursor` 以秒为单位，`freeze_time` 应在首次生成 id 之前调用，否则已经预先
+/// 生成的 `Block` 仍然使用冻结前的时间。
+pub fn freeze_time<Tz: TimeZone>(datetime: DateTime<Tz>) {
+    set_clock(FrozenClock(Mutex::new(datetime.into())));
+}
+
+/// 将当前的时间来源推进 `duration`，未替换时间来源时不做任何处理。
+pub fn advance(duration: Duration) {
+    advance_mocked(duration);
+}
+
+/// 恢复使用系统时间。
+pub fn reset_clock() {
+    *CLOCK.write().unwrap() = None;
+}
+
+pub(crate) fn now() -> Option<SystemTime> {
+    CLOCK.read().unwrap().as_ref().map(|clock| clock.now())
+}
+
+pub(crate) fn advance_mocked(duration: Duration) -> bool {
+    let clock = CLOCK.read().unwrap().clone();
+    clock.map(|clock| clock.advance(duration)).is_some()
+}
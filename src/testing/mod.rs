@@ -0,0 +1,44 @@
+use lazy_static::lazy_static;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::sync::Mutex;
+
+pub(crate) mod clock;
+pub use clock::{advance, freeze_time, reset_clock, set_clock, Clock};
+
+lazy_static! {
+    /// 确定性模式下全局共享的随机数生成器，为 `None` 时各组件使用各自的熵源。
+    static ref RNG: Mutex<Option<StdRng>> = Mutex::new(None);
+}
+
+/// ## 确定性模式
+///
+/// `seed` 使 fastsend 中的以下随机要素由 `seed` 决定，以便在测试中生成可复现的测试数据（如 golden file 测试）：
+///
+///   - 编译时未提供 `FASTSEND_RANDOM_VALUE` 时的 `RV`（`RV` 只会被解析一次，因此必须在首次生成 id 或序列号
+///     之前调用）；
+///   - V4、V7 版本 `UUID` 的随机部分；
+///   - `BlockFrame` 构造 `Block` 时对批次顺序的打乱，以及 `BlockFrame::entropy_bits` 填充的随机位。
+///
+/// `Token` 的 id 不会因此变得可复现：`Ident` 中的 `c`（未配置设备号时为进程号的低 8 位）和 `d`（线程 ID 经
+/// 进程内随机密钥哈希后的低 8 位）不受 `seed` 影响，每次运行都可能不同。因此 `Token` 以及由其派生的输出（如
+/// `define_id!` 定义的 id、`feed` 了 `Token` 的序列号）不适合用作 golden file，测试中应只比较其中的时间游标等
+/// 确定的部分。
+///
+/// `Random62Serialer` 的输出只由 `feed` 的数据决定，本身就是确定性的，不受 `seed` 影响。所有组件共享同一个随机数
+/// 生成器，因此只有在调用顺序确定（如单线程测试）时输出才是可复现的；id 中的时间部分可以通过 `freeze_time` 固定。
+///
+/// 确定性模式只应在测试中使用，生产环境下开启会使 id 和序列号可被预测，并大幅增加冲突的概率。
+pub fn seed(seed: u64) {
+    *RNG.lock().unwrap() = Some(StdRng::seed_from_u64(seed));
+}
+
+/// 退出确定性模式，各组件恢复使用各自的熵源。
+pub fn unseed() {
+    *RNG.lock().unwrap() = None;
+}
+
+/// 处于确定性模式时，使用全局的随机数生成器执行 `f`，否则返回 `None`。
+pub(crate) fn with_rng<T, F: FnOnce(&mut StdRng) -> T>(f: F) -> Option<T> {
+    RNG.lock().unwrap().as_mut().map(f)
+}
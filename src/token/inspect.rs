@@ -0,0 +1,67 @@
+use crate::block::epoch;
+use crate::Token;
+use std::time::{Duration, SystemTime};
+
+/// `inspect_id` 从 id 中还原出的各个字段，用于排查某个 id 由哪台设备、在什么时间生成。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct IdInfo {
+    /// 游标代表的时间（秒级），按 id 所属纪元的基准起始时间（见 `register_epoch`）计算。游标耗尽时会向后借用，因此该时间
+    /// 可能略晚于实际的生成时间。
+    pub timestamp: SystemTime,
+
+    /// 发号序号，即 `BlockFrame` 在该游标下分配的序号。
+    pub sequence: u16,
+
+    /// id 中的设备字节：配置了设备号时为经过 `RV` 混淆的设备号（见 `IdInfo::original_device`），未配置时为生成进程
+    /// 进程号的后 8 位，两者无法从 id 中区分。
+    pub device: u8,
+
+    /// 线程号（线程 ID 哈希值的后 8 位），设置了标签位数时高位为标签。
+    pub thread_bits: u8,
+
+    /// 通过 `next_token_tagged` 嵌入的标签，按当前进程的标签位数（见 `set_tag_bits`）计算。
+    pub tag: u8,
+
+    /// 纪元版本，按当前进程的版本位数（见 `set_epoch`）计算。
+    pub epoch: u8,
+}
+
+impl IdInfo {
+    /// 使用生成方的 `RV`（编译时的 `FASTSEND_RANDOM_VALUE`）还原混淆前的设备号，即 `FASTSEND_DEVICE_ID` 等来源中
+    /// 配置的原始值。`rv` 与生成方不一致时得到的结果没有意义。
+    pub fn original_device(&self, rv: u8) -> u8 {
+        // `DEVICE_ID` 混淆过程（循环左移 3 位后与 `RV` 异或，共 3 次）的逆过程
+        (0..3).fold(self.device, |device, _| (device ^ rv).rotate_right(3))
+    }
+
+    /// 使用当前程序编译时提供的 `FASTSEND_RANDOM_VALUE` 还原混淆前的设备号，适用于检查工具与生成方使用相同的
+    /// 构建配置的场合；编译时未提供 `FASTSEND_RANDOM_VALUE` 时返回 `None`。
+    pub fn build_original_device(&self) -> Option<u8> {
+        option_env!("FASTSEND_RANDOM_VALUE")
+            .and_then(|var| var.parse::<u8>().ok())
+            .map(|rv| self.original_device(rv))
+    }
+}
+
+/// 还原 `Token` id 中打包的各个字段，是 `Token` 位布局的逆过程：
+///
+/// ```
+/// let info = fastsend::inspect_id(0x0000_0001_1234_5678);
+/// assert_eq!(info.sequence, 0x1234);
+/// assert_eq!(info.device, 0x56);
+/// assert_eq!(info.thread_bits, 0x78);
+/// assert_eq!(info.original_device(0), 0x56u8.rotate_right(9));
+/// ```
+pub fn inspect_id(id: u64) -> IdInfo {
+    let token = Token::from_id(id);
+    let [a, b, c, d] = (id as u32).to_be_bytes();
+
+    IdInfo {
+        timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(epoch::unix_secs(token.cursor)),
+        sequence: u16::from_be_bytes([a, b]),
+        device: c,
+        thread_bits: d,
+        tag: token.tag(),
+        epoch: token.epoch(),
+    }
+}
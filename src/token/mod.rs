@@ -1,5 +1,19 @@
-use crate::{Block, BlockFrame, ConstructBlock, Cursor, Serial, Serialer, ID};
-use std::mem::{self, MaybeUninit};
+use crate::{Block, BlockFrame, ConstructBlock, Cursor, SequenceStride, Serial, Serialer, ID};
+use std::array;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+
+#[cfg(feature = "strict_order")]
+pub(crate) mod ordered;
+
+mod range;
+pub use range::IdRange;
+
+mod partition;
+pub use partition::{partition_of, Granularity, PartitionKey};
+
+mod inspect;
+pub use inspect::{inspect_id, IdInfo};
 
 /// `Token` 是一个完全独立的标记，通常用于表示某个完全独立的事物，其由两个部分组成：
 /// `Cursor` 和 `Ident`，分别代表了 `Token` 生成的时间和该时间下代表事物独立性
@@ -14,6 +28,16 @@ use std::mem::{self, MaybeUninit};
 /// 依赖设备号、线程 ID、进程 ID 以及通过发号机创建的编号，`Ident` 是有可能重复的，只
 /// 有将 `Ident` 和 `Cursor` 组合才能完成一个独立的 `Token`。
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::BigInt)
+)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "bincode", derive(bincode::Encode, bincode::Decode))]
 pub struct Token {
     cursor: Cursor,
     ident: Ident,
@@ -25,6 +49,166 @@ impl Token {
     }
 }
 
+impl Token {
+    /// 从 id 还原 `Token`，是 `ID::id` 的逆过程（`Token::from_id(token.id()) == token`），通常用于从数据库等
+    /// 外部存储中读取已生成的 id。
+    pub fn from_id(id: u64) -> Token {
+        let [a, b, c, d] = (id as u32).to_be_bytes();
+        Token::new(Cursor::from_inner((id >> 32) as u32), Ident { a, b, c, d })
+    }
+
+    /// 用于分片路由的键，即 id 的低 32 位（`Ident`：发号序号、设备号及线程号），不包含时间游标，因此同一个
+    /// `Token` 无论何时计算都会得到相同的结果。通常不需要直接使用，而是通过 `shard_of` 得到分片编号，
+    /// `shard_of(token.id(), n)` 与基于 `shard_key` 计算的结果一致。
+    pub fn shard_key(&self) -> u32 {
+        self.ident.construct()
+    }
+
+    /// 通过 `next_token_tagged` 嵌入的标签，未设置标签位数（见 `set_tag_bits`）时始终为 0。解析其他进程生成的
+    /// id 时，当前进程需要与生成方设置相同的标签位数。
+    pub fn tag(&self) -> u8 {
+        let bits = configured_tag_bits();
+        (self.ident.d as u32)
+            .checked_shr(u8::BITS - bits)
+            .unwrap_or(0) as u8
+    }
+
+    /// id 所属的纪元版本，见 `set_epoch`。
+    pub fn epoch(&self) -> u8 {
+        crate::block::epoch::split(self.cursor.into_inner()).0
+    }
+
+    /// 将 `d` 的高 `bits` 位替换为 `tag`，`tag` 需要能以 `bits` 位表示。
+    pub(crate) fn with_tag(mut self, tag: u8, bits: u32) -> Token {
+        if bits > 0 {
+            let shift = u8::BITS - bits;
+            let mask = 0xffu32 >> bits;
+            self.ident.d = ((self.ident.d as u32 & mask) | (tag as u32) << shift) as u8;
+        }
+        self
+    }
+}
+
+/// 全局 `BlockFrame`（`next_token`）使用的发号序号步长，以及该步长是否已经被使用。
+static SEQUENCE_STRIDE: Mutex<(SequenceStride, bool)> = Mutex::new((SequenceStride::NONE, false));
+
+/// 在首次调用 `next_token` 之前设置全局发号序号的步长：只使用满足 `sequence % step == offset` 的发号序号，见
+/// `BlockFrame::sequence_stride`。例如两个同时写入同一张表的进程分别设置 `(0, 2)` 与 `(1, 2)`，两者生成的 id
+/// 在发号序号的奇偶性上互不相交，无需协调服务。全局生成器已经初始化时设置不会生效并返回 `false`。
+///
+/// # Panics
+///
+/// 参数不满足 `SequenceStride::new` 的要求时 panic。
+pub fn set_sequence_stride(offset: u16, step: u16) -> bool {
+    let stride = SequenceStride::new(offset, step);
+
+    let mut state = SEQUENCE_STRIDE.lock().unwrap();
+    if state.1 {
+        return false;
+    }
+
+    state.0 = stride.with_entropy(state.0.entropy_bits());
+    true
+}
+
+/// 在首次调用 `next_token` 之前设置全局发号序号的随机位数：发号序号的低 `bits` 位由随机数填充，使同一秒内的 id
+/// 无法通过已知的 id 推算（见 `SequenceStride::with_entropy`）。每秒可发放的 `Token` 数量相应地减少为原来的
+/// 1/2^`bits`，建议使用 4~8 位。全局生成器已经初始化时设置不会生效并返回 `false`。
+///
+/// # Panics
+///
+/// 参数不满足 `SequenceStride::with_entropy` 的要求（包括与已设置的步长合计超出上限）时 panic。
+pub fn set_entropy_bits(bits: u32) -> bool {
+    let mut state = SEQUENCE_STRIDE.lock().unwrap();
+    if state.1 {
+        return false;
+    }
+
+    state.0 = state.0.with_entropy(bits);
+    true
+}
+
+/// 由 `set_global_frame` 调用：全局步长与随机位数均未设置且尚未被使用时将其标记为已使用并返回 `true`，否则
+/// 返回 `false`（已设置的配置不能被自行构造的 `BlockFrame` 静默覆盖）。
+#[cfg(not(feature = "strict_order"))]
+pub(crate) fn claim_sequence_stride() -> bool {
+    let mut state = SEQUENCE_STRIDE.lock().unwrap();
+    if state.1 || state.0 != SequenceStride::NONE {
+        return false;
+    }
+
+    state.1 = true;
+    true
+}
+
+/// 获取全局发号序号的步长，仅在初始化全局生成器时调用，调用后步长不能再修改。
+pub(crate) fn sequence_stride() -> SequenceStride {
+    let mut state = SEQUENCE_STRIDE.lock().unwrap();
+    state.1 = true;
+    state.0
+}
+
+/// 为调用方标签（实体类型、环境等）预留的位数（低位），以及标签位数是否已经被使用（`TAG_BITS_USED`），见
+/// `set_tag_bits`。二者放在同一个原子变量中，设置与首次使用之间不会出现先检查后写入的竞争。
+static TAG_BITS: AtomicU32 = AtomicU32::new(0);
+const TAG_BITS_USED: u32 = 1 << 16;
+
+/// 在首次生成 `Token` 之前设置标签位数（0..=8，缺省为 0），之后可以通过 `next_token_tagged` 在 id 中嵌入
+/// 调用方提供的标签，并通过 `Token::tag` 读取。已经生成过 `Token` 时设置不会生效并返回 `false`。
+///
+/// 标签占据 `Ident` 中 `d`（线程号）的高 `bits` 位，不会占用设备号 `c`，因此不影响多设备部署的唯一性：同一进程内
+/// `Token` 的唯一性由 `Cursor` 与发号序号保证，线程号只用于在未配置设备号时进一步区分同一设备上的进程。标签位数越多，
+/// 这部分区分能力越弱，未配置设备号的部署应尽量少用标签位。设置标签位数后 `next_token` 生成的 `Token` 标签为 0。
+///
+/// # Panics
+///
+/// `bits` 超过 8 时 panic。
+pub fn set_tag_bits(bits: u32) -> bool {
+    assert!(bits <= u8::BITS, "tag bits must be within 0..={}", u8::BITS);
+
+    TAG_BITS
+        .fetch_update(Ordering::AcqRel, Ordering::Acquire, |state| {
+            (state & TAG_BITS_USED == 0).then_some(bits)
+        })
+        .is_ok()
+}
+
+/// 读取当前设置的标签位数，不会将其标记为已使用，用于解析已有的 `Token`。
+fn configured_tag_bits() -> u32 {
+    TAG_BITS.load(Ordering::Acquire) & !TAG_BITS_USED
+}
+
+/// 获取用于生成 `Token` 的标签位数，调用后标签位数不能再修改。
+pub(crate) fn tag_bits() -> u32 {
+    let state = TAG_BITS.load(Ordering::Acquire);
+    if state & TAG_BITS_USED != 0 {
+        return state & !TAG_BITS_USED;
+    }
+
+    // 标记为已使用，并取得标记时的标签位数
+    TAG_BITS.fetch_or(TAG_BITS_USED, Ordering::AcqRel) & !TAG_BITS_USED
+}
+
+/// 将 id 稳定地映射到 `n_shards` 个分片中的一个（0..n_shards），各服务可以据此按 id 一致地路由，而不必各自
+/// 解析 id 的位布局。
+///
+/// 映射方式：取 id 的低 32 位（即 `Token::shard_key`），经过 murmur3 的 32 位终结混淆（fmix32）使序号的
+/// 低位变化均匀地扩散到各个位上，再通过乘法缩放（`hash * n_shards >> 32`）映射到分片区间。该映射是固定的，
+/// 不依赖于运行时的随机状态，在不同进程、不同版本之间保持一致；但分片数量变化时大部分 id 的分片会随之变化，
+/// 需要扩缩容时请预先设置足够多的逻辑分片。
+pub fn shard_of(id: u64, n_shards: u32) -> u32 {
+    assert!(n_shards > 0);
+
+    let mut hash = id as u32;
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^= hash >> 16;
+
+    ((hash as u64 * n_shards as u64) >> 32) as u32
+}
+
 impl ID for Token {
     fn id(self) -> u64 {
         let mut bytes = [0; 8];
@@ -77,42 +261,35 @@ impl Serial for Token {
 
 impl ConstructBlock for Token {
     fn construct_block(n: usize, cursor: Cursor) -> Block<Self> {
-        debug_assert!(n <= BlockFrame::<Self>::QUEUE_SIZE);
-
-        let n = n as u16;
-        let size = Block::<Self>::SIZE as u16;
-
-        // 使用 Rust 官方推荐的对数组进行 "逐个元素初始化"，使用了 `MaybeUninit`。
-        // （具体说明请查看 `MaybeUninit` 的文档）
-        let array = unsafe {
-            // Create an uninitialized array of `MaybeUninit`. The `assume_init` is
-            // safe because the type we are claiming to have initialized here is a
-            // bunch of `MaybeUninit`s, which do not require initialization.
-            let mut array: [MaybeUninit<Token>; Block::<Self>::SIZE] =
-                MaybeUninit::uninit().assume_init();
-
-            // Dropping a `MaybeUninit` does nothing. Thus using raw pointer
-            // assignment instead of `ptr::write` does not cause the old
-            // uninitialized value to be dropped. Also if there is a panic during
-            // this loop, we have a memory leak, but there is no memory safety
-            // issue.
-            for (i, elem) in array.iter_mut().enumerate() {
-                let offset = i as u16;
-                *elem = MaybeUninit::new(Token::new(cursor, Ident::new(n * size + offset)));
-            }
+        Self::construct_strided_block(n, cursor, SequenceStride::NONE)
+    }
 
-            // Everything is initialized. Transmute the array to the
-            // initialized type.
-            mem::transmute::<_, [Token; Block::<Self>::SIZE]>(array)
-        };
+    fn construct_strided_block(n: usize, cursor: Cursor, stride: SequenceStride) -> Block<Self> {
+        debug_assert!(n < BlockFrame::<Self>::QUEUE_SIZE / stride.ratio());
 
-        Block::new(array)
+        let size = Block::<Self>::SIZE;
+
+        // `array::from_fn` 按下标逐个初始化数组元素，无需借助 `MaybeUninit`
+        let array: [Token; Block::<Self>::SIZE] =
+            array::from_fn(|i| Token::new(cursor, Ident::new(stride.sequence(n * size + i))));
+
+        // 跳过 `exclude_ids` 登记的区间，未登记时不需要逐个检查
+        if crate::block::blocklist::is_active() {
+            Block::new(array).skip_where(|token| crate::is_excluded(token.id()))
+        } else {
+            Block::new(array)
+        }
     }
 }
 
 /// `Ident` 表示在某一特定时间节点下的一系列具有独立性的要素，目前的实现方式中包含四个部分，见字段说明。
 /// （`c` 和 `d` 的实现详见于 `cd` 函数的注释）
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "bincode", derive(bincode::Encode, bincode::Decode))]
 struct Ident {
     /// `a` 和 `b` 是一组数据，是从发号机中获取的一个 u16 数字拆分成两个字节，这里的发号机特指 `BlockFrame`
     /// 及 `BlockFrame` 所产生的 `Block`。
@@ -124,6 +301,9 @@ struct Ident {
 
     /// `d` 的实现依赖于线程 ID，在通常情况下无法从 Rust 标准库中获取 u64 类型的线程 ID，因此使用一些特殊但
     /// 又常规的方式获取线程 ID 的最后 8 位。
+    ///
+    /// 队列中保存的是 `Block` 的描述，`Block` 在发放时才由领取它的线程构造，因此 `d` 代表的是领取 `Block` 的线程，
+    /// 而非执行补充的 `supply` 线程；同一个 `Block` 中的全部 `Token` 仍然具有相同的 `d`。
     d: u8,
 }
 
@@ -146,7 +326,19 @@ use std::{process, thread};
 
 /// `cd` 用于通过系统环境获取两个 u8 数值用于构建 `Ident`，通常而言 `cd` 代表着设备信息，用于
 /// 区分不同的设备，而环境信息则选择了进程和线程 id，并通过部分截取来构建 `cd`。
+///
+/// 补充 `Block` 时每个元素都需要调用 `cd`（每次补充 65536 次），而其结果在线程的生命周期内不会改变，因此在
+/// `thread_local` 中缓存每个线程首次计算的结果，避免重复构建哈希器。
 fn cd() -> (u8, u8) {
+    thread_local! {
+        static CD: (u8, u8) = compute_cd();
+    }
+
+    CD.with(|cd| *cd)
+}
+
+/// 计算当前线程的 `cd`，见 `cd` 的说明。
+fn compute_cd() -> (u8, u8) {
     // 对于 c 值，如果有从环境变量提供的 "FASTSEND_DEVICE_ID" 值，则取该值，如果没有
     // 则取进程 id 后八位作为 c 值以增加随机性。
     let c = if let Some(device_id) = *crate::DEVICE_ID {
@@ -0,0 +1,123 @@
+use super::{cd, Ident, Token};
+use crate::{Cursor, FastsendError, SequenceStride, ID};
+use std::sync::{Mutex, OnceLock};
+
+/// ## 严格递增模式
+///
+/// 开启 'strict_order' feature 后，`next_token` 不再从各线程的 `Block` 中获取 `Token`，而是由进程内唯一的
+/// `OrderedFrame` 按顺序发放：同一个 `Cursor` 下的发号序号从 0 开始依次递增，耗尽 65536 个序号后切换到新的
+/// `Cursor`（开启 'pause_on_start' 时等待至下一秒，否则直接递增），`c`、`d` 在进程内保持不变。
+///
+/// 因此同一进程发放的 `Token`，其 `id()` 按发放的先后顺序严格递增：若一次 `next_token` 调用在另一次调用开始
+/// 之前就已经返回（包括跨线程、跨 `Cursor` 的情况），则前者的 id 一定小于后者。代价是所有线程共享同一把锁，
+/// 并且切换 `Cursor` 时会阻塞当前线程直至下一秒。
+struct OrderedFrame {
+    /// 当前的 `Cursor` 以及下一个发号序号的下标，下标达到 `stride.capacity()` 时需要切换 `Cursor`。
+    state: Mutex<(Cursor, usize)>,
+
+    /// 发号序号的步长，见 `fastsend::set_sequence_stride`。
+    stride: SequenceStride,
+
+    /// 进程内固定的 `c`、`d`。
+    cd: (u8, u8),
+}
+
+impl OrderedFrame {
+    fn try_new() -> Result<Self, FastsendError> {
+        #[allow(unused)]
+        let cursor = Cursor::try_new()?;
+
+        // 与 `BlockFrame::new` 相同，启动时等待至下一秒以避免与重启前的进程冲突
+        #[cfg(feature = "pause_on_start")]
+        let cursor = cursor.try_next()?;
+
+        Ok(OrderedFrame {
+            state: Mutex::new((cursor, 0)),
+            stride: super::sequence_stride(),
+            cd: cd(),
+        })
+    }
+
+    fn try_next(&self) -> Result<Token, FastsendError> {
+        let mut state = self.state.lock().unwrap_or_else(|error| error.into_inner());
+        let (cursor, sequence) = &mut *state;
+
+        loop {
+            if *sequence == self.stride.capacity() {
+                // 切换失败时保持原状态不变，之后的调用会重新尝试切换
+                *cursor = if cfg!(feature = "pause_on_start") {
+                    cursor.try_next()?
+                } else {
+                    unsafe { cursor.incr() }
+                };
+                *sequence = 0;
+            }
+
+            let [a, b] = self.stride.sequence(*sequence).to_be_bytes();
+            let (c, d) = self.cd;
+            *sequence += 1;
+
+            // 跳过 `exclude_ids` 登记的区间
+            let token = Token::new(*cursor, Ident { a, b, c, d });
+            if !crate::is_excluded(token.id()) {
+                return Ok(token);
+            }
+        }
+    }
+
+    /// 预留 `cursors` 个连续的新 `Cursor` 并返回其中的第一个，之后的发号从预留的最后一个 `Cursor` 之后继续。
+    fn try_reserve(&self, cursors: u32) -> Result<Cursor, FastsendError> {
+        let mut state = self.state.lock().unwrap_or_else(|error| error.into_inner());
+        let (cursor, sequence) = &mut *state;
+
+        let first = if cfg!(feature = "pause_on_start") {
+            cursor.try_next()?
+        } else {
+            unsafe { cursor.incr() }
+        };
+        let last = crate::block::epoch::checked_add(first, cursors - 1)?.into_inner();
+
+        // 将序号置为耗尽，下一次发号时切换到预留的最后一个 `Cursor` 之后
+        *cursor = Cursor::from_inner(last);
+        *sequence = self.stride.capacity();
+        Ok(first)
+    }
+}
+
+/// 全局的 `OrderedFrame`。
+static FRAME: OnceLock<OrderedFrame> = OnceLock::new();
+
+/// 获取全局的 `OrderedFrame`，初始化失败时不会被缓存，下一次调用会重新尝试初始化。
+fn frame() -> Result<&'static OrderedFrame, FastsendError> {
+    if let Some(frame) = FRAME.get() {
+        return Ok(frame);
+    }
+
+    let frame = OrderedFrame::try_new()?;
+    Ok(FRAME.get_or_init(|| frame))
+}
+
+/// 全局的 `OrderedFrame` 正在使用的 `Cursor`，尚未初始化时返回 `None`。
+pub(crate) fn cursor() -> Option<Cursor> {
+    let frame = FRAME.get()?;
+    let state = frame
+        .state
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+    Some(state.0)
+}
+
+/// 初始化全局的 `OrderedFrame`，见 `fastsend::warm_up`。
+pub(crate) fn init() -> Result<(), FastsendError> {
+    frame().map(|_| ())
+}
+
+/// 从全局的 `OrderedFrame` 获取下一个 `Token`。
+pub(crate) fn next_token() -> Result<Token, FastsendError> {
+    frame()?.try_next()
+}
+
+/// 从全局的 `OrderedFrame` 预留 `cursors` 个连续的 `Cursor`，见 `fastsend::reserve_range`。
+pub(crate) fn reserve_cursors(cursors: u32) -> Result<Cursor, FastsendError> {
+    frame()?.try_reserve(cursors)
+}
@@ -0,0 +1,150 @@
+use crate::block::epoch;
+use crate::{Cursor, IdRange, Token};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+/// 分区的时间粒度，均按 UTC 时间划分。
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Granularity {
+    Hour,
+    Day,
+    Month,
+}
+
+/// ## 时间分区键
+///
+/// `PartitionKey` 表示 id 生成时间所在的时间分区（自 '1970-01-01 00:00:00 UTC' 起的第 `index` 个小时、天或月），
+/// 由 `Token::partition_key` 或 `fastsend::partition_of` 从 id 的游标中直接计算，按创建时间分区的表不需要额外的
+/// 时间戳列：
+///
+/// ```
+/// use fastsend::{Granularity, Token, ID};
+///
+/// // 游标 0 即基准起始时间 '2021-12-10 12:27:33 UTC'
+/// let token = Token::from_id(42);
+/// let key = token.partition_key(Granularity::Day);
+/// assert_eq!(key.to_string(), "2021-12-10");
+/// assert!(key.id_range().contains(token.id()));
+/// ```
+///
+/// 需要注意的是，游标耗尽时会向后借用（见 `BlockFrame`），因此临近分区边界生成的 id 可能落入下一个分区。
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct PartitionKey {
+    granularity: Granularity,
+    index: u32,
+}
+
+impl PartitionKey {
+    /// 时间 `time` 所在的分区，早于 '1970-01-01 00:00:00 UTC' 时视为该时刻。
+    pub fn of(time: SystemTime, granularity: Granularity) -> PartitionKey {
+        let secs = time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let index = match granularity {
+            Granularity::Hour => secs / 3600,
+            Granularity::Day => secs / 86400,
+            Granularity::Month => {
+                let date = datetime(secs);
+                (date.year() as u64 - 1970) * 12 + date.month0() as u64
+            }
+        };
+
+        PartitionKey {
+            granularity,
+            index: index as u32,
+        }
+    }
+
+    pub fn granularity(&self) -> Granularity {
+        self.granularity
+    }
+
+    /// 自 '1970-01-01 00:00:00 UTC' 起的分区序号。
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// 下一个分区。
+    pub fn next(&self) -> PartitionKey {
+        PartitionKey {
+            granularity: self.granularity,
+            index: self.index + 1,
+        }
+    }
+
+    /// 分区的起始时间（包含）。
+    pub fn start(&self) -> SystemTime {
+        let secs = match self.granularity {
+            Granularity::Hour => self.index as u64 * 3600,
+            Granularity::Day => self.index as u64 * 86400,
+            Granularity::Month => {
+                let year = 1970 + (self.index / 12) as i32;
+                NaiveDate::from_ymd_opt(year, self.index % 12 + 1, 1)
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+                    .map_or(0, |date| date.and_utc().timestamp() as u64)
+            }
+        };
+
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    /// 分区的结束时间（不包含），即下一个分区的起始时间。
+    pub fn end(&self) -> SystemTime {
+        self.next().start()
+    }
+
+    /// 该分区内所有 `Token` 的 id 区间，按当前的基准起始时间（`Cursor::timebase()`）及纪元版本计算，可以直接用作按 id 范围
+    /// 分区的表的分区边界。超出游标范围的部分会被截断，分区完全早于基准起始时间时返回空区间。
+    pub fn id_range(&self) -> IdRange {
+        let bound = |time: SystemTime| {
+            let secs = time
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let offset = secs.saturating_sub(Cursor::timebase());
+            (epoch::encode_saturating(offset).into_inner() as u64) << 32
+        };
+
+        IdRange::from_bounds(bound(self.start()), bound(self.end()))
+    }
+}
+
+impl fmt::Display for PartitionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let start = datetime(
+            self.start()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        );
+
+        match self.granularity {
+            Granularity::Hour => write!(f, "{}", start.format("%Y-%m-%dT%H")),
+            Granularity::Day => write!(f, "{}", start.format("%Y-%m-%d")),
+            Granularity::Month => write!(f, "{}", start.format("%Y-%m")),
+        }
+    }
+}
+
+fn datetime(secs: u64) -> DateTime<Utc> {
+    DateTime::from_timestamp(secs as i64, 0).unwrap_or_default()
+}
+
+impl Token {
+    /// 生成时间所在的时间分区，见 `PartitionKey`。
+    pub fn partition_key(&self, granularity: Granularity) -> PartitionKey {
+        let secs = epoch::unix_secs(self.cursor);
+        PartitionKey::of(
+            SystemTime::UNIX_EPOCH + Duration::from_secs(secs),
+            granularity,
+        )
+    }
+}
+
+/// id 生成时间所在的时间分区，等价于 `Token::from_id(id).partition_key(granularity)`。
+pub fn partition_of(id: u64, granularity: Granularity) -> PartitionKey {
+    Token::from_id(id).partition_key(granularity)
+}
@@ -0,0 +1,77 @@
+use crate::Cursor;
+use std::ops::Range;
+
+/// `IdRange` 是通过 `fastsend::reserve_range` 预留的一段连续的 id，区间为 [start, end)。
+///
+/// 预留以 `Cursor` 为单位：每个 `Cursor` 对应 2^32 个 id（即 `Cursor` 作为高 32 位、低 32 位任意取值），区间从
+/// 预留的第一个 `Cursor` 的第一个 id 开始，长度为请求的数量。同一进程之后通过 `next_token` 生成的 id 不会落入
+/// 预留的 `Cursor`，因此也不会落入该区间。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct IdRange {
+    start: u64,
+    end: u64,
+}
+
+impl IdRange {
+    /// 从 `first` 的第一个 id 开始的 `count` 个 id，超出 u64 的范围时返回 `None`。
+    pub(crate) fn new(first: Cursor, count: u64) -> Option<Self> {
+        let start = (first.into_inner() as u64) << 32;
+        let end = start.checked_add(count)?;
+        Some(IdRange { start, end })
+    }
+
+    /// [start, end) 区间，`end` 小于 `start` 时为空区间。
+    pub(crate) fn from_bounds(start: u64, end: u64) -> Self {
+        IdRange {
+            start,
+            end: end.max(start),
+        }
+    }
+
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    pub fn len(&self) -> u64 {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn contains(&self, id: u64) -> bool {
+        (self.start..self.end).contains(&id)
+    }
+
+    /// 区间内第一个 id 所在的 `Cursor`。
+    pub fn cursor(&self) -> Cursor {
+        Cursor::from_inner((self.start >> 32) as u32)
+    }
+}
+
+impl IntoIterator for IdRange {
+    type Item = u64;
+    type IntoIter = Range<u64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.start..self.end
+    }
+}
+
+/// `range.end` 小于 `range.start` 时为空区间。
+impl From<Range<u64>> for IdRange {
+    fn from(range: Range<u64>) -> Self {
+        IdRange::from_bounds(range.start, range.end)
+    }
+}
+
+impl From<IdRange> for Range<u64> {
+    fn from(range: IdRange) -> Self {
+        range.start..range.end
+    }
+}
@@ -0,0 +1,67 @@
+use rand::RngCore;
+use std::time::SystemTime;
+
+/// ## W3C Trace Context
+///
+/// 生成符合 [W3C Trace Context](https://www.w3.org/TR/trace-context/) 要求的 128 bits trace-id，由高到低依次为：
+///
+/// | 毫秒级时间戳 | 设备号 | 随机数 |
+/// |:-----------:|:-----:|:-----:|
+/// |   48 bits   | 8 bits | 72 bits |
+///
+/// 设备号未配置时使用随机数。右侧 7 个字节始终为随机数，满足 Trace Context Level 2 中 'random' 标志的要求；
+/// 生成的 trace-id 不会全为 0（全 0 是规范中的无效值）。
+///
+/// ```
+/// let trace_id = fastsend::next_trace_id();
+/// let span_id = fastsend::next_span_id();
+/// assert_ne!(trace_id, [0; 16]);
+/// assert_ne!(span_id, [0; 8]);
+/// ```
+pub fn next_trace_id() -> [u8; 16] {
+    let timestamp = crate::clock::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default();
+    let device = crate::DEVICE_ID.unwrap_or_else(rand::random);
+
+    let mut trace_id = [0; 16];
+    trace_id[..6].copy_from_slice(&timestamp.to_be_bytes()[2..]);
+    trace_id[6] = device;
+    trace_id[7..].copy_from_slice(&non_zero::<9>());
+    trace_id
+}
+
+/// 生成 64 bits 的随机 span-id（parent-id），不会全为 0。
+pub fn next_span_id() -> [u8; 8] {
+    non_zero::<8>()
+}
+
+/// 按照 `traceparent` 请求头的格式（version 00）拼接 trace-id 与 span-id，`sampled` 对应 trace-flags 中的
+/// 采样标志：
+///
+/// ```
+/// let header = fastsend::traceparent(&[0xab; 16], &[0xcd; 8], true);
+/// assert_eq!(header, format!("00-{}-{}-01", "ab".repeat(16), "cd".repeat(8)));
+/// ```
+pub fn traceparent(trace_id: &[u8; 16], span_id: &[u8; 8], sampled: bool) -> String {
+    let hex =
+        |bytes: &[u8]| -> String { bytes.iter().map(|byte| format!("{:02x}", byte)).collect() };
+    format!(
+        "00-{}-{}-{:02x}",
+        hex(trace_id),
+        hex(span_id),
+        sampled as u8
+    )
+}
+
+/// 不全为 0 的 `N` 个随机字节。
+fn non_zero<const N: usize>() -> [u8; N] {
+    let mut bytes = [0; N];
+    loop {
+        rand::thread_rng().fill_bytes(&mut bytes);
+        if bytes.iter().any(|&byte| byte != 0) {
+            return bytes;
+        }
+    }
+}
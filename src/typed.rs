@@ -0,0 +1,129 @@
+use crate::{Block, BlockFrame, ConstructBlock, FastsendError, Token};
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// 各元素类型的全局 `BlockFrame`，以 `TypeId` 为键。`BlockFrame` 与全局生成器一样在进程内常驻，因此直接泄漏为
+/// `'static` 引用，读取时不需要在持有锁的情况下等待补充。
+static FRAMES: RwLock<Option<HashMap<TypeId, &'static (dyn Any + Send + Sync)>>> =
+    RwLock::new(None);
+
+thread_local! {
+    /// 各元素类型在当前线程中正在使用的 `Block<T>`，与 `BLOCK` 相同，其中不会保存已经耗尽的 `Block`。
+    static BLOCKS: RefCell<HashMap<TypeId, Box<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+/// 为元素类型 `T` 注册全局 `BlockFrame`，之后通过 `next_for::<T>()` 从该生成器中获取元素。`T` 已经注册过（包括
+/// 首次调用 `next_for::<T>()` 时自动创建的生成器）时注册不会生效并返回 `false`。
+///
+/// `Token` 的全局生成器见 `set_global_frame`，为 `Token` 注册的生成器永远不会被使用，因此总是返回 `false`。
+pub fn register_frame<T: ConstructBlock + 'static>(frame: BlockFrame<T>) -> bool {
+    if TypeId::of::<T>() == TypeId::of::<Token>() {
+        return false;
+    }
+
+    let mut frames = FRAMES.write().unwrap_or_else(|error| error.into_inner());
+    let frames = frames.get_or_insert_with(HashMap::new);
+    if frames.contains_key(&TypeId::of::<T>()) {
+        return false;
+    }
+
+    frames.insert(TypeId::of::<T>(), Box::leak(Box::new(frame)));
+    true
+}
+
+/// 获取 `T` 的全局 `BlockFrame`，尚未注册时以缺省配置创建。
+fn frame<T: ConstructBlock + 'static>() -> Result<&'static BlockFrame<T>, FastsendError> {
+    let registered = FRAMES
+        .read()
+        .unwrap_or_else(|error| error.into_inner())
+        .as_ref()
+        .and_then(|frames| frames.get(&TypeId::of::<T>()).copied());
+
+    let frame = match registered {
+        Some(frame) => frame,
+        None => {
+            // 与全局生成器相同，创建失败时不会被缓存；并发创建时以先注册的为准
+            register_frame(BlockFrame::<T>::try_new()?);
+            FRAMES
+                .read()
+                .unwrap_or_else(|error| error.into_inner())
+                .as_ref()
+                .unwrap()[&TypeId::of::<T>()]
+        }
+    };
+
+    Ok(frame.downcast_ref().unwrap())
+}
+
+/// `next_token` 的泛型版本：从元素类型 `T` 的全局 `BlockFrame` 中获取下一个元素，与 `next_token` 一样在线程本地
+/// 缓存领取到的 `Block`。`T` 的生成器可以通过 `register_frame` 预先注册，否则在首次调用时以缺省配置创建。
+///
+/// ```
+/// use fastsend::{Block, ConstructBlock, Cursor, Token, ID};
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct OrderId(u64);
+///
+/// impl ConstructBlock for OrderId {
+///     fn construct_block(n: usize, cursor: Cursor) -> Block<Self> {
+///         let mut tokens = Token::construct_block(n, cursor);
+///         std::array::from_fn(|_| OrderId(tokens.next().unwrap().id())).into()
+///     }
+/// }
+///
+/// # futures::executor::block_on(async {
+/// let first = fastsend::next_for::<OrderId>().await;
+/// let second = fastsend::next_for::<OrderId>().await;
+/// assert_ne!(first, second);
+/// # });
+/// ```
+///
+/// 不同元素类型的生成器相互独立，各自的元素只在同一类型内保证唯一：上例中的 `OrderId` 与 `next_token` 生成的
+/// id 可能相同，适用于各自独立的 id 空间（如不同的表），与 `Token` 共用 id 空间的场合应直接使用 `next_token`。
+/// `next_for::<Token>()` 等同于 `next_token`。
+///
+/// # Panics
+///
+/// 系统时间异常时 panic，见 `try_next_for`。
+pub async fn next_for<T: ConstructBlock + Clone + 'static>() -> T {
+    try_next_for()
+        .await
+        .unwrap_or_else(|error| panic!("{} on next_for()", error))
+}
+
+/// `next_for` 的非 panic 版本，系统时间异常时返回 `FastsendError`。
+pub async fn try_next_for<T: ConstructBlock + Clone + 'static>() -> Result<T, FastsendError> {
+    if TypeId::of::<T>() == TypeId::of::<Token>() {
+        let token: Box<dyn Any> = Box::new(crate::try_next_token().await?);
+        return Ok(*token.downcast().unwrap());
+    }
+
+    let should_assign = BLOCKS.with(|blocks| !blocks.borrow().contains_key(&TypeId::of::<T>()));
+
+    // 与 `with_block` 相同，`borrow_mut` 需要在异步任务断点之后调用
+    if should_assign {
+        let block = frame::<T>()?.try_next_block().await?;
+        BLOCKS.with(|blocks| {
+            blocks
+                .borrow_mut()
+                .insert(TypeId::of::<T>(), Box::new(block))
+        });
+    }
+
+    BLOCKS.with(|blocks| {
+        let mut blocks = blocks.borrow_mut();
+        let block = blocks
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|block| block.downcast_mut::<Block<T>>())
+            .ok_or(FastsendError::DrainedBlock)?;
+        let output = block.next().ok_or(FastsendError::DrainedBlock)?;
+
+        if block.size_hint().0 == 0 {
+            blocks.remove(&TypeId::of::<T>());
+        }
+
+        Ok(output)
+    })
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "actix")]
+
+use actix_web::http::header::HeaderName;
+use actix_web::http::StatusCode;
+use actix_web::{test, web, App, HttpMessage, HttpRequest};
+use fastsend::{RequestId, RequestIdMiddleware};
+
+async fn handler(request_id: RequestId, request: HttpRequest) -> String {
+    assert_eq!(request.extensions().get::<RequestId>(), Some(&request_id));
+    request_id.as_u64().to_string()
+}
+
+#[actix_web::test]
+async fn test_request_id_middleware() {
+    let app = test::init_service(
+        App::new()
+            .wrap(RequestIdMiddleware::new())
+            .route("/", web::get().to(handler)),
+    )
+    .await;
+
+    let response = test::call_service(&app, test::TestRequest::get().uri("/").to_request()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let header = response
+        .headers()
+        .get("x-request-id")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_owned();
+    let body = test::read_body(response).await;
+    assert_eq!(header.as_bytes(), &body[..]);
+
+    // 每个请求分配不同的 ID
+    let response = test::call_service(&app, test::TestRequest::get().uri("/").to_request()).await;
+    assert_ne!(
+        response.headers().get("x-request-id").unwrap(),
+        header.as_str()
+    );
+}
+
+#[actix_web::test]
+async fn test_request_id_middleware_header() {
+    let app = test::init_service(
+        App::new()
+            .wrap(RequestIdMiddleware::new().header(HeaderName::from_static("x-trace-id")))
+            .route("/", web::get().to(|| async { "ok" })),
+    )
+    .await;
+
+    let response = test::call_service(&app, test::TestRequest::get().uri("/").to_request()).await;
+    assert!(response.headers().contains_key("x-trace-id"));
+    assert!(!response.headers().contains_key("x-request-id"));
+}
+
+#[actix_web::test]
+async fn test_request_id_missing() {
+    let app = test::init_service(App::new().route("/", web::get().to(handler))).await;
+
+    let response = test::call_service(&app, test::TestRequest::get().uri("/").to_request()).await;
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
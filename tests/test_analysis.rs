@@ -0,0 +1,123 @@
+use fastsend::{analysis, ID};
+
+#[tokio::test]
+async fn test_report_tokens() {
+    let mut ids = Vec::new();
+    for _ in 0..10000 {
+        ids.push(fastsend::next_token().await.id());
+    }
+
+    let report = analysis::report(&ids);
+    assert_eq!(report.count(), 10000);
+    assert_eq!(report.collisions(), 0);
+
+    // 各个位的统计与数据一致，不依赖 id 的具体布局
+    for bit in 0..64 {
+        let ones = ids.iter().filter(|&&id| (id >> bit) & 1 == 1).count();
+        assert_eq!(report.bit_ratio(bit), ones as f64 / ids.len() as f64);
+        assert_eq!(
+            report.constant_bits().contains(&bit),
+            ones == 0 || ones == ids.len()
+        );
+    }
+
+    // 短时间内生成的 id 共用游标的高位，因此总有取值不变的位
+    assert!(!report.constant_bits().is_empty());
+    assert!(report.bit_entropy() < 64.0);
+
+    let increasing = ids.windows(2).filter(|pair| pair[1] > pair[0]).count();
+    assert_eq!(
+        report.monotonicity(),
+        increasing as f64 / (ids.len() - 1) as f64
+    );
+
+    // 'strict_order' 模式下 id 严格递增，否则 `Block` 内的发号序号是打乱的，id 不会严格按照生成顺序递增
+    #[cfg(feature = "strict_order")]
+    assert_eq!(report.monotonicity(), 1.0);
+    #[cfg(not(feature = "strict_order"))]
+    assert!(report.monotonicity() < 1.0);
+    assert!(report.to_string().contains("collisions: 0"));
+}
+
+#[test]
+fn test_report_sequential() {
+    let ids = (0..256).collect::<Vec<u64>>();
+    let report = analysis::report(&ids);
+
+    assert_eq!(report.monotonicity(), 1.0);
+    assert_eq!(report.constant_bits(), (8..64).collect::<Vec<_>>());
+    assert!((0..8).all(|bit| report.bit_ratio(bit) == 0.5));
+    assert!((report.bit_entropy() - 8.0).abs() < 1e-9);
+
+    let empty = analysis::report(&[]);
+    assert_eq!(empty.count(), 0);
+    assert_eq!(empty.monotonicity(), 0.0);
+    assert_eq!(empty.bit_ratio(0), 0.0);
+}
+
+#[test]
+fn test_report_serials() {
+    let serials = ["0A", "0B", "1A", "1B", "1B", "2"];
+    let report = analysis::report_serials(&serials);
+
+    assert_eq!(report.count(), 6);
+    assert_eq!(report.collisions(), 1);
+    assert_eq!(report.length_distribution().get(&2), Some(&5));
+    assert_eq!(report.length_distribution().get(&1), Some(&1));
+    assert_eq!(report.char_frequency().get(&'B'), Some(&3));
+    assert!((report.monotonicity() - 0.8).abs() < 1e-9);
+    assert!(report.char_entropy() > 0.0);
+
+    let owned = serials.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    assert_eq!(analysis::report_serials(&owned), report);
+}
+
+#[test]
+fn test_import_check_counter() {
+    let report = analysis::ImportCheck::counter(100).check([1, 99, 100, 101, 102, 150, 102]);
+    assert_eq!(report.count(), 7);
+    assert_eq!(report.overlaps(), &[100, 101, 102, 150]);
+    assert!(!report.is_safe());
+
+    let ranges = report
+        .reserved_ranges()
+        .iter()
+        .map(|range| (range.start(), range.end()))
+        .collect::<Vec<_>>();
+    assert_eq!(ranges, vec![(100, 103), (150, 151)]);
+    assert_eq!(report.safe_start(), Some(151));
+    assert_eq!(report.safe_cursor(), None);
+
+    // 旧值全部小于起始计数器时无需预留
+    let report = analysis::ImportCheck::counter(100).check_serials(["7", " 42 ", "legacy-9"]);
+    assert!(report.is_safe());
+    assert_eq!(report.unparsed(), 1);
+    assert_eq!(report.safe_start(), Some(100));
+}
+
+#[test]
+fn test_import_check_token() {
+    use fastsend::{ConstructBlock, Cursor, Token};
+    use std::time::{Duration, SystemTime};
+
+    let timebase = SystemTime::UNIX_EPOCH + Duration::from_secs(Cursor::timebase());
+    let check = analysis::ImportCheck::token_since(timebase + Duration::from_secs(1000));
+
+    // 游标早于起始时间的旧 id 不会冲突，游标晚于起始时间的旧 id 可能冲突
+    let past = 999u64 << 32 | 0xFFFF_FFFF;
+    let future = 2000u64 << 32 | 42;
+    let report = check.check([past, future]);
+    assert_eq!(report.overlaps(), &[future]);
+    assert_eq!(report.safe_start(), Some(2001 << 32));
+
+    // 以 `safe_cursor` 构造的 `Token` 晚于所有旧 id
+    let cursor = report.safe_cursor().unwrap();
+    let token = Token::construct_block(0, cursor).next().unwrap();
+    assert_eq!(token.id() >> 32, 2001);
+
+    // 没有可能冲突的旧 id 时，安全的起始游标即为起始时间对应的游标
+    let report = check.check([past]);
+    assert!(report.is_safe());
+    assert_eq!(report.safe_start(), Some(1000 << 32));
+    assert!(report.to_string().contains("overlaps: 0"));
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "async-graphql")]
+
+use async_graphql::{value, EmptyMutation, EmptySubscription, Object, Schema};
+use fastsend::{Token, ID};
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn echo(&self, id: Token) -> Token {
+        id
+    }
+}
+
+#[tokio::test]
+async fn test_async_graphql_token() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema.sdl().contains("scalar Token"));
+
+    // 超出 2^53 的 id 以字符串形式原样返回
+    let id = u64::MAX - 1;
+    let response = schema
+        .execute(format!(r#"{{ a: echo(id: "{id}") b: echo(id: 42) }}"#))
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    assert_eq!(response.data, value!({ "a": id.to_string(), "b": "42" }));
+
+    let response = schema.execute(r#"{ echo(id: "-1") }"#).await;
+    assert_eq!(response.errors.len(), 1);
+
+    let token = fastsend::next_token().await;
+    let response = schema
+        .execute(format!(r#"{{ echo(id: "{}") }}"#, token.id()))
+        .await;
+    assert_eq!(response.data, value!({ "echo": token.id().to_string() }));
+}
+
+#[cfg(feature = "uuid")]
+#[tokio::test]
+async fn test_async_graphql_uuid() {
+    use fastsend::{Serialer, UUIDSerialer, UUID};
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn echo(&self, uuid: UUID) -> UUID {
+            uuid
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let uuid = UUIDSerialer::new_v4().build().await.unwrap();
+
+    let response = schema
+        .execute(format!(r#"{{ echo(uuid: "{}") }}"#, uuid))
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    assert_eq!(response.data, value!({ "echo": uuid.to_string() }));
+
+    let response = schema.execute(r#"{ echo(uuid: "not-a-uuid") }"#).await;
+    assert_eq!(response.errors.len(), 1);
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "axum")]
+
+use axum::body::Body;
+use axum::http::{HeaderName, Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
+use fastsend::{RequestId, RequestIdLayer};
+use tower::ServiceExt;
+
+async fn handler(request_id: RequestId) -> String {
+    request_id.as_u64().to_string()
+}
+
+#[tokio::test]
+async fn test_request_id_layer() {
+    let app = Router::new()
+        .route("/", get(handler))
+        .layer(RequestIdLayer::new());
+
+    let response = app
+        .clone()
+        .oneshot(Request::new(Body::empty()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let header = response.headers()["x-request-id"]
+        .to_str()
+        .unwrap()
+        .to_owned();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(header.as_bytes(), &body[..]);
+
+    // 每个请求分配不同的 ID
+    let response = app.oneshot(Request::new(Body::empty())).await.unwrap();
+    assert_ne!(response.headers()["x-request-id"], header.as_str());
+}
+
+#[tokio::test]
+async fn test_request_id_header() {
+    let app = Router::new()
+        .route("/", get(|| async { "ok" }))
+        .layer(RequestIdLayer::new().header(HeaderName::from_static("x-trace-id")));
+
+    let response = app.oneshot(Request::new(Body::empty())).await.unwrap();
+    assert!(response.headers().contains_key("x-trace-id"));
+    assert!(!response.headers().contains_key("x-request-id"));
+}
+
+#[tokio::test]
+async fn test_request_id_missing() {
+    let app = Router::new().route("/", get(handler));
+
+    let response = app.oneshot(Request::new(Body::empty())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -0,0 +1,88 @@
+#[cfg(feature = "rkyv")]
+#[tokio::test]
+async fn test_rkyv() {
+    use fastsend::{Cursor, Token, ID};
+    use rkyv::rancor::Error;
+
+    let token = fastsend::next_token().await;
+    let bytes = rkyv::to_bytes::<Error>(&token).unwrap();
+    let read = rkyv::from_bytes::<Token, Error>(&bytes).unwrap();
+    assert_eq!(read, token);
+    assert_eq!(read.id(), token.id());
+
+    let cursor = Cursor::new();
+    let bytes = rkyv::to_bytes::<Error>(&cursor).unwrap();
+    assert_eq!(rkyv::from_bytes::<Cursor, Error>(&bytes).unwrap(), cursor);
+}
+
+#[cfg(all(feature = "rkyv", feature = "uuid"))]
+#[tokio::test]
+async fn test_rkyv_uuid() {
+    use fastsend::{Serialer, UUIDSerialer, UUID};
+    use rkyv::rancor::Error;
+
+    let uuid = UUIDSerialer::new_v7().build().await.unwrap();
+    let bytes = rkyv::to_bytes::<Error>(&uuid).unwrap();
+    let read = rkyv::from_bytes::<UUID, Error>(&bytes).unwrap();
+    assert_eq!(read.to_string(), uuid.to_string());
+}
+
+#[cfg(all(feature = "rkyv", feature = "uuid"))]
+#[test]
+fn test_rkyv_uuid_invalid_version() {
+    use fastsend::UUID;
+    use rkyv::rancor::Error;
+
+    // 归档内容只有 16 字节，版本号为 0 的字节无法还原为 UUID
+    let bytes = rkyv::to_bytes::<Error>(&[0u8; 16]).unwrap();
+    assert!(rkyv::from_bytes::<UUID, Error>(&bytes).is_err());
+}
+
+#[cfg(feature = "bincode")]
+#[tokio::test]
+async fn test_bincode() {
+    use fastsend::{Token, ID};
+
+    let config = bincode::config::standard();
+    let tokens = vec![fastsend::next_token().await, fastsend::next_token().await];
+
+    let bytes = bincode::encode_to_vec(&tokens, config).unwrap();
+    let (read, len): (Vec<Token>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(read, tokens);
+    assert_eq!(read[0].id(), tokens[0].id());
+}
+
+#[cfg(all(feature = "bincode", feature = "uuid"))]
+#[tokio::test]
+async fn test_bincode_uuid() {
+    use fastsend::{Serialer, UUIDSerialer, UUID};
+
+    let config = bincode::config::standard();
+    let uuid = UUIDSerialer::new_v4().build().await.unwrap();
+
+    let bytes = bincode::encode_to_vec(uuid, config).unwrap();
+    let (read, len): (UUID, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(len, 16);
+    assert_eq!(read, uuid);
+    assert_eq!(read.to_bytes(), uuid.to_bytes());
+}
+
+#[cfg(all(feature = "bincode", feature = "uuid"))]
+#[test]
+fn test_bincode_uuid_invalid_version() {
+    use fastsend::UUID;
+
+    let config = bincode::config::standard();
+
+    // 版本号为 0 的字节无法解码为 UUID
+    let bytes = bincode::encode_to_vec([0u8; 16], config).unwrap();
+    assert!(bincode::decode_from_slice::<UUID, _>(&bytes, config).is_err());
+
+    // 版本号为 1 的字节同样被拒绝
+    let mut v1 = [0u8; 16];
+    v1[6] = 0x10;
+    v1[8] = 0x80;
+    let bytes = bincode::encode_to_vec(v1, config).unwrap();
+    assert!(bincode::decode_from_slice::<UUID, _>(&bytes, config).is_err());
+}
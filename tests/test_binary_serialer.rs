@@ -0,0 +1,85 @@
+use fastsend::BinarySerialer;
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// 输出打包数值的 `BinarySerialer`：4 字节的全局计数器之后紧跟 feed 的数据。
+struct Packed(Vec<u8>);
+
+static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+impl BinarySerialer for Packed {
+    type Output = Vec<u8>;
+
+    type Error = Infallible;
+
+    fn build(self) -> Pin<Box<dyn Future<Output = Result<Vec<u8>, Infallible>> + Send + 'static>> {
+        let mut bytes = COUNTER
+            .fetch_add(1, Ordering::Relaxed)
+            .to_be_bytes()
+            .to_vec();
+        bytes.extend_from_slice(&self.0);
+        Box::pin(async move { Ok(bytes) })
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.0.extend_from_slice(data);
+    }
+}
+
+#[tokio::test]
+async fn test_binary_serialer() {
+    let mut serials = HashSet::new();
+    for _ in 0..100 {
+        let serial = Packed(Vec::new()).oneshot(b"order").await.unwrap();
+        assert_eq!(serial.len(), 9);
+        assert_eq!(&serial[4..], b"order");
+        assert!(serials.insert(serial));
+    }
+}
+
+#[cfg(feature = "uuid")]
+#[tokio::test]
+async fn test_uuid_bytes() {
+    use fastsend::encoding::base64url;
+    use fastsend::{Serialer, UUIDSerialer, UUID};
+
+    let bytes = BinarySerialer::build(UUIDSerialer::new_v7()).await.unwrap();
+    assert_eq!(bytes[6] >> 4, 7);
+    assert_eq!(bytes[8] & 0xc0, 0x80);
+    assert_eq!(UUID::from_bytes(bytes).unwrap().to_bytes(), bytes);
+
+    // base64url 表示与字节表示一一对应
+    let uuid = UUID::from_bytes(bytes).unwrap();
+    assert_eq!(uuid.to_base64url(), base64url::encode(&bytes));
+    assert_eq!(UUID::from_base64url(&uuid.to_base64url()), Ok(uuid));
+    assert!(UUID::from_base64url(&base64url::encode(&[0; 16])).is_err());
+
+    // V5 的字节表示与 `Serialer` 构建的 `UUID` 一致
+    let mut serialer = UUIDSerialer::new_v5();
+    Serialer::feed(&mut serialer, b"fastsend");
+    let uuid = Serialer::build(serialer).await.unwrap();
+    let bytes = BinarySerialer::oneshot(UUIDSerialer::new_v5(), b"fastsend").await;
+    assert_eq!(uuid.to_bytes(), bytes.unwrap());
+}
+
+#[cfg(feature = "flake128")]
+#[tokio::test]
+async fn test_flake128_bytes() {
+    use fastsend::{Flake128, Flake128Serialer};
+
+    // 按字节比较的顺序与生成顺序一致
+    let mut prev = BinarySerialer::build(Flake128Serialer::new())
+        .await
+        .unwrap();
+    for _ in 0..1000 {
+        let next = BinarySerialer::build(Flake128Serialer::new())
+            .await
+            .unwrap();
+        assert!(next > prev);
+        assert_eq!(Flake128::from_bytes(next).to_bytes(), next);
+        prev = next;
+    }
+}
@@ -0,0 +1,53 @@
+use fastsend::{Block, BlockFrame, ConstructBlock, Cursor, Token, ID};
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
+
+/// 记录每次构造的参数及所在线程的元素类型。
+#[derive(Debug, Clone)]
+struct Recorded(u64);
+
+static CONSTRUCTED: Mutex<Vec<(usize, Cursor, ThreadId)>> = Mutex::new(Vec::new());
+
+impl ConstructBlock for Recorded {
+    fn construct_block(n: usize, cursor: Cursor) -> Block<Self> {
+        CONSTRUCTED
+            .lock()
+            .unwrap()
+            .push((n, cursor, thread::current().id()));
+
+        let mut tokens = Token::construct_block(n, cursor);
+        std::array::from_fn(|_| Recorded(tokens.next().unwrap().id())).into()
+    }
+}
+
+#[test]
+fn test_block_descriptor() {
+    let frame = BlockFrame::<Recorded>::new();
+
+    // 补充队列时只推送描述，`Block` 在发放时才在领取的线程中构造
+    let block = futures::executor::block_on(frame.next_block());
+
+    let constructed = CONSTRUCTED.lock().unwrap().clone();
+    assert_eq!(constructed.len(), 1);
+    let (n, cursor, thread_id) = constructed[0];
+    assert_eq!(thread_id, thread::current().id());
+
+    // 由描述构造的 `Block` 与直接以相同的 `n` 和 `cursor` 调用 `construct_block` 得到的 id 完全一致
+    let ids: Vec<u64> = block.map(|recorded| recorded.0).collect();
+    let expected: Vec<u64> = Token::construct_block(n, cursor).map(ID::id).collect();
+    assert_eq!(ids, expected);
+
+    // 补充线程可能仍在推送描述
+    let block = loop {
+        if let Some(block) = frame.pop_block() {
+            break block;
+        }
+        thread::yield_now();
+    };
+    let constructed = CONSTRUCTED.lock().unwrap().clone();
+    assert_eq!(constructed.len(), 2);
+    let (n, cursor, _) = constructed[1];
+    let ids: Vec<u64> = block.map(|recorded| recorded.0).collect();
+    let expected: Vec<u64> = Token::construct_block(n, cursor).map(ID::id).collect();
+    assert_eq!(ids, expected);
+}
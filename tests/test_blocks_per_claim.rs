@@ -0,0 +1,59 @@
+#![cfg(not(feature = "strict_order"))]
+
+use fastsend::ID;
+use std::collections::HashSet;
+
+// 每次领取的 `Block` 数量是进程内共享的设置，因此各用例放在同一个测试中顺序执行
+#[tokio::test(flavor = "current_thread")]
+async fn test_blocks_per_claim() {
+    fastsend::set_blocks_per_claim(4);
+
+    // 单个线程连续获取的 32 个 `Token` 来自一次领取的 4 个 `Block`，每个 `Block` 覆盖 8 个连续的发号序号
+    let mut ids = Vec::with_capacity(32);
+    for _ in 0..32 {
+        ids.push(fastsend::next_token().await.id());
+    }
+    assert_eq!(ids.iter().collect::<HashSet<_>>().len(), 32);
+
+    let blocks = ids
+        .chunks(8)
+        .map(|chunk| {
+            let blocks = chunk
+                .iter()
+                .map(|id| (id >> 32, (id >> 16 & 0xFFFF) / 8))
+                .collect::<HashSet<_>>();
+            assert_eq!(blocks.len(), 1);
+            blocks.into_iter().next().unwrap()
+        })
+        .collect::<HashSet<_>>();
+    assert_eq!(blocks.len(), 4);
+
+    // 多个线程并发获取时全局不重复
+    fastsend::set_blocks_per_claim(64);
+    let handles = (0..4)
+        .map(|_| {
+            std::thread::spawn(|| {
+                futures::executor::block_on(async {
+                    let mut ids = Vec::with_capacity(10000);
+                    for _ in 0..10000 {
+                        ids.push(fastsend::next_token().await.id());
+                    }
+                    ids
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut unique = ids.into_iter().collect::<HashSet<_>>();
+    for handle in handles {
+        for id in handle.join().unwrap() {
+            assert!(unique.insert(id));
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_blocks_per_claim_zero() {
+    fastsend::set_blocks_per_claim(0);
+}
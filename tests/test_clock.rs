@@ -0,0 +1,42 @@
+#![cfg(feature = "test-util")]
+
+use chrono::{Local, TimeZone};
+use fastsend::{testing, Cursor, Serialer, TimeSerialer};
+use std::time::Duration;
+
+// 虚拟时间是全局状态，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_freeze_time() {
+    let datetime = Local.with_ymd_and_hms(2024, 5, 1, 8, 30, 0).unwrap();
+    testing::freeze_time(datetime);
+
+    let cursor = Cursor::new();
+    assert_eq!(Cursor::new(), cursor);
+
+    testing::advance(Duration::from_secs(3));
+    let advanced = Cursor::new();
+    assert!(advanced > cursor);
+
+    // 等待下一秒时直接推进虚拟时间，而不是阻塞
+    assert!(advanced.next() > advanced);
+
+    let first = serial(b"a").await;
+    assert!(first.starts_with("202405010830"));
+
+    // 同一秒内重复的序列号会推进到下一秒
+    let second = serial(b"a").await;
+    assert_eq!(
+        second[..14].parse::<u64>(),
+        first[..14].parse::<u64>().map(|n| n + 1)
+    );
+
+    testing::reset_clock();
+    let year = Local::now().format("%Y").to_string();
+    assert!(serial(b"a").await.starts_with(&year));
+}
+
+async fn serial(data: &[u8]) -> String {
+    let mut serialer = TimeSerialer::new();
+    serialer.feed(data);
+    serialer.build().await.unwrap()
+}
@@ -0,0 +1,78 @@
+use fastsend::config::preset;
+use fastsend::{Config, ConfigError, DeviceIdSetting, TimeSerialer, DEVICE_ID};
+
+#[test]
+fn test_config_apply() {
+    Config::new()
+        .device_id(DeviceIdSetting::Value(5))
+        .preset("time", TimeSerialer::new)
+        .apply()
+        .unwrap();
+
+    assert!(DEVICE_ID.is_some());
+    assert!(preset::<TimeSerialer>("time").is_some());
+    assert!(preset::<TimeSerialer>("missing").is_none());
+
+    let result = Config::new().blocks_per_claim(0).apply();
+    assert!(matches!(result, Err(ConfigError::Invalid(_))));
+
+    // 设备号已被使用，无法再修改
+    let result = Config::new().device_id(DeviceIdSetting::Value(6)).apply();
+    assert!(matches!(result, Err(ConfigError::AlreadyInUse(_))));
+
+    Config::new().id_permutation(7).apply().unwrap();
+    let result = Config::new().id_permutation(8).apply();
+    assert!(matches!(result, Err(ConfigError::AlreadyInUse(_))));
+}
+
+#[cfg(all(feature = "config", feature = "coupon", feature = "slug"))]
+#[tokio::test]
+async fn test_config_from_toml() {
+    use fastsend::{CouponSerialer, Serialer, SlugSerialer};
+
+    Config::from_toml(
+        r#"
+        [presets.coupon.spring]
+        prefix = "SPRING"
+        length = 6
+        group = 0
+
+        [presets.slug.links]
+        length = 7
+        base58 = true
+        "#,
+    )
+    .unwrap()
+    .apply()
+    .unwrap();
+
+    let coupon = preset::<CouponSerialer>("spring").unwrap();
+    let code = coupon.clone().build().await.unwrap();
+    assert!(code.starts_with("SPRING"));
+    assert!(coupon.validate(&code));
+
+    let slug = preset::<SlugSerialer>("links")
+        .unwrap()
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(slug.len(), 7);
+
+    let invalid = Config::from_toml("[presets.slug.short]\nlength = 3\n");
+    assert!(matches!(invalid, Err(ConfigError::Invalid(_))));
+
+    let unknown = Config::from_toml("device_id = \"dns\"\n");
+    assert!(matches!(unknown, Err(ConfigError::Invalid(_))));
+
+    let future = Config::from_toml("timebase = 99999999999\n");
+    assert!(matches!(future, Err(ConfigError::Invalid(_))));
+
+    let stride = Config::from_toml("sequence_stride = { offset = 2, step = 2 }\n");
+    assert!(matches!(stride, Err(ConfigError::Invalid(_))));
+
+    let blocks = Config::from_toml("blocks_per_claim = 0\n");
+    assert!(matches!(blocks, Err(ConfigError::Invalid(_))));
+
+    let malformed = Config::from_toml("device_id = [1, 2]\n");
+    assert!(matches!(malformed, Err(ConfigError::Parse(_))));
+}
@@ -0,0 +1,34 @@
+use fastsend::{Block, BlockFrame, ConstructBlock, Cursor, Token, ID};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 首次构造时 panic 的元素类型。
+#[derive(Debug, Clone)]
+struct Flaky(u64);
+
+static PANICKED: AtomicBool = AtomicBool::new(false);
+
+impl ConstructBlock for Flaky {
+    fn construct_block(n: usize, cursor: Cursor) -> Block<Self> {
+        if !PANICKED.swap(true, Ordering::SeqCst) {
+            panic!("construct_block failed");
+        }
+
+        let mut tokens = Token::construct_block(n, cursor);
+        std::array::from_fn(|_| Flaky(tokens.next().unwrap().id())).into()
+    }
+}
+
+#[test]
+fn test_construct_panic() {
+    let frame = BlockFrame::<Flaky>::new();
+
+    // 构造 `Block` 时的 panic 传递给调用方，之后的调用不会因此永久等待
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        futures::executor::block_on(frame.next_block())
+    }));
+    assert!(result.is_err());
+
+    let mut block = futures::executor::block_on(frame.next_block());
+    assert_ne!(block.next().unwrap().0, 0);
+}
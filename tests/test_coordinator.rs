@@ -0,0 +1,102 @@
+#![cfg(feature = "coordinator")]
+
+use fastsend::{Bootstrap, BootstrapError, Coordinator, CoordinatorError, HttpCoordinator};
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+struct Fixed {
+    worker_id: u8,
+    offset: i64,
+}
+
+impl Coordinator for Fixed {
+    fn worker_id(&self) -> Pin<Box<dyn Future<Output = Result<u8, CoordinatorError>> + Send + '_>> {
+        Box::pin(async move { Ok(self.worker_id) })
+    }
+
+    fn heartbeat(
+        &self,
+        _: u8,
+    ) -> Pin<Box<dyn Future<Output = Result<(), CoordinatorError>> + Send + '_>> {
+        Box::pin(async move { Ok(()) })
+    }
+
+    fn time_offset(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<i64, CoordinatorError>> + Send + '_>> {
+        Box::pin(async move { Ok(self.offset) })
+    }
+}
+
+// 分配到的设备号会写入全局设备号，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_bootstrap() {
+    let skewed = Fixed {
+        worker_id: 7,
+        offset: -5_000,
+    };
+    let result = Bootstrap::new(skewed).run().await;
+    assert!(matches!(result, Err(BootstrapError::ClockSkew(-5_000))));
+
+    let fixed = Fixed {
+        worker_id: 7,
+        offset: 200,
+    };
+    assert_eq!(Bootstrap::new(fixed).run().await.unwrap(), 7);
+    assert!(fastsend::DEVICE_ID.is_some());
+
+    let fixed = Fixed {
+        worker_id: 8,
+        offset: 0,
+    };
+    let result = Bootstrap::new(fixed).run().await;
+    assert!(matches!(result, Err(BootstrapError::AlreadyResolved)));
+
+    fastsend::shutdown().await;
+}
+
+/// 按请求路径返回固定响应的 HTTP 模拟服务，每个连接只处理一个请求。
+async fn serve(listener: TcpListener) {
+    while let Ok((mut stream, _)) = listener.accept().await {
+        let mut buf = vec![0; 4096];
+        let n = stream.read(&mut buf).await.unwrap();
+        let request = String::from_utf8_lossy(&buf[..n]);
+
+        let body = if request.starts_with("GET /time ") {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis();
+            format!("{{\"unix_millis\":{}}}", now)
+        } else if request.starts_with("POST /worker-id ") {
+            "{\"worker_id\":300}".to_owned()
+        } else {
+            String::new()
+        };
+
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_http_coordinator() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}/", listener.local_addr().unwrap());
+    tokio::spawn(serve(listener));
+
+    let coordinator = HttpCoordinator::new(&endpoint).instance("test");
+    let offset = coordinator.time_offset().await.unwrap();
+    assert!(offset.abs() < Duration::from_secs(1).as_millis() as i64);
+    coordinator.heartbeat(3).await.unwrap();
+
+    // 超出 u8 范围的设备号视为错误
+    assert!(coordinator.worker_id().await.is_err());
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "coupon")]
+
+use fastsend::{CouponSerialer, Serialer};
+
+#[tokio::test]
+async fn test_coupon_format() {
+    let serialer = CouponSerialer::new().prefix("spring");
+    let coupon = serialer.clone().build().await.unwrap();
+
+    assert!(coupon.starts_with("SPRING-"));
+    assert_eq!(coupon.len(), "SPRING-XXXX-XXXX-X".len());
+    assert!(serialer.validate(&coupon));
+    assert!(serialer.validate(&coupon.to_ascii_lowercase()));
+    assert!(!CouponSerialer::new().prefix("summer").validate(&coupon));
+}
+
+#[test]
+fn test_coupon_checksum() {
+    let serialer = CouponSerialer::new().group(0).length(6);
+    for coupon in serialer.batch(1000) {
+        assert!(serialer.validate(&coupon));
+
+        // 任意单个字符被替换后，校验位都应能发现错误
+        let mut tampered = coupon.into_bytes();
+        tampered[0] = if tampered[0] == b'2' { b'3' } else { b'2' };
+        assert!(!serialer.validate(&String::from_utf8(tampered).unwrap()));
+    }
+}
+
+#[test]
+fn test_coupon_batch_unique() {
+    // 字符表为 2 个字符、长度为 10 时，券码空间仅有 1024 个，批量生成仍需保证不重复
+    let serialer = CouponSerialer::new().alphabet("AB").length(10);
+    let batch = serialer.batch(1024);
+    let set = batch.iter().collect::<std::collections::HashSet<_>>();
+    assert_eq!(set.len(), 1024);
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "cuid2")]
+
+use fastsend::{Cuid2Serialer, Serialer};
+use futures::future;
+use std::collections::HashSet;
+
+const TOP: usize = 10999;
+
+#[tokio::test]
+async fn test_cuid2_format() {
+    for length in [2, 10, Cuid2Serialer::DEFAULT_LENGTH, 32] {
+        let cuid = Cuid2Serialer::new().length(length).build().await.unwrap();
+        assert_eq!(cuid.len(), length);
+        assert!(cuid.chars().next().unwrap().is_ascii_lowercase());
+        assert!(cuid
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()));
+    }
+}
+
+#[tokio::test]
+async fn test_unique_cuid2() {
+    let set = future::join_all((0..TOP).map(|_| Cuid2Serialer::new().build()))
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect::<HashSet<_>>();
+
+    assert_eq!(set.len(), TOP);
+}
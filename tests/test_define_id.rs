@@ -0,0 +1,89 @@
+use fastsend::encoding::EncodingError;
+use fastsend::{ParseIdError, ID};
+
+fastsend::define_id!(
+    /// 订单 id
+    pub OrderId,
+    prefix = "ord"
+);
+fastsend::define_id!(UserId, prefix = "usr");
+
+#[tokio::test]
+async fn test_define_id() {
+    let order = OrderId::next().await;
+    assert_ne!(order, OrderId::next().await);
+
+    // 定长 base62 编码，字典序与数值大小一致
+    let text = order.to_string();
+    assert_eq!(text.len(), "ord_".len() + 11);
+    assert_eq!(text.parse::<OrderId>(), Ok(order));
+    assert_eq!(OrderId::from_id(61).to_string(), "ord_0000000000z");
+    assert!(OrderId::from_id(61).to_string() < OrderId::from_id(62).to_string());
+    assert_eq!(OrderId::from_id(order.id()), order);
+
+    // 前缀不符的字符串不能解析为其他类型的 id
+    assert_eq!(
+        text.parse::<UserId>(),
+        Err(ParseIdError::Prefix { expected: "usr" })
+    );
+    assert_eq!(
+        "ord0000000000z".parse::<OrderId>(),
+        Err(ParseIdError::Prefix { expected: "ord" })
+    );
+    assert!(matches!(
+        "ord_0000000000-".parse::<OrderId>(),
+        Err(ParseIdError::Encoding(_))
+    ));
+
+    // 只接受定长编码，同一个 id 没有其他的字符串表示
+    assert_eq!(
+        "ord_1".parse::<OrderId>(),
+        Err(ParseIdError::Encoding(EncodingError::InvalidLength(1)))
+    );
+    assert_eq!(
+        "ord_000000000001".parse::<OrderId>(),
+        Err(ParseIdError::Encoding(EncodingError::InvalidLength(12)))
+    );
+    assert_eq!(
+        "ord_00000000001".parse::<OrderId>(),
+        Ok(OrderId::from_id(1))
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_define_id_serde() {
+    let order = OrderId::from_id(61);
+    let json = serde_json::to_string(&order).unwrap();
+    assert_eq!(json, "\"ord_0000000000z\"");
+    assert_eq!(serde_json::from_str::<OrderId>(&json).unwrap(), order);
+    assert!(serde_json::from_str::<UserId>(&json).is_err());
+    assert!(serde_json::from_str::<OrderId>("61").is_err());
+}
+
+#[cfg(feature = "sqlx")]
+#[tokio::test]
+async fn test_define_id_sqlx() {
+    use sqlx::sqlite::SqlitePool;
+
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    sqlx::query("CREATE TABLE orders (id BIGINT PRIMARY KEY)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    for order in [OrderId::next().await, OrderId::from_id(u64::MAX - 1)] {
+        sqlx::query("INSERT INTO orders (id) VALUES (?)")
+            .bind(order)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let (read,): (OrderId,) = sqlx::query_as("SELECT id FROM orders WHERE id = ?")
+            .bind(order)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(read, order);
+    }
+}
@@ -0,0 +1,56 @@
+use fastsend::device_id::{from_ipv4, from_k8s, from_mac, mac_address, DeviceIdError};
+use fastsend::{set_device_id, DEVICE_ID};
+use std::env;
+use std::net::Ipv4Addr;
+
+#[test]
+fn test_set_device_id() {
+    assert!(set_device_id(42));
+    assert!(DEVICE_ID.is_some());
+
+    // 设备号已被解析，再次设置不会生效
+    assert!(!set_device_id(43));
+}
+
+#[test]
+fn test_from_mac() {
+    if mac_address().is_none() {
+        assert_eq!(from_mac(8), None);
+        return;
+    }
+
+    for bits in 1..=8 {
+        let id = from_mac(bits).unwrap();
+        assert!((id as u16) < 1 << bits);
+        assert_eq!(from_mac(bits), Some(id));
+    }
+}
+
+// 各用例共享进程环境变量，因此放在同一个测试中顺序执行
+#[test]
+fn test_from_k8s() {
+    env::remove_var("POD_NAME");
+
+    env::set_var("HOSTNAME", "myapp-3");
+    assert_eq!(from_k8s(8), Ok(3));
+
+    env::set_var("POD_NAME", "my-stateful-app-12");
+    assert_eq!(from_k8s(4), Ok(12));
+    assert_eq!(
+        from_k8s(3),
+        Err(DeviceIdError::Overflow { value: 12, bits: 3 })
+    );
+
+    env::set_var("POD_NAME", "myapp-7d9f8c6b5-x2k4p");
+    assert!(matches!(from_k8s(8), Err(DeviceIdError::Malformed(_))));
+}
+
+#[test]
+fn test_from_ipv4() {
+    let ip = Ipv4Addr::new(10, 1, 2, 203);
+    assert_eq!(from_ipv4(ip, 0, 8), 203);
+    assert_eq!(from_ipv4(ip, 8, 8), 2);
+    assert_eq!(from_ipv4(Ipv4Addr::new(172, 16, 7, 1), 0, 8), 1);
+    // 仅取低 4 位
+    assert_eq!(from_ipv4(ip, 0, 4), 0b1011);
+}
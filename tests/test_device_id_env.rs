@@ -0,0 +1,11 @@
+// 设备号只在首次生成 id 时解析一次，因此环境变量需要在同一个测试中、首次生成 id 之前设置
+#[tokio::test]
+async fn test_unresolvable_device_id_source() {
+    std::env::set_var("FASTSEND_DEVICE_ID", "k8s");
+    std::env::set_var("POD_NAME", "web");
+
+    // pod 名称中没有序号时视为未配置设备号，而不是在生成 id 时 panic
+    fastsend::next_token().await;
+    assert!(fastsend::try_next_token().await.is_ok());
+    assert_eq!(*fastsend::DEVICE_ID, None);
+}
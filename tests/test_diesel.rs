@@ -0,0 +1,64 @@
+#![cfg(feature = "diesel")]
+
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use fastsend::{Token, ID};
+
+diesel::table! {
+    orders (id) {
+        id -> BigInt,
+        buyer -> Nullable<BigInt>,
+    }
+}
+
+#[derive(Debug, Insertable, Queryable)]
+#[diesel(table_name = orders)]
+struct Order {
+    id: Token,
+    buyer: Option<Token>,
+}
+
+#[tokio::test]
+async fn test_diesel_token() {
+    let order = Order {
+        id: fastsend::next_token().await,
+        buyer: None,
+    };
+
+    let sql = diesel::debug_query::<Pg, _>(&diesel::insert_into(orders::table).values(&order))
+        .to_string();
+    assert!(sql.starts_with(r#"INSERT INTO "orders" ("id", "buyer") VALUES ($1, DEFAULT)"#));
+
+    let query = orders::table.filter(orders::id.eq(order.id));
+    let sql = diesel::debug_query::<Pg, _>(&query).to_string();
+    assert!(sql.contains(r#"WHERE ("orders"."id" = $1)"#));
+
+    // 最高位为 1 的 id 在数据库中表现为负数，读取时按位还原（`FromSql` 对所有后端生效，这里使用内存中的 SQLite）
+    let mut conn = SqliteConnection::establish(":memory:").unwrap();
+    for token in [order.id, Token::from_id(u64::MAX - 1)] {
+        let read = diesel::select(diesel::dsl::sql::<BigInt>(&(token.id() as i64).to_string()))
+            .get_result::<Token>(&mut conn)
+            .unwrap();
+        assert_eq!(read, token);
+    }
+}
+
+#[cfg(all(feature = "uuid", feature = "diesel-postgres"))]
+#[tokio::test]
+async fn test_diesel_uuid() {
+    use fastsend::{Serialer, UUIDSerialer};
+
+    let uuid = UUIDSerialer::new_v7().build().await.unwrap();
+
+    diesel::table! {
+        coupons (code) {
+            code -> Uuid,
+        }
+    }
+
+    let query = coupons::table.filter(coupons::code.eq(uuid));
+    let sql = diesel::debug_query::<Pg, _>(&query).to_string();
+    assert!(sql.contains(r#"WHERE ("coupons"."code" = $1)"#));
+    assert!(sql.contains(&format!("binds: [{:?}]", uuid)));
+}
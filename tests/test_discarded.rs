@@ -0,0 +1,30 @@
+#![cfg(not(feature = "strict_order"))]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+// 丢弃计数与钩子是进程内共享的状态，因此各用例放在同一个测试中顺序执行
+#[test]
+fn test_discarded_tokens() {
+    let hooked = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&hooked);
+    fastsend::on_block_dropped(move |tokens| {
+        counter.fetch_add(tokens, Ordering::SeqCst);
+    });
+
+    let before = fastsend::discarded_tokens();
+
+    // 只使用 1 个 `Token` 就退出的线程会丢弃 `Block` 中剩余的 `Token`
+    std::thread::spawn(|| futures::executor::block_on(fastsend::next_token()))
+        .join()
+        .unwrap();
+
+    let discarded = fastsend::discarded_tokens() - before;
+    assert!(discarded > 0);
+    assert_eq!(hooked.load(Ordering::SeqCst) as u64, discarded);
+
+    // 未领取 `Block` 的线程退出时不会丢弃 `Token`
+    std::thread::spawn(|| {}).join().unwrap();
+    assert_eq!(fastsend::discarded_tokens() - before, discarded);
+    assert_eq!(hooked.load(Ordering::SeqCst) as u64, discarded);
+}
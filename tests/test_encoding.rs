@@ -0,0 +1,256 @@
+use fastsend::encoding::{EncodingError, Radix, DIGITS_FIRST};
+
+#[test]
+fn test_encode() {
+    let radix = Radix::digits_first(36);
+    assert_eq!(radix.encode(0), "0");
+    assert_eq!(radix.encode(35), "Z");
+    assert_eq!(radix.encode_padded(36, 4), "0010");
+
+    // 超出宽度时 `encode_padded` 保留全部的位，`encode_fixed` 返回错误
+    assert_eq!(radix.encode_padded(36 * 36, 2), "100");
+    assert_eq!(
+        radix.encode_fixed(36 * 36, 2),
+        Err(EncodingError::Overflow { width: 2 })
+    );
+    assert_eq!(radix.encode_fixed(36 * 36 - 1, 2).unwrap(), "ZZ");
+
+    let radix = Radix::letters_first(26);
+    assert_eq!(radix.encode_padded(27, 3), "ABB");
+}
+
+#[test]
+fn test_encode_fixed_into() {
+    let radix = Radix::digits_first(36);
+    let mut output = String::from("ID-");
+    radix.encode_fixed_into(35, 3, &mut output).unwrap();
+    radix
+        .encode_fixed_into(36 * 36 - 1, 2, &mut output)
+        .unwrap();
+    assert_eq!(output, "ID-00ZZZ");
+
+    // 溢出时不写入任何字符
+    assert_eq!(
+        radix.encode_fixed_into(36 * 36, 2, &mut output),
+        Err(EncodingError::Overflow { width: 2 })
+    );
+    assert_eq!(output, "ID-00ZZZ");
+
+    // 宽度超出 u64 所能表示的位数时前向填充
+    let mut output = String::new();
+    Radix::new("01")
+        .unwrap()
+        .encode_fixed_into(u64::MAX, 70, &mut output)
+        .unwrap();
+    assert_eq!(output, format!("{:070b}", u64::MAX));
+}
+
+#[test]
+fn test_decode() {
+    let radix = Radix::new("01").unwrap();
+    assert_eq!(radix.encode(5), "101");
+    assert_eq!(radix.decode("00101"), Ok(5));
+    assert_eq!(radix.decode("2"), Err(EncodingError::InvalidChar('2')));
+    assert_eq!(radix.decode(""), Err(EncodingError::Empty));
+
+    let radix = Radix::new(DIGITS_FIRST).unwrap();
+    assert_eq!(radix.decode(&radix.encode(u64::MAX)), Ok(u64::MAX));
+    assert!(matches!(
+        radix.decode("ZZZZZZZZZZZZZZ"),
+        Err(EncodingError::Overflow { .. })
+    ));
+}
+
+#[test]
+fn test_alphabet() {
+    assert_eq!(Radix::new("0"), Err(EncodingError::InvalidAlphabet));
+    assert_eq!(Radix::new("aba"), Err(EncodingError::InvalidAlphabet));
+    assert_eq!(Radix::new("a字"), Err(EncodingError::InvalidAlphabet));
+    assert_eq!(Radix::new("0123456789").unwrap().radix(), 10);
+}
+
+#[test]
+fn test_base62() {
+    use fastsend::encoding::base62;
+
+    assert_eq!(base62::encode_u64(0), "0");
+    assert_eq!(base62::encode_u64(62), "10");
+    assert_eq!(base62::encode_u64(u64::MAX), "LygHa16AHYF");
+    assert_eq!(base62::encode_u64_fixed(0), "00000000000");
+    assert_eq!(base62::encode_u64_fixed(u64::MAX).len(), base62::U64_WIDTH);
+    assert_eq!(
+        base62::encode_u128_fixed(u128::MAX).len(),
+        base62::U128_WIDTH
+    );
+
+    for n in [0, 1, 61, 62, 1 << 40, u64::MAX] {
+        assert_eq!(base62::decode_u64(&base62::encode_u64(n)), Ok(n));
+        assert_eq!(base62::decode_u64(&base62::encode_u64_fixed(n)), Ok(n));
+        assert_eq!(
+            base62::decode_u64_fixed(&base62::encode_u64_fixed(n)),
+            Ok(n)
+        );
+        assert_eq!(
+            base62::decode_u128(&base62::encode_u128(n as u128)),
+            Ok(n as u128)
+        );
+    }
+    // 定长解码拒绝非定长的输入
+    assert_eq!(
+        base62::decode_u64_fixed("z"),
+        Err(EncodingError::InvalidLength(1))
+    );
+    assert_eq!(
+        base62::decode_u64_fixed("000000000000z"),
+        Err(EncodingError::InvalidLength(13))
+    );
+    assert_eq!(
+        base62::decode_u128(&base62::encode_u128_fixed(u128::MAX)),
+        Ok(u128::MAX)
+    );
+
+    // 定长编码的字典序与数值大小一致
+    let mut encoded = [5, 61, 62, 1 << 33, u64::MAX]
+        .map(base62::encode_u64_fixed)
+        .to_vec();
+    let sorted = encoded.clone();
+    encoded.sort();
+    assert_eq!(encoded, sorted);
+
+    assert_eq!(base62::decode_u64(""), Err(EncodingError::Empty));
+    assert_eq!(
+        base62::decode_u64("a-b"),
+        Err(EncodingError::InvalidChar('-'))
+    );
+    assert_eq!(
+        base62::decode_u64("LygHa16AHYG"),
+        Err(EncodingError::Overflow { width: 11 })
+    );
+    assert!(base62::decode_u128(&"z".repeat(23)).is_err());
+}
+
+#[test]
+fn test_crockford() {
+    use fastsend::encoding::crockford;
+
+    assert_eq!(crockford::encode_u64(0), "0");
+    assert_eq!(crockford::encode_u64(32), "10");
+    assert_eq!(crockford::encode_u64(u64::MAX), "FZZZZZZZZZZZZ");
+    assert_eq!(crockford::encode_u64_fixed(31), "000000000000Z");
+    assert_eq!(
+        crockford::encode_u128_fixed(u128::MAX),
+        "7ZZZZZZZZZZZZZZZZZZZZZZZZZ"
+    );
+
+    for n in [0, 1, 31, 32, 1 << 40, u64::MAX] {
+        assert_eq!(crockford::decode_u64(&crockford::encode_u64(n)), Ok(n));
+        assert_eq!(
+            crockford::decode_u64(&crockford::encode_u64_fixed(n)),
+            Ok(n)
+        );
+        assert_eq!(
+            crockford::decode_u64_checked(&crockford::encode_u64_checked(n)),
+            Ok(n)
+        );
+        assert_eq!(
+            crockford::decode_u128_checked(&crockford::encode_u128_checked(n as u128)),
+            Ok(n as u128)
+        );
+    }
+    assert_eq!(
+        crockford::decode_u128(&crockford::encode_u128_fixed(u128::MAX)),
+        Ok(u128::MAX)
+    );
+
+    // 不区分大小写，忽略 '-'，'I'/'L' 视为 '1'、'O' 视为 '0'
+    assert_eq!(crockford::decode_u64("1o"), Ok(32));
+    assert_eq!(crockford::decode_u64("I-0"), Ok(32));
+    assert_eq!(crockford::decode_u64("l0"), Ok(32));
+    assert_eq!(
+        crockford::decode_u64("U"),
+        Err(EncodingError::InvalidChar('U'))
+    );
+    assert_eq!(crockford::decode_u64("--"), Err(EncodingError::Empty));
+
+    // 校验符号
+    assert_eq!(crockford::check_symbol(36), 'U');
+    assert_eq!(crockford::encode_u64_checked(36), "14U");
+    assert_eq!(crockford::decode_u64_checked("14u"), Ok(36));
+    assert_eq!(
+        crockford::decode_u64_checked("15U"),
+        Err(EncodingError::Checksum)
+    );
+    assert_eq!(crockford::decode_u64_checked(""), Err(EncodingError::Empty));
+
+    // 溢出
+    assert_eq!(
+        crockford::decode_u64("G000000000000"),
+        Err(EncodingError::Overflow { width: 13 })
+    );
+    assert_eq!(
+        crockford::decode_u128("80000000000000000000000000"),
+        Err(EncodingError::Overflow { width: 26 })
+    );
+}
+
+#[test]
+fn test_base64url() {
+    use fastsend::encoding::base64url;
+
+    // RFC 4648 测试向量（去掉填充）
+    let vectors = [
+        ("", ""),
+        ("f", "Zg"),
+        ("fo", "Zm8"),
+        ("foo", "Zm9v"),
+        ("foob", "Zm9vYg"),
+        ("fooba", "Zm9vYmE"),
+        ("foobar", "Zm9vYmFy"),
+    ];
+    for (plain, encoded) in vectors {
+        assert_eq!(base64url::encode(plain.as_bytes()), encoded);
+        if !plain.is_empty() {
+            assert_eq!(base64url::decode(encoded).unwrap(), plain.as_bytes());
+        }
+    }
+
+    // URL 安全的字符：'-' 与 '_' 代替 '+' 与 '/'
+    assert_eq!(base64url::encode(&[0xfb, 0xff]), "-_8");
+    assert_eq!(base64url::decode("-_8").unwrap(), [0xfb, 0xff]);
+
+    let all = (0..=255).collect::<Vec<u8>>();
+    assert_eq!(base64url::decode(&base64url::encode(&all)).unwrap(), all);
+
+    for n in [0, 1, u64::MAX as u128, u128::MAX] {
+        let encoded = base64url::encode_u128(n);
+        assert_eq!(encoded.len(), base64url::U128_WIDTH);
+        assert_eq!(base64url::decode_u128(&encoded), Ok(n));
+    }
+
+    // 不合法的输入
+    assert_eq!(base64url::decode(""), Err(EncodingError::Empty));
+    assert_eq!(
+        base64url::decode("Zm9vY"),
+        Err(EncodingError::InvalidLength(5))
+    );
+    assert_eq!(
+        base64url::decode("Zm9v+g"),
+        Err(EncodingError::InvalidChar('+'))
+    );
+    assert_eq!(
+        base64url::decode("Zm9=Yg"),
+        Err(EncodingError::InvalidChar('='))
+    );
+    assert_eq!(
+        base64url::decode("Zmé"),
+        Err(EncodingError::InvalidChar('é'))
+    );
+    assert_eq!(
+        base64url::decode("Zh"),
+        Err(EncodingError::InvalidChar('h'))
+    );
+    assert_eq!(
+        base64url::decode_array::<16>("Zm9v"),
+        Err(EncodingError::InvalidLength(4))
+    );
+}
@@ -0,0 +1,58 @@
+use fastsend::{BlockFrame, SequenceStride, Token, ID};
+use std::collections::HashSet;
+
+fn sequence(id: u64) -> u64 {
+    id >> 16 & 0xFFFF
+}
+
+// 全局随机位数只能在首次生成 id 之前设置，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_entropy_bits() {
+    assert!(fastsend::set_entropy_bits(6));
+
+    // 跨越多个 `Cursor` 时 id 不重复，且低 6 位确实被随机填充
+    let mut ids = HashSet::new();
+    let mut low = HashSet::new();
+    for _ in 0..10_000 {
+        let id = fastsend::next_token().await.id();
+        low.insert(sequence(id) & 0x3F);
+        assert!(ids.insert(id));
+    }
+    assert!(low.len() > 32);
+
+    // 全局生成器已经初始化，不能再修改随机位数
+    assert!(!fastsend::set_entropy_bits(4));
+}
+
+#[tokio::test]
+async fn test_frame_entropy_bits() {
+    let frame = BlockFrame::<Token>::new()
+        .sequence_stride(1, 2)
+        .entropy_bits(4);
+
+    // 每个 `Cursor` 下只有 65536 / 2 / 16 个发号序号，即 256 个 `Block`，步长作用于随机位之外的高位
+    let mut sequences = HashSet::new();
+    let mut cursors = HashSet::new();
+    for _ in 0..256 {
+        for token in frame.next_block().await {
+            let id = token.id();
+            cursors.insert(id >> 32);
+            assert_eq!((sequence(id) >> 4) % 2, 1);
+            assert!(sequences.insert(sequence(id) >> 4));
+        }
+    }
+    assert_eq!(cursors.len(), 1);
+    assert_eq!(
+        sequences.len(),
+        SequenceStride::new(1, 2).with_entropy(4).capacity()
+    );
+
+    let next = frame.next_block().await.next().unwrap().id() >> 32;
+    assert!(!cursors.contains(&next));
+}
+
+#[test]
+#[should_panic]
+fn test_entropy_bits_overflow() {
+    SequenceStride::new(0, 64).with_entropy(4);
+}
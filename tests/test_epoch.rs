@@ -0,0 +1,67 @@
+use fastsend::{Cursor, Granularity, ID};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// 纪元布局与基准起始时间是全局状态，且只能在首次获取 `Cursor` 之前修改，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_epoch_migration() {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    // 旧纪元使用缺省的基准起始时间，其 id 的游标没有用到版本位
+    let legacy = Cursor::timebase();
+    let old_id = (now - legacy) << 32 | 0x1234_5678;
+    fastsend::register_epoch(0, UNIX_EPOCH + Duration::from_secs(legacy));
+
+    // 新纪元以一天之前为基准起始时间
+    let timebase = now - 86400;
+    assert!(fastsend::set_epoch(
+        1,
+        1,
+        UNIX_EPOCH + Duration::from_secs(timebase)
+    ));
+    assert_eq!(Cursor::timebase(), timebase);
+
+    let token = fastsend::next_token().await;
+    let id = token.id();
+    assert_eq!(token.epoch(), 1);
+    assert_eq!(fastsend::epoch_of(id), 1);
+    assert_eq!(fastsend::epoch_of(old_id), 0);
+
+    // 版本位位于符号位之后：新 id 为正数且大于全部旧 id
+    assert!((id as i64) > 0);
+    assert!(id > old_id);
+    assert_eq!(id >> 62, 0b01);
+    assert!(((id >> 32) & 0x3fff_ffff) >= 86400);
+
+    // 新旧 id 各自按所属纪元的基准起始时间还原生成时间
+    let elapsed = |id: u64| {
+        fastsend::inspect_id(id)
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            .abs_diff(now)
+    };
+    assert!(elapsed(id) < 60);
+    assert_eq!(elapsed(old_id), 0);
+    assert_eq!(fastsend::inspect_id(id).epoch, 1);
+    assert_eq!(fastsend::inspect_id(old_id).epoch, 0);
+
+    // 新 id 落在当前纪元的分区区间内
+    let key = token.partition_key(Granularity::Day);
+    assert_eq!(fastsend::partition_of(id, Granularity::Day), key);
+    assert!(key.id_range().contains(id));
+    assert!(!key.id_range().contains(old_id));
+
+    // `Cursor` 已经被使用，无法再切换纪元
+    assert!(!fastsend::set_epoch(0, 1, UNIX_EPOCH));
+    assert_eq!(fastsend::epoch_of(fastsend::next_token().await.id()), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_set_epoch_version_overflow() {
+    fastsend::set_epoch(2, 1, SystemTime::now());
+}
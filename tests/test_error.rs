@@ -0,0 +1,44 @@
+#![cfg(feature = "test-util")]
+
+use chrono::{TimeZone, Utc};
+use fastsend::{testing, BlockFrame, Cursor, FastsendError, Token};
+
+// 虚拟时间是全局状态，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_try_next_token() {
+    // 早于 `Cursor::TIMEBASE` 的时间
+    testing::freeze_time(Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap());
+    assert_eq!(Cursor::try_new(), Err(FastsendError::ClockBeforeTimebase));
+    assert!(BlockFrame::<Token>::try_new().is_err());
+    assert_eq!(
+        fastsend::try_next_token().await,
+        Err(FastsendError::ClockBeforeTimebase)
+    );
+
+    // 超出 u32 范围的时间
+    testing::freeze_time(Utc.with_ymd_and_hms(2200, 1, 1, 0, 0, 0).unwrap());
+    assert_eq!(Cursor::try_new(), Err(FastsendError::CursorOverflow));
+
+    // 时间恢复正常后，全局生成器会重新初始化
+    testing::freeze_time(Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap());
+    let token = fastsend::try_next_token().await.unwrap();
+
+    // 耗尽当前游标下的全部 `Block`，使下一次获取需要补充，补充时的时间错误以 `Err` 返回而不是 panic
+    for _ in 1..1 << 16 {
+        fastsend::try_next_token().await.unwrap();
+    }
+    // （未开启 'pause_on_start' 时补充使用 `Cursor::incr`，不依赖当前时间）
+    if cfg!(feature = "pause_on_start") {
+        testing::freeze_time(Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(
+            fastsend::try_next_token().await,
+            Err(FastsendError::ClockBeforeTimebase)
+        );
+    }
+
+    testing::freeze_time(Utc.with_ymd_and_hms(2024, 5, 2, 0, 0, 0).unwrap());
+    let next = fastsend::try_next_token().await.unwrap();
+    assert_ne!(next, token);
+
+    testing::reset_clock();
+}
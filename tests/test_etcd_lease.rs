@@ -0,0 +1,16 @@
+#![cfg(feature = "etcd_lease")]
+
+use fastsend::device_id::{EtcdLease, LeaseError};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_etcd_lease_unreachable() {
+    // 端口 1 上不会有 etcd 服务，分配失败时应返回错误而不是回退到随机设备号
+    let result = EtcdLease::new("http://127.0.0.1:1")
+        .range(0..=15)
+        .ttl(Duration::from_secs(5))
+        .claim()
+        .await;
+
+    assert!(matches!(result, Err(LeaseError::Etcd(_))));
+}
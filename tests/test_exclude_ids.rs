@@ -0,0 +1,48 @@
+use fastsend::{Block, BlockFrame, ConstructBlock, Cursor, Token, ID};
+
+// 登记的区间是全局状态，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_exclude_ids() {
+    // 未登记任何区间
+    assert!(!fastsend::is_excluded(0));
+
+    // 构造 `Block` 时跳过登记的 id，相邻的区间会被合并
+    let cursor = Cursor::new();
+    let ids: Vec<u64> = Token::construct_block(0, cursor).map(ID::id).collect();
+    fastsend::exclude_ids(ids[1]..ids[2]);
+    fastsend::exclude_ids(ids[2]..ids[3]);
+    fastsend::exclude_ids(ids[6]..ids[6] + 1);
+    fastsend::exclude_ids(ids[0]..ids[0]);
+    assert!(!fastsend::is_excluded(ids[0]));
+    assert!(fastsend::is_excluded(ids[1]) && fastsend::is_excluded(ids[2]));
+    assert!(!fastsend::is_excluded(ids[3]));
+
+    let block: Block<Token> = Token::construct_block(0, cursor);
+    assert_eq!(block.size_hint().0, 5);
+    let issued: Vec<u64> = block.map(ID::id).collect();
+    assert_eq!(issued, [ids[0], ids[3], ids[4], ids[5], ids[7]]);
+
+    // 当前游标下的 id 全部被登记后，剩余的 `Block` 都不会被发放，`BlockFrame` 切换到新的游标
+    let frame = BlockFrame::<Token>::new();
+    let current = frame.next_block().await.next().unwrap().id() >> 32;
+    fastsend::exclude_ids(current << 32..(current + 1) << 32);
+    assert!(frame.pop_block().is_none());
+    let next = frame.next_block().await.next().unwrap().id() >> 32;
+    assert!(next > current);
+
+    #[cfg(feature = "auto_increment")]
+    {
+        use fastsend::{IncrStateBuilder, Serialer};
+
+        // 计数器同样跳过登记的区间
+        let state = IncrStateBuilder::new().with_start(0).build(|n: i64| n + 1);
+        fastsend::exclude_ids(3..5);
+
+        let mut values = Vec::new();
+        for _ in 0..4 {
+            let serial = state.incr().build().await.unwrap();
+            values.push(serial[..serial.len() - 2].parse::<u64>().unwrap());
+        }
+        assert_eq!(values, [1, 2, 5, 6]);
+    }
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "expiring")]
+
+use fastsend::serial::expiring::verify_with;
+use fastsend::{
+    verify_expiring, ExpiringError, ExpiringSerialer, HmacSigner, Serialer, TimeSerialer,
+};
+use std::time::{Duration, SystemTime};
+
+const KEY: &[u8] = b"reset-password-key";
+
+#[tokio::test]
+async fn test_expiring() {
+    let ttl = Duration::from_secs(15 * 60);
+    let token = ExpiringSerialer::new(KEY, ttl, TimeSerialer::new())
+        .oneshot(fastsend::next_token().await)
+        .await
+        .unwrap();
+
+    let now = SystemTime::now();
+    let claims = verify_expiring(&token, KEY, now).unwrap();
+    assert!(token.starts_with(claims.serial));
+    assert!(claims.expires_at > now);
+    assert!(claims.expires_at <= now + ttl);
+
+    // 过期、篡改及错误的密钥
+    let later = now + ttl + Duration::from_secs(1);
+    assert_eq!(
+        verify_expiring(&token, KEY, later),
+        Err(ExpiringError::Expired {
+            expires_at: claims.expires_at
+        })
+    );
+    assert_eq!(
+        verify_expiring(&token, b"other-key", now),
+        Err(ExpiringError::Invalid)
+    );
+
+    let mut tampered = token.clone().into_bytes();
+    tampered[0] = if tampered[0] == b'1' { b'2' } else { b'1' };
+    let tampered = String::from_utf8(tampered).unwrap();
+    assert_eq!(
+        verify_expiring(&tampered, KEY, now),
+        Err(ExpiringError::Invalid)
+    );
+    assert_eq!(verify_expiring("", KEY, now), Err(ExpiringError::Invalid));
+
+    // 自定义签名长度
+    let token = ExpiringSerialer::new(KEY, ttl, TimeSerialer::new())
+        .tag_len(16)
+        .oneshot(fastsend::next_token().await)
+        .await
+        .unwrap();
+    assert_eq!(token.rsplit_once('.').unwrap().1.len(), 32);
+    assert!(verify_with(&token, &HmacSigner::new(KEY).tag_len(16), now).is_ok());
+    assert_eq!(
+        verify_expiring(&token, KEY, now),
+        Err(ExpiringError::Invalid)
+    );
+}
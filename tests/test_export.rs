@@ -0,0 +1,117 @@
+#![cfg(feature = "export")]
+
+use fastsend::export::{self, CsvExport, ExportError};
+use fastsend::{Serialer, ID};
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::fs;
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+/// 以 `Token` 的 id 作为序列号。
+struct TokenSerialer;
+
+impl Serialer for TokenSerialer {
+    type Output = u64;
+
+    type Error = Infallible;
+
+    fn build(self) -> Pin<Box<dyn Future<Output = Result<u64, Infallible>> + Send + 'static>> {
+        Box::pin(async { Ok(fastsend::next_token().await.id()) })
+    }
+
+    fn feed(&mut self, _: &[u8]) {}
+}
+
+/// 始终生成相同的序列号。
+struct ConstSerialer(&'static str);
+
+impl Serialer for ConstSerialer {
+    type Output = &'static str;
+
+    type Error = Infallible;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<&'static str, Infallible>> + Send + 'static>> {
+        Box::pin(future::ready(Ok(self.0)))
+    }
+
+    fn feed(&mut self, _: &[u8]) {}
+}
+
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!(
+        "fastsend-export-{}-{}.csv",
+        name,
+        std::process::id()
+    ))
+}
+
+#[tokio::test]
+async fn test_write_csv_resume() {
+    let path = temp_path("resume");
+    let _ = fs::remove_file(&path);
+
+    let progress = export::write_csv(|_| TokenSerialer, 500, &path)
+        .await
+        .unwrap();
+    assert_eq!((progress.existing, progress.written), (0, 500));
+
+    // 模拟中断：末尾留下未写完的一行
+    let mut content = fs::read_to_string(&path).unwrap();
+    content.push_str("12345");
+    fs::write(&path, content).unwrap();
+
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&reports);
+    let progress = CsvExport::new(|_| TokenSerialer)
+        .progress_interval(200)
+        .on_progress(move |progress| recorded.lock().unwrap().push(progress.completed()))
+        .write(1000, &path)
+        .await
+        .unwrap();
+    assert_eq!((progress.existing, progress.written), (500, 500));
+    assert_eq!(*reports.lock().unwrap(), vec![700, 900, 1000]);
+
+    let content = fs::read_to_string(&path).unwrap();
+    let mut lines = content.lines();
+    assert_eq!(lines.next(), Some("id"));
+    let ids = lines.collect::<Vec<_>>();
+    assert_eq!(ids.len(), 1000);
+    assert_eq!(ids.iter().collect::<HashSet<_>>().len(), 1000);
+    assert!(ids.iter().all(|id| id.parse::<u64>().is_ok()));
+
+    // 已经导出完毕，不再写入
+    let progress = export::write_csv(|_| TokenSerialer, 1000, &path)
+        .await
+        .unwrap();
+    assert_eq!((progress.existing, progress.written), (1000, 0));
+
+    // 表头不一致的文件不能续写
+    let result = CsvExport::new(|_| TokenSerialer)
+        .header(Some("code"))
+        .write(2000, &path)
+        .await;
+    assert!(matches!(result, Err(ExportError::InvalidFile(_))));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_write_csv_duplicate() {
+    let path = temp_path("duplicate");
+    let _ = fs::remove_file(&path);
+
+    let result = CsvExport::new(|_| ConstSerialer("a,\"b\""))
+        .header(None)
+        .write(2, &path)
+        .await;
+    assert!(matches!(result, Err(ExportError::Duplicate(_))));
+
+    // 第一行已经写入（按 CSV 规则转义），重复的第二行被丢弃
+    assert_eq!(fs::read_to_string(&path).unwrap(), "\"a,\"\"b\"\"\"\n");
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "log")]
+
+use fastsend::{Serialer, TimeSerialer};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::sync::Mutex;
+
+/// 记录所有 'fastsend' target 下警告的简易 logger。
+struct Capture(Mutex<Vec<String>>);
+
+impl Log for Capture {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target() == "fastsend" && metadata.level() == Level::Warn
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let fallback = record
+                .key_values()
+                .get("fallback".into())
+                .map(|value| value.to_string())
+                .unwrap_or_default();
+            self.0.lock().unwrap().push(fallback);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+
+// 回退只会在全局状态首次解析时发生，因此所有断言放在同一个测试中
+#[tokio::test]
+async fn test_fallback_warning() {
+    if std::env::var_os("FASTSEND_DEVICE_ID").is_some() {
+        return;
+    }
+
+    log::set_logger(&CAPTURE).unwrap();
+    log::set_max_level(LevelFilter::Warn);
+
+    fastsend::next_token().await;
+    fastsend::next_token().await;
+    TimeSerialer::new().build().await.unwrap();
+    TimeSerialer::new().build().await.unwrap();
+
+    let warnings = CAPTURE.0.lock().unwrap().clone();
+    assert_eq!(warnings, ["device_id", "serial_device"]);
+}
@@ -0,0 +1,88 @@
+use fastsend::{Serialer, StableState, TimeSerialer};
+use std::hash::{BuildHasher, Hasher};
+
+/// 超出 `feed` 缓冲区内联容量（`TimeSerialer`、`TicketSerialer` 为 16 字节，`Random62Serialer` 为 32 字节）的数据。
+fn long_data() -> Vec<u8> {
+    (0u8..40)
+        .map(|i| i.wrapping_mul(37).wrapping_add(0x65))
+        .collect()
+}
+
+/// 依次 `feed` 每个分块，缓冲区在分块之间由内联转为堆上存储。
+fn feed_chunks<S: Serialer>(serialer: &mut S, data: &[u8]) {
+    for chunk in data.chunks(7) {
+        serialer.feed(chunk);
+    }
+}
+
+#[tokio::test]
+async fn test_time_serialer_spilled() {
+    let data = long_data();
+
+    let mut whole = TimeSerialer::new();
+    whole.feed(&data);
+    let whole = whole.build().await.unwrap();
+
+    let mut chunked = TimeSerialer::new();
+    feed_chunks(&mut chunked, &data);
+    let chunked = chunked.build().await.unwrap();
+
+    // 后 4 位仍由全部数据的哈希得到，与数据是否超出内联容量无关
+    let mut hasher = StableState::new().build_hasher();
+    hasher.write(&(data.len() as u64).to_le_bytes());
+    hasher.write(&data);
+    let sum = hasher.finish();
+    let expected = format!("{:04}", (sum ^ (sum >> 32)) % 10000);
+
+    assert_eq!(whole[17..], expected);
+    assert_eq!(chunked[17..], expected);
+}
+
+#[cfg(feature = "random62")]
+#[tokio::test]
+async fn test_random62_serialer_spilled() {
+    use fastsend::Random62Serialer;
+
+    let data = long_data();
+
+    let mut whole = Random62Serialer::new();
+    whole.feed(&data);
+    let whole = whole.build().await.unwrap();
+
+    let mut chunked = Random62Serialer::new();
+    feed_chunks(&mut chunked, &data);
+    let chunked = chunked.build().await.unwrap();
+
+    // 种子只取前 32 个字节
+    let mut truncated = Random62Serialer::new();
+    truncated.feed(&data[..32]);
+    let truncated = truncated.build().await.unwrap();
+
+    // 与以 `Vec` 保存数据时生成的序列号一致
+    assert_eq!(whole, "uPC9PilybxiBmCpfRLbq3aBZQSMdSGmJdJt");
+    assert_eq!(chunked, whole);
+    assert_eq!(truncated, whole);
+}
+
+#[cfg(feature = "ticket")]
+#[tokio::test]
+async fn test_ticket_serialer_spilled() {
+    use fastsend::TicketSerialer;
+
+    let serialer = || TicketSerialer::new(|_: &str| Box::pin(async { Ok::<_, ()>(false) }));
+
+    let mut data = vec![0x65, 0x43, 0x21, 0x00];
+    data.extend(1u8..=20);
+
+    let mut whole = serialer();
+    whole.feed(&data);
+    let whole = whole.build().await.unwrap();
+
+    let mut chunked = serialer();
+    feed_chunks(&mut chunked, &data);
+    let chunked = chunked.build().await.unwrap();
+
+    // 尾部数字序列同样超出内联容量，与以 `Vec` 保存数据时生成的序列号一致
+    assert!(whole.ends_with("-00258-007720128601800023140282803342038560437004884-AO"));
+    assert_eq!(chunked, whole);
+}
@@ -0,0 +1,101 @@
+use fastsend::{Serial, Serialer};
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+
+/// 记录 `feed` 数据的 `Serialer`，序列号为数据的十六进制形式。
+#[derive(Debug, Default, Clone)]
+struct Recorder(Vec<u8>);
+
+impl Serialer for Recorder {
+    type Output = String;
+
+    type Error = Infallible;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        let output = self.0.iter().map(|byte| format!("{:02x}", byte)).collect();
+        Box::pin(async move { Ok(output) })
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.0.extend_from_slice(data);
+    }
+}
+
+struct Order {
+    user_id: u64,
+    coupon: Option<&'static str>,
+}
+
+impl Serial for Order {
+    fn serial<S: Serialer>(self, serialer: &mut S) {
+        serialer.feed_field("user_id", &self.user_id.to_be_bytes());
+        if let Some(coupon) = self.coupon {
+            serialer.feed_field("coupon", coupon.as_bytes());
+        }
+    }
+}
+
+#[test]
+fn test_field_digest() {
+    // 缺省实现提供字段摘要的 8 个字节
+    let mut recorder = Recorder::default();
+    recorder.feed_field("user_id", &42u64.to_be_bytes());
+    assert_eq!(
+        recorder.0,
+        fastsend::field_digest("user_id", &42u64.to_be_bytes()).to_be_bytes()
+    );
+
+    // 摘要与字段名绑定，且字段名与值之间有明确的边界
+    assert_ne!(
+        fastsend::field_digest("a", b"1"),
+        fastsend::field_digest("b", b"1")
+    );
+    assert_ne!(
+        fastsend::field_digest("ab", b"c"),
+        fastsend::field_digest("a", b"bc")
+    );
+    assert_eq!(
+        fastsend::field_digest("a", b"1"),
+        fastsend::field_digest("a", b"1")
+    );
+}
+
+#[tokio::test]
+async fn test_feed_field() {
+    let plain = Recorder::default()
+        .oneshot(Order {
+            user_id: 42,
+            coupon: None,
+        })
+        .await
+        .unwrap();
+    let with_coupon = Recorder::default()
+        .oneshot(Order {
+            user_id: 42,
+            coupon: Some("SPRING"),
+        })
+        .await
+        .unwrap();
+
+    // 缺省的可选字段不改变已有字段提供的数据
+    assert_eq!(plain.len(), 16);
+    assert!(with_coupon.starts_with(&plain));
+    assert_eq!(
+        &with_coupon[16..],
+        format!("{:016x}", fastsend::field_digest("coupon", b"SPRING"))
+    );
+
+    // 经过包装的 `Serialer` 得到相同的数据
+    let hooked = Recorder::default()
+        .on_generate(|_| {})
+        .oneshot(Order {
+            user_id: 42,
+            coupon: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(hooked, plain);
+}
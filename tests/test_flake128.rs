@@ -0,0 +1,81 @@
+#![cfg(feature = "flake128")]
+
+use fastsend::{Flake128, Flake128Serialer, Serialer, SyncSerialer};
+use std::time::SystemTime;
+
+#[tokio::test]
+async fn test_flake128_sortable() {
+    let mut prev = Flake128Serialer::new().build().await.unwrap();
+    for _ in 0..10000 {
+        let next = Flake128Serialer::new().build().await.unwrap();
+        assert!(next > prev);
+        assert!(next.to_string() > prev.to_string());
+        prev = next;
+    }
+
+    let repr = prev.to_string();
+    assert_eq!(repr.len(), 26);
+    assert_eq!(repr.parse::<Flake128>(), Ok(prev));
+    assert_eq!(repr.to_lowercase().parse::<Flake128>(), Ok(prev));
+}
+
+#[tokio::test]
+async fn test_flake128_fields() {
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+
+    let id = Flake128Serialer::new().build().await.unwrap();
+    assert!(id.timestamp_millis() >= now);
+    assert!(id.timestamp_millis() - now < 60_000);
+}
+
+#[test]
+fn test_flake128_parse() {
+    assert_eq!(
+        "7ZZZZZZZZZZZZZZZZZZZZZZZZZ".parse::<Flake128>(),
+        Ok(Flake128::from_u128(u128::MAX))
+    );
+    assert!("8ZZZZZZZZZZZZZZZZZZZZZZZZZ".parse::<Flake128>().is_err());
+    assert!("0000".parse::<Flake128>().is_err());
+    assert!("0000000000000000000000000U".parse::<Flake128>().is_err());
+}
+
+#[tokio::test]
+async fn test_flake128_build_sync() {
+    // 同步构建与异步构建共享同一个序列，生成的 id 依然保持递增
+    let first = Flake128Serialer::new().build_sync().unwrap();
+    let second = Flake128Serialer::new().build().await.unwrap();
+    let third = Flake128Serialer::new().build_sync().unwrap();
+    assert!(first < second && second < third);
+}
+
+#[test]
+fn test_flake128_base64url() {
+    let id = Flake128Serialer::new().build_sync().unwrap();
+    let encoded = id.to_base64url();
+    assert_eq!(encoded.len(), 22);
+    assert_eq!(Flake128::from_base64url(&encoded), Ok(id));
+    assert_eq!(Flake128::from_bytes(id.to_bytes()), id);
+    assert!(Flake128::from_base64url(&id.to_string()).is_err());
+}
+
+#[tokio::test]
+async fn test_flake128_oneshot_many() {
+    let mut tokens = Vec::new();
+    for _ in 0..100 {
+        tokens.push(fastsend::next_token().await);
+    }
+
+    // 同步与异步的批量构建都按顺序返回严格递增的 id
+    let ids = Flake128Serialer::new().oneshot_many_sync(tokens.clone());
+    let more = Flake128Serialer::new().oneshot_many(tokens).await;
+    let ids = ids
+        .into_iter()
+        .chain(more)
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+    assert_eq!(ids.len(), 200);
+    assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+}
@@ -0,0 +1,33 @@
+#![cfg(all(feature = "fpe", feature = "auto_increment"))]
+
+use fastsend::{FpeCipher, IncrStateBuilder, Serialer};
+use std::collections::HashSet;
+
+#[tokio::test]
+async fn test_fpe_incr_serial() {
+    let state = IncrStateBuilder::new()
+        .with_start(0)
+        .build(|current: i64| current + 1);
+    let cipher = FpeCipher::new(&[7; 32], 12);
+
+    let mut set = HashSet::new();
+    for _ in 0..1000 {
+        // `IncrSerialer` 会在尾部附加两位后缀，整体仍然是纯数字
+        let serial = cipher.wrap(state.incr()).build().await.unwrap();
+        assert_eq!(serial.len(), 12);
+        assert!(serial.chars().all(|c| c.is_ascii_digit()));
+        set.insert(serial);
+    }
+
+    assert_eq!(set.len(), 1000);
+}
+
+#[test]
+fn test_fpe_roundtrip() {
+    let cipher = FpeCipher::new(&[42; 32], 12).tweak(b"invoice");
+    let encrypted = cipher.encrypt("123").unwrap();
+
+    assert_eq!(cipher.decrypt(&encrypted).unwrap(), "000000000123");
+    assert!(cipher.encrypt("1234567890123").is_none());
+    assert!(cipher.encrypt("12a").is_none());
+}
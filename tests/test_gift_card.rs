@@ -0,0 +1,38 @@
+#![cfg(feature = "gift_card")]
+
+use fastsend::{GiftCardError, GiftCardSerialer, Serialer};
+
+#[tokio::test]
+async fn test_gift_card_entropy() {
+    let serialer = GiftCardSerialer::new("6035", 19);
+    for _ in 0..100 {
+        let number = serialer.clone().build().await.unwrap();
+        assert_eq!(number.len(), 19);
+        assert!(number.starts_with("6035"));
+        assert!(GiftCardSerialer::validate(&number));
+    }
+}
+
+#[tokio::test]
+async fn test_gift_card_counter() {
+    let mut n = 0;
+    let serialer = GiftCardSerialer::new("60351234", 16).counter(move || {
+        n += 1;
+        n
+    });
+
+    assert_eq!(serialer.clone().build().await.unwrap(), "6035123400000010");
+    assert_eq!(serialer.clone().build().await.unwrap(), "6035123400000028");
+
+    let serialer = GiftCardSerialer::new("60351234", 16).counter(|| 10_000_000);
+    assert_eq!(
+        serialer.build().await,
+        Err(GiftCardError::CounterOverflow(10_000_000))
+    );
+}
+
+#[test]
+fn test_luhn_validate() {
+    assert!(GiftCardSerialer::validate("79927398713"));
+    assert!(!GiftCardSerialer::validate("79927398710"));
+}
@@ -0,0 +1,33 @@
+use fastsend::{BlockFrame, Token};
+
+#[cfg(not(feature = "strict_order"))]
+fn sequence(id: u64) -> u64 {
+    id >> 16 & 0xFFFF
+}
+
+// 全局生成器只能在首次生成 id 之前设置，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+#[cfg(not(feature = "strict_order"))]
+async fn test_global_frame() {
+    let frame = BlockFrame::<Token>::new().sequence_stride(2, 4);
+    assert!(fastsend::set_global_frame(frame));
+
+    // `next_token` 使用设置的生成器，发号序号满足其步长
+    let mut ids = std::collections::HashSet::new();
+    for _ in 0..50_000 {
+        let id = fastsend::ID::id(fastsend::next_token().await);
+        assert_eq!(sequence(id) % 4, 2);
+        assert!(ids.insert(id));
+    }
+
+    // 全局生成器已经设置，不能再替换，全局步长也不再生效
+    assert!(!fastsend::set_global_frame(BlockFrame::new()));
+    assert!(!fastsend::set_sequence_stride(0, 2));
+}
+
+#[test]
+#[cfg(feature = "strict_order")]
+fn test_global_frame_strict_order() {
+    // 'strict_order' 下全局生成器不是 `BlockFrame`，设置总是失败
+    assert!(!fastsend::set_global_frame(BlockFrame::<Token>::new()));
+}
@@ -0,0 +1,15 @@
+#![cfg(not(feature = "strict_order"))]
+
+use fastsend::{BlockFrame, Token};
+
+// 全局配置只能在首次生成 id 之前设置，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_global_frame_configured() {
+    // 已经设置了全局随机位数，自行构造的生成器不能将其静默覆盖
+    assert!(fastsend::set_entropy_bits(4));
+    assert!(!fastsend::set_global_frame(BlockFrame::<Token>::new()));
+
+    // 全局生成器仍按全局配置初始化
+    fastsend::next_token().await;
+    assert!(!fastsend::set_entropy_bits(2));
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "gs1")]
+
+use fastsend::{Gs1Error, Gs1Format, Gs1Serialer, Serialer};
+
+#[tokio::test]
+async fn test_gs1_ean13() {
+    let mut item = 12345;
+    let serialer = Gs1Serialer::new(Gs1Format::Ean13, "400638", move || {
+        item += 1;
+        item - 1
+    });
+
+    let code = serialer.clone().build().await.unwrap();
+    assert_eq!(code, "4006380123458");
+    assert!(Gs1Serialer::validate(&code));
+    // 4006381333931 是常见的 EAN-13 示例
+    assert!(Gs1Serialer::validate("4006381333931"));
+    assert!(!Gs1Serialer::validate("4006381333932"));
+    assert!(!Gs1Serialer::validate("400638133393é"));
+    assert!(!Gs1Serialer::validate("é"));
+}
+
+#[tokio::test]
+async fn test_gs1_overflow() {
+    let serialer = Gs1Serialer::new(Gs1Format::Ean8, "12345", || 1000);
+    assert_eq!(
+        serialer.build().await,
+        Err(Gs1Error::ItemReferenceOverflow(1000))
+    );
+
+    // UPC-A: 036000291452
+    assert!(Gs1Serialer::validate("036000291452"));
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "hlc")]
+
+use fastsend::{Hlc, HlcError, HlcId, HlcTimestamp};
+use std::collections::HashSet;
+use std::time::{Duration, SystemTime};
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+#[test]
+fn test_hlc_now() {
+    let hlc = Hlc::new();
+
+    let first = hlc.now();
+    assert!(first.physical_millis().abs_diff(now_millis()) < 1000);
+
+    // 同一毫秒内逻辑计数器递增，时间戳严格递增
+    let mut prev = first;
+    for _ in 0..100_000 {
+        let next = hlc.now();
+        assert!(next > prev);
+        prev = next;
+    }
+}
+
+#[test]
+fn test_hlc_observe() {
+    let hlc = Hlc::new();
+    let local = hlc.now();
+
+    // 落后的远端时间戳不影响本地时钟
+    let behind = HlcTimestamp::new(local.physical_millis() - 5000, 3);
+    assert!(hlc.observe(behind).unwrap() > local);
+
+    // 超前的远端时间戳：之后的本地时间戳沿用远端的物理时间，并递增逻辑计数器
+    let ahead = HlcTimestamp::new(local.physical_millis() + 5000, 3);
+    let merged = hlc.observe(ahead).unwrap();
+    assert_eq!(merged, HlcTimestamp::new(ahead.physical_millis(), 4));
+    assert_eq!(hlc.now(), HlcTimestamp::new(ahead.physical_millis(), 5));
+
+    // 逻辑计数器溢出时向后借用一毫秒
+    let full = HlcTimestamp::new(ahead.physical_millis() + 1, u16::MAX);
+    let merged = hlc.observe(full).unwrap();
+    assert_eq!(merged, HlcTimestamp::new(ahead.physical_millis() + 2, 0));
+
+    assert_eq!(HlcTimestamp::from_u64(merged.as_u64()), merged);
+}
+
+#[test]
+fn test_hlc_max_offset() {
+    let hlc = Hlc::new().max_offset(Duration::from_secs(1));
+    let local = hlc.now();
+
+    let ahead = HlcTimestamp::new(local.physical_millis() + 60_000, 0);
+    assert!(matches!(
+        hlc.observe(ahead),
+        Err(HlcError::ClockOffset { offset }) if offset > Duration::from_secs(59)
+    ));
+
+    // 被拒绝的时间戳不会被合并
+    assert!(hlc.now() < ahead);
+}
+
+#[test]
+fn test_hlc_id() {
+    let mut prev = fastsend::next_hlc_id();
+    let mut ids = HashSet::new();
+    for _ in 0..10_000 {
+        let id = fastsend::next_hlc_id();
+        assert!(id > prev);
+        assert!(id.timestamp() > prev.timestamp());
+        assert!(ids.insert(id));
+        prev = id;
+    }
+
+    // 合并上游的时间戳之后，生成的 id 大于上游的 id
+    let upstream = HlcId::from_u128(((now_millis() + 10_000) as u128) << 80);
+    fastsend::observe_hlc(upstream.timestamp()).unwrap();
+    let id = fastsend::next_hlc_id();
+    assert!(id > upstream);
+
+    let encoded = id.to_string();
+    assert_eq!(encoded.len(), 26);
+    assert_eq!(encoded.parse::<HlcId>(), Ok(id));
+    assert!("0".parse::<HlcId>().is_err());
+}
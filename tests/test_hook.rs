@@ -0,0 +1,55 @@
+use fastsend::{GenerateEvent, Generated, Serialer, TimeSerialer, ID};
+use std::sync::{Arc, Mutex};
+
+fn record(events: &Arc<Mutex<Vec<(&'static str, String)>>>) -> impl Fn(&GenerateEvent<'_>) {
+    let events = Arc::clone(events);
+    move |event| {
+        events
+            .lock()
+            .unwrap()
+            .push((event.kind, event.output.to_string()))
+    }
+}
+
+// 全局钩子是进程内共享的状态，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_hook() {
+    let global = Arc::new(Mutex::new(Vec::new()));
+    let id = fastsend::on_generate(record(&global));
+
+    let token = fastsend::next_token().await;
+    assert_eq!(
+        global.lock().unwrap().as_slice(),
+        [("Token", token.id().to_string())]
+    );
+
+    // 带有钩子的 `Serialer` 同时调用自身的钩子及全局钩子，未包装的 `Serialer` 不触发钩子
+    let local = Arc::new(Mutex::new(Vec::new()));
+    let serial = TimeSerialer::new()
+        .on_generate(record(&local))
+        .oneshot(token)
+        .await
+        .unwrap();
+    TimeSerialer::new().oneshot(token).await.unwrap();
+
+    assert_eq!(
+        local.lock().unwrap().as_slice(),
+        [("TimeSerialer", serial.clone())]
+    );
+    assert_eq!(global.lock().unwrap().len(), 2);
+    assert_eq!(global.lock().unwrap()[1], ("TimeSerialer", serial));
+
+    // 事件中携带设备号及时间
+    let check = fastsend::on_generate(|event| {
+        assert!(matches!(event.output, Generated::Id(_)));
+        assert_eq!(event.device, *fastsend::DEVICE_ID);
+        assert!(event.timestamp <= std::time::SystemTime::now());
+    });
+    fastsend::next_token().await;
+    assert!(fastsend::remove_hook(check));
+
+    assert!(fastsend::remove_hook(id));
+    assert!(!fastsend::remove_hook(id));
+    fastsend::next_token().await;
+    assert_eq!(global.lock().unwrap().len(), 3);
+}
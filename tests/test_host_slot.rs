@@ -0,0 +1,31 @@
+use fastsend::device_id::{HostSlot, HostSlotError};
+use fastsend::DEVICE_ID;
+
+#[test]
+fn test_host_slot() {
+    let dir = std::env::temp_dir().join(format!("fastsend-host-slot-{}", std::process::id()));
+    let slots = HostSlot::new().base(5).bits(1).dir(&dir);
+
+    let first = slots.lock().unwrap();
+    assert_eq!((first.slot(), first.device_id()), (0, 10));
+
+    let second = slots.claim().unwrap();
+    assert_eq!((second.slot(), second.device_id()), (1, 11));
+    assert!(DEVICE_ID.is_some());
+
+    assert!(matches!(slots.lock(), Err(HostSlotError::Exhausted)));
+
+    // 释放后槽位可以被重新占用
+    drop(first);
+    assert_eq!(slots.lock().unwrap().slot(), 0);
+
+    // 5 << 7 超出 8 位设备号的范围
+    let result = HostSlot::new().base(5).bits(7).dir(&dir).lock();
+    assert!(matches!(
+        result,
+        Err(HostSlotError::Overflow { base: 5, bits: 7 })
+    ));
+
+    drop(second);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,60 @@
+use fastsend::{IdSource, IdValue, SerialerSource, TimeSerialer, Token, TokenSource};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// 对 `IdSource` 泛型的应用代码。
+async fn collect<S: IdSource>(source: &S, n: usize) -> Vec<IdValue> {
+    let mut ids = Vec::with_capacity(n);
+    for _ in 0..n {
+        ids.push(source.next().await);
+    }
+    ids
+}
+
+#[tokio::test]
+async fn test_token_source() {
+    let ids = collect(&TokenSource, 1000).await;
+    assert!(ids.iter().all(|id| matches!(id, IdValue::U64(_))));
+    assert_eq!(ids.iter().collect::<HashSet<_>>().len(), ids.len());
+
+    let id = ids[0].as_u64().unwrap();
+    assert_eq!(ids[0].as_u128(), Some(id as u128));
+    assert_eq!(ids[0].to_string(), id.to_string());
+    assert_eq!(IdValue::from(Token::from_id(id)), ids[0]);
+}
+
+#[tokio::test]
+async fn test_serialer_source() {
+    let source: Arc<dyn IdSource> = Arc::new(SerialerSource::new(TimeSerialer::default()));
+    let ids = collect(&source, 100).await;
+    assert!(ids
+        .iter()
+        .all(|id| id.as_str().is_some_and(|s| !s.is_empty())));
+    assert_eq!(ids.iter().collect::<HashSet<_>>().len(), ids.len());
+    assert_eq!(ids[0].as_u64(), None);
+    assert_eq!(ids[0].to_string(), ids[0].as_str().unwrap());
+}
+
+#[cfg(feature = "flake128")]
+#[tokio::test]
+async fn test_flake128_source() {
+    use fastsend::Flake128Serialer;
+
+    let source: Box<dyn IdSource> = Box::new(SerialerSource::new(Flake128Serialer::new()));
+    let ids = collect(&source, 100).await;
+    let values = ids
+        .iter()
+        .map(|id| id.as_u128().unwrap())
+        .collect::<Vec<_>>();
+    assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[cfg(feature = "uuid")]
+#[tokio::test]
+async fn test_uuid_v7_source() {
+    use fastsend::UUIDSerialer;
+
+    let source = SerialerSource::new(UUIDSerialer::new_v7());
+    let id = source.next().await.as_u128().unwrap();
+    assert_eq!(id >> 76 & 0xF, 7);
+}
@@ -0,0 +1,214 @@
+use fastsend::device_id::{CheckError, ConflictCheck, UdpBroadcast};
+use fastsend::{
+    set_device_id, ClockErrorPolicy, ClockSkewPolicy, ClockSource, ConflictPolicy, Init, InitError,
+    Ntp,
+};
+use std::future::Future;
+use std::net::UdpSocket;
+use std::pin::Pin;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+struct Always(bool);
+
+impl ConflictCheck for Always {
+    fn check(
+        &mut self,
+        _: u8,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, CheckError>> + Send + '_>> {
+        let conflicted = self.0;
+        Box::pin(async move { Ok(conflicted) })
+    }
+}
+
+#[tokio::test]
+async fn test_init_conflict_policy() {
+    set_device_id(7);
+
+    assert!(Init::new()
+        .conflict_check(Always(false))
+        .run()
+        .await
+        .is_ok());
+
+    let result = Init::new().conflict_check(Always(true)).run().await;
+    assert!(matches!(result, Err(InitError::Conflict(_))));
+
+    let result = Init::new()
+        .conflict_check(Always(true))
+        .on_conflict(ConflictPolicy::Warn)
+        .run()
+        .await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_init_udp_broadcast() {
+    set_device_id(7);
+
+    let result = Init::new()
+        .conflict_check(
+            UdpBroadcast::new()
+                .port(47_399)
+                .timeout(Duration::from_millis(200)),
+        )
+        .run()
+        .await;
+
+    // 沙箱环境中可能不存在广播路由，此时检测本身失败而不是误报冲突
+    assert!(!matches!(result, Err(InitError::Conflict(_))));
+
+    // 再次检测复用已启动的应答线程，而不是因端口已被占用而失败
+    let result = Init::new()
+        .conflict_check(
+            UdpBroadcast::new()
+                .port(47_399)
+                .timeout(Duration::from_millis(200)),
+        )
+        .run()
+        .await;
+    let in_use = match &result {
+        Err(InitError::Check(error)) => error
+            .downcast_ref::<std::io::Error>()
+            .is_some_and(|error| error.kind() == std::io::ErrorKind::AddrInUse),
+        _ => false,
+    };
+    assert!(!in_use);
+    assert!(!matches!(result, Err(InitError::Conflict(_))));
+
+    // 应答线程仍对本进程设备号的探测进行应答
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket
+        .set_read_timeout(Some(Duration::from_secs(1)))
+        .unwrap();
+    let device_id = fastsend::DEVICE_ID.unwrap();
+    socket
+        .send_to(
+            format!("fastsend?{}", device_id).as_bytes(),
+            "127.0.0.1:47399",
+        )
+        .unwrap();
+    let mut buf = [0u8; 64];
+    let n = socket.recv(&mut buf).unwrap();
+    assert!(buf[..n].starts_with(format!("fastsend!{}:", device_id).as_bytes()));
+}
+
+struct Skewed(i64);
+
+impl ClockSource for Skewed {
+    fn offset(&mut self) -> Pin<Box<dyn Future<Output = Result<i64, CheckError>> + Send + '_>> {
+        let offset = self.0;
+        Box::pin(async move { Ok(offset) })
+    }
+}
+
+#[tokio::test]
+async fn test_init_clock_skew_policy() {
+    set_device_id(7);
+
+    assert!(Init::new().clock_check(Skewed(-800)).run().await.is_ok());
+
+    let result = Init::new().clock_check(Skewed(-5_000)).run().await;
+    assert!(matches!(result, Err(InitError::ClockSkew(-5_000))));
+
+    let start = Instant::now();
+    let result = Init::new()
+        .clock_check(Skewed(300))
+        .max_clock_skew(Duration::from_millis(100))
+        .on_clock_skew(ClockSkewPolicy::Pause)
+        .run()
+        .await;
+    assert!(result.is_ok());
+    assert!(start.elapsed() >= Duration::from_millis(300));
+}
+
+struct Unreachable;
+
+impl ClockSource for Unreachable {
+    fn offset(&mut self) -> Pin<Box<dyn Future<Output = Result<i64, CheckError>> + Send + '_>> {
+        Box::pin(async { Err("time server is unreachable".into()) })
+    }
+}
+
+#[tokio::test]
+async fn test_init_clock_error_policy() {
+    set_device_id(7);
+
+    let result = Init::new().clock_check(Unreachable).run().await;
+    assert!(matches!(result, Err(InitError::Clock(_))));
+
+    // 设备号冲突的处理策略不影响时钟校验
+    let result = Init::new()
+        .clock_check(Unreachable)
+        .on_conflict(ConflictPolicy::Warn)
+        .run()
+        .await;
+    assert!(matches!(result, Err(InitError::Clock(_))));
+
+    // 跳过无法访问的时钟来源，其余时钟来源仍然生效
+    let result = Init::new()
+        .clock_check(Unreachable)
+        .clock_check(Skewed(-5_000))
+        .on_clock_error(ClockErrorPolicy::Warn)
+        .run()
+        .await;
+    assert!(matches!(result, Err(InitError::ClockSkew(-5_000))));
+}
+
+#[tokio::test]
+async fn test_init_ntp() {
+    // 模拟一个时钟快 5 秒的 NTP 服务器
+    let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap().to_string();
+    thread::spawn(move || {
+        let mut request = [0u8; 48];
+        let (_, from) = server.recv_from(&mut request).unwrap();
+
+        let now = u64::from_be_bytes(request[40..48].try_into().unwrap());
+        let remote = (now + (5 << 32)).to_be_bytes();
+
+        let mut response = [0u8; 48];
+        response[0] = 0x24;
+        response[24..32].copy_from_slice(&request[40..48]);
+        response[32..40].copy_from_slice(&remote);
+        response[40..48].copy_from_slice(&remote);
+        server.send_to(&response, from).unwrap();
+    });
+
+    let mut ntp = Ntp::new(&addr);
+    let offset = ntp.offset().await.unwrap();
+    assert!((4_900..=5_000).contains(&offset));
+}
+
+#[tokio::test]
+async fn test_init_cursor_file() {
+    let path = std::env::temp_dir().join(format!("fastsend-cursor-{}", std::process::id()));
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    // 上一次运行的游标超前于当前时钟（重启前后时钟被回拨）
+    std::fs::write(&path, (now + 2).to_string()).unwrap();
+    let result = Init::new().cursor_file(&path).run().await;
+    assert!(matches!(result, Err(InitError::TimeTravel(1..=2))));
+
+    // 等待至时钟超过持久化的游标
+    let start = Instant::now();
+    let result = Init::new()
+        .cursor_file(&path)
+        .persist_interval(Duration::from_millis(50))
+        .on_clock_skew(ClockSkewPolicy::Pause)
+        .run()
+        .await;
+    assert!(result.is_ok());
+    assert!(start.elapsed() >= Duration::from_secs(1));
+
+    // 生成 id 之后，全局生成器的游标被持久化，且不早于启动时的时间
+    fastsend::next_token().await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let persisted = std::fs::read_to_string(&path).unwrap();
+    assert!(persisted.parse::<u64>().unwrap() > now + 2);
+
+    std::fs::remove_file(&path).unwrap();
+}
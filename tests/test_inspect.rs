@@ -0,0 +1,46 @@
+use fastsend::ID;
+use std::time::{Duration, SystemTime};
+
+/// 与 `DEVICE_ID` 相同的混淆过程。
+fn mix(device: u8, rv: u8) -> u8 {
+    (0..3).fold(device, |device, _| device.rotate_left(3) ^ rv)
+}
+
+#[test]
+fn test_inspect_id() {
+    let cursor = 100u64;
+    let id = cursor << 32 | 0xbeef_2a07;
+
+    let info = fastsend::inspect_id(id);
+    assert_eq!(
+        info.timestamp,
+        SystemTime::UNIX_EPOCH + Duration::from_secs(fastsend::Cursor::timebase() + cursor)
+    );
+    assert_eq!(info.sequence, 0xbeef);
+    assert_eq!(info.device, 0x2a);
+    assert_eq!(info.thread_bits, 0x07);
+    assert_eq!(info.tag, 0);
+    assert_eq!(info.epoch, 0);
+}
+
+#[test]
+fn test_original_device() {
+    for rv in [0, 1, 42, 255] {
+        for device in 0..=u8::MAX {
+            let id = (mix(device, rv) as u64) << 8;
+            assert_eq!(fastsend::inspect_id(id).original_device(rv), device);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_inspect_token() {
+    let token = fastsend::next_token().await;
+    let info = fastsend::inspect_id(token.id());
+
+    let elapsed = SystemTime::now()
+        .duration_since(info.timestamp)
+        .unwrap_or_default();
+    assert!(elapsed < Duration::from_secs(5));
+    assert_eq!(info.sequence as u32, token.shard_key() >> 16);
+}
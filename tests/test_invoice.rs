@@ -0,0 +1,66 @@
+#![cfg(feature = "invoice")]
+
+use chrono::{Datelike, Local, NaiveDate};
+use fastsend::{InvoiceState, MemoryCounter, Serialer};
+
+#[test]
+fn test_fiscal_year() {
+    let state = InvoiceState::new(MemoryCounter::default()).start_month(4);
+    let date = |y, m| NaiveDate::from_ymd_opt(y, m, 1).unwrap();
+
+    assert_eq!(state.fiscal_year(date(2025, 3)), 2024);
+    assert_eq!(state.fiscal_year(date(2025, 4)), 2025);
+}
+
+#[tokio::test]
+async fn test_invoice_serialer() {
+    let state = InvoiceState::new(MemoryCounter::default());
+    let year = Local::now().year();
+
+    assert_eq!(
+        state.serialer().await.build().await.unwrap(),
+        format!("INV-{}-000001", year)
+    );
+    assert_eq!(
+        state.serialer().await.build().await.unwrap(),
+        format!("INV-{}-000002", year)
+    );
+}
+
+#[tokio::test]
+async fn test_invoice_gapless() {
+    let state = InvoiceState::new(MemoryCounter::default())
+        .prefix("F")
+        .width(3);
+    let year = Local::now().year();
+
+    let first = state.reserve().await;
+    assert_eq!(first.as_str(), format!("F-{}-001", year));
+    first.rollback();
+
+    // 被放弃的编号会被重新发放
+    let second = state.reserve().await;
+    assert_eq!(second.as_str(), format!("F-{}-001", year));
+    assert_eq!(second.confirm(), format!("F-{}-001", year));
+
+    let third = state.reserve().await;
+    assert_eq!(third.confirm(), format!("F-{}-002", year));
+}
+
+#[tokio::test]
+async fn test_invoice_pending_reservation() {
+    let state = InvoiceState::new(MemoryCounter::default());
+    let year = Local::now().year();
+
+    // 存在未决的预留编号时 `serialer` 等待，预留编号被放弃后该编号由 `serialer` 发放，不会留下断号
+    let pending = state.reserve().await;
+    let serialer = state.serialer();
+    futures::pin_mut!(serialer);
+    assert!(futures::poll!(&mut serialer).is_pending());
+
+    pending.rollback();
+    assert_eq!(
+        serialer.await.build().await.unwrap(),
+        format!("INV-{}-000001", year)
+    );
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "js_safe")]
+
+use fastsend::{JsSafeId, Token, ID};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Order {
+    #[serde(with = "fastsend::js_safe")]
+    id: u64,
+    #[serde(with = "fastsend::js_safe")]
+    token: Token,
+    buyer: JsSafeId,
+}
+
+#[test]
+fn test_js_safe_field() {
+    let id = u64::MAX - 1;
+    let order = Order {
+        id,
+        token: Token::from_id(id),
+        buyer: JsSafeId(Token::from_id(42)),
+    };
+
+    let value = serde_json::to_value(&order).unwrap();
+    assert_eq!(
+        value,
+        json!({ "id": id.to_string(), "token": id.to_string(), "buyer": "42" })
+    );
+    assert_eq!(serde_json::from_value::<Order>(value).unwrap(), order);
+
+    // 同时接受数字形式
+    let order =
+        serde_json::from_value::<Order>(json!({ "id": 1, "token": 2, "buyer": 3 })).unwrap();
+    assert_eq!((order.id, order.token.id(), order.buyer.id()), (1, 2, 3));
+
+    assert!(serde_json::from_value::<JsSafeId<u64>>(json!(-1)).is_err());
+    assert!(serde_json::from_value::<JsSafeId<u64>>(json!("0x10")).is_err());
+}
+
+#[test]
+fn test_token_serde() {
+    let token = Token::from_id(u64::MAX - 1);
+    // 启用 'js_safe' feature 不改变 `Token` 自身的序列化形式
+    let value = serde_json::to_value(token).unwrap();
+    assert_eq!(value, json!(u64::MAX - 1));
+    assert_eq!(serde_json::from_value::<Token>(value).unwrap(), token);
+}
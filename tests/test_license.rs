@@ -0,0 +1,63 @@
+#![cfg(feature = "license")]
+
+use ed25519_dalek::SigningKey;
+use fastsend::{validate_license, LicenseSerialer, LicenseVerifier, Serialer};
+
+#[tokio::test]
+async fn test_license_hmac() {
+    let mut serialer = LicenseSerialer::hmac(b"secret");
+    serialer.feed(b"alice@example.com");
+
+    let key = serialer.build().await.unwrap();
+    assert_eq!(key.len(), 23);
+    assert_eq!(key.split('-').count(), 4);
+
+    let payload = validate_license(&key, LicenseVerifier::Hmac(b"secret"), b"alice@example.com");
+    assert_eq!(payload.map(|payload| payload.len()), Some(5));
+
+    // 忽略大小写与分隔符
+    let loose = key.to_lowercase().replace('-', "");
+    assert!(validate_license(
+        &loose,
+        LicenseVerifier::Hmac(b"secret"),
+        b"alice@example.com"
+    )
+    .is_some());
+
+    assert!(
+        validate_license(&key, LicenseVerifier::Hmac(b"other"), b"alice@example.com").is_none()
+    );
+    assert!(validate_license(&key, LicenseVerifier::Hmac(b"secret"), b"bob@example.com").is_none());
+}
+
+#[tokio::test]
+async fn test_license_tampered() {
+    let key = LicenseSerialer::hmac(b"secret").build().await.unwrap();
+
+    let mut tampered = key.into_bytes();
+    tampered[0] = if tampered[0] == b'0' { b'1' } else { b'0' };
+    let tampered = String::from_utf8(tampered).unwrap();
+
+    assert!(validate_license(&tampered, LicenseVerifier::Hmac(b"secret"), b"").is_none());
+    assert!(validate_license("!!!!!-!!!!!", LicenseVerifier::Hmac(b"secret"), b"").is_none());
+}
+
+#[tokio::test]
+async fn test_license_ed25519() {
+    let signing_key = SigningKey::from_bytes(&[7; 32]);
+    let verifying_key = signing_key.verifying_key();
+
+    let mut serialer = LicenseSerialer::ed25519(&signing_key).payload_len(8);
+    serialer.feed(b"product-42");
+    let key = serialer.build().await.unwrap();
+
+    let payload = validate_license(
+        &key,
+        LicenseVerifier::Ed25519(&verifying_key),
+        b"product-42",
+    );
+    assert_eq!(payload.map(|payload| payload.len()), Some(8));
+
+    let other = SigningKey::from_bytes(&[8; 32]).verifying_key();
+    assert!(validate_license(&key, LicenseVerifier::Ed25519(&other), b"product-42").is_none());
+}
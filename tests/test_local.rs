@@ -0,0 +1,89 @@
+use fastsend::{Block, BlockFrame, ConstructBlock, Cursor, LocalSerialer};
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+/// 在单线程内共享已生成序列号的 `LocalSerialer`，构建过程跨越等待点持有 `Rc`。
+struct Recorder {
+    seen: Rc<RefCell<HashSet<u64>>>,
+    next: Rc<Cell<u64>>,
+    data: Vec<u8>,
+}
+
+impl LocalSerialer for Recorder {
+    type Output = String;
+
+    type Error = Infallible;
+
+    fn build(self) -> Pin<Box<dyn Future<Output = Result<String, Infallible>> + 'static>> {
+        Box::pin(async move {
+            let n = self.next.get();
+            tokio::task::yield_now().await;
+            self.next.set(n + 1);
+            self.seen.borrow_mut().insert(n);
+            Ok(format!("{}-{}", String::from_utf8_lossy(&self.data), n))
+        })
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.data.extend_from_slice(data);
+    }
+}
+
+/// 包含 `Rc` 的元素，不满足 `Send`。
+#[derive(Debug, Clone)]
+struct LocalItem(Rc<(usize, usize)>);
+
+impl ConstructBlock for LocalItem {
+    fn construct_block(n: usize, _: Cursor) -> Block<Self> {
+        Block::from(std::array::from_fn(|i| LocalItem(Rc::new((n, i)))))
+    }
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_local_serialer() {
+    let local = tokio::task::LocalSet::new();
+    local
+        .run_until(async {
+            let seen = Rc::new(RefCell::new(HashSet::new()));
+            let next = Rc::new(Cell::new(0));
+
+            let serial = tokio::task::spawn_local(
+                Recorder {
+                    seen: Rc::clone(&seen),
+                    next: Rc::clone(&next),
+                    data: Vec::new(),
+                }
+                .oneshot(b"order"),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+            assert_eq!(serial, "order-0");
+            assert_eq!(next.get(), 1);
+            assert!(seen.borrow().contains(&0));
+        })
+        .await;
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_local_block_frame() {
+    let frame = BlockFrame::<LocalItem>::new();
+
+    let local = tokio::task::LocalSet::new();
+    local
+        .run_until(async move {
+            let mut batches = HashSet::new();
+            for _ in 0..16 {
+                let block = tokio::task::spawn_local(frame.next_block()).await.unwrap();
+                let items = block.map(|item| *item.0).collect::<Vec<_>>();
+                assert_eq!(items.len(), 8);
+                assert!(batches.insert(items[0].0));
+            }
+        })
+        .await;
+}
@@ -0,0 +1,38 @@
+#![cfg(all(fastsend_loom, feature = "test-util"))]
+
+//! 运行方式：RUSTFLAGS="--cfg fastsend_loom" cargo test --release --features test-util --test test_loom
+
+use chrono::{TimeZone, Utc};
+use fastsend::{testing, BlockFrame, Token, ID};
+use loom::future::block_on;
+use loom::thread;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+// 两个线程同时从空的 `BlockFrame` 获取 `Block`，无论补充线程与消费线程如何交错，都应当各自拿到 `Block`（不会
+// 丢失唤醒而永久等待），且 `Block` 中的 `Token` 互不重复
+#[test]
+fn test_concurrent_next_block() {
+    // 冻结时间，使 `Cursor::next` 直接推进虚拟时间而不是自旋等待
+    testing::freeze_time(Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap());
+
+    // 限制抢占次数以控制状态空间，绝大多数并发问题在 3 次以内的抢占下即可复现
+    let mut builder = loom::model::Builder::new();
+    builder.preemption_bound = Some(3);
+
+    builder.check(|| {
+        let frame = Arc::new(BlockFrame::<Token>::new());
+
+        let handle = thread::spawn({
+            let frame = Arc::clone(&frame);
+            move || block_on(frame.next_block()).collect::<Vec<_>>()
+        });
+
+        let mut tokens = block_on(frame.next_block()).collect::<Vec<_>>();
+        tokens.extend(handle.join().unwrap());
+
+        assert_eq!(tokens.len(), 16);
+        let ids = tokens.into_iter().map(ID::id).collect::<HashSet<_>>();
+        assert_eq!(ids.len(), 16);
+    });
+}
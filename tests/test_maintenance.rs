@@ -0,0 +1,76 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 等待 `counter` 增加到 `n` 以上，超时返回 `false`。
+async fn wait_for(counter: &AtomicUsize, n: usize) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while counter.load(Ordering::SeqCst) < n {
+        if Instant::now() > deadline {
+            return false;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    true
+}
+
+// 维护任务及注册的任务是全局状态，因此各用例放在同一个测试中顺序执行
+#[tokio::test(flavor = "multi_thread")]
+async fn test_maintenance() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let job = {
+        let counter = counter.clone();
+        fastsend::register_job("counter", Duration::from_millis(20), move || {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+    };
+    assert!(fastsend::maintenance_jobs().contains(&"counter"));
+
+    // 未调用 `spawn_maintenance` 时由后台线程执行
+    assert!(wait_for(&counter, 2).await);
+
+    #[cfg(feature = "maintenance")]
+    {
+        let handle = tokio::runtime::Handle::current();
+        let maintenance = fastsend::spawn_maintenance(&handle).unwrap();
+        assert!(fastsend::spawn_maintenance(&handle).is_none());
+
+        // 交给运行时之后任务继续执行
+        let n = counter.load(Ordering::SeqCst);
+        assert!(wait_for(&counter, n + 2).await);
+
+        // 停止之后重新由后台线程执行
+        maintenance.stop();
+        let n = counter.load(Ordering::SeqCst);
+        assert!(wait_for(&counter, n + 2).await);
+    }
+
+    assert!(fastsend::remove_job(job));
+    assert!(!fastsend::remove_job(job));
+    assert!(!fastsend::maintenance_jobs().contains(&"counter"));
+
+    // 移除之后不再执行（等待可能正在进行的一次执行结束）
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let n = counter.load(Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(counter.load(Ordering::SeqCst), n);
+
+    // 任务 panic 不会终止维护任务，该任务之后照常执行
+    let runs = Arc::new(AtomicUsize::new(0));
+    let job = {
+        let runs = runs.clone();
+        fastsend::register_job("panicking", Duration::from_millis(20), move || {
+            let runs = runs.clone();
+            async move {
+                if runs.fetch_add(1, Ordering::SeqCst) == 0 {
+                    panic!("first run panics");
+                }
+            }
+        })
+    };
+    assert!(wait_for(&runs, 3).await);
+    assert!(fastsend::remove_job(job));
+}
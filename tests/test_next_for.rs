@@ -0,0 +1,71 @@
+use fastsend::{Block, BlockFrame, ConstructBlock, Cursor, Token, ID};
+use std::collections::HashSet;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+struct OrderId(u64);
+
+impl ConstructBlock for OrderId {
+    fn construct_block(n: usize, cursor: Cursor) -> Block<Self> {
+        let mut tokens = Token::construct_block(n, cursor);
+        std::array::from_fn(|_| OrderId(tokens.next().unwrap().id())).into()
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+struct InvoiceId(u64);
+
+impl ConstructBlock for InvoiceId {
+    fn construct_block(n: usize, cursor: Cursor) -> Block<Self> {
+        let mut tokens = Token::construct_block(n, cursor);
+        std::array::from_fn(|_| InvoiceId(tokens.next().unwrap().id())).into()
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_next_for() {
+    assert!(fastsend::register_frame(BlockFrame::<OrderId>::new()));
+    assert!(!fastsend::register_frame(BlockFrame::<OrderId>::new()));
+
+    // 多个线程并发获取时不重复
+    let handles = (0..4)
+        .map(|_| {
+            tokio::spawn(async {
+                let mut ids = Vec::new();
+                for _ in 0..10_000 {
+                    ids.push(fastsend::next_for::<OrderId>().await);
+                }
+                ids
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut ids = HashSet::new();
+    for handle in handles {
+        for id in handle.await.unwrap() {
+            assert!(ids.insert(id));
+        }
+    }
+    assert_eq!(ids.len(), 40_000);
+}
+
+#[tokio::test]
+async fn test_next_for_unregistered() {
+    // 未注册的类型在首次使用时以缺省配置创建生成器，之后不能再注册
+    let mut ids = HashSet::new();
+    for _ in 0..1000 {
+        assert!(ids.insert(fastsend::try_next_for::<InvoiceId>().await.unwrap()));
+    }
+    assert!(!fastsend::register_frame(BlockFrame::<InvoiceId>::new()));
+}
+
+#[tokio::test]
+async fn test_next_for_token() {
+    // `Token` 总是使用 `next_token` 的全局生成器
+    assert!(!fastsend::register_frame(BlockFrame::<Token>::new()));
+
+    let mut ids = HashSet::new();
+    for _ in 0..1000 {
+        assert!(ids.insert(fastsend::next_for::<Token>().await.id()));
+        assert!(ids.insert(fastsend::next_token().await.id()));
+    }
+}
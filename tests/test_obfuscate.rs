@@ -0,0 +1,40 @@
+use fastsend::{Obfuscator, ID};
+use std::collections::HashSet;
+
+#[test]
+fn test_obfuscator() {
+    let obfuscator = Obfuscator::from_seed(7);
+    assert_eq!(obfuscator, Obfuscator::from_seed(7));
+    assert_ne!(obfuscator, Obfuscator::from_seed(8));
+
+    for id in (0..1000).chain([u64::MAX, u64::MAX - 1, 1 << 63]) {
+        assert_eq!(obfuscator.decode(obfuscator.encode(id)), id);
+    }
+
+    // 连续的 id 混淆后不再连续
+    let encoded = (0..1000)
+        .map(|id| obfuscator.encode(id))
+        .collect::<Vec<_>>();
+    assert_eq!(encoded.iter().collect::<HashSet<_>>().len(), 1000);
+    assert!(encoded.windows(2).all(|pair| pair[0].abs_diff(pair[1]) > 1));
+
+    let obfuscator = Obfuscator::new(0x9E37_79B9_7F4A_7C15, 0);
+    assert_eq!(obfuscator.encode(0), 0);
+    assert_eq!(obfuscator.decode(obfuscator.encode(12345)), 12345);
+}
+
+#[test]
+#[should_panic]
+fn test_obfuscator_even_multiplier() {
+    Obfuscator::new(2, 0);
+}
+
+#[tokio::test]
+async fn test_obfuscated_token() {
+    let obfuscator = Obfuscator::from_seed(42);
+    let token = fastsend::next_token().await;
+
+    let public = obfuscator.wrap(token).id();
+    assert_eq!(public, obfuscator.encode(token.id()));
+    assert_eq!(obfuscator.decode(public), token.id());
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "order")]
+
+use chrono::{Local, NaiveDate};
+use fastsend::{OrderSerialer, SyncSerialer};
+use std::sync::{Arc, Mutex};
+
+fn today() -> String {
+    Local::now().format("%Y%m%d").to_string()
+}
+
+/// 按 Luhn 算法校验末尾的校验位。
+fn luhn_valid(code: &str) -> bool {
+    let sum: u32 = code
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(i, c)| {
+            let d = (c - b'0') as u32;
+            if i % 2 == 1 {
+                let d = d * 2;
+                d / 10 + d % 10
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+// 虚拟时间与按日序号表都是全局状态，因此各用例放在同一个测试中顺序执行
+#[test]
+fn test_order_serialer() {
+    // 日期 + 门店编码 + 补零的当日序号，序号按门店分别递增
+    let first = OrderSerialer::new().store("012").build_sync().unwrap();
+    let second = OrderSerialer::new().store("012").build_sync().unwrap();
+    let other = OrderSerialer::new().store("345").build_sync().unwrap();
+    assert_eq!(first, format!("{}012000001", today()));
+    assert_eq!(second, format!("{}012000002", today()));
+    assert_eq!(other, format!("{}345000001", today()));
+
+    // 序号宽度只是最小宽度
+    let order = OrderSerialer::new()
+        .store("678")
+        .width(2)
+        .build_sync()
+        .unwrap();
+    assert_eq!(order, format!("{}67801", today()));
+
+    // 校验位附加在末尾，任意改动一位数字都无法通过校验
+    let order = OrderSerialer::new()
+        .store("901")
+        .check_digit()
+        .build_sync()
+        .unwrap();
+    assert_eq!(order.len(), 8 + 3 + 6 + 1);
+    assert!(order.starts_with(&format!("{}901000001", today())));
+    assert!(luhn_valid(&order));
+    let mut tampered = order.clone().into_bytes();
+    tampered[12] = if tampered[12] == b'9' {
+        b'0'
+    } else {
+        tampered[12] + 1
+    };
+    assert!(!luhn_valid(std::str::from_utf8(&tampered).unwrap()));
+
+    // 外部序号来源收到当天日期和门店编码
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let serialer = OrderSerialer::new().store("555").sequence({
+        let calls = calls.clone();
+        move |date: NaiveDate, store: &str| {
+            calls.lock().unwrap().push((date, store.to_owned()));
+            42
+        }
+    });
+    assert_eq!(
+        serialer.build_sync().unwrap(),
+        format!("{}555000042", today())
+    );
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec![(Local::now().date_naive(), "555".to_owned())]
+    );
+
+    // 跨日后当日序号重新从 1 开始
+    #[cfg(feature = "test-util")]
+    {
+        use chrono::TimeZone;
+        use fastsend::testing;
+        use std::time::Duration;
+
+        testing::freeze_time(Local.with_ymd_and_hms(2024, 3, 15, 23, 59, 0).unwrap());
+
+        let serialer = || OrderSerialer::new().store("777");
+        assert_eq!(serialer().build_sync().unwrap(), "20240315777000001");
+        assert_eq!(serialer().build_sync().unwrap(), "20240315777000002");
+
+        testing::advance(Duration::from_secs(60));
+        assert_eq!(serialer().build_sync().unwrap(), "20240316777000001");
+
+        testing::reset_clock();
+    }
+}
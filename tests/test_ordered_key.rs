@@ -0,0 +1,34 @@
+#![cfg(feature = "ordered_key")]
+
+use fastsend::{OrderedKeys, Serialer};
+use std::env;
+use std::process;
+
+#[tokio::test]
+async fn test_ordered_key_monotonic() {
+    let keys = OrderedKeys::with_epoch(1);
+
+    let mut prev = keys.next_key();
+    assert_eq!(prev.len(), 20);
+
+    for _ in 0..10000 {
+        let next = keys.serialer().build().await.unwrap();
+        assert!(next > prev);
+        prev = next;
+    }
+}
+
+#[test]
+fn test_ordered_key_across_restart() {
+    let path = env::temp_dir().join(format!("fastsend-ordered-key-{}", process::id()));
+
+    let before = OrderedKeys::open(&path).unwrap();
+    let key = before.next_key();
+
+    // 模拟重启：再次打开纪元文件，纪元 +1 后生成的键一定更大
+    let after = OrderedKeys::open(&path).unwrap();
+    assert_eq!(after.epoch(), before.epoch() + 1);
+    assert!(after.next_key() > key);
+
+    std::fs::remove_file(&path).unwrap();
+}
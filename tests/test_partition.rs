@@ -0,0 +1,81 @@
+use fastsend::{Granularity, PartitionKey, Token, ID};
+use std::time::{Duration, SystemTime};
+
+/// 缺省的基准起始时间，即 '2021-12-10 04:27:33 UTC'。
+const TIMEBASE: u64 = 1639110453;
+
+fn id_at(secs: u64) -> u64 {
+    (secs - TIMEBASE) << 32 | 0x1234_5678
+}
+
+#[test]
+fn test_partition_key() {
+    let id = id_at(TIMEBASE);
+    assert_eq!(
+        fastsend::partition_of(id, Granularity::Hour).to_string(),
+        "2021-12-10T04"
+    );
+    assert_eq!(
+        fastsend::partition_of(id, Granularity::Day).to_string(),
+        "2021-12-10"
+    );
+    assert_eq!(
+        fastsend::partition_of(id, Granularity::Month).to_string(),
+        "2021-12"
+    );
+
+    // 跨越月份（及年份）的边界
+    let next_month = fastsend::partition_of(id, Granularity::Month).next();
+    assert_eq!(next_month.to_string(), "2022-01");
+    assert_eq!(
+        next_month.start(),
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1640995200)
+    );
+    assert_eq!(
+        fastsend::partition_of(id_at(1640995200), Granularity::Month),
+        next_month
+    );
+    assert_eq!(
+        fastsend::partition_of(id_at(1640995199), Granularity::Month),
+        fastsend::partition_of(id, Granularity::Month)
+    );
+
+    // 同一个 `Token` 通过两种方式计算的结果一致
+    let token = Token::from_id(id_at(1700000000));
+    for granularity in [Granularity::Hour, Granularity::Day, Granularity::Month] {
+        assert_eq!(
+            token.partition_key(granularity),
+            fastsend::partition_of(token.id(), granularity)
+        );
+    }
+}
+
+#[test]
+fn test_partition_bounds() {
+    let key = fastsend::partition_of(id_at(1700000000), Granularity::Day);
+    assert_eq!(key.to_string(), "2023-11-14");
+    assert_eq!(key.end(), key.next().start());
+    assert_eq!(
+        key.end().duration_since(key.start()).unwrap(),
+        Duration::from_secs(86400)
+    );
+
+    // 分区的 id 区间与相邻分区首尾相接，且恰好包含该分区内生成的 id
+    let range = key.id_range();
+    assert_eq!(range.end(), key.next().id_range().start());
+    assert!(range.contains(id_at(1700000000)));
+    assert_eq!(fastsend::partition_of(range.start(), Granularity::Day), key);
+    assert_eq!(
+        fastsend::partition_of(range.end() - 1, Granularity::Day),
+        key
+    );
+    assert_eq!(
+        fastsend::partition_of(range.end(), Granularity::Day),
+        key.next()
+    );
+
+    // 早于基准起始时间的分区
+    let before = PartitionKey::of(SystemTime::UNIX_EPOCH, Granularity::Day);
+    assert_eq!(before.index(), 0);
+    assert!(before.id_range().is_empty());
+}
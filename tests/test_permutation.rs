@@ -0,0 +1,47 @@
+use fastsend::{BitPermutation, Token, ID};
+use std::collections::HashSet;
+
+#[test]
+fn test_bit_permutation() {
+    let permutation = BitPermutation::from_seed(42);
+    assert_eq!(permutation, BitPermutation::from_seed(42));
+    assert_ne!(permutation, BitPermutation::from_seed(43));
+
+    for id in [0, 1, 42, u64::MAX, 0x61b2_d3a5_0042_7f1e] {
+        assert_eq!(permutation.decode(permutation.encode(id)), id);
+        assert_eq!(permutation.encode(permutation.decode(id)), id);
+    }
+
+    // 每一位都被移动到了不同的位置，结果互不相同
+    let encoded = (0..64)
+        .map(|bit| permutation.encode(1 << bit) ^ permutation.encode(0))
+        .collect::<HashSet<_>>();
+    assert_eq!(encoded.len(), 64);
+    assert!(encoded.iter().all(|bits| bits.count_ones() == 1));
+}
+
+// 全局置换是进程内共享的状态，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_public_id() {
+    let token = fastsend::next_token().await;
+
+    // 未设置全局置换时返回原始的 id
+    assert_eq!(token.public_id(), token.id());
+    assert_eq!(Token::from_public_id(token.id()), token);
+
+    assert!(fastsend::set_id_permutation(0x5EED));
+    assert!(!fastsend::set_id_permutation(0x5EED));
+
+    let public = token.public_id();
+    assert_eq!(public, BitPermutation::from_seed(0x5EED).encode(token.id()));
+    assert_ne!(public, token.id());
+    assert_eq!(Token::from_public_id(public), token);
+
+    // 同一秒内生成的 id 的高位（游标）相同，置换后不再相同
+    let cursors = futures::future::join_all((0..16).map(|_| fastsend::next_token()))
+        .await
+        .into_iter()
+        .map(|token| token.public_id() >> 32)
+        .collect::<HashSet<_>>();
+    assert!(cursors.len() > 1);
+}
@@ -0,0 +1,63 @@
+#![cfg(not(feature = "strict_order"))]
+
+use fastsend::{Priority, ID};
+use std::collections::HashSet;
+use std::time::{Duration, Instant, SystemTime};
+
+fn second() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+// 保留余量是进程内共享的状态，因此各用例放在同一个测试中顺序执行
+#[test]
+fn test_priority() {
+    // 初始化全局生成器
+    futures::executor::block_on(fastsend::next_token());
+
+    // 余量总是不足时，低优先级的调用方在领取 `Block` 之前等待至下一秒
+    fastsend::set_priority_reserve(Priority::Low, usize::MAX);
+    let (start, end) = std::thread::spawn(|| {
+        let start = second();
+        futures::executor::block_on(fastsend::next_token_with_priority(Priority::Low));
+        (start, second())
+    })
+    .join()
+    .unwrap();
+    assert!(end > start);
+
+    // 高优先级从不等待；线程本地仍有 `Token` 时低优先级也不需要等待
+    let (ids, elapsed) = std::thread::spawn(|| {
+        futures::executor::block_on(async {
+            let start = Instant::now();
+            let mut ids = HashSet::new();
+            for _ in 0..1001 {
+                let token = fastsend::next_token_with_priority(Priority::High).await;
+                assert!(ids.insert(token.id()));
+            }
+
+            // 1001 个 `Token` 之后当前 `Block` 还剩余 7 个
+            for _ in 0..7 {
+                let token = fastsend::next_token_with_priority(Priority::Low).await;
+                assert!(ids.insert(token.id()));
+            }
+            (ids, start.elapsed())
+        })
+    })
+    .join()
+    .unwrap();
+    assert_eq!(ids.len(), 1008);
+    assert!(elapsed < Duration::from_millis(500));
+
+    // 保留余量为 0 时不会等待
+    fastsend::set_priority_reserve(Priority::Low, 0);
+    let start = Instant::now();
+    std::thread::spawn(|| {
+        futures::executor::block_on(fastsend::next_token_with_priority(Priority::Low))
+    })
+    .join()
+    .unwrap();
+    assert!(start.elapsed() < Duration::from_millis(500));
+}
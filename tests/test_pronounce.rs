@@ -0,0 +1,40 @@
+#![cfg(feature = "pronounce")]
+
+use fastsend::{Pronounce, Serial, Serialer, ID};
+
+#[test]
+fn test_proquint() {
+    // 来自 proquint 规范的示例：127.0.0.1 => 'lusab-babad'
+    assert_eq!(Pronounce::Proquint.encode(&[127, 0, 0, 1]), "lusab-babad");
+    assert_eq!(
+        Pronounce::Proquint.decode("lusab-babad"),
+        Some(vec![127, 0, 0, 1])
+    );
+    assert_eq!(Pronounce::Proquint.decode("lusab-babax"), None);
+}
+
+#[test]
+fn test_pronounce_roundtrip() {
+    for style in [Pronounce::Proquint, Pronounce::Words] {
+        for n in [0, 1, 0xdead_beef, u64::MAX] {
+            assert_eq!(style.decode_u64(&style.encode_u64(n)), Some(n));
+        }
+        assert_eq!(
+            style.decode_u128(&style.encode_u128(u128::MAX)),
+            Some(u128::MAX)
+        );
+        assert_eq!(style.decode_u64(&style.encode_u128(u128::MAX)), None);
+    }
+}
+
+#[tokio::test]
+async fn test_pronounce_serialer() {
+    let token = fastsend::next_token().await;
+
+    let mut serialer = Pronounce::Words.serialer();
+    token.serial(&mut serialer);
+    let output = serialer.build().await.unwrap();
+
+    assert_eq!(output.split('-').count(), 8);
+    assert_eq!(Pronounce::Words.decode_u64(&output), Some(token.id()));
+}
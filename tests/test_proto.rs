@@ -0,0 +1,44 @@
+#![cfg(feature = "prost")]
+
+use fastsend::{IdProto, Token, ID};
+use prost::Message;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[tokio::test]
+async fn test_id_proto() {
+    let token = fastsend::next_token().await;
+    let proto = IdProto::from(token);
+    assert_eq!(proto.id, token.id());
+    assert_eq!(u64::from(token), token.id());
+    assert_eq!(Token::from(token.id()), token);
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    assert!(proto.timestamp.abs_diff(now) <= 2);
+
+    let bytes = proto.encode_to_vec();
+    // fixed64 占用 1 个字节的 tag 以及 8 个字节的值
+    assert_eq!(
+        bytes[..9],
+        [0x09]
+            .into_iter()
+            .chain(token.id().to_le_bytes())
+            .collect::<Vec<_>>()[..]
+    );
+
+    let decoded = IdProto::decode(&bytes[..]).unwrap();
+    assert_eq!(decoded, proto);
+    assert_eq!(Token::from(decoded), token);
+}
+
+#[test]
+fn test_id_proto_decomposition() {
+    let proto = IdProto::from(Token::from_id(0x0000_0010_0102_0304));
+    assert_eq!(proto.timestamp, 1639110453 + 0x10);
+    assert_eq!(
+        (proto.sequence, proto.device, proto.thread),
+        (0x0102, 0x03, 0x04)
+    );
+}
@@ -0,0 +1,14 @@
+#![cfg(feature = "push_id")]
+
+use fastsend::{PushIdSerialer, Serialer};
+
+#[tokio::test]
+async fn test_push_id_ordered() {
+    let mut prev = String::new();
+    for _ in 0..10999 {
+        let next = PushIdSerialer::new().build().await.unwrap();
+        assert_eq!(next.len(), 20);
+        assert!(next > prev);
+        prev = next;
+    }
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "rate_limit")]
+
+use fastsend::{LimitError, RateLimiter, Serialer, TimeSerialer};
+use std::time::Duration;
+
+// 虚拟时间是全局状态，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_rate_limit() {
+    let limiter = RateLimiter::new(3, Duration::from_secs(3600));
+
+    for _ in 0..3 {
+        assert!(limiter.next_token().await.is_ok());
+    }
+
+    match limiter.next_token().await {
+        Err(LimitError::RateLimited(error)) => {
+            assert!(error.retry_after() > Duration::ZERO);
+            assert!(error.retry_after() <= Duration::from_secs(1200));
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+
+    // 包装 `Serialer` 时与 `next_token` 共用同一个令牌桶
+    let token = fastsend::next_token().await;
+    let serial = limiter.limit(TimeSerialer::new()).oneshot(token).await;
+    assert!(matches!(serial, Err(LimitError::RateLimited(_))));
+
+    let limiter = RateLimiter::new(1, Duration::from_secs(3600));
+    let serial = limiter.limit(TimeSerialer::new()).oneshot(token).await;
+    assert!(serial.is_ok());
+
+    #[cfg(feature = "test-util")]
+    {
+        use fastsend::testing;
+
+        testing::freeze_time(chrono::Utc::now());
+
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        assert!(limiter.try_acquire().is_ok());
+        assert!(limiter.try_acquire().is_ok());
+        assert!(limiter.try_acquire().is_err());
+
+        // 每 30 秒补充一个令牌，且令牌数量不超过容量
+        testing::advance(Duration::from_secs(30));
+        assert!(limiter.try_acquire().is_ok());
+        assert!(limiter.try_acquire().is_err());
+
+        testing::advance(Duration::from_secs(600));
+        assert!(limiter.try_acquire().is_ok());
+        assert!(limiter.try_acquire().is_ok());
+        assert!(limiter.try_acquire().is_err());
+
+        testing::reset_clock();
+    }
+}
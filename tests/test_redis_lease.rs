@@ -0,0 +1,17 @@
+#![cfg(feature = "redis_lease")]
+
+use fastsend::device_id::{LeaseError, RedisLease};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_redis_lease_unreachable() {
+    // 端口 1 上不会有 Redis 服务，申请租约应返回错误而不是回退到随机设备号
+    let client = redis::Client::open("redis://127.0.0.1:1/").unwrap();
+    let result = RedisLease::new(client)
+        .range(0..=15)
+        .ttl(Duration::from_secs(5))
+        .claim()
+        .await;
+
+    assert!(matches!(result, Err(LeaseError::Redis(_))));
+}
@@ -0,0 +1,32 @@
+use fastsend::ID;
+
+// 预留会推进全局生成器的游标，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_reserve_range() {
+    let before = fastsend::next_token().await.id();
+
+    let range = fastsend::reserve_range(1000);
+    assert_eq!(range.len(), 1000);
+    assert_eq!(range.start() & 0xFFFF_FFFF, 0);
+    assert!(range.start() > before);
+    assert!(range.into_iter().all(|id| range.contains(id)));
+
+    // 预留多个 `Cursor` 时区间连续，且与之前预留的区间不重叠
+    let large = fastsend::reserve_range((1 << 32) + 1);
+    assert_eq!(large.len(), (1 << 32) + 1);
+    assert!(large.start() >= range.end());
+
+    // 之后生成的 id（跨越多个 `Cursor`）不会落入预留的区间
+    for _ in 0..200_000 {
+        let id = fastsend::next_token().await.id();
+        assert!(!range.contains(id) && !large.contains(id));
+    }
+
+    assert!(fastsend::try_reserve_range(u64::MAX).is_err());
+}
+
+#[test]
+#[should_panic]
+fn test_reserve_range_empty() {
+    fastsend::reserve_range(0);
+}
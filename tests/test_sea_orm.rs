@@ -0,0 +1,75 @@
+#![cfg(feature = "sea-orm")]
+
+use fastsend::{Token, ID};
+use sea_orm::entity::prelude::*;
+use sea_orm::{ActiveValue::Set, DatabaseBackend, MockDatabase};
+
+mod order {
+    use fastsend::Token;
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "orders")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Token,
+        pub buyer: Option<Token>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+#[tokio::test]
+async fn test_sea_orm_token() {
+    let id = fastsend::next_token().await;
+    // 最高位为 1 的 id 在数据库中表现为负数，读取时按位还原
+    let buyer = Token::from_id(u64::MAX - 1);
+
+    // Postgres 的 INSERT 会通过 RETURNING 返回插入的行
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([
+            [order::Model { id, buyer: None }],
+            [order::Model {
+                id,
+                buyer: Some(buyer),
+            }],
+        ])
+        .into_connection();
+
+    let inserted = order::ActiveModel {
+        id: Set(id),
+        buyer: Set(None),
+    }
+    .insert(&db)
+    .await
+    .unwrap();
+    assert_eq!(inserted.id, id);
+
+    let found = order::Entity::find_by_id(id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.id, id);
+    assert_eq!(found.buyer.map(ID::id), Some(u64::MAX - 1));
+
+    let log = db.into_transaction_log();
+    assert_eq!(
+        log[0].statements()[0].values.as_ref().unwrap().0,
+        vec![Value::BigInt(Some(id.id() as i64)), Value::BigInt(None)]
+    );
+}
+
+#[cfg(feature = "uuid")]
+#[tokio::test]
+async fn test_sea_orm_uuid() {
+    use fastsend::{Serialer, UUIDSerialer, UUID};
+
+    let uuid = UUIDSerialer::new_v7().build().await.unwrap();
+    let value = Value::from(uuid);
+    assert_eq!(value.to_string(), format!("'{}'", uuid));
+    assert_eq!(value.unwrap::<UUID>(), uuid);
+}
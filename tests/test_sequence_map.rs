@@ -0,0 +1,59 @@
+#![cfg(feature = "auto_increment")]
+
+use fastsend::{SequenceMap, SequenceMapBuilder};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[test]
+fn test_sequence_map() {
+    let map = SequenceMap::new();
+    assert_eq!(map.current(&"a"), None);
+    assert_eq!(map.next(&"a"), Ok(1));
+    assert_eq!(map.next(&"a"), Ok(2));
+    assert_eq!(map.next(&"b"), Ok(1));
+    assert_eq!(map.current(&"a"), Some(2));
+
+    // 多个线程并发获取同一个键时序号不重复且连续
+    let map = Arc::new(SequenceMap::<u64>::new());
+    let handles = (0..4)
+        .map(|_| {
+            let map = Arc::clone(&map);
+            std::thread::spawn(move || (0..1000).map(|_| map.next(&7).unwrap()).collect::<Vec<_>>())
+        })
+        .collect::<Vec<_>>();
+    let mut sequences = handles
+        .into_iter()
+        .flat_map(|handle| handle.join().unwrap())
+        .collect::<Vec<_>>();
+    sequences.sort_unstable();
+    assert_eq!(sequences, (1..=4000).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_sequence_map_eviction() {
+    // 被淘汰的键从 `SequenceStore` 中恢复
+    let map = SequenceMapBuilder::new()
+        .with_start(100)
+        .with_capacity(2)
+        .with_store(HashMap::new())
+        .build(|current| current + 10);
+
+    assert_eq!(map.next(&1), Ok(110));
+    assert_eq!(map.next(&2), Ok(110));
+    assert_eq!(map.next(&1), Ok(120));
+    assert_eq!(map.next(&3), Ok(110));
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.next(&2), Ok(120));
+
+    // 未持久化时被淘汰的键从起始值重新开始
+    let map = SequenceMapBuilder::new()
+        .with_capacity(1)
+        .build(|current| current + 1);
+    assert_eq!(map.next(&1), Ok(1));
+    assert_eq!(map.next(&2), Ok(1));
+    assert_eq!(map.next(&1), Ok(1));
+
+    // 引擎没有递增时返回 `FAILED`
+    let map = SequenceMapBuilder::new().build(|current| current);
+    assert_eq!(map.next(&1), Err(fastsend::serial::auto_increment::FAILED));
+}
@@ -0,0 +1,51 @@
+use fastsend::{BlockFrame, SequenceStride, Token, ID};
+use std::collections::HashSet;
+
+fn sequence(id: u64) -> u64 {
+    id >> 16 & 0xFFFF
+}
+
+// 全局步长只能在首次生成 id 之前设置，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_sequence_stride() {
+    assert!(fastsend::set_sequence_stride(1, 2));
+
+    // 跨越多个 `Cursor` 时发号序号均为奇数，且不重复
+    let mut ids = HashSet::new();
+    for _ in 0..100_000 {
+        let id = fastsend::next_token().await.id();
+        assert_eq!(sequence(id) % 2, 1);
+        assert!(ids.insert(id));
+    }
+
+    // 全局生成器已经初始化，不能再修改步长
+    assert!(!fastsend::set_sequence_stride(0, 2));
+}
+
+#[tokio::test]
+async fn test_frame_sequence_stride() {
+    let frame = BlockFrame::<Token>::new().sequence_stride(3, 4);
+
+    // 每个 `Cursor` 下只有 65536 / 4 个发号序号，即 2048 个 `Block`
+    let mut sequences = HashSet::new();
+    let mut cursors = HashSet::new();
+    for _ in 0..2048 {
+        for token in frame.next_block().await {
+            let id = token.id();
+            cursors.insert(id >> 32);
+            assert_eq!(sequence(id) % 4, 3);
+            assert!(sequences.insert(sequence(id)));
+        }
+    }
+    assert_eq!(cursors.len(), 1);
+    assert_eq!(sequences.len(), SequenceStride::new(3, 4).capacity());
+
+    let next = frame.next_block().await.next().unwrap().id() >> 32;
+    assert!(!cursors.contains(&next));
+}
+
+#[test]
+#[should_panic]
+fn test_sequence_stride_offset() {
+    SequenceStride::new(2, 2);
+}
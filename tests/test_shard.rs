@@ -0,0 +1,58 @@
+#![cfg(feature = "sharded")]
+
+use fastsend::{next_sharded_id, ShardFrame, ShardedId, ID};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::thread;
+
+#[test]
+fn test_sharded_id_roundtrip() {
+    let user_id = 123_456_789u64;
+    let shard = (user_id % 4096) as u16;
+
+    let id = next_sharded_id(shard);
+    assert_eq!(id.shard(), shard);
+
+    let n = id.id();
+    assert_eq!(ShardedId::from_id(n), id);
+    assert_eq!(ShardedId::from_id(n).shard(), shard);
+}
+
+#[test]
+fn test_sharded_id_per_shard_sequence() {
+    let frame = ShardFrame::new();
+
+    let mut prev = frame.next(1).id();
+    for _ in 0..5000 {
+        let next = frame.next(1).id();
+        assert!(next > prev);
+        prev = next;
+    }
+
+    // 其他分片的序列号不受影响
+    assert_eq!(frame.next(2).sequence(), 0);
+}
+
+#[test]
+fn test_sharded_id_concurrent() {
+    let frame = Arc::new(ShardFrame::new());
+
+    let handles = (0..4)
+        .map(|_| {
+            let frame = Arc::clone(&frame);
+            thread::spawn(move || (0..5000).map(|_| frame.next(7).id()).collect::<Vec<_>>())
+        })
+        .collect::<Vec<_>>();
+
+    let ids = handles
+        .into_iter()
+        .flat_map(|handle| handle.join().unwrap())
+        .collect::<HashSet<_>>();
+    assert_eq!(ids.len(), 20000);
+}
+
+#[test]
+#[should_panic]
+fn test_sharded_id_shard_overflow() {
+    next_sharded_id(ShardFrame::SHARD_CAP);
+}
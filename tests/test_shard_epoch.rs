@@ -0,0 +1,26 @@
+#![cfg(all(feature = "sharded", feature = "test-util"))]
+
+use chrono::{TimeZone, Utc};
+use fastsend::{testing, FastsendError, ShardFrame};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Duration;
+
+// 虚拟时间是全局状态，因此各用例放在同一个测试中顺序执行
+#[test]
+fn test_shard_clock_before_epoch() {
+    let epoch = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    testing::freeze_time(epoch);
+
+    let frame = ShardFrame::new().epoch(epoch.into());
+    assert_eq!(frame.try_next(3).unwrap().timestamp(), 0);
+
+    testing::advance(Duration::from_secs(1));
+    assert_eq!(frame.next(3).timestamp(), 1000);
+
+    // 系统时间回拨到起始时间之前时返回错误，而不是得到溢出的时间戳
+    testing::freeze_time(Utc.with_ymd_and_hms(2023, 12, 31, 23, 59, 59).unwrap());
+    assert_eq!(frame.try_next(3), Err(FastsendError::ClockBeforeShardEpoch));
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| frame.next(3))).is_err());
+
+    testing::reset_clock();
+}
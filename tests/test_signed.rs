@@ -0,0 +1,56 @@
+#![cfg(feature = "crypto")]
+
+use fastsend::{HmacSigner, Serialer, TimeSerialer};
+
+#[tokio::test]
+async fn test_signed_serialer() {
+    let signer = HmacSigner::new(b"secret");
+    let signed = signer.wrap(TimeSerialer::new()).build().await.unwrap();
+
+    let (serial, tag) = signed.rsplit_once('.').unwrap();
+    assert_eq!(tag.len(), HmacSigner::DEFAULT_TAG_LEN * 2);
+    assert_eq!(signer.verify(&signed), Some(serial));
+
+    assert_eq!(HmacSigner::new(b"other").verify(&signed), None);
+    assert_eq!(signer.clone().tag_len(16).verify(&signed), None);
+}
+
+#[test]
+fn test_signed_tampered() {
+    let signer = HmacSigner::new(b"secret").tag_len(4);
+    let signed = signer.sign("1234567890");
+    assert_eq!(signer.verify(&signed), Some("1234567890"));
+
+    let forged = signed.replacen("1234567890", "1234567891", 1);
+    assert_eq!(signer.verify(&forged), None);
+
+    assert_eq!(signer.verify("1234567890"), None);
+    assert_eq!(signer.verify("1234567890.zzzzzzzz"), None);
+}
+
+#[test]
+fn test_signed_canonical_tag() {
+    let signer = HmacSigner::new(b"secret").tag_len(4);
+
+    // 同一个签名只有 `sign` 输出的一种写法：'+' 前缀（`from_str_radix` 接受 "+f" 作为 0x0f）及大写字母都会被拒绝
+    let (signed, index) = (0..1000)
+        .map(|n| signer.sign(&n.to_string()))
+        .find_map(|signed| {
+            let (_, tag) = signed.rsplit_once('.').unwrap();
+            let index = (0..tag.len()).step_by(2).find(|&i| &tag[i..i + 1] == "0")?;
+            Some((signed.clone(), signed.len() - tag.len() + index))
+        })
+        .unwrap();
+    assert!(signer.verify(&signed).is_some());
+
+    let mut forged = signed.clone();
+    forged.replace_range(index..index + 1, "+");
+    assert_eq!(signer.verify(&forged), None);
+
+    let signed = signer.sign("1234567890");
+    let (serial, tag) = signed.rsplit_once('.').unwrap();
+    let upper = format!("{}.{}", serial, tag.to_uppercase());
+    if upper != signed {
+        assert_eq!(signer.verify(&upper), None);
+    }
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "test-util")]
+
+use fastsend::simulate::{GeneratorKind, Simulation};
+
+#[test]
+fn test_simulate_configured() {
+    let report = Simulation::new().devices(8).seconds(10).restarts(2).run();
+
+    let token = report.outcome(GeneratorKind::Token).unwrap();
+    assert!(token.generated > 0);
+    assert_eq!(token.collisions, 0);
+    assert_eq!(
+        report
+            .outcome(GeneratorKind::TimeSerial)
+            .unwrap()
+            .collisions,
+        0
+    );
+}
+
+#[test]
+fn test_simulate_reproducible() {
+    let simulation = Simulation::new()
+        .devices(16)
+        .seconds(5)
+        .restarts(3)
+        .device_ids(false)
+        .kinds(&[GeneratorKind::TimeSerial])
+        .seed(42);
+
+    let (a, b) = (simulation.run(), simulation.run());
+    assert_eq!(a.outcomes(), b.outcomes());
+    assert_eq!(a.outcomes().len(), 1);
+}
+
+#[test]
+fn test_simulate_collisions() {
+    // 设备数量超过 256 时设备号重复，同一秒内的 TimeSerialer 序列号只剩下 10000 个哈希桶可以区分
+    let report = Simulation::new()
+        .devices(300)
+        .threads(1)
+        .rate(100)
+        .seconds(2)
+        .kinds(&[GeneratorKind::TimeSerial])
+        .run();
+    assert!(report.collisions() > 0);
+
+    // 未配置设备号时，TimeSerialer 每次随机设备号，不同设备之间的序列号会发生冲突
+    let report = Simulation::new()
+        .seconds(3)
+        .device_ids(false)
+        .kinds(&[GeneratorKind::TimeSerial])
+        .run();
+    assert!(report.collisions() > 0);
+    assert!(report.to_string().starts_with("TimeSerialer: "));
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "slug")]
+
+use fastsend::{next_token, Serialer, SlugSerialError, SlugSerialer};
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+async fn test_slug_format() {
+    let slug = SlugSerialer::new().build().await.unwrap();
+    assert_eq!(slug.len(), SlugSerialer::DEFAULT_LENGTH);
+    assert!(slug
+        .bytes()
+        .all(|c| c.is_ascii_digit() || c.is_ascii_lowercase()));
+
+    let slug = SlugSerialer::new()
+        .length(6)
+        .base58()
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(slug.len(), 6);
+    assert!(slug
+        .bytes()
+        .all(|c| c.is_ascii_alphanumeric() && !b"0OIl".contains(&c)));
+}
+
+#[tokio::test]
+async fn test_slug_from_token() {
+    let token = next_token().await;
+    let a = SlugSerialer::new().oneshot(token).await.unwrap();
+    let b = SlugSerialer::new().oneshot(token).await.unwrap();
+    assert_eq!(a, b);
+
+    let c = SlugSerialer::new()
+        .oneshot(next_token().await)
+        .await
+        .unwrap();
+    assert_ne!(a, c);
+}
+
+#[tokio::test]
+async fn test_slug_inspect() {
+    let store = Arc::new(Mutex::new(HashSet::new()));
+
+    for _ in 0..3 {
+        let store = store.clone();
+        let mut serialer = SlugSerialer::new().inspect(move |slug| {
+            let duplicated = !store.lock().unwrap().insert(slug.to_owned());
+            Box::pin(async move { Ok::<_, Infallible>(duplicated) })
+        });
+        // 相同的数据，依赖 `inspect` 重试来避免重复
+        serialer.feed(b"same");
+        serialer.build().await.unwrap();
+    }
+    assert_eq!(store.lock().unwrap().len(), 3);
+
+    let result = SlugSerialer::new()
+        .retry_times(3)
+        .inspect(|_| Box::pin(async { Ok::<_, Infallible>(true) }))
+        .build()
+        .await;
+    assert!(matches!(result, Err(SlugSerialError::MaxRetry)));
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "sqids")]
+
+use fastsend::{Serial, Serialer, Sqids, ID};
+
+#[test]
+fn test_sqids_encode() {
+    let sqids = Sqids::new();
+    assert_eq!(sqids.encode(&[1, 2, 3]), "86Rf07");
+    assert_eq!(sqids.decode("86Rf07"), vec![1, 2, 3]);
+
+    let sqids = Sqids::new().min_length(10);
+    assert_eq!(sqids.encode(&[1, 2, 3]), "86Rf07xd4z");
+    assert_eq!(sqids.decode("86Rf07xd4z"), vec![1, 2, 3]);
+
+    let sqids =
+        Sqids::new().alphabet("k3G7QAe51FCsPW92uEOyq4Bg6Sp8YzVTmnU0liwDdHXLajZrfxNhobJIRcMvKt");
+    assert_eq!(sqids.encode(&[1, 2, 3]), "XRKUdQ");
+}
+
+#[test]
+fn test_sqids_roundtrip() {
+    let sqids = Sqids::new().min_length(8);
+    for numbers in [vec![0], vec![u64::MAX], vec![7, 0, 42, u64::MAX / 3]] {
+        assert_eq!(sqids.decode(&sqids.encode(&numbers)), numbers);
+    }
+
+    assert!(sqids.decode("").is_empty());
+    assert!(sqids.decode("*").is_empty());
+}
+
+#[tokio::test]
+async fn test_sqids_serialer() {
+    let sqids = Sqids::new();
+    let token = fastsend::next_token().await;
+
+    let mut serialer = sqids.serialer();
+    token.serial(&mut serialer);
+    let output = serialer.build().await.unwrap();
+
+    assert_eq!(sqids.decode(&output), vec![token.id()]);
+}
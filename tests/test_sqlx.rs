@@ -0,0 +1,47 @@
+#![cfg(feature = "sqlx")]
+
+use fastsend::{Token, ID};
+use sqlx::sqlite::SqlitePool;
+
+#[tokio::test]
+async fn test_sqlx_token() {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    sqlx::query("CREATE TABLE t (id BIGINT PRIMARY KEY)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // 最高位为 1 的 id 在数据库中表现为负数，读取时按位还原
+    let tokens = [fastsend::next_token().await, Token::from_id(u64::MAX - 1)];
+    for token in tokens {
+        sqlx::query("INSERT INTO t (id) VALUES (?)")
+            .bind(token)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let (read,): (Token,) = sqlx::query_as("SELECT id FROM t WHERE id = ?")
+            .bind(token)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(read, token);
+        assert_eq!(read.id(), token.id());
+    }
+}
+
+#[cfg(feature = "uuid")]
+#[tokio::test]
+async fn test_sqlx_uuid() {
+    use fastsend::{Serialer, UUIDSerialer, UUID};
+
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    let uuid = UUIDSerialer::new_v7().build().await.unwrap();
+
+    let (read,): (UUID,) = sqlx::query_as("SELECT ?")
+        .bind(uuid)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(read, uuid);
+}
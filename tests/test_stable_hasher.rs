@@ -0,0 +1,68 @@
+use fastsend::{Serialer, StableState, TimeSerialer};
+use std::hash::{BuildHasher, BuildHasherDefault, Hasher};
+
+fn tail(serial: &str) -> &str {
+    &serial[serial.len() - 4..]
+}
+
+async fn build<S: BuildHasher + Clone>(mut serialer: TimeSerialer<S>, data: &[u8]) -> String {
+    serialer.feed(data);
+    serialer.build().await.unwrap()
+}
+
+#[test]
+fn test_sip_hasher() {
+    // 固定的算法与密钥得到固定的哈希值，不随 Rust 版本变化
+    let hasher = StableState::new().build_hasher();
+    assert_eq!(hasher.finish(), 0xd1fb_a762_150c_532c);
+
+    let mut hasher =
+        StableState::with_keys(0x0706_0504_0302_0100, 0x0f0e_0d0c_0b0a_0908).build_hasher();
+    hasher.write(&(0..15).collect::<Vec<u8>>());
+    assert_eq!(hasher.finish(), 0xd320_d86d_2a51_9956);
+
+    // 分多次写入与一次写入的结果相同
+    let mut split = StableState::new().build_hasher();
+    split.write(b"fast");
+    split.write(b"send");
+    let mut whole = StableState::new().build_hasher();
+    whole.write(b"fastsend");
+    assert_eq!(split.finish(), whole.finish());
+}
+
+#[tokio::test]
+async fn test_stable_tail() {
+    // 相同的 `feed` 得到相同的后 4 位
+    for _ in 0..3 {
+        let serial = build(TimeSerialer::new(), b"order-1").await;
+        assert_eq!(tail(&serial), "5085");
+    }
+}
+
+/// 忽略输入、总是返回固定值的哈希器。
+#[derive(Default)]
+struct ConstHasher;
+
+impl Hasher for ConstHasher {
+    fn finish(&self) -> u64 {
+        12_345_678
+    }
+
+    fn write(&mut self, _: &[u8]) {}
+}
+
+#[tokio::test]
+async fn test_custom_hasher() {
+    let serialer = TimeSerialer::with_hasher(BuildHasherDefault::<ConstHasher>::default());
+    let serial = build(serialer, b"order-1").await;
+    assert_eq!(tail(&serial), "5678");
+
+    // 自定义的密钥得到不同的后 4 位
+    let keyed = build(
+        TimeSerialer::with_hasher(StableState::with_keys(7, 11)),
+        b"order-1",
+    )
+    .await;
+    assert_eq!(keyed.len(), 21);
+    assert_ne!(tail(&keyed), "5085");
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "tower")]
+
+use fastsend::{IdLayer, Serialer, Token, ID};
+use http::{HeaderName, HeaderValue, Request, Response};
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use tower::{service_fn, Layer, ServiceExt};
+
+#[tokio::test]
+async fn test_id_layer() {
+    let service = IdLayer::new().layer(service_fn(|request: Request<()>| async move {
+        Ok::<_, Infallible>(Response::new(*request.extensions().get::<Token>().unwrap()))
+    }));
+
+    let a = service.clone().oneshot(Request::new(())).await.unwrap();
+    let b = service.oneshot(Request::new(())).await.unwrap();
+    assert_ne!(a.body(), b.body());
+
+    // 未指定请求头时不写入请求头及响应头
+    assert!(a.headers().is_empty());
+}
+
+#[tokio::test]
+async fn test_id_layer_header() {
+    let header = HeaderName::from_static("x-token");
+    let service = IdLayer::new()
+        .header(header.clone(), |token| HeaderValue::from(token.id()))
+        .layer(service_fn(|request: Request<()>| async move {
+            let token = *request.extensions().get::<Token>().unwrap();
+            assert_eq!(request.headers()["x-token"], token.id().to_string());
+            Ok::<_, Infallible>(Response::new(token))
+        }));
+
+    // 请求头与响应头中的取值与 extensions 中的 id 一致
+    let response = service.oneshot(Request::new(())).await.unwrap();
+    assert_eq!(
+        response.headers()[&header],
+        response.body().id().to_string()
+    );
+}
+
+#[cfg(feature = "uuid")]
+#[tokio::test]
+async fn test_id_layer_serialer() {
+    use fastsend::{UUIDSerialer, UUID};
+
+    let service =
+        IdLayer::with(UUIDSerialer::new_v7).layer(service_fn(|request: Request<()>| async move {
+            Ok::<_, Infallible>(Response::new(*request.extensions().get::<UUID>().unwrap()))
+        }));
+
+    let uuid = service.oneshot(Request::new(())).await.unwrap().into_body();
+    assert_eq!(uuid.to_bytes()[6] >> 4, 7);
+}
+
+/// 总是构建失败的 `Serialer`。
+struct Failing;
+
+impl Serialer for Failing {
+    type Output = String;
+
+    type Error = std::fmt::Error;
+
+    fn build(
+        self,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Self::Error>> + Send + 'static>> {
+        Box::pin(async { Err(std::fmt::Error) })
+    }
+
+    fn feed(&mut self, _: &[u8]) {}
+}
+
+#[tokio::test]
+async fn test_id_layer_error() {
+    // 生成失败时请求不会被转发给下游服务，错误转换为内部服务的错误类型
+    let service = IdLayer::with(|| Failing).layer(service_fn(|_: Request<()>| async {
+        Ok::<_, Box<dyn std::error::Error + Send + Sync>>(Response::new("forwarded"))
+    }));
+
+    assert!(service.oneshot(Request::new(())).await.is_err());
+}
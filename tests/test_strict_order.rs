@@ -0,0 +1,53 @@
+#![cfg(feature = "strict_order")]
+
+use fastsend::ID;
+use std::collections::HashSet;
+use std::sync::mpsc;
+
+// 全局计数器是进程内共享的状态，因此各用例放在同一个测试中顺序执行
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_strict_order() {
+    // 单个任务内跨越多个 `Cursor`（每个 `Cursor` 65536 个序号）严格递增
+    let mut last = fastsend::next_token().await.id();
+    for _ in 0..3 << 16 {
+        let id = fastsend::next_token().await.id();
+        assert!(id > last, "{} <= {}", id, last);
+        last = id;
+    }
+
+    // 多个线程并发获取时，各线程内严格递增且全局不重复
+    let handles = (0..4)
+        .map(|_| {
+            tokio::spawn(async {
+                let mut ids = Vec::with_capacity(20000);
+                for _ in 0..20000 {
+                    ids.push(fastsend::next_token().await.id());
+                }
+                ids
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut seen = HashSet::new();
+    for handle in handles {
+        let ids = handle.await.unwrap();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ids.iter().all(|id| *id > last));
+        seen.extend(ids);
+    }
+    assert_eq!(seen.len(), 4 * 20000);
+
+    // 一个线程获取的 id 在另一个线程获取之前完成时，后者一定更大
+    let (sender, receiver) = mpsc::channel();
+    let before = std::thread::spawn(move || {
+        let id = futures::executor::block_on(fastsend::next_token()).id();
+        sender.send(id).unwrap();
+    });
+    let after = std::thread::spawn(move || {
+        let previous = receiver.recv().unwrap();
+        let id = futures::executor::block_on(fastsend::next_token()).id();
+        assert!(id > previous);
+    });
+    before.join().unwrap();
+    after.join().unwrap();
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "support_code")]
+
+use fastsend::SupportCodeError;
+
+#[test]
+fn test_support_code() {
+    for id in [0, 1, 42, u64::MAX, 0x61b2_d3a5_0042_7f1e] {
+        let code = fastsend::support_code(id);
+        assert_eq!(code.len(), 8);
+        assert!(code
+            .chars()
+            .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase()));
+        assert!(!code.contains(['I', 'L', 'O', 'U']));
+
+        // 相同的 id 总是得到相同的支持码
+        assert_eq!(fastsend::support_code(id), code);
+        assert_eq!(
+            fastsend::parse_support_code(&code),
+            Ok(fastsend::support_hash(id))
+        );
+        assert!(fastsend::support_hash(id) < 1 << fastsend::SUPPORT_HASH_BITS);
+    }
+
+    // 相邻的 id 得到差异明显的支持码
+    assert_ne!(fastsend::support_code(1), fastsend::support_code(2));
+}
+
+#[test]
+fn test_parse_support_code() {
+    let id = 0x61b2_d3a5_0042_7f1e;
+    let code = fastsend::support_code(id);
+    let hash = fastsend::support_hash(id);
+
+    // 不区分大小写，忽略分隔符，'O'、'I'、'L' 按 Crockford 的规则处理
+    let spaced = format!("{}-{} ", &code[..4], &code[4..]).to_lowercase();
+    assert_eq!(fastsend::parse_support_code(&spaced), Ok(hash));
+    let aliased = code.replace('0', "O").replace('1', "l");
+    assert_eq!(fastsend::parse_support_code(&aliased), Ok(hash));
+
+    assert_eq!(
+        fastsend::parse_support_code(&code[..7]),
+        Err(SupportCodeError::InvalidLength)
+    );
+    assert_eq!(
+        fastsend::parse_support_code(&format!("U{}", &code[1..])),
+        Err(SupportCodeError::InvalidChar('U'))
+    );
+}
+
+#[test]
+fn test_support_code_checksum() {
+    const ALPHABET: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+    let code = fastsend::support_code(0x61b2_d3a5_0042_7f1e).into_bytes();
+
+    // 任意单个字符的错误都能被发现
+    for position in 0..code.len() {
+        for &replacement in ALPHABET {
+            if replacement == code[position] {
+                continue;
+            }
+
+            let mut typo = code.clone();
+            typo[position] = replacement;
+            let typo = String::from_utf8(typo).unwrap();
+            assert_eq!(
+                fastsend::parse_support_code(&typo),
+                Err(SupportCodeError::Checksum),
+                "{}",
+                typo
+            );
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use fastsend::{Token, ID};
+use std::collections::HashSet;
+
+// 标签位数是进程内共享的状态，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_tagged_token() {
+    // 读取已有 `Token` 的标签不会使标签位数无法修改
+    assert_eq!(Token::from_id(0xFFFF_FFFF).tag(), 0);
+    assert!(fastsend::set_tag_bits(3));
+    assert!(fastsend::set_tag_bits(2));
+
+    let mut ids = HashSet::new();
+    for tag in 0..4 {
+        for _ in 0..1000 {
+            let token = fastsend::next_token_tagged(tag).await;
+            assert_eq!(token.tag(), tag);
+            assert_eq!(Token::from_id(token.id()).tag(), tag);
+            assert!(ids.insert(token.id()));
+        }
+    }
+
+    // 设置标签位数后，未指定标签的 `Token` 标签为 0
+    let token = fastsend::next_token().await;
+    assert_eq!(token.tag(), 0);
+    assert!(ids.insert(token.id()));
+
+    // 已经生成过 `Token` 之后不能再修改标签位数
+    assert!(!fastsend::set_tag_bits(4));
+    assert_eq!(fastsend::next_token_tagged(3).await.tag(), 3);
+
+    // 写入标签之后的 id 落入登记的区间时改用下一个 `Token`：取到 `Block` 中的第一个 `Token` 后登记其后两个
+    // 带标签的 id，下一次发放跳过二者
+    #[cfg(not(feature = "strict_order"))]
+    {
+        let mut token = fastsend::next_token_tagged(3).await;
+        while (token.id() >> 16 & 0xFFFF) % 8 != 0 {
+            token = fastsend::next_token_tagged(3).await;
+        }
+
+        fastsend::exclude_ids(token.id() + (1 << 16)..token.id() + (3 << 16));
+        let next = fastsend::next_token_tagged(3).await;
+        assert_eq!(next.id(), token.id() + (3 << 16));
+    }
+
+    // 超出标签位数的标签
+    let overflow =
+        std::panic::catch_unwind(|| futures::executor::block_on(fastsend::next_token_tagged(4)));
+    assert!(overflow.is_err());
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "tenant")]
+
+use fastsend::{next_token_for, TenantFrame, TenantToken, ID};
+use std::collections::HashSet;
+
+#[tokio::test]
+async fn test_tenant_token_roundtrip() {
+    let token = next_token_for(42).await;
+    assert_eq!(token.tenant(), 42);
+
+    let restored = TenantToken::from_id(token.id(), fastsend::tenant::DEFAULT_TENANT_BITS);
+    assert_eq!(restored, token);
+}
+
+#[test]
+fn test_tenant_isolated_sequence() {
+    let frame = TenantFrame::new(4);
+
+    let a = (0..1000)
+        .map(|_| frame.next_token_for(1).id())
+        .collect::<HashSet<_>>();
+    let b = (0..1000)
+        .map(|_| frame.next_token_for(2).id())
+        .collect::<HashSet<_>>();
+
+    assert_eq!(a.len(), 1000);
+    assert_eq!(b.len(), 1000);
+    assert!(a.is_disjoint(&b));
+
+    assert!(a
+        .iter()
+        .all(|&id| TenantToken::from_id(id, 4).tenant() == 1));
+
+    // 各租户的序列号独立计数
+    assert_eq!(frame.next_token_for(3).sequence(), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_tenant_overflow() {
+    TenantFrame::new(4).next_token_for(16);
+}
+
+#[cfg(feature = "pause_on_start")]
+#[test]
+fn test_tenant_pause_on_start() {
+    // 构建时等待至下一秒，模拟在同一秒内重启的进程不会生成与之前相同的 id
+    use fastsend::{ConstructBlock, Cursor, Token};
+
+    let before = Token::construct_block(0, Cursor::new())
+        .next()
+        .unwrap()
+        .id()
+        >> 32;
+    let frame = TenantFrame::new(4);
+    assert!(frame.next_token_for(1).id() >> 32 > before);
+}
@@ -0,0 +1,27 @@
+#![cfg(all(feature = "test-util", feature = "uuid"))]
+
+use fastsend::{testing, Serialer, UUIDSerialer, UUID};
+
+async fn uuids() -> Vec<UUID> {
+    let mut uuids = Vec::new();
+    for _ in 0..4 {
+        uuids.push(UUIDSerialer::new_v4().build().await.unwrap());
+    }
+    uuids
+}
+
+// 确定性模式是全局状态，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_seed() {
+    testing::seed(42);
+    let a = uuids().await;
+    testing::seed(42);
+    let b = uuids().await;
+    assert_eq!(a, b);
+
+    testing::seed(7);
+    assert_ne!(uuids().await, a);
+
+    testing::unseed();
+    assert_ne!(uuids().await, uuids().await);
+}
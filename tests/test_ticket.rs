@@ -0,0 +1,101 @@
+#![cfg(feature = "ticket")]
+
+use fastsend::{DegradePolicy, Serialer, TicketSerialError, TicketSerialer};
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+async fn test_ticket_retry() {
+    // 前两次生成的序列号视为重复，记录每次校验的序列号
+    let inspected = Arc::new(Mutex::new(Vec::new()));
+    let mut serialer = TicketSerialer::new({
+        let inspected = Arc::clone(&inspected);
+        move |ticket: &str| {
+            let mut inspected = inspected.lock().unwrap();
+            inspected.push(ticket.to_owned());
+            let duplicated = inspected.len() < 3;
+            Box::pin(async move { Ok::<_, ()>(duplicated) })
+        }
+    });
+    serialer.feed(&[0x65, 0x43, 0x21, 0x00, 1, 2, 3, 4, 5, 6, 7]);
+
+    let ticket = serialer.build().await.unwrap();
+    assert_eq!(ticket.len(), 35);
+    assert!(ticket.ends_with("-00258-007720128600007-FE"));
+
+    // 重试时仅代表秒的部分（每次顺延一秒）发生变化，0x65432100 的秒数为 36（36 进制下为 '10'）
+    let inspected = inspected.lock().unwrap();
+    assert_eq!(inspected.len(), 3);
+    assert_eq!(inspected[2], ticket);
+    for (i, candidate) in inspected.iter().enumerate() {
+        assert_eq!(candidate[..8], ticket[..8]);
+        assert_eq!(candidate[10..], ticket[10..]);
+        assert_eq!(&candidate[8..10], ["10", "11", "12"][i]);
+    }
+}
+
+#[tokio::test]
+async fn test_ticket_max_retry() {
+    let mut serialer = TicketSerialer::new(|_: &str| Box::pin(async { Ok::<_, ()>(true) }))
+        .short_repr()
+        .no_sep()
+        .lowercase()
+        .retry_times(3);
+    serialer.feed(&[0x65, 0x43, 0x21, 0x00, 1, 2, 3, 4]);
+
+    assert!(matches!(
+        serialer.build().await,
+        Err(TicketSerialError::MaxRetry)
+    ));
+}
+
+#[tokio::test]
+async fn test_ticket_degrade() {
+    fn serialer(policy: DegradePolicy<&'static str>) -> TicketSerialer<&'static str> {
+        let mut serialer =
+            TicketSerialer::new(|_: &str| Box::pin(async { Err("inspector is down") }))
+                .degrade(policy);
+        serialer.feed(&[0x65, 0x43, 0x21, 0x00, 1, 2, 3, 4, 5, 6, 7]);
+        serialer
+    }
+
+    // 缺省配置下返回错误
+    assert!(matches!(
+        serialer(DegradePolicy::Fail).build().await,
+        Err(TicketSerialError::InspectFailed("inspector is down"))
+    ));
+
+    // 降级发放的序列号在末尾追加 10 个字符，且不会重复
+    let first = serialer(DegradePolicy::Local).build_ticket().await.unwrap();
+    let second = serialer(DegradePolicy::Local).build_ticket().await.unwrap();
+    assert!(!first.verified);
+    assert_eq!(first.serial.len(), 35 + 11);
+    assert_eq!(first.serial[..35], second.serial[..35]);
+    assert_ne!(first.serial, second.serial);
+
+    // 降级发放的序列号同时交给回调
+    let queued = Arc::new(Mutex::new(Vec::new()));
+    let ticket = serialer(DegradePolicy::queue({
+        let queued = Arc::clone(&queued);
+        move |ticket: &str, error: &&str| {
+            queued
+                .lock()
+                .unwrap()
+                .push((ticket.to_owned(), error.to_string()));
+        }
+    }))
+    .build()
+    .await
+    .unwrap();
+    assert_eq!(
+        *queued.lock().unwrap(),
+        [(ticket, "inspector is down".to_owned())]
+    );
+
+    // 校验成功时不受降级策略影响
+    let mut serialer = TicketSerialer::new(|_: &str| Box::pin(async { Ok::<_, ()>(false) }))
+        .degrade(DegradePolicy::Local);
+    serialer.feed(&[0x65, 0x43, 0x21, 0x00, 1, 2, 3, 4, 5, 6, 7]);
+    let ticket = serialer.build_ticket().await.unwrap();
+    assert!(ticket.verified);
+    assert_eq!(ticket.serial.len(), 35);
+}
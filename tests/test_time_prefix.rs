@@ -0,0 +1,45 @@
+#![cfg(feature = "test-util")]
+
+use chrono::{Local, TimeZone};
+use fastsend::{testing, Serialer, TimeSerialer, TimeSlot};
+use std::time::Duration;
+
+async fn prefix(data: &[u8]) -> String {
+    let mut serialer = TimeSerialer::new().slot(TimeSlot::new());
+    serialer.feed(data);
+    serialer.build().await.unwrap()[..14].to_owned()
+}
+
+// 虚拟时间是全局状态，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_time_prefix_rollover() {
+    let datetime = Local.with_ymd_and_hms(2024, 12, 31, 23, 59, 58).unwrap();
+
+    // 首次生成序列号时初始化全局 slot，开启 'pause_on_start' 时会推进虚拟时间，因此先完成初始化再冻结时间
+    testing::freeze_time(datetime);
+    prefix(b"").await;
+    testing::freeze_time(datetime);
+
+    // 同一秒内复用缓存的前缀
+    assert_eq!(prefix(b"a").await, "20241231235958");
+    assert_eq!(prefix(b"b").await, "20241231235958");
+
+    testing::advance(Duration::from_secs(1));
+    assert_eq!(prefix(b"a").await, "20241231235959");
+
+    // 跨秒时重新计算前缀，年、月、日、时、分同时进位
+    testing::advance(Duration::from_secs(1));
+    assert_eq!(prefix(b"a").await, "20250101000000");
+    assert_eq!(prefix(b"b").await, "20250101000000");
+
+    testing::advance(Duration::from_secs(59));
+    assert_eq!(prefix(b"a").await, "20250101000059");
+    testing::advance(Duration::from_secs(1));
+    assert_eq!(prefix(b"a").await, "20250101000100");
+
+    // 跨越多秒后不会沿用过期的缓存
+    testing::advance(Duration::from_secs(3600 * 24 + 1));
+    assert_eq!(prefix(b"a").await, "20250102000101");
+
+    testing::reset_clock();
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "test-util")]
+
+use chrono::{TimeZone, Utc};
+use fastsend::{testing, Serialer, TimeSerialer, TimeSlot};
+
+async fn build(mut serialer: TimeSerialer, data: &[u8]) -> String {
+    serialer.feed(data);
+    serialer.build().await.unwrap()
+}
+
+// 设备号与冻结的时间是全局状态，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_time_slot() {
+    assert!(fastsend::set_device_id(7));
+    testing::freeze_time(Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap());
+
+    // 首次生成序列号时的停顿会推进虚拟时间，先完成一次生成
+    build(TimeSerialer::new(), b"warm up").await;
+
+    // 不同命名空间的 slot 互不判重，同一秒内可以生成相同的序列号
+    let order = build(TimeSerialer::new().namespace("orders"), b"x").await;
+    let refund = build(TimeSerialer::new().namespace("refunds"), b"x").await;
+    assert_eq!(order, refund);
+
+    // 同名的命名空间共用同一个 slot，重复的序列号在下一秒生成
+    let next = build(TimeSerialer::new().namespace("orders"), b"x").await;
+    assert!(next > order);
+    assert_eq!(next[14..], order[14..]);
+
+    // 克隆的 `TimeSlot` 共用同一个 slot，新建的 `TimeSlot` 与其他 slot 互不判重
+    let slot = TimeSlot::new();
+    let first = build(TimeSerialer::new().slot(slot.clone()), b"x").await;
+    let second = build(TimeSerialer::new().slot(slot), b"x").await;
+    assert!(second > first);
+    assert_eq!(
+        build(TimeSerialer::new().slot(TimeSlot::new()), b"x").await,
+        second
+    );
+
+    // 缺省的全局 slot 不受影响
+    let global = build(TimeSerialer::new(), b"x").await;
+    assert_eq!(global, second);
+
+    testing::reset_clock();
+}
@@ -0,0 +1,30 @@
+use fastsend::{Cursor, ID};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// 基准起始时间是全局状态，且只能在首次获取 `Cursor` 之前修改，因此各用例放在同一个测试中顺序执行
+#[tokio::test]
+async fn test_set_timebase() {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    assert_eq!(Cursor::timebase(), 1639110453);
+
+    // 基准起始时间设置为一天之前，游标约为 86400
+    let timebase = UNIX_EPOCH + Duration::from_secs(now - 86400);
+    assert!(fastsend::set_timebase(timebase));
+    assert_eq!(Cursor::timebase(), now - 86400);
+
+    let cursor = fastsend::next_token().await.id() >> 32;
+    assert!((86400..86400 + 60).contains(&cursor));
+
+    // `Cursor` 已经被使用，无法再修改
+    assert!(!fastsend::set_timebase(UNIX_EPOCH));
+    assert_eq!(Cursor::timebase(), now - 86400);
+}
+
+#[test]
+#[should_panic]
+fn test_set_timebase_in_future() {
+    fastsend::set_timebase(SystemTime::now() + Duration::from_secs(3600));
+}
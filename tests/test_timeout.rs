@@ -0,0 +1,73 @@
+use fastsend::{FastsendError, ID};
+use std::collections::HashSet;
+use std::time::Duration;
+
+// 超时与取消都依赖全局 `BlockFrame` 的补充过程，因此各用例放在同一个测试中顺序执行
+#[tokio::test(flavor = "current_thread")]
+async fn test_next_token_timeout() {
+    let mut ids = HashSet::new();
+
+    let token = fastsend::next_token_timeout(Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert!(ids.insert(token.id()));
+
+    // 同一秒内用完全部发号序号后，开启 'pause_on_start' 时补充需要等待下一秒，此时会超时
+    let mut timeouts = 0;
+    for _ in 0..(1 << 17) {
+        match fastsend::next_token_timeout(Duration::from_millis(10)).await {
+            Ok(token) => assert!(ids.insert(token.id())),
+            Err(FastsendError::Timeout) => {
+                timeouts += 1;
+                break;
+            }
+            Err(error) => panic!("{}", error),
+        }
+    }
+    if cfg!(feature = "pause_on_start") && cfg!(not(feature = "strict_order")) {
+        assert_eq!(timeouts, 1);
+    }
+
+    // 在等待补充的过程中被 `select!` 取消，不会影响之后获取 `Token`，也不会产生重复的 id
+    let mut cancelled = 0;
+    for _ in 0..(1 << 17) {
+        tokio::select! {
+            token = fastsend::next_token() => assert!(ids.insert(token.id())),
+            _ = tokio::time::sleep(Duration::from_millis(1)) => cancelled += 1,
+        }
+        if cancelled > 0 {
+            break;
+        }
+    }
+
+    for _ in 0..1000 {
+        let token = fastsend::next_token_timeout(Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert!(ids.insert(token.id()));
+    }
+
+    // 需要等待补充时的计时共用同一个计时线程，获取到 `Token` 之后不会留下等待超时的线程
+    #[cfg(target_os = "linux")]
+    {
+        let threads = || {
+            std::fs::read_to_string("/proc/self/status")
+                .unwrap()
+                .lines()
+                .find_map(|line| line.strip_prefix("Threads:"))
+                .unwrap()
+                .trim()
+                .parse::<usize>()
+                .unwrap()
+        };
+
+        let before = threads();
+        for _ in 0..(1 << 17) {
+            let token = fastsend::next_token_timeout(Duration::from_secs(60))
+                .await
+                .unwrap();
+            assert!(ids.insert(token.id()));
+        }
+        assert!(threads() <= before + 1);
+    }
+}
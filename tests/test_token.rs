@@ -1,8 +1,9 @@
-use fastsend::ID;
+use fastsend::{BlockFrame, ConstructBlock, Cursor, Token, ID};
 use fastsend::{Serial, Serialer, TimeSerialer};
 use futures::future;
 use std::collections::HashSet;
 use std::error::Error;
+use std::future::Future;
 use std::result::Result as StdResult;
 
 type Result<T> = StdResult<T, Box<dyn Error>>;
@@ -22,6 +23,25 @@ macro_rules! into_hashset {
     }};
 }
 
+#[tokio::test]
+async fn test_shard_of() {
+    // 映射是固定的，不随进程变化
+    assert_eq!(fastsend::shard_of(0, 16), 0);
+    assert_eq!(fastsend::shard_of(1, 16), 5);
+    assert_eq!(fastsend::shard_of(1 << 32 | 1, 16), 5);
+
+    let mut counts = [0usize; 8];
+    for _ in 0..TOP {
+        let token = fastsend::next_token().await;
+        let shard = fastsend::shard_of(token.id(), 8);
+        assert_eq!(fastsend::shard_of(token.shard_key() as u64, 8), shard);
+        counts[shard as usize] += 1;
+    }
+
+    // 连续的序号应当较为均匀地分布到各个分片中
+    assert!(counts.iter().all(|&count| count > TOP / 8 / 2));
+}
+
 #[tokio::test]
 async fn test_unique_id() {
     let set = into_hashset!(@it
@@ -70,3 +90,105 @@ async fn test_unique_serial_concurrently() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_oneshot_many() -> Result<()> {
+    let mut tokens = Vec::new();
+    for _ in 0..1000 {
+        tokens.push(fastsend::next_token().await);
+    }
+
+    let serials = TimeSerialer::new().oneshot_many(tokens).await;
+    assert_eq!(serials.len(), 1000);
+
+    let set = into_hashset!(@ok serials);
+    assert_eq!(set.len(), 1000);
+    assert!(set.iter().all(|serial| serial.len() == 21));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_serial_build_into() -> Result<()> {
+    let mut buffer = String::with_capacity(64);
+    let mut set = HashSet::new();
+    for _ in 0..100 {
+        buffer.clear();
+        buffer.push_str("SN");
+        let mut serialer = TimeSerialer::new();
+        fastsend::next_token().await.serial(&mut serialer);
+        serialer.build_into(&mut buffer).await?;
+
+        assert_eq!(buffer.len(), 2 + 21);
+        assert!(buffer[2..].bytes().all(|c| c.is_ascii_digit()));
+        set.insert(buffer.clone());
+    }
+    assert_eq!(set.len(), 100);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_block_future() {
+    fn assert_future<F: Future + Send + 'static>(future: F) -> F {
+        future
+    }
+
+    let frame = BlockFrame::<Token>::new();
+    let block = assert_future(frame.next_block()).await;
+    assert_eq!(block.count(), 8);
+
+    let block = assert_future(frame.try_next_block()).await.unwrap();
+    assert_eq!(block.count(), 8);
+}
+
+// 不依赖运行时及系统线程，可以在 Miri 下执行：`cargo +nightly miri test --test test_token test_construct_block`
+#[test]
+fn test_construct_block() {
+    let ids = Token::construct_block(3, Cursor::new())
+        .map(ID::id)
+        .collect::<Vec<_>>();
+
+    assert_eq!(ids.len(), 8);
+    for (i, id) in ids.iter().enumerate() {
+        assert_eq!(id >> 32, ids[0] >> 32);
+        assert_eq!((id >> 16) as u16, 3 * 8 + i as u16);
+    }
+}
+
+/// `Token` 的最低两个字节，即 `Ident` 中的设备号 `c` 与线程号 `d`。
+fn cd_of(construct: impl Fn() -> Vec<u64>) -> HashSet<(u8, u8)> {
+    construct()
+        .into_iter()
+        .map(|id| ((id >> 8) as u8, id as u8))
+        .collect()
+}
+
+#[test]
+fn test_construct_block_cd() {
+    let construct = || {
+        (0..16)
+            .flat_map(|n| Token::construct_block(n, Cursor::new()).map(ID::id))
+            .collect::<Vec<_>>()
+    };
+
+    // 同一线程中多次构造得到相同的 `c` 与 `d`
+    let local = cd_of(construct);
+    assert_eq!(local.len(), 1);
+    let (c, _) = *local.iter().next().unwrap();
+
+    // 各线程分别缓存，`c` 相同而 `d` 取决于所在线程（256 种取值，16 个线程全部相同的概率可以忽略）
+    let handles = (0..16)
+        .map(|_| std::thread::spawn(move || cd_of(construct)))
+        .collect::<Vec<_>>();
+    let mut d = HashSet::new();
+    for handle in handles {
+        let cd = handle.join().unwrap();
+        assert_eq!(cd.len(), 1);
+        for (other, thread) in cd {
+            assert_eq!(other, c);
+            d.insert(thread);
+        }
+    }
+    assert!(d.len() > 1);
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "trace_context")]
+
+use std::collections::HashSet;
+use std::time::SystemTime;
+
+#[test]
+fn test_trace_id() {
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+
+    let ids = (0..10000)
+        .map(|_| fastsend::next_trace_id())
+        .collect::<HashSet<_>>();
+    assert_eq!(ids.len(), 10000);
+
+    for id in &ids {
+        assert_ne!(id, &[0; 16]);
+
+        let mut timestamp = [0; 8];
+        timestamp[2..].copy_from_slice(&id[..6]);
+        assert!(u64::from_be_bytes(timestamp) >= now);
+    }
+}
+
+#[test]
+fn test_span_id() {
+    let ids = (0..10000)
+        .map(|_| fastsend::next_span_id())
+        .collect::<HashSet<_>>();
+    assert_eq!(ids.len(), 10000);
+    assert!(!ids.contains(&[0; 8]));
+}
+
+#[test]
+fn test_traceparent() {
+    let trace_id = fastsend::next_trace_id();
+    let span_id = fastsend::next_span_id();
+
+    let header = fastsend::traceparent(&trace_id, &span_id, false);
+    let parts = header.split('-').collect::<Vec<_>>();
+    assert_eq!(parts.len(), 4);
+    assert_eq!(parts[0], "00");
+    assert_eq!(parts[1].len(), 32);
+    assert_eq!(parts[2].len(), 16);
+    assert_eq!(parts[3], "00");
+    assert_eq!(
+        u128::from_str_radix(parts[1], 16).unwrap(),
+        u128::from_be_bytes(trace_id)
+    );
+    assert_eq!(
+        u64::from_str_radix(parts[2], 16).unwrap(),
+        u64::from_be_bytes(span_id)
+    );
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "typeid")]
+
+use fastsend::{Serialer, TypeId, TypeIdError, TypeIdSerialer};
+
+#[tokio::test]
+async fn test_typeid_roundtrip() {
+    let typeid = TypeIdSerialer::new("user").build().await.unwrap();
+    let repr = typeid.to_string();
+
+    assert!(repr.starts_with("user_"));
+    assert_eq!(repr.len(), "user_".len() + 26);
+    assert_eq!(repr.parse::<TypeId>().unwrap(), typeid);
+    assert_eq!(typeid.uuid().unwrap().to_string().as_bytes()[14], b'7');
+    assert_eq!(typeid.uuid().unwrap().to_bytes(), typeid.to_bytes());
+}
+
+#[test]
+fn test_typeid_any_uuid() {
+    // 后缀可以是任意 UUID，包括 nil UUID 及 V7 以外的版本
+    let nil = "user_00000000000000000000000000".parse::<TypeId>().unwrap();
+    assert_eq!(nil.prefix(), "user");
+    assert_eq!(nil.to_bytes(), [0; 16]);
+    assert_eq!(nil.uuid(), None);
+    assert_eq!(nil.to_string(), "user_00000000000000000000000000");
+
+    let max = "7zzzzzzzzzzzzzzzzzzzzzzzzz".parse::<TypeId>().unwrap();
+    assert_eq!(max.prefix(), "");
+    assert_eq!(max.to_bytes(), [0xff; 16]);
+    assert_eq!(max.to_string(), "7zzzzzzzzzzzzzzzzzzzzzzzzz");
+
+    // V1 UUID 6ba7b810-9dad-11d1-80b4-00c04fd430c8
+    let typeid = "order_3bmyw117dd278r1d00r17x8c68"
+        .parse::<TypeId>()
+        .unwrap();
+    assert_eq!(
+        u128::from_be_bytes(typeid.to_bytes()),
+        0x6ba7_b810_9dad_11d1_80b4_00c0_4fd4_30c8
+    );
+    assert_eq!(typeid.uuid(), None);
+    assert_eq!(typeid.to_string(), "order_3bmyw117dd278r1d00r17x8c68");
+}
+
+#[tokio::test]
+async fn test_typeid_sortable() {
+    let first = TypeIdSerialer::new("").build().await.unwrap().to_string();
+    tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+    let second = TypeIdSerialer::new("").build().await.unwrap().to_string();
+
+    assert_eq!(first.len(), 26);
+    assert!(first < second);
+}
+
+#[test]
+fn test_typeid_parse_error() {
+    assert_eq!(
+        "_01h455vb4pex5vsknk084sn02q".parse::<TypeId>(),
+        Err(TypeIdError::InvalidPrefix)
+    );
+    assert_eq!(
+        "User_01h455vb4pex5vsknk084sn02q".parse::<TypeId>(),
+        Err(TypeIdError::InvalidPrefix)
+    );
+    assert_eq!(
+        "user_81h455vb4pex5vsknk084sn02q".parse::<TypeId>(),
+        Err(TypeIdError::InvalidSuffix)
+    );
+    assert_eq!(
+        "user_01h455vb4pex5vsknk084sn0".parse::<TypeId>(),
+        Err(TypeIdError::InvalidSuffix)
+    );
+    assert!("user_01h455vb4pex5vsknk084sn02q".parse::<TypeId>().is_ok());
+}
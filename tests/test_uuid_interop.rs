@@ -0,0 +1,39 @@
+#![cfg(feature = "uuid_interop")]
+
+use fastsend::{Serial, Serialer, UUIDSerialer, UUID};
+use uuid_crate::Uuid;
+
+#[tokio::test]
+async fn test_uuid_conversion() {
+    let uuid = UUIDSerialer::new_v7().build().await.unwrap();
+
+    let converted = Uuid::from(uuid);
+    assert_eq!(converted.get_version_num(), 7);
+    assert_eq!(converted.to_string(), uuid.to_string());
+    assert_eq!(UUID::try_from(converted).unwrap(), uuid);
+
+    assert!(UUID::try_from(Uuid::nil()).is_err());
+}
+
+#[tokio::test]
+async fn test_uuid_serial() {
+    let uuid = Uuid::from_u128(0x0193_0a5e_7c4d_7a3b_8c2d_1e2f_3a4b_5c6d);
+
+    // V5 UUID 由输入数据的摘要生成，相同的输入得到相同的结果
+    let build = || async {
+        let mut serialer = UUIDSerialer::new_v5();
+        uuid.serial(&mut serialer);
+        serialer.build().await.unwrap()
+    };
+    assert_eq!(build().await, build().await);
+}
+
+#[cfg(feature = "typeid")]
+#[test]
+fn test_typeid_conversion() {
+    use fastsend::TypeId;
+
+    // `TypeId` 的后缀可以是 `UUID` 不支持的版本，仍然可以无损转换为 `uuid::Uuid`
+    let typeid = "user_00000000000000000000000000".parse::<TypeId>().unwrap();
+    assert_eq!(Uuid::from(&typeid), Uuid::nil());
+}
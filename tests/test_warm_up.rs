@@ -0,0 +1,24 @@
+use fastsend::ID;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+// 预热作用于全局生成器，因此各用例放在同一个测试中顺序执行
+#[tokio::test(flavor = "current_thread")]
+async fn test_warm_up() {
+    fastsend::warm_up(1000).await.unwrap();
+
+    // 预热之后获取 `Token` 不再需要等待初始化及补充
+    let start = Instant::now();
+    let ids = futures::future::join_all((0..1000).map(|_| fastsend::next_token()))
+        .await
+        .into_iter()
+        .map(ID::id)
+        .collect::<HashSet<_>>();
+    assert!(start.elapsed() < Duration::from_millis(500));
+    assert_eq!(ids.len(), 1000);
+
+    // 重复预热不会产生副作用
+    fastsend::warm_up(0).await.unwrap();
+    fastsend::warm_up(usize::MAX).await.unwrap();
+    assert!(!ids.contains(&fastsend::next_token().await.id()));
+}